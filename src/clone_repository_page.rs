@@ -18,6 +18,7 @@
  */
 
 use adw::subclass::prelude::*;
+use adw::traits::ComboRowExt;
 use adw::traits::ExpanderRowExt;
 use adw::traits::PreferencesRowExt;
 use email_address::EmailAddress;
@@ -27,10 +28,17 @@ use gtk::{glib, prelude::*, template_callbacks, CompositeTemplate};
 use once_cell::sync::Lazy;
 
 use crate::models::bagit_git_profile::BagitGitProfile;
+use crate::utils::clone_mode::CloneMode;
+use crate::utils::clone_options::CloneOptions;
 use crate::utils::clone_page_profile_mode_type::ClonePageProfileModeType;
 use crate::utils::clone_page_profile_mode_type::ClonePageProfileModeValues;
+use crate::utils::db::AppDatabase;
 use crate::utils::git_profile_utils::GitProfileUtils;
+use crate::utils::secret_store::SecretStoreUtils;
+use crate::utils::transfer_progress::TransferProgress;
 use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use uuid::Uuid;
 
@@ -90,9 +98,29 @@ mod imp {
         pub new_profile_revealer: TemplateChild<gtk::Revealer>,
         #[template_child]
         pub main_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub progress_bar: TemplateChild<gtk::ProgressBar>,
+        #[template_child]
+        pub progress_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub cancel_clone_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub depth_spin_button: TemplateChild<gtk::SpinButton>,
+        #[template_child]
+        pub branch_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub branch_list_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub bare_switch: TemplateChild<gtk::Switch>,
+        #[template_child]
+        pub submodules_switch: TemplateChild<gtk::Switch>,
 
         pub profile_mode: RefCell<ProfileMode>,
 
+        pub remote_branch_names: RefCell<Vec<String>>,
+
+        pub is_clone_cancelled: RefCell<Arc<AtomicBool>>,
+
         pub app_database: RefCell<AppDatabase>,
     }
 
@@ -126,6 +154,20 @@ mod imp {
 
             self.passphrase_revealer
                 .set_reveal_child(RepositoryUtils::is_using_ssh(&url_text_row.text()));
+
+            // The listed branches belong to the previous URL:
+            self.obj().set_remote_branch_names(vec![]);
+        }
+
+        #[template_callback]
+        fn list_remote_branches(&self, _button: &gtk::Button) {
+            if self.url_row.text().trim().is_empty() {
+                return;
+            }
+
+            self.branch_list_stack.set_visible_child_name("spinner");
+            self.obj()
+                .emit_by_name::<()>("list-remote-branches", &[&self.url_row.text()]);
         }
 
         #[template_callback]
@@ -206,8 +248,34 @@ mod imp {
             self.obj().emit_by_name::<()>("select-private-key", &[]);
         }
 
+        #[template_callback]
+        fn cancel_clone(&self, button: &gtk::Button) {
+            self.is_clone_cancelled
+                .borrow()
+                .store(true, Ordering::Relaxed);
+            button.set_sensitive(false);
+            self.progress_label
+                .set_label(&gettext("_Cancelling transfer"));
+        }
+
         #[template_callback]
         pub fn try_clone_repository(&self, _clone_button: &gtk::Button) {
+            // The password of the selected profile cannot be read while the credential store is locked:
+            let is_using_selected_profile = matches!(
+                self.profile_mode.borrow().get_profile_mode(),
+                ProfileMode::SelectedProfile(_)
+            );
+            if is_using_selected_profile
+                && matches!(
+                    RepositoryUtils::get_clone_mode_of_url(&self.url_row.text()),
+                    CloneMode::HTTPS
+                )
+                && !SecretStoreUtils::is_open()
+            {
+                SecretStoreUtils::request_unlock();
+                return;
+            }
+
             self.obj().to_loading_page();
             self.obj().emit_by_name::<()>(
                 "clone-repository",
@@ -260,6 +328,8 @@ mod imp {
             app_database.create_connection();
 
             self.app_database.replace(app_database);
+
+            self.obj().set_remote_branch_names(vec![]);
         }
 
         fn signals() -> &'static [Signal] {
@@ -271,6 +341,9 @@ mod imp {
                     Signal::builder("clone-repository")
                         .param_types([str::static_type(), str::static_type()])
                         .build(),
+                    Signal::builder("list-remote-branches")
+                        .param_types([str::static_type()])
+                        .build(),
                     Signal::builder("clone-repository-and-add-profile")
                         .param_types([
                             str::static_type(),
@@ -346,6 +419,10 @@ impl BagitCloneRepositoryPage {
         self.imp().private_key_path.set_text("");
         self.imp().passphrase.set_text("");
         self.imp().signing_key.set_text("");
+        self.imp().depth_spin_button.set_value(0.0);
+        self.imp().bare_switch.set_active(false);
+        self.imp().submodules_switch.set_active(true);
+        self.set_remote_branch_names(vec![]);
 
         // We clear the list informations and the profile mode used:
         self.clear_profiles_list(true);
@@ -483,10 +560,120 @@ impl BagitCloneRepositoryPage {
      * Used to go to the loading page of the cloning page.
      */
     pub fn to_loading_page(&self) {
+        self.imp()
+            .is_clone_cancelled
+            .replace(Arc::new(AtomicBool::new(false)));
+        self.imp().progress_bar.set_fraction(0.0);
+        self.imp()
+            .progress_label
+            .set_label(&gettext("_Connecting to remote"));
+        self.imp().cancel_clone_button.set_sensitive(true);
+
         self.imp().main_stack.set_visible_child_name("loading page");
         self.imp().back_button.set_sensitive(false);
     }
 
+    /**
+     * Used to show the branches of the remote which can be cloned alone.
+     */
+    pub fn set_remote_branch_names(&self, branch_names: Vec<String>) {
+        let mut choices = vec![gettext("_All branches")];
+        choices.extend(branch_names.iter().cloned());
+        let choices: Vec<&str> = choices.iter().map(|choice| choice.as_str()).collect();
+
+        self.imp()
+            .branch_row
+            .set_model(Some(&gtk::StringList::new(&choices)));
+        self.imp().branch_row.set_selected(0);
+        self.imp()
+            .branch_list_stack
+            .set_visible_child_name("button");
+        self.imp().remote_branch_names.replace(branch_names);
+    }
+
+    /**
+     * Used to get the options chosen for the clone.
+     */
+    pub fn get_clone_options(&self) -> CloneOptions {
+        let selected = self.imp().branch_row.selected() as usize;
+        let branch_name = match selected {
+            0 => String::new(),
+            _ => self
+                .imp()
+                .remote_branch_names
+                .borrow()
+                .get(selected - 1)
+                .cloned()
+                .unwrap_or_default(),
+        };
+
+        return CloneOptions {
+            depth: self.imp().depth_spin_button.value_as_int() as u32,
+            branch_name,
+            is_bare: self.imp().bare_switch.is_active(),
+            is_recursive: self.imp().submodules_switch.is_active(),
+        };
+    }
+
+    /**
+     * Used to get the username, password, private key path and passphrase used to reach the remote,
+     * according to the profile mode.
+     */
+    pub fn get_authentication_information(&self) -> (String, String, String, String) {
+        let passphrase = self.imp().passphrase.text().to_string();
+
+        return match self.imp().profile_mode.borrow().get_profile_mode() {
+            ProfileMode::NoProfile => (String::new(), String::new(), String::new(), passphrase),
+            ProfileMode::NewProfile => (
+                self.imp().https_username.text().to_string(),
+                self.imp().https_pwd.text().to_string(),
+                self.imp().private_key_path.text().to_string(),
+                passphrase,
+            ),
+            ProfileMode::SelectedProfile(profile) => (
+                profile.username.clone(),
+                AppDatabase::retrieve_password_of_git_profile(&profile),
+                profile.private_key_path,
+                passphrase,
+            ),
+        };
+    }
+
+    /**
+     * Used to get the flag set when the user cancels the clone.
+     */
+    pub fn get_clone_cancellation(&self) -> Arc<AtomicBool> {
+        return self.imp().is_clone_cancelled.borrow().clone();
+    }
+
+    /**
+     * Used to know if the user cancelled the clone.
+     */
+    pub fn is_clone_cancelled(&self) -> bool {
+        return self
+            .imp()
+            .is_clone_cancelled
+            .borrow()
+            .load(Ordering::Relaxed);
+    }
+
+    /**
+     * Used to show the progress of the clone.
+     */
+    pub fn update_progress(&self, progress: &TransferProgress) {
+        if self.is_clone_cancelled() {
+            return;
+        }
+
+        match progress.get_fraction() {
+            Some(fraction) => self.imp().progress_bar.set_fraction(fraction),
+            None => self.imp().progress_bar.pulse(),
+        };
+        self.imp()
+            .progress_label
+            .set_label(&progress.get_description());
+    }
+
     /**
      * Used to go to the main page of the cloning page.
      */
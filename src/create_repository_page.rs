@@ -18,6 +18,7 @@
  */
 
 use adw::subclass::prelude::*;
+use adw::traits::ComboRowExt;
 use adw::traits::ExpanderRowExt;
 use adw::traits::PreferencesRowExt;
 use email_address::EmailAddress;
@@ -35,6 +36,7 @@ use std::cell::RefCell;
 use uuid::Uuid;
 
 use crate::utils::profile_mode::ProfileMode;
+use crate::utils::repository_template::{RepositoryTemplate, GITIGNORE_TEMPLATES, LICENSES};
 
 mod imp {
 
@@ -55,6 +57,16 @@ mod imp {
         #[template_child]
         pub location_row: TemplateChild<adw::EntryRow>,
         #[template_child]
+        pub initial_branch_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub gitignore_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub license_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub readme_switch: TemplateChild<gtk::Switch>,
+        #[template_child]
+        pub initial_commit_switch: TemplateChild<gtk::Switch>,
+        #[template_child]
         pub create_repository_button: TemplateChild<gtk::Button>,
         #[template_child]
         pub create_repository_button_and_profile: TemplateChild<gtk::Button>,
@@ -132,6 +144,20 @@ mod imp {
                 .set_sensitive(self.obj().can_create_button_with_new_profile_be_sensitive());
         }
 
+        #[template_callback]
+        fn initial_branch_row_changed(&self, initial_branch_row: &adw::EntryRow) {
+            if self.obj().is_initial_branch_valid() {
+                initial_branch_row.remove_css_class("error");
+            } else {
+                initial_branch_row.add_css_class("error");
+            }
+
+            self.create_repository_button
+                .set_sensitive(self.obj().can_create_button_be_sensitive());
+            self.create_repository_button_and_profile
+                .set_sensitive(self.obj().can_create_button_with_new_profile_be_sensitive());
+        }
+
         #[template_callback]
         fn profile_name_changed(&self, profile_name_row: &adw::EntryRow) {
             self.create_repository_button_and_profile
@@ -256,6 +282,8 @@ mod imp {
             app_database.create_connection();
 
             self.app_database.replace(app_database);
+
+            self.obj().setup_template_choices();
         }
 
         fn signals() -> &'static [Signal] {
@@ -313,13 +341,76 @@ impl BagitCreateRepositoryPage {
     /// Checks if the create button can be sensitive.
     pub fn can_create_button_be_sensitive(&self) -> bool {
         return (self.imp().repository_name_row.text().trim() != "")
-            && (self.imp().location_row.text().trim() != "");
+            && (self.imp().location_row.text().trim() != "")
+            && self.is_initial_branch_valid();
+    }
+
+    /// Checks if the initial branch is a valid branch name.
+    /// An empty initial branch keeps the default one.
+    pub fn is_initial_branch_valid(&self) -> bool {
+        let initial_branch = self.imp().initial_branch_row.text();
+
+        return initial_branch.trim().is_empty()
+            || git2::Branch::name_is_valid(initial_branch.trim()).unwrap_or(false);
+    }
+
+    /// Fills the choices of the bundled .gitignore templates and licenses.
+    fn setup_template_choices(&self) {
+        let mut gitignore_choices = vec![gettext("_None")];
+        gitignore_choices.extend(GITIGNORE_TEMPLATES.iter().map(|(name, _)| name.to_string()));
+        let gitignore_choices: Vec<&str> = gitignore_choices
+            .iter()
+            .map(|choice| choice.as_str())
+            .collect();
+
+        self.imp()
+            .gitignore_row
+            .set_model(Some(&gtk::StringList::new(&gitignore_choices)));
+
+        let mut license_choices = vec![gettext("_None")];
+        license_choices.extend(LICENSES.iter().map(|license| license.to_string()));
+        let license_choices: Vec<&str> = license_choices
+            .iter()
+            .map(|choice| choice.as_str())
+            .collect();
+
+        self.imp()
+            .license_row
+            .set_model(Some(&gtk::StringList::new(&license_choices)));
+    }
+
+    /// Used to get the initial content chosen for the repository.
+    pub fn get_repository_template(&self) -> RepositoryTemplate {
+        let gitignore_template = match self.imp().gitignore_row.selected() as usize {
+            0 => String::new(),
+            selected => GITIGNORE_TEMPLATES
+                .get(selected - 1)
+                .map(|(_, resource_name)| resource_name.to_string())
+                .unwrap_or_default(),
+        };
+
+        let license = match self.imp().license_row.selected() as usize {
+            0 => String::new(),
+            selected => LICENSES
+                .get(selected - 1)
+                .map(|license| license.to_string())
+                .unwrap_or_default(),
+        };
+
+        return RepositoryTemplate {
+            initial_branch: self.imp().initial_branch_row.text().trim().to_string(),
+            gitignore_template,
+            license,
+            has_readme: self.imp().readme_switch.is_active(),
+            has_initial_commit: self.imp().initial_commit_switch.is_active(),
+        };
     }
 
     /// Checks if the create button (with new profile) can be sensitive.
     pub fn can_create_button_with_new_profile_be_sensitive(&self) -> bool {
         return (self.imp().repository_name_row.text().trim() != "")
             && (self.imp().location_row.text().trim() != "")
+            && self.is_initial_branch_valid()
             && (self.imp().profile_name.text().trim() != "")
             && (self.imp().https_username.text().trim() != "")
             && (EmailAddress::is_valid(self.imp().email.text().trim())
@@ -330,6 +421,11 @@ impl BagitCreateRepositoryPage {
     pub fn clear_page(&self) {
         self.imp().repository_name_row.set_text("");
         self.imp().location_row.set_text("");
+        self.imp().initial_branch_row.set_text("main");
+        self.imp().gitignore_row.set_selected(0);
+        self.imp().license_row.set_selected(0);
+        self.imp().readme_switch.set_active(false);
+        self.imp().initial_commit_switch.set_active(false);
         self.imp().profile_name.set_text("");
         self.imp().email.set_text("");
         self.imp().https_username.set_text("");
@@ -17,18 +17,22 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::thread;
 
 use crate::models::bagit_git_profile::BagitGitProfile;
 use crate::utils::db::AppDatabase;
+use crate::utils::passphrase_cache::PassphraseCache;
 use crate::widgets::preferences::{
     preferences_git_profiles::BagitPreferencesGitProfiles,
+    preferences_passphrases::BagitPreferencesPassphrases,
     preferences_sidebar::BagitPreferencesSideBar,
 };
 use adw::subclass::prelude::*;
 use adw::traits::MessageDialogExt;
 use gettextrs::gettext;
-use gtk::glib::{clone, MainContext};
+use gtk::glib::{clone, Continue, MainContext, Priority};
 use gtk::prelude::FileExt;
 use gtk::template_callbacks;
 use gtk::traits::{EditableExt, GtkWindowExt, WidgetExt};
@@ -55,11 +59,15 @@ mod imp {
         #[template_child]
         pub identities: TemplateChild<BagitPreferencesGitProfiles>,
         #[template_child]
+        pub passphrases: TemplateChild<BagitPreferencesPassphrases>,
+        #[template_child]
         pub stack: TemplateChild<gtk::Stack>,
         #[template_child]
         pub toast_overlay: TemplateChild<adw::ToastOverlay>,
 
         pub app_database: RefCell<AppDatabase>,
+        /// The saved passwords of the Git profiles, as retrieving them from the secret store may block.
+        pub git_profile_passwords: RefCell<HashMap<String, String>>,
     }
 
     #[template_callbacks]
@@ -140,6 +148,12 @@ impl BagitPreferences {
                             }
 
                         }
+                        1 => {
+                            win.imp().stack.set_visible_child_name("passphrases");
+                            if win.imp().leaflet.is_folded() {
+                                win.imp().leaflet.navigate(adw::NavigationDirection::Forward);
+                            }
+                        }
                         _ => {
                             win.imp().stack.set_visible_child_name("identities");
                             if win.imp().leaflet.is_folded() {
@@ -151,6 +165,20 @@ impl BagitPreferences {
             ),
         );
 
+        self.imp().passphrases.connect_closure(
+            "forget-passphrases",
+            false,
+            closure_local!(@watch self as win => move |
+                _passphrases: BagitPreferencesPassphrases
+                | {
+                    PassphraseCache::forget_all();
+
+                    let toast = adw::Toast::new(&gettext("_Passphrases forgotten"));
+                    win.imp().toast_overlay.add_toast(toast);
+                }
+            ),
+        );
+
         self.imp().identities.connect_closure(
             "can-add-profile",
             false,
@@ -258,6 +286,8 @@ impl BagitPreferences {
 
                             let toast = adw::Toast::new(&gettext("_Could not update Git profile"));
                             win.imp().toast_overlay.add_toast(toast);
+                        } else {
+                            win.imp().git_profile_passwords.borrow_mut().insert(profile_id.to_string(), password.to_string());
                         }
                     } else {
                         if let Err(error) = app_database.add_git_profile(
@@ -275,6 +305,8 @@ impl BagitPreferences {
 
                             let toast = adw::Toast::new(&gettext("_Could not add Git profile"));
                             win.imp().toast_overlay.add_toast(toast);
+                        } else {
+                            win.imp().git_profile_passwords.borrow_mut().insert(profile_id.to_string(), password.to_string());
                         }
 
                         ()
@@ -308,7 +340,6 @@ impl BagitPreferences {
                         profile_name,
                         email,
                         username,
-                        password,
                         private_key_path,
                         signing_key
                     ) {
@@ -323,7 +354,13 @@ impl BagitPreferences {
 
                     win.imp().app_database.replace(app_database);
 
-                    revealer.set_reveal_child(!does_git_profile_exist && profile_name != "" && username != "" && email != "");
+                    // The password is not in the database, it is compared with the saved one:
+                    let is_same_password = match win.imp().git_profile_passwords.borrow().get(profile_id) {
+                        Some(saved_password) => saved_password == password,
+                        None => password.is_empty(),
+                    };
+
+                    revealer.set_reveal_child(!(does_git_profile_exist && is_same_password) && profile_name != "" && username != "" && email != "");
                 }
             ),
         );
@@ -443,20 +480,49 @@ impl BagitPreferences {
 
         self.imp().app_database.replace(app_database);
 
-        for profile in git_profiles {
-            self.imp().identities.imp().status_page.set_visible(false);
-            self.imp().identities.imp().git_profiles.set_visible(true);
-
-            self.imp().identities.imp().obj().add_new_git_profile(
-                profile.profile_id,
-                &profile.profile_name,
-                &profile.email,
-                &profile.username,
-                &profile.password,
-                &profile.private_key_path,
-                &profile.signing_key,
-                false,
-            );
-        }
+        // The passwords are retrieved in another thread, as the secret store may block:
+        let (sender, receiver) = MainContext::channel::<Vec<BagitGitProfile>>(Priority::default());
+
+        thread::spawn(move || {
+            let git_profiles = git_profiles
+                .into_iter()
+                .map(|mut profile| {
+                    profile.password = AppDatabase::retrieve_password_of_git_profile(&profile);
+                    profile
+                })
+                .collect();
+
+            sender
+                .send(git_profiles)
+                .expect("Could not send through channel");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false), move |git_profiles| {
+                for profile in git_profiles {
+                    win.imp()
+                        .git_profile_passwords
+                        .borrow_mut()
+                        .insert(profile.profile_id.to_string(), profile.password.clone());
+
+                    win.imp().identities.imp().status_page.set_visible(false);
+                    win.imp().identities.imp().git_profiles.set_visible(true);
+
+                    win.imp().identities.imp().obj().add_new_git_profile(
+                        profile.profile_id,
+                        &profile.profile_name,
+                        &profile.email,
+                        &profile.username,
+                        &profile.password,
+                        &profile.private_key_path,
+                        &profile.signing_key,
+                        false,
+                    );
+                }
+
+                Continue(true)
+            }),
+        );
     }
 }
@@ -18,20 +18,42 @@
  */
 
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, TryRecvError};
+use std::sync::Arc;
 use std::thread;
 
 use crate::models::bagit_git_profile::BagitGitProfile;
 use crate::utils::action_type::ActionType;
+use crate::utils::agent_utils::AgentUtils;
+use crate::utils::blame_line::BlameLine;
 use crate::utils::changed_file::ChangedFile;
 use crate::utils::clone_mode::CloneMode;
+use crate::utils::commit_details::CommitDetails;
+use crate::utils::db::AppDatabase;
 use crate::utils::fetch_result::FetchResult;
-use crate::utils::git::fetch_checked_out_branch;
+use crate::utils::file_history_entry::FileHistoryEntry;
+use crate::utils::git::{fetch_checked_out_branch, get_commit_details};
+use crate::utils::interactive_rebase::RebaseStop;
+use crate::utils::merge_conflict::{has_conflict_markers, ConflictSide};
+use crate::utils::passphrase_cache::PassphraseCache;
 use crate::utils::profile_mode::ProfileMode;
+use crate::utils::pull_strategy::PullStrategy;
 use crate::utils::repository_utils::RepositoryUtils;
+use crate::utils::secret_store::SecretStoreUtils;
 use crate::utils::selected_repository::SelectedRepository;
+use crate::utils::transfer_progress::TransferProgress;
+use crate::widgets::repository::commit_detail_view::BagitCommitDetailView;
 use crate::widgets::repository::commit_view::BagitCommitView;
 use crate::widgets::repository::commits_sidebar::BagitCommitsSideBar;
+use crate::widgets::repository::file_browser_view::BagitFileBrowserView;
+use crate::widgets::repository::file_view::BagitFileView;
+use crate::widgets::repository::merge_conflict_view::BagitMergeConflictView;
+use crate::widgets::repository::rebase_view::BagitRebaseView;
+use crate::widgets::repository::remote_management_view::BagitRemoteManagementView;
+use crate::widgets::repository::stash_view::BagitStashView;
+use crate::widgets::repository::submodule_view::BagitSubmoduleView;
+use crate::widgets::repository::tag_management_view::BagitTagManagementView;
 use adw::subclass::prelude::*;
 use gettextrs::gettext;
 use git2::Status;
@@ -46,13 +68,10 @@ use crate::widgets::repository::branch_management_view::BagitBranchManagementVie
 
 mod imp {
 
-    use crate::widgets::repository::file_view::BagitFileView;
-
     use super::*;
 
     use std::{
         cell::{Cell, RefCell},
-        collections::HashMap,
         sync::mpsc,
     };
 
@@ -90,8 +109,26 @@ mod imp {
         #[template_child]
         pub branch_button: TemplateChild<gtk::Button>,
         #[template_child]
+        pub detached_head_banner: TemplateChild<adw::Banner>,
+        #[template_child]
         pub file_view: TemplateChild<BagitFileView>,
         #[template_child]
+        pub commit_detail_view: TemplateChild<BagitCommitDetailView>,
+        #[template_child]
+        pub merge_conflict_view: TemplateChild<BagitMergeConflictView>,
+        #[template_child]
+        pub rebase_view: TemplateChild<BagitRebaseView>,
+        #[template_child]
+        pub stash_view: TemplateChild<BagitStashView>,
+        #[template_child]
+        pub tag_view: TemplateChild<BagitTagManagementView>,
+        #[template_child]
+        pub remote_view: TemplateChild<BagitRemoteManagementView>,
+        #[template_child]
+        pub submodule_view: TemplateChild<BagitSubmoduleView>,
+        #[template_child]
+        pub file_browser_view: TemplateChild<BagitFileBrowserView>,
+        #[template_child]
         pub repository_name_label: TemplateChild<gtk::Label>,
         #[template_child]
         pub git_action_button: TemplateChild<SplitButton>,
@@ -102,6 +139,12 @@ mod imp {
         #[template_child]
         pub push_button: TemplateChild<gtk::Button>,
         #[template_child]
+        pub deepen_history_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub merge_strategy_button: TemplateChild<gtk::CheckButton>,
+        #[template_child]
+        pub rebase_strategy_button: TemplateChild<gtk::CheckButton>,
+        #[template_child]
         pub git_action_label: TemplateChild<Label>,
         #[template_child]
         pub git_action_spinner: TemplateChild<Spinner>,
@@ -113,6 +156,10 @@ mod imp {
         pub pull_indication_box: TemplateChild<gtk::Box>,
         #[template_child]
         pub git_action_pull_number: TemplateChild<Label>,
+        #[template_child]
+        pub transfer_progress_label: TemplateChild<Label>,
+        #[template_child]
+        pub cancel_transfer_button: TemplateChild<gtk::Button>,
 
         pub app_database: RefCell<AppDatabase>,
 
@@ -120,8 +167,9 @@ mod imp {
 
         pub current_git_action: RefCell<ActionType>,
         pub is_doing_git_action: Cell<bool>,
-
-        pub ssh_passphrases: RefCell<HashMap<String, String>>,
+        pub is_transfer_cancelled: RefCell<Arc<AtomicBool>>,
+        /// The number of commits added to the history by the next deepening, 0 for the whole history.
+        pub history_deepening: Cell<u32>,
 
         pub directory_watcher_thread_mpsc_sender: RefCell<Option<mpsc::Sender<()>>>,
     }
@@ -142,15 +190,27 @@ mod imp {
             self.current_git_action.replace(current_git_action);
 
             match current_git_action {
-                ActionType::Push => self
-                    .obj()
-                    .do_git_action_with_auth_check(ActionType::Push, &""),
+                ActionType::Push => {
+                    self.obj()
+                        .do_git_action_with_auth_check(ActionType::Push, &"", &"")
+                }
+                ActionType::Publish => self.obj().emit_by_name::<()>("publish-dialog", &[]),
                 _ => self
                     .obj()
                     .try_do_git_action_without_auth_check(current_git_action),
             }
         }
 
+        #[template_callback]
+        fn cancel_transfer(&self, button: gtk::Button) {
+            self.is_transfer_cancelled
+                .borrow()
+                .store(true, Ordering::Relaxed);
+            button.set_sensitive(false);
+            self.transfer_progress_label
+                .set_label(&gettext("_Cancelling transfer"));
+        }
+
         #[template_callback]
         fn fetch(&self, _button: gtk::Button) {
             self.obj()
@@ -170,7 +230,39 @@ mod imp {
         fn push(&self, _button: gtk::Button) {
             self.obj().update_git_action_button_action(ActionType::Push);
             self.obj()
-                .do_git_action_with_auth_check(ActionType::Push, &"");
+                .do_git_action_with_auth_check(ActionType::Push, &"", &"");
+        }
+
+        #[template_callback]
+        fn deepen_history(&self, _button: gtk::Button) {
+            self.obj().emit_by_name::<()>("deepen-history-dialog", &[]);
+        }
+
+        #[template_callback]
+        fn pull_strategy_toggled(&self, button: gtk::CheckButton) {
+            // Only the newly activated button needs to be handled.
+            if !button.is_active() {
+                return;
+            }
+
+            let strategy = if self.rebase_strategy_button.is_active() {
+                PullStrategy::Rebase
+            } else {
+                PullStrategy::Merge
+            };
+
+            if let Some(repository) = &self.selected_repository.borrow().git_repository {
+                if RepositoryUtils::get_pull_strategy(&repository) == strategy {
+                    return;
+                }
+
+                if let Err(error) = RepositoryUtils::set_pull_strategy(&repository, strategy) {
+                    tracing::warn!("Could not save pull strategy: {}", error);
+
+                    self.obj()
+                        .show_toast(&gettext("_Could not save pull strategy"));
+                }
+            }
         }
 
         #[template_callback]
@@ -180,6 +272,60 @@ mod imp {
             self.branch_view
                 .fetch_all_branches(self.obj().get_selected_repository_path());
         }
+
+        #[template_callback]
+        fn create_branch_at_detached_head(&self, _banner: adw::Banner) {
+            let head_commit_id = match &self.selected_repository.borrow().git_repository {
+                Some(repository) => match RepositoryUtils::find_last_commit(repository) {
+                    Ok(commit) => commit.id().to_string(),
+                    Err(_) => return,
+                },
+                None => return,
+            };
+
+            self.obj()
+                .emit_by_name::<()>("create-branch-from-commit-dialog", &[&head_commit_id]);
+        }
+
+        #[template_callback]
+        fn stash_button_action(&self, _button: gtk::Button) {
+            self.main_view_stack.set_visible_child_name("stash view");
+
+            self.obj().update_stash_view();
+        }
+
+        #[template_callback]
+        fn file_browser_button_action(&self, _button: gtk::Button) {
+            self.main_view_stack
+                .set_visible_child_name("file browser view");
+
+            self.obj().open_file_browser(None);
+        }
+
+        #[template_callback]
+        fn tag_button_action(&self, _button: gtk::Button) {
+            self.main_view_stack.set_visible_child_name("tag view");
+
+            self.tag_view
+                .fetch_all_tags(self.obj().get_selected_repository_path());
+        }
+
+        #[template_callback]
+        fn remote_button_action(&self, _button: gtk::Button) {
+            self.main_view_stack.set_visible_child_name("remote view");
+
+            self.remote_view
+                .fetch_all_remotes(self.obj().get_selected_repository_path());
+        }
+
+        #[template_callback]
+        fn submodule_button_action(&self, _button: gtk::Button) {
+            self.main_view_stack
+                .set_visible_child_name("submodule view");
+
+            self.submodule_view
+                .fetch_all_submodules(self.obj().get_selected_repository_path());
+        }
     }
 
     // The central trait for subclassing a GObject
@@ -205,6 +351,15 @@ mod imp {
             self.obj().connect_sidebar_signals();
             self.obj().connect_commit_view_signals();
             self.obj().connect_branch_management_view_signals();
+            self.obj().connect_file_view_signals();
+            self.obj().connect_commit_detail_view_signals();
+            self.obj().connect_merge_conflict_view_signals();
+            self.obj().connect_rebase_view_signals();
+            self.obj().connect_stash_view_signals();
+            self.obj().connect_tag_management_view_signals();
+            self.obj().connect_remote_management_view_signals();
+            self.obj().connect_submodule_view_signals();
+            self.obj().connect_file_browser_view_signals();
 
             self.is_doing_git_action.set(false);
 
@@ -240,12 +395,42 @@ mod imp {
                             bool::static_type(),
                         ])
                         .build(),
+                    Signal::builder("integrate-fetched-branch-with-signing-key")
+                        .param_types([str::static_type(), str::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("conclude-merge-with-signing-key")
+                        .param_types([str::static_type(), str::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("apply-commit-with-signing-key")
+                        .param_types([
+                            str::static_type(),
+                            str::static_type(),
+                            str::static_type(),
+                            str::static_type(),
+                            bool::static_type(),
+                        ])
+                        .build(),
+                    Signal::builder("reset-to-commit-dialog")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("create-branch-from-commit-dialog")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("interactive-rebase-with-signing-key")
+                        .param_types([
+                            str::static_type(),
+                            str::static_type(),
+                            str::static_type(),
+                            bool::static_type(),
+                        ])
+                        .build(),
                     Signal::builder("missing-ssh-information")
                         .param_types([
                             str::static_type(),
                             str::static_type(),
                             ActionType::static_type(),
                             str::static_type(),
+                            str::static_type(),
                         ])
                         .build(),
                     Signal::builder("missing-https-information")
@@ -254,6 +439,7 @@ mod imp {
                             str::static_type(),
                             ActionType::static_type(),
                             str::static_type(),
+                            str::static_type(),
                         ])
                         .build(),
                     Signal::builder("ssh-passphrase-dialog")
@@ -262,11 +448,45 @@ mod imp {
                             str::static_type(),
                             ActionType::static_type(),
                             str::static_type(),
+                            str::static_type(),
                         ])
                         .build(),
                     Signal::builder("delete-branch")
                         .param_types([str::static_type(), str::static_type(), bool::static_type()])
                         .build(),
+                    Signal::builder("drop-stash-dialog")
+                        .param_types([u32::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("create-tag-dialog")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("create-tag-with-signing-key")
+                        .param_types([
+                            str::static_type(),
+                            str::static_type(),
+                            str::static_type(),
+                            str::static_type(),
+                            str::static_type(),
+                            str::static_type(),
+                        ])
+                        .build(),
+                    Signal::builder("delete-tag-dialog")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("edit-remote-dialog")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("delete-remote-dialog")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("deepen-history-dialog").build(),
+                    Signal::builder("publish-dialog").build(),
+                    Signal::builder("open-submodule")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("save-file-version-dialog")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
                 ]
             });
             SIGNALS.as_ref()
@@ -341,7 +561,7 @@ impl BagitRepositoryPage {
                 _sidebar: BagitCommitsSideBar
                 | {
                     win.imp().main_view_stack.set_visible_child_name("commit view");
-                    win.imp().commit_view.update_commit_view(win.imp().sidebar.imp().changed_files.borrow().get_number_of_selected_files());
+                    win.imp().commit_view.update_commit_view(win.imp().sidebar.imp().changed_files.borrow().get_number_of_staged_files());
 
                     if win.imp().flap.is_folded() {
                         win.imp().flap.set_reveal_flap(false);
@@ -388,9 +608,136 @@ impl BagitRepositoryPage {
             closure_local!(@watch self as win => move |
                 _sidebar: BagitCommitsSideBar,
                 parent_folder: &str,
-                file_name: &str
+                file_name: &str,
+                is_staged: bool
+                | {
+                    if win.imp().file_view.is_showing_staged_changes() == is_staged {
+                        win.try_showing_file_content(parent_folder, file_name);
+                    } else {
+                        // Changing the shown differences shows the file again:
+                        win.imp().file_view.set_file_information(parent_folder, file_name);
+                        win.imp().file_view.set_showing_staged_changes(is_staged);
+                    }
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "change-file-staging",
+            false,
+            closure_local!(@watch self as win => move |
+                _sidebar: BagitCommitsSideBar,
+                file_path: &str,
+                is_staging: bool
+                | {
+                    win.change_files_staging_and_update_ui(vec![file_path.to_string()], is_staging);
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "change-folder-staging",
+            false,
+            closure_local!(@watch self as win => move |
+                sidebar: BagitCommitsSideBar,
+                folder_path: &str,
+                is_staging: bool
+                | {
+                    let file_paths: Vec<String> = sidebar
+                        .imp()
+                        .changed_files
+                        .borrow()
+                        .get_files_of_folder(folder_path)
+                        .iter()
+                        .filter(|file| file.is_staged != is_staging)
+                        .map(|file| RepositoryUtils::build_path_of_file(&file.parent, &file.name))
+                        .collect();
+
+                    win.change_files_staging_and_update_ui(file_paths, is_staging);
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "change-all-files-staging",
+            false,
+            closure_local!(@watch self as win => move |
+                sidebar: BagitCommitsSideBar,
+                is_staging: bool
+                | {
+                    let file_paths: Vec<String> = sidebar
+                        .imp()
+                        .changed_files
+                        .borrow()
+                        .get_files_in_section(!is_staging)
+                        .iter()
+                        .map(|file| RepositoryUtils::build_path_of_file(&file.parent, &file.name))
+                        .collect();
+
+                    win.change_files_staging_and_update_ui(file_paths, is_staging);
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "commit-selected",
+            false,
+            closure_local!(@watch self as win => move |
+                _sidebar: BagitCommitsSideBar,
+                commit_id: &str
+                | {
+                    win.show_commit_details(commit_id);
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "cherry-pick-commit",
+            false,
+            closure_local!(@watch self as win => move |
+                _sidebar: BagitCommitsSideBar,
+                commit_id: &str
+                | {
+                    win.apply_commit_with_author_information(commit_id, false);
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "revert-commit",
+            false,
+            closure_local!(@watch self as win => move |
+                _sidebar: BagitCommitsSideBar,
+                commit_id: &str
+                | {
+                    win.apply_commit_with_author_information(commit_id, true);
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "reset-to-commit",
+            false,
+            closure_local!(@watch self as win => move |
+                _sidebar: BagitCommitsSideBar,
+                commit_id: &str
+                | {
+                    win.emit_by_name::<()>("reset-to-commit-dialog", &[&commit_id]);
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "create-branch-from-commit",
+            false,
+            closure_local!(@watch self as win => move |
+                _sidebar: BagitCommitsSideBar,
+                commit_id: &str
+                | {
+                    win.emit_by_name::<()>("create-branch-from-commit-dialog", &[&commit_id]);
+                }
+            ),
+        );
+        self.imp().sidebar.connect_closure(
+            "checkout-commit",
+            false,
+            closure_local!(@watch self as win => move |
+                _sidebar: BagitCommitsSideBar,
+                commit_id: &str
                 | {
-                    win.try_showing_file_content(parent_folder, file_name);
+                    win.checkout_commit_and_update_ui(commit_id);
                 }
             ),
         );
@@ -440,7 +787,7 @@ impl BagitRepositoryPage {
                         }
 
                         commit_view.update_commit_view(
-                            win.imp().sidebar.imp().changed_files.borrow().get_number_of_selected_files()
+                            win.imp().sidebar.imp().changed_files.borrow().get_number_of_staged_files()
                         );
                     }
 
@@ -474,7 +821,7 @@ impl BagitRepositoryPage {
                     };
 
                     commit_view.update_commit_view(
-                        win.imp().sidebar.imp().changed_files.borrow().get_number_of_selected_files()
+                        win.imp().sidebar.imp().changed_files.borrow().get_number_of_staged_files()
                     );
                 }
             ),
@@ -517,6 +864,40 @@ impl BagitRepositoryPage {
                 }
             ),
         );
+        self.imp().commit_view.connect_closure(
+            "amend-toggled",
+            false,
+            closure_local!(@watch self as win => move |
+                commit_view: BagitCommitView,
+                is_amending: bool
+                | {
+                    if !is_amending {
+                        commit_view.imp().message_row.set_text("");
+                        commit_view.imp().description_row.set_text("");
+                        return;
+                    }
+
+                    let last_commit_message = match &win.imp().selected_repository.borrow().git_repository {
+                        Some(repository) => RepositoryUtils::get_last_commit_message(&repository),
+                        None => return,
+                    };
+
+                    match last_commit_message {
+                        Ok((title, description)) => commit_view.show_amended_commit(
+                            &title,
+                            &description,
+                            win.imp().sidebar.is_last_commit_pushed(),
+                        ),
+                        Err(error) => {
+                            tracing::warn!("Could not get last commit message: {}", error);
+
+                            win.show_toast(&gettext("_No commit to amend"));
+                            commit_view.stop_amending();
+                        }
+                    }
+                }
+            ),
+        );
         self.imp().commit_view.connect_closure(
             "toggle-commit-button",
             false,
@@ -524,7 +905,7 @@ impl BagitRepositoryPage {
                 commit_view: BagitCommitView,
                 | {
                     commit_view.update_commit_view(
-                        win.imp().sidebar.imp().changed_files.borrow().get_number_of_selected_files()
+                        win.imp().sidebar.imp().changed_files.borrow().get_number_of_staged_files()
                     );
                 }
             ),
@@ -616,6 +997,7 @@ impl BagitRepositoryPage {
                         move |_event| {
                             win.update_changed_files();
                             win.update_file_view_if_necessary();
+                            win.update_merge_conflict_view();
 
                             Continue(true)
                         }
@@ -623,1263 +1005,3782 @@ impl BagitRepositoryPage {
         );
     }
 
-    /// Connects the signals sent by the branch management view.
-    pub fn connect_branch_management_view_signals(&self) {
-        self.imp().branch_view.connect_closure(
-            "change-branch",
+    /// Connects the signals sent by the file view.
+    pub fn connect_file_view_signals(&self) {
+        self.imp().file_view.connect_closure(
+            "diff-source-changed",
             false,
             closure_local!(@watch self as win => move |
-            _branch_view: BagitBranchManagementView,
-            branch_name: &str,
-            is_remote: bool
-            | {
-                let has_changed_files = win.has_changed_files();
-                win.imp().obj()
-                    .emit_by_name::<()>("select-branch", &[
-                        &branch_name,
-                        &is_remote,
-                        &has_changed_files
-                    ]);
-            }),
+                file_view: BagitFileView
+                | {
+                    let current_shown_file_info = file_view.get_current_shown_file_information();
+                    win.try_showing_file_content(
+                        &current_shown_file_info.0,
+                        &current_shown_file_info.1,
+                    );
+                }
+            ),
         );
 
-        self.imp().branch_view.connect_closure(
-            "create-branch",
+        self.imp().file_view.connect_closure(
+            "change-lines-staging",
             false,
             closure_local!(@watch self as win => move |
-            _branch_view: BagitBranchManagementView,
-            branch_name: &str,
-            | {
-                let selected_repository = win.get_selected_repository();
+                file_view: BagitFileView,
+                whole_hunks: bool
+                | {
+                    win.change_lines_staging_and_update_ui(&file_view, whole_hunks);
+                }
+            ),
+        );
 
-                if selected_repository.git_repository.is_none() {
-                    return;
+        self.connect_file_mode_signals(&self.imp().file_view);
+    }
+
+    /// Connects the signals sent by a file view to show the blame and the history of its file.
+    fn connect_file_mode_signals(&self, file_view: &BagitFileView) {
+        file_view.connect_closure(
+            "show-blame",
+            false,
+            closure_local!(@watch self as win => move |
+                file_view: BagitFileView
+                | {
+                    win.show_blame_of_file(&file_view);
                 }
+            ),
+        );
 
-                match RepositoryUtils::create_branch(&selected_repository.git_repository.as_ref().unwrap(), branch_name) {
-                    Ok(_) => {
-                        win.checkout_branch_and_update_ui(
-                            branch_name.to_string(),
-                            false
-                        );
-                        win.imp().branch_view.imp().new_branch_row.set_text("");
-                    },
-                    Err(error) => win.show_toast(&error.to_string())
+        file_view.connect_closure(
+            "show-file-history",
+            false,
+            closure_local!(@watch self as win => move |
+                file_view: BagitFileView
+                | {
+                    win.show_history_of_file(&file_view);
                 }
-            }),
+            ),
         );
 
-        self.imp().branch_view.connect_closure(
-            "delete-branch",
+        file_view.connect_closure(
+            "commit-selected",
             false,
             closure_local!(@watch self as win => move |
-            _branch_view: BagitBranchManagementView,
-            branch_name: &str,
+                _file_view: BagitFileView,
+                commit_id: &str
+                | {
+                    win.show_commit_details(commit_id);
+                }
+            ),
+        );
+    }
+
+    /// Connects the signals sent by the commit detail view.
+    pub fn connect_commit_detail_view_signals(&self) {
+        self.imp().commit_detail_view.connect_closure(
+            "commit-file-selected",
+            false,
+            closure_local!(@watch self as win => move |
+                commit_detail_view: BagitCommitDetailView,
+                commit_id: &str,
+                file_path: &str
+                | {
+                    let selected_repository = win.get_selected_repository();
+
+                    if selected_repository.git_repository.is_none() {
+                        return;
+                    }
+
+                    match RepositoryUtils::get_diff_of_file_in_commit(
+                        &selected_repository.git_repository.unwrap(),
+                        commit_id,
+                        file_path,
+                    ) {
+                        Ok(file_diff) => commit_detail_view.show_file_diff(file_path, file_diff),
+                        Err(error) => {
+                            tracing::warn!("Could not get the differences of a commit file: {}", error);
+                            win.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+                        }
+                    }
+                }
+            ),
+        );
+        self.imp().commit_detail_view.connect_closure(
+            "create-tag",
+            false,
+            closure_local!(@watch self as win => move |
+                _commit_detail_view: BagitCommitDetailView,
+                commit_id: &str
+                | {
+                    win.emit_by_name::<()>("create-tag-dialog", &[&commit_id]);
+                }
+            ),
+        );
+        self.imp().commit_detail_view.connect_closure(
+            "rebase-from-commit",
+            false,
+            closure_local!(@watch self as win => move |
+                _commit_detail_view: BagitCommitDetailView,
+                commit_id: &str
+                | {
+                    win.open_rebase_editor(commit_id);
+                }
+            ),
+        );
+        self.imp().commit_detail_view.connect_closure(
+            "browse-commit-files",
+            false,
+            closure_local!(@watch self as win => move |
+                _commit_detail_view: BagitCommitDetailView,
+                commit_id: &str
+                | {
+                    win.imp()
+                        .main_view_stack
+                        .set_visible_child_name("file browser view");
+                    win.open_file_browser(Some(commit_id.to_string()));
+                }
+            ),
+        );
+    }
+
+    /// Used to connect signals sent by the merge conflict view.
+    pub fn connect_merge_conflict_view_signals(&self) {
+        self.imp().merge_conflict_view.connect_closure(
+            "resolve-conflict",
+            false,
+            closure_local!(@watch self as win => move |
+                _merge_conflict_view: BagitMergeConflictView,
+                relative_path: &str,
+                content: &str,
+                is_deleting_file: bool
+                | {
+                    win.resolve_merge_conflict_and_update_ui(relative_path, content, is_deleting_file);
+                }
+            ),
+        );
+        self.imp().merge_conflict_view.connect_closure(
+            "resolve-conflict-with-side",
+            false,
+            closure_local!(@watch self as win => move |
+                _merge_conflict_view: BagitMergeConflictView,
+                relative_path: &str,
+                is_using_ours: bool
+                | {
+                    let side = if is_using_ours {
+                        ConflictSide::Ours
+                    } else {
+                        ConflictSide::Theirs
+                    };
+                    win.resolve_merge_conflict_with_side_and_update_ui(relative_path, side);
+                }
+            ),
+        );
+        self.imp().merge_conflict_view.connect_closure(
+            "conclude-merge",
+            false,
+            closure_local!(@watch self as win => move |
+                _merge_conflict_view: BagitMergeConflictView
+                | {
+                    // The conflicts of a step of an interactive rebase are concluded by continuing the rebase:
+                    if win.is_rebasing() {
+                        win.continue_interactive_rebase_with_author_information();
+                        return;
+                    }
+
+                    let (author, author_email, signing_key) = win.get_commit_author_information();
+
+                    if signing_key.is_empty() {
+                        win.conclude_merge_and_update_ui(&author, &author_email, "", "");
+                    } else {
+                        win.emit_by_name::<()>(
+                            "conclude-merge-with-signing-key",
+                            &[&author, &author_email, &signing_key],
+                        );
+                    }
+                }
+            ),
+        );
+        self.imp().merge_conflict_view.connect_closure(
+            "abort-merge",
+            false,
+            closure_local!(@watch self as win => move |
+                _merge_conflict_view: BagitMergeConflictView
+                | {
+                    if win.is_rebasing() {
+                        win.abort_interactive_rebase_and_update_ui();
+                    } else {
+                        win.abort_merge_and_update_ui();
+                    }
+                }
+            ),
+        );
+    }
+
+    /// Used to connect signals sent by the rebase view.
+    pub fn connect_rebase_view_signals(&self) {
+        self.imp().rebase_view.connect_closure(
+            "start-rebase",
+            false,
+            closure_local!(@watch self as win => move |
+                _rebase_view: BagitRebaseView
+                | {
+                    let (author, author_email, signing_key) = win.get_commit_author_information();
+
+                    if signing_key.is_empty() {
+                        win.start_interactive_rebase_and_update_ui(&author, &author_email, "", "");
+                    } else {
+                        win.emit_by_name::<()>(
+                            "interactive-rebase-with-signing-key",
+                            &[&author, &author_email, &signing_key, &false],
+                        );
+                    }
+                }
+            ),
+        );
+        self.imp().rebase_view.connect_closure(
+            "cancel-rebase",
+            false,
+            closure_local!(@watch self as win => move |
+                _rebase_view: BagitRebaseView
+                | {
+                    win.imp()
+                        .main_view_stack
+                        .set_visible_child_name("hello page");
+                }
+            ),
+        );
+        self.imp().rebase_view.connect_closure(
+            "continue-rebase",
+            false,
+            closure_local!(@watch self as win => move |
+                _rebase_view: BagitRebaseView
+                | {
+                    win.continue_interactive_rebase_with_author_information();
+                }
+            ),
+        );
+        self.imp().rebase_view.connect_closure(
+            "abort-rebase",
+            false,
+            closure_local!(@watch self as win => move |
+                _rebase_view: BagitRebaseView
+                | {
+                    win.abort_interactive_rebase_and_update_ui();
+                }
+            ),
+        );
+    }
+
+    /// Connects the signals sent by the stash view.
+    pub fn connect_stash_view_signals(&self) {
+        self.imp().stash_view.connect_closure(
+            "save-stash",
+            false,
+            closure_local!(@watch self as win => move |
+                _stash_view: BagitStashView,
+                message: &str,
+                include_untracked: bool,
+                only_selected_files: bool
+                | {
+                    win.save_stash_and_update_ui(message, include_untracked, only_selected_files);
+                }
+            ),
+        );
+        self.imp().stash_view.connect_closure(
+            "apply-stash",
+            false,
+            closure_local!(@watch self as win => move |
+                _stash_view: BagitStashView,
+                index: u32
+                | {
+                    win.apply_stash_and_update_ui(index as usize, false);
+                }
+            ),
+        );
+        self.imp().stash_view.connect_closure(
+            "pop-stash",
+            false,
+            closure_local!(@watch self as win => move |
+                _stash_view: BagitStashView,
+                index: u32
+                | {
+                    win.apply_stash_and_update_ui(index as usize, true);
+                }
+            ),
+        );
+        self.imp().stash_view.connect_closure(
+            "drop-stash",
+            false,
+            closure_local!(@watch self as win => move |
+                _stash_view: BagitStashView,
+                index: u32,
+                message: &str
+                | {
+                    win.emit_by_name::<()>("drop-stash-dialog", &[&index, &message]);
+                }
+            ),
+        );
+        self.imp().stash_view.connect_closure(
+            "stash-selected",
+            false,
+            closure_local!(@watch self as win => move |
+                stash_view: BagitStashView,
+                stash_id: &str
+                | {
+                    let selected_repository = win.get_selected_repository();
+
+                    if selected_repository.git_repository.is_none() {
+                        return;
+                    }
+
+                    match RepositoryUtils::get_stash_changed_files(
+                        &selected_repository.git_repository.unwrap(),
+                        stash_id,
+                    ) {
+                        Ok(changed_files) => stash_view.show_stash_files(stash_id, changed_files),
+                        Err(error) => {
+                            tracing::warn!("Could not get the files changed by a stash: {}", error);
+                            win.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+                        }
+                    }
+                }
+            ),
+        );
+        self.imp().stash_view.connect_closure(
+            "stash-file-selected",
+            false,
+            closure_local!(@watch self as win => move |
+                stash_view: BagitStashView,
+                stash_id: &str,
+                file_path: &str
+                | {
+                    let selected_repository = win.get_selected_repository();
+
+                    if selected_repository.git_repository.is_none() {
+                        return;
+                    }
+
+                    match RepositoryUtils::get_diff_of_file_in_stash(
+                        &selected_repository.git_repository.unwrap(),
+                        stash_id,
+                        file_path,
+                    ) {
+                        Ok(file_diff) => stash_view.show_file_diff(file_path, file_diff),
+                        Err(error) => {
+                            tracing::warn!("Could not get the differences of a stashed file: {}", error);
+                            win.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+                        }
+                    }
+                }
+            ),
+        );
+    }
+
+    /// Connects the signals sent by the tag management view.
+    pub fn connect_tag_management_view_signals(&self) {
+        self.imp().tag_view.connect_closure(
+            "push-tag",
+            false,
+            closure_local!(@watch self as win => move |
+                _tag_view: BagitTagManagementView,
+                tag_name: &str
+                | {
+                    win.do_git_action_with_auth_check(ActionType::PushTags, tag_name, &"");
+                }
+            ),
+        );
+        self.imp().tag_view.connect_closure(
+            "delete-tag",
+            false,
+            closure_local!(@watch self as win => move |
+                _tag_view: BagitTagManagementView,
+                tag_name: &str
+                | {
+                    win.emit_by_name::<()>("delete-tag-dialog", &[&tag_name]);
+                }
+            ),
+        );
+    }
+
+    /// Connects the signals sent by the remote management view.
+    pub fn connect_remote_management_view_signals(&self) {
+        self.imp().remote_view.connect_closure(
+            "add-remote",
+            false,
+            closure_local!(@watch self as win => move |
+                _remote_view: BagitRemoteManagementView,
+                remote_name: &str,
+                remote_url: &str
+                | {
+                    win.add_remote_and_update_ui(remote_name, remote_url);
+                }
+            ),
+        );
+        self.imp().remote_view.connect_closure(
+            "edit-remote",
+            false,
+            closure_local!(@watch self as win => move |
+                _remote_view: BagitRemoteManagementView,
+                remote_name: &str,
+                remote_url: &str
+                | {
+                    win.emit_by_name::<()>("edit-remote-dialog", &[&remote_name, &remote_url]);
+                }
+            ),
+        );
+        self.imp().remote_view.connect_closure(
+            "delete-remote",
+            false,
+            closure_local!(@watch self as win => move |
+                _remote_view: BagitRemoteManagementView,
+                remote_name: &str
+                | {
+                    win.emit_by_name::<()>("delete-remote-dialog", &[&remote_name]);
+                }
+            ),
+        );
+        self.imp().remote_view.connect_closure(
+            "fetch-remote",
+            false,
+            closure_local!(@watch self as win => move |
+                _remote_view: BagitRemoteManagementView,
+                remote_name: &str
+                | {
+                    win.do_git_action_with_auth_check(ActionType::Fetch, &"", remote_name);
+                }
+            ),
+        );
+        self.imp().remote_view.connect_closure(
+            "fetch-all-remotes",
+            false,
+            closure_local!(@watch self as win => move |
+                _remote_view: BagitRemoteManagementView
+                | {
+                    win.do_git_action_with_auth_check(ActionType::FetchAllRemotes, &"", &"");
+                }
+            ),
+        );
+        self.imp().remote_view.connect_closure(
+            "push-to-remote",
+            false,
+            closure_local!(@watch self as win => move |
+                _remote_view: BagitRemoteManagementView,
+                remote_name: &str
+                | {
+                    win.do_git_action_with_auth_check(ActionType::Push, &"", remote_name);
+                }
+            ),
+        );
+    }
+
+    /// Connects the signals sent by the submodule view.
+    pub fn connect_submodule_view_signals(&self) {
+        self.imp().submodule_view.connect_closure(
+            "init-submodule",
+            false,
+            closure_local!(@watch self as win => move |
+                _submodule_view: BagitSubmoduleView,
+                submodule_name: &str
+                | {
+                    win.init_submodules_and_update_ui(submodule_name);
+                }
+            ),
+        );
+        self.imp().submodule_view.connect_closure(
+            "update-submodule",
+            false,
+            closure_local!(@watch self as win => move |
+                _submodule_view: BagitSubmoduleView,
+                submodule_name: &str
+                | {
+                    win.do_git_action_with_auth_check(ActionType::UpdateSubmodules, submodule_name, &"");
+                }
+            ),
+        );
+        self.imp().submodule_view.connect_closure(
+            "sync-submodule",
+            false,
+            closure_local!(@watch self as win => move |
+                _submodule_view: BagitSubmoduleView,
+                submodule_name: &str
+                | {
+                    win.sync_submodules_and_update_ui(submodule_name);
+                }
+            ),
+        );
+        self.imp().submodule_view.connect_closure(
+            "open-submodule",
+            false,
+            closure_local!(@watch self as win => move |
+                _submodule_view: BagitSubmoduleView,
+                submodule_path: &str
+                | {
+                    win.emit_by_name::<()>("open-submodule", &[&submodule_path]);
+                }
+            ),
+        );
+    }
+
+    /// Connects the signals sent by the file browser view.
+    pub fn connect_file_browser_view_signals(&self) {
+        self.imp().file_browser_view.connect_closure(
+            "folder-requested",
+            false,
+            closure_local!(@watch self as win => move |
+                file_browser_view: BagitFileBrowserView,
+                revision: &str,
+                folder_path: &str
+                | {
+                    let selected_repository = win.get_selected_repository();
+
+                    if selected_repository.git_repository.is_none() {
+                        return;
+                    }
+
+                    match RepositoryUtils::get_tree_entries(
+                        &selected_repository.git_repository.unwrap(),
+                        revision,
+                        folder_path,
+                    ) {
+                        Ok(entries) => file_browser_view.show_folder_entries(folder_path, entries),
+                        Err(error) => {
+                            tracing::warn!("Could not get the files of a folder: {}", error);
+                            win.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+                        }
+                    }
+                }
+            ),
+        );
+        self.imp().file_browser_view.connect_closure(
+            "file-selected",
+            false,
+            closure_local!(@watch self as win => move |
+                file_browser_view: BagitFileBrowserView,
+                revision: &str,
+                file_path: &str
+                | {
+                    let selected_repository = win.get_selected_repository();
+
+                    if selected_repository.git_repository.is_none() {
+                        return;
+                    }
+
+                    match RepositoryUtils::get_file_content_at_revision(
+                        &selected_repository.git_repository.unwrap(),
+                        revision,
+                        file_path,
+                    ) {
+                        Ok(content) => file_browser_view.show_file_content(file_path, content),
+                        Err(error) => {
+                            tracing::warn!("Could not get the content of a file: {}", error);
+                            win.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+                        }
+                    }
+                }
+            ),
+        );
+        self.imp().file_browser_view.connect_closure(
+            "save-file",
+            false,
+            closure_local!(@watch self as win => move |
+                _file_browser_view: BagitFileBrowserView,
+                revision: &str,
+                file_path: &str
+                | {
+                    win.emit_by_name::<()>("save-file-version-dialog", &[&revision, &file_path]);
+                }
+            ),
+        );
+
+        self.connect_file_mode_signals(&self.imp().file_browser_view.file_view());
+    }
+
+    /// Connects the signals sent by the branch management view.
+    pub fn connect_branch_management_view_signals(&self) {
+        self.imp().branch_view.connect_closure(
+            "change-branch",
+            false,
+            closure_local!(@watch self as win => move |
+            _branch_view: BagitBranchManagementView,
+            branch_name: &str,
+            is_remote: bool
+            | {
+                let has_changed_files = win.has_changed_files();
+                win.imp().obj()
+                    .emit_by_name::<()>("select-branch", &[
+                        &branch_name,
+                        &is_remote,
+                        &has_changed_files
+                    ]);
+            }),
+        );
+
+        self.imp().branch_view.connect_closure(
+            "create-branch",
+            false,
+            closure_local!(@watch self as win => move |
+            _branch_view: BagitBranchManagementView,
+            branch_name: &str,
+            | {
+                let selected_repository = win.get_selected_repository();
+
+                if selected_repository.git_repository.is_none() {
+                    return;
+                }
+
+                match RepositoryUtils::create_branch(&selected_repository.git_repository.as_ref().unwrap(), branch_name) {
+                    Ok(_) => {
+                        win.checkout_branch_and_update_ui(
+                            branch_name.to_string(),
+                            false
+                        );
+                        win.imp().branch_view.imp().new_branch_row.set_text("");
+                    },
+                    Err(error) => win.show_toast(&error.to_string())
+                }
+            }),
+        );
+
+        self.imp().branch_view.connect_closure(
+            "delete-branch",
+            false,
+            closure_local!(@watch self as win => move |
+            _branch_view: BagitBranchManagementView,
+            branch_name: &str,
             is_remote: bool
             | {
                 let selected_repository = win.get_selected_repository();
 
-                win.emit_by_name::<()>("delete-branch", &[
-                        &selected_repository.user_repository.path,
-                        &branch_name,
-                        &is_remote
-                    ]);
-            }),
+                win.emit_by_name::<()>("delete-branch", &[
+                        &selected_repository.user_repository.path,
+                        &branch_name,
+                        &is_remote
+                    ]);
+            }),
+        );
+    }
+
+    /// Used to initialize the repository page with a selected repository.
+    pub fn init_repository_page(&self, repository: SelectedRepository) {
+        self.create_file_watcher(Path::new(&repository.user_repository.path));
+
+        self.imp()
+            .main_view_stack
+            .set_visible_child_name("hello page");
+
+        let status_page_title = format!(
+            "{} {}.",
+            gettext("_You are on"),
+            repository.user_repository.name
+        );
+
+        self.imp().status_page.set_title(&status_page_title);
+
+        self.init_git_action_button();
+        self.imp().sidebar.init_commits_sidebar();
+        self.imp()
+            .sidebar
+            .init_commit_list(repository.user_repository.path.clone());
+        self.imp().commit_view.init_commit_view();
+
+        self.imp()
+            .repository_name_label
+            .set_label(&repository.user_repository.name);
+
+        if repository.user_repository.git_profile_id.is_some() {
+            let selected_profile;
+
+            let app_database = self.imp().app_database.take();
+
+            match app_database
+                .get_git_profile_from_id(repository.user_repository.git_profile_id.unwrap())
+            {
+                Ok(profile) => selected_profile = profile,
+                Err(error) => {
+                    // TODO: Show error (maybe with a toast).
+
+                    tracing::warn!("Could not get Git profile from ID: {}", error);
+
+                    return;
+                }
+            }
+
+            self.imp().app_database.replace(app_database);
+
+            match selected_profile {
+                Some(profile) => {
+                    self.imp()
+                        .commit_view
+                        .set_and_show_selected_profile(profile);
+                }
+                None => self.imp().commit_view.show_no_selected_profile(),
+            }
+        } else {
+            self.imp().commit_view.show_no_selected_profile();
+        }
+
+        self.imp().selected_repository.replace(repository);
+
+        self.init_pull_strategy_buttons();
+        self.update_deepen_history_button();
+        self.update_commits_sidebar();
+        self.update_branch_name();
+        self.imp().branch_view.init_branch_view();
+
+        if !self.show_merge_conflicts_if_merging() {
+            self.show_stopped_rebase_if_rebasing();
+        }
+    }
+
+    /// Used to update the repository page information.
+    pub fn update_repository_page(&self) {
+        self.update_commits_sidebar();
+        self.imp().commit_view.update_git_profiles_list();
+        self.update_branch_name();
+
+        self.imp()
+            .branch_view
+            .fetch_all_branches(self.get_selected_repository_path());
+    }
+
+    /// Updates the changed files and commit history.
+    pub fn update_commits_sidebar(&self) {
+        self.imp()
+            .sidebar
+            .refresh_commit_list_if_needed(self.get_selected_repository_path());
+
+        self.update_changed_files();
+    }
+
+    /**
+     * Used to update changed files list.
+     */
+    fn update_changed_files(&self) {
+        let borrowed_repo = self.imp().selected_repository.borrow();
+        if borrowed_repo.git_repository.is_some() {
+            let repo = borrowed_repo.git_repository.as_ref().unwrap();
+            match repo.statuses(None) {
+                Ok(statuses) => {
+                    self.imp().sidebar.clear_changed_ui_files_list();
+                    let (staged_hash_map, unstaged_hash_map) = self
+                        .imp()
+                        .sidebar
+                        .build_hash_maps(statuses, &RepositoryUtils::get_submodule_paths(repo));
+
+                    for (is_staged, hash_map) in
+                        [(true, staged_hash_map), (false, unstaged_hash_map)]
+                    {
+                        if hash_map.is_empty() {
+                            continue;
+                        }
+
+                        let total_files = hash_map.values().map(|files| files.len()).sum();
+                        self.imp()
+                            .sidebar
+                            .generate_section_header(is_staged, total_files);
+
+                        for key in hash_map.keys().sorted() {
+                            let value = &hash_map[key];
+                            if key != "" {
+                                let borrowed_changed_folders =
+                                    self.imp().sidebar.imp().changed_files.borrow();
+                                let folder = borrowed_changed_folders
+                                    .get_changed_folder_from_list(&key)
+                                    .unwrap();
+                                self.imp().sidebar.generate_folder(
+                                    folder,
+                                    (&value).to_vec(),
+                                    is_staged,
+                                );
+                            }
+                        }
+                        if hash_map.contains_key("") {
+                            for file in &hash_map[""] {
+                                let new_row = self.imp().sidebar.generate_changed_file(file, 4, 8);
+                                self.imp().sidebar.imp().menu.append(&new_row);
+                            }
+                        }
+                    }
+                }
+                Err(_) => {}
+            };
+        }
+    }
+
+    /// Used to show the checked out branch, or that HEAD is detached.
+    pub fn update_branch_name(&self) {
+        self.update_detached_head_state();
+
+        let branch_name = match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) if RepositoryUtils::is_head_detached(repository) => {
+                gettext("_Detached HEAD")
+            }
+            Some(repository) => match RepositoryUtils::get_current_branch_name(repository) {
+                Ok(branch_name) => branch_name,
+                Err(_) => return,
+            },
+            None => return,
+        };
+
+        let button_box = self
+            .imp()
+            .branch_button
+            .first_child()
+            .and_downcast::<gtk::Box>();
+
+        if button_box.is_none() {
+            return;
+        }
+
+        let button_label = button_box
+            .unwrap()
+            .last_child()
+            .and_downcast::<gtk::Label>();
+
+        if let Some(label) = button_label {
+            label.set_text(&branch_name);
+        }
+    }
+
+    /// Used to check if HEAD of the selected repository is detached.
+    fn is_head_detached(&self) -> bool {
+        return match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) => RepositoryUtils::is_head_detached(repository),
+            None => false,
+        };
+    }
+
+    /// Used to show the commit of a detached HEAD, on which a branch can be created.
+    /// Push and pull are not available until a branch is checked out.
+    fn update_detached_head_state(&self) {
+        let head_commit_id = match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) if RepositoryUtils::is_head_detached(repository) => {
+                RepositoryUtils::find_last_commit(repository)
+                    .map(|commit| commit.id().to_string())
+                    .ok()
+            }
+            _ => None,
+        };
+
+        match head_commit_id {
+            Some(commit_id) => {
+                self.imp()
+                    .detached_head_banner
+                    .set_title(&gettext("_HEAD detached at commit").replace("{}", &commit_id[..7]));
+                self.imp().detached_head_banner.set_revealed(true);
+            }
+            None => self.imp().detached_head_banner.set_revealed(false),
+        }
+
+        self.try_to_find_correct_git_button_action();
+    }
+
+    /// Used to show the pull strategy of the selected repository.
+    fn init_pull_strategy_buttons(&self) {
+        let strategy = match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) => RepositoryUtils::get_pull_strategy(&repository),
+            None => PullStrategy::default(),
+        };
+
+        match strategy {
+            PullStrategy::Merge => self.imp().merge_strategy_button.set_active(true),
+            PullStrategy::Rebase => self.imp().rebase_strategy_button.set_active(true),
+        }
+    }
+
+    /// Initialize the git action button.
+    pub fn init_git_action_button(&self) {
+        self.update_push_indication_box(0);
+        self.update_pull_indication_box(0);
+        self.try_to_find_correct_git_button_action();
+    }
+
+    /// Used to try to find the correct git button action.
+    /// The default action is fetch, and the preffered one is push.
+    /// Without any remote, the repository can only be published.
+    pub fn try_to_find_correct_git_button_action(&self) {
+        if self.get_remote_names().is_empty() {
+            self.update_git_action_button_action(ActionType::Publish);
+            return;
+        }
+        if self.imp().push_indication_box.is_visible() {
+            self.update_git_action_button_action(ActionType::Push);
+            return;
+        }
+        if self.imp().pull_indication_box.is_visible() {
+            self.update_git_action_button_action(ActionType::Pull);
+            return;
+        }
+        self.update_git_action_button_action(ActionType::Fetch);
+    }
+
+    /// Used to update the popover menu buttons visibility.
+    /// The actions needing a remote are hidden while the repository is not published.
+    fn update_popover_menu_buttons(&self, action_type: ActionType) {
+        let is_published = action_type != ActionType::Publish;
+
+        // Without a checked out branch, there is nothing to push or pull:
+        let is_head_detached = self.is_head_detached();
+        let unavailable_reason = gettext("_Push and pull need a checked out branch");
+        for button in [&self.imp().pull_button, &self.imp().push_button] {
+            button.set_sensitive(!is_head_detached);
+            button.set_tooltip_text(is_head_detached.then_some(unavailable_reason.as_str()));
+        }
+
+        self.imp()
+            .fetch_button
+            .set_visible(is_published && !(action_type == ActionType::Fetch));
+        self.imp()
+            .pull_button
+            .set_visible(is_published && !(action_type == ActionType::Pull));
+        self.imp()
+            .push_button
+            .set_visible(is_published && !(action_type == ActionType::Push));
+    }
+
+    /// Used to update the git action button text and current action.
+    /// The default git action is the fetch;
+    fn update_git_action_button_action(&self, action_type: ActionType) {
+        let action_type = match action_type {
+            ActionType::Push | ActionType::Pull if self.is_head_detached() => ActionType::Fetch,
+            action_type => action_type,
+        };
+
+        match action_type {
+            ActionType::Push => {
+                self.imp().current_git_action.replace(ActionType::Push);
+
+                self.imp()
+                    .git_action_label
+                    .get()
+                    .set_label(&gettext("_Push"));
+            }
+            ActionType::Pull => {
+                self.imp().current_git_action.replace(ActionType::Pull);
+
+                self.imp()
+                    .git_action_label
+                    .get()
+                    .set_label(&gettext("_Pull"));
+            }
+            ActionType::Publish => {
+                self.imp().current_git_action.replace(ActionType::Publish);
+
+                self.imp()
+                    .git_action_label
+                    .get()
+                    .set_label(&gettext("_Publish"));
+            }
+            _ => {
+                self.imp().current_git_action.replace(ActionType::Fetch);
+
+                self.imp()
+                    .git_action_label
+                    .get()
+                    .set_label(&gettext("_Fetch"));
+            }
+        }
+        self.update_popover_menu_buttons(action_type);
+    }
+
+    /// Used to update the push indication box
+    fn update_push_indication_box(&self, total_commits_to_push: i64) {
+        self.imp()
+            .push_indication_box
+            .set_visible(total_commits_to_push != 0);
+
+        self.imp()
+            .git_action_push_number
+            .set_label(&total_commits_to_push.to_string());
+    }
+
+    /// Used to update the pull indication box
+    fn update_pull_indication_box(&self, total_commits_to_pull: i64) {
+        self.imp()
+            .pull_indication_box
+            .set_visible(total_commits_to_pull != 0);
+
+        self.imp()
+            .git_action_pull_number
+            .set_label(&total_commits_to_pull.to_string());
+    }
+
+    /// Activates or deactivates the action button.
+    pub fn toggle_git_action_button(&self, is_active: bool) {
+        self.imp().git_action_button.set_sensitive(is_active);
+        self.imp().git_action_spinner.set_visible(!is_active);
+    }
+
+    /// Shows the progress of a new transfer with a remote, which can be cancelled.
+    /// Returns the cancellation flag and the sender of the progress to give to the transfer.
+    fn start_transfer(&self) -> (Arc<AtomicBool>, glib::Sender<TransferProgress>) {
+        let is_cancelled = Arc::new(AtomicBool::new(false));
+        self.imp()
+            .is_transfer_cancelled
+            .replace(is_cancelled.clone());
+
+        self.imp()
+            .transfer_progress_label
+            .set_label(&gettext("_Connecting to remote"));
+        self.imp().transfer_progress_label.set_visible(true);
+        self.imp().cancel_transfer_button.set_sensitive(true);
+        self.imp().cancel_transfer_button.set_visible(true);
+
+        let (sender, receiver) = MainContext::channel::<TransferProgress>(Priority::default());
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+                move |progress| {
+                    if !win.imp().is_transfer_cancelled.borrow().load(Ordering::Relaxed) {
+                        win.imp().transfer_progress_label.set_label(&progress.get_description());
+                    }
+                    Continue(true)
+                }
+            ),
+        );
+
+        return (is_cancelled, sender);
+    }
+
+    /// Hides the progress of the finished transfer.
+    /// Returns true when the transfer was cancelled by the user.
+    fn stop_transfer(&self) -> bool {
+        self.imp().transfer_progress_label.set_visible(false);
+        self.imp().cancel_transfer_button.set_visible(false);
+
+        return self
+            .imp()
+            .is_transfer_cancelled
+            .borrow()
+            .load(Ordering::Relaxed);
+    }
+
+    /// Updates the git action button.
+    pub fn update_git_action_button(&self, fetch_result: FetchResult) {
+        self.update_push_indication_box(fetch_result.total_commits_to_push);
+        self.update_pull_indication_box(fetch_result.total_commits_to_pull);
+
+        if fetch_result.total_commits_to_push == 0 && fetch_result.total_commits_to_pull == 0 {
+            self.update_git_action_button_action(ActionType::Fetch);
+            self.toggle_git_action_button(true);
+            return;
+        }
+
+        if fetch_result.total_commits_to_push > 0 {
+            self.update_git_action_button_action(ActionType::Push);
+        } else {
+            self.update_git_action_button_action(ActionType::Pull);
+        }
+
+        self.toggle_git_action_button(true);
+    }
+
+    /// Commits files and update UI.
+    pub fn commit_files_and_update_ui(
+        &self,
+        author: &str,
+        author_email: &str,
+        message: &str,
+        signing_key: &str,
+        passphrase: &str,
+        description: &str,
+        need_to_save_profile: bool,
+    ) {
+        let borrowed_repo = self.imp().selected_repository.take();
+        if borrowed_repo.git_repository.is_some() {
+            let git_repository = borrowed_repo.git_repository.as_ref().unwrap();
+            // We save the profile if we need to :
+            if need_to_save_profile {
+                let new_profile_id = Uuid::new_v4();
+
+                // We make sure that the profile name is unique:
+                let same_profile_name_number;
+
+                let app_database = self.imp().app_database.take();
+
+                match app_database
+                    .get_number_of_git_profiles_with_name(&author, &new_profile_id.to_string())
+                {
+                    Ok(number) => same_profile_name_number = number,
+                    Err(error) => {
+                        // TODO: Show error (maybe with a toast).
+
+                        tracing::warn!("Could not get number of git profiles with name: {}", error);
+
+                        return;
+                    }
+                }
+
+                let final_profil_name: String = if same_profile_name_number != 0 {
+                    let new_name = format!("{} ({})", author, same_profile_name_number);
+                    new_name
+                } else {
+                    author.to_string()
+                };
+
+                let new_profile = BagitGitProfile::new(
+                    new_profile_id.clone(),
+                    final_profil_name,
+                    author_email.to_string(),
+                    author.to_string(),
+                    String::from(""),
+                    String::from(""),
+                    signing_key.to_string(),
+                );
+
+                if let Err(error) = app_database.add_git_profile(&new_profile) {
+                    tracing::warn!("Could not add Git profile: {}", error);
+
+                    let toast = adw::Toast::new(&gettext("_Could not add Git profile"));
+                    self.imp().toast_overlay.add_toast(toast);
+                }
+
+                // We set the new profile to the repository:
+                if let Err(error) = app_database.change_git_profile_of_repository(
+                    borrowed_repo.user_repository.repository_id,
+                    Some(new_profile_id),
+                ) {
+                    tracing::warn!("Could not change Git profile of repository: {}", error);
+
+                    let toast = adw::Toast::new(&gettext("_Could not change Git profile"));
+                    self.imp().toast_overlay.add_toast(toast);
+                }
+
+                self.imp().app_database.replace(app_database);
+
+                self.imp()
+                    .commit_view
+                    .imp()
+                    .profile_mode
+                    .replace(ProfileMode::SelectedProfile(new_profile));
+
+                // We update the view:
+                self.imp().commit_view.update_git_profiles_list();
+            }
+            let is_amending = self.imp().commit_view.is_amending();
+
+            let result = if is_amending {
+                RepositoryUtils::amend_last_commit(
+                    git_repository,
+                    message,
+                    description,
+                    author,
+                    author_email,
+                    self.imp().commit_view.is_resetting_author(),
+                    signing_key,
+                    passphrase,
+                )
+            } else {
+                RepositoryUtils::commit_files(
+                    git_repository,
+                    message,
+                    description,
+                    author,
+                    author_email,
+                    signing_key,
+                    passphrase,
+                )
+            };
+
+            match result {
+                Ok(_) => {
+                    let toast = adw::Toast::new(&if is_amending {
+                        gettext("_Commit amended")
+                    } else {
+                        gettext("_Commit created successfully")
+                    });
+                    self.imp().toast_overlay.add_toast(toast);
+                    self.imp().commit_view.stop_amending();
+                    // We remove the last commit message:
+                    self.imp().commit_view.imp().message_row.set_text("");
+                    self.imp().commit_view.imp().description_row.set_text("");
+
+                    self.imp().selected_repository.replace(borrowed_repo);
+                    self.update_commits_sidebar();
+                    self.imp().commit_view.update_commit_view(0);
+                }
+                Err(error) => {
+                    self.imp().selected_repository.replace(borrowed_repo);
+                    self.emit_by_name("error", &[&error.to_string()])
+                }
+            }
+        }
+    }
+
+    /// Check if we can pull (if we have no changed files).
+    fn check_if_can_pull(&self) -> bool {
+        let changed_files_number = self
+            .imp()
+            .sidebar
+            .imp()
+            .changed_files
+            .borrow()
+            .get_number_of_changed_files();
+
+        return changed_files_number == 0;
+    }
+
+    /// Used to define wich git action we need to do:
+    pub fn do_git_action_with_information(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+        action_type: ActionType,
+        branch_name: String,
+        remote_name: String,
+    ) {
+        match action_type {
+            ActionType::Fetch => self.fetch_repository_checked_out_branch_and_update_ui(
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                if remote_name.is_empty() {
+                    vec![]
+                } else {
+                    vec![remote_name]
+                },
+            ),
+            ActionType::FetchAllRemotes => self.fetch_repository_checked_out_branch_and_update_ui(
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                self.get_remote_names(),
+            ),
+            ActionType::Push => self.push_and_update_ui(
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                remote_name,
+            ),
+            ActionType::Pull => {
+                self.pull_and_update_ui(username, password, private_key_path, passphrase)
+            }
+            ActionType::DeleteRemoteBranch => self.delete_branch_and_update_ui(
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                branch_name,
+            ),
+            ActionType::PushTags => self.push_tags_and_update_ui(
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                branch_name,
+                remote_name,
+            ),
+            ActionType::DeepenHistory => {
+                self.deepen_history_and_update_ui(username, password, private_key_path, passphrase)
+            }
+            ActionType::DeleteRemoteTag => self.delete_remote_tag_and_update_ui(
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                branch_name,
+                remote_name,
+            ),
+            ActionType::UpdateSubmodules => self.update_submodules_and_update_ui(
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                branch_name,
+            ),
+            ActionType::Publish => self.publish_and_update_ui(
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                remote_name,
+                branch_name,
+            ),
+        };
+    }
+
+    fn try_do_git_action_without_auth_check(&self, action_type: ActionType) {
+        let profile_mode = self.imp().commit_view.imp().profile_mode.take();
+
+        self.imp()
+            .commit_view
+            .imp()
+            .profile_mode
+            .replace(profile_mode.clone());
+
+        // If we want to pull, we need to check that we don't have changed files:
+        if action_type == ActionType::Pull && !self.check_if_can_pull() {
+            let toast = adw::Toast::new(&gettext("_Changed files when pull"));
+            self.imp().toast_overlay.add_toast(toast);
+            self.toggle_git_action_button(true);
+            self.try_to_find_correct_git_button_action();
+            return;
+        }
+
+        match action_type {
+            ActionType::Fetch => return self.try_fetch_without_auth_and_update_ui(),
+            ActionType::Pull => return self.try_pull_without_auth_and_update_ui(),
+            _ => {}
+        };
+    }
+
+    /// Retrieves the remembered passphrase of an SSH private key.
+    /// An empty passphrase is returned when the key is left to the ssh-agent.
+    fn retrieve_saved_ssh_passphrase(&self, private_key_path: &str) -> Option<String> {
+        if let Some(passphrase) = PassphraseCache::get_ssh_passphrase(private_key_path) {
+            return Some(passphrase);
+        }
+
+        if PassphraseCache::is_agent_delegation_enabled()
+            && AgentUtils::is_ssh_key_in_agent(private_key_path)
+        {
+            return Some(String::new());
+        }
+
+        return None;
+    }
+
+    /// Retrieves the names of the remotes of the selected repository.
+    fn get_remote_names(&self) -> Vec<String> {
+        let selected_repository = self.get_selected_repository();
+
+        match &selected_repository.git_repository {
+            Some(repository) => match RepositoryUtils::get_remotes(repository) {
+                Ok(remotes) => remotes.into_iter().map(|remote| remote.name).collect(),
+                Err(error) => {
+                    tracing::warn!("Could not retrieve remotes: {}", error);
+                    vec![]
+                }
+            },
+            None => vec![],
+        }
+    }
+
+    /// Does a git action that need authentification.
+    /// The branch name parameter is used when wanting to delete a remote branch,
+    /// or holds the tag name when pushing or deleting a remote tag,
+    /// or the submodule name when updating submodules,
+    /// or the URL of the new remote when publishing the repository.
+    /// The remote name is the remote targeted by the action, an empty name meaning the default remote.
+    pub fn do_git_action_with_auth_check(
+        &self,
+        action_type: ActionType,
+        remote_branch_name: &str,
+        remote_name: &str,
+    ) {
+        let selected_repository = self.get_selected_repository();
+        let profile_mode = self.imp().commit_view.imp().profile_mode.take();
+
+        self.imp()
+            .commit_view
+            .imp()
+            .profile_mode
+            .replace(profile_mode.clone());
+
+        // If we want to pull, we need to check that we don't have changed files:
+        if action_type == ActionType::Pull && !self.check_if_can_pull() {
+            let toast = adw::Toast::new(&gettext("_Changed files when pull"));
+            self.imp().toast_overlay.add_toast(toast);
+            self.toggle_git_action_button(true);
+            self.try_to_find_correct_git_button_action();
+            return;
+        }
+
+        match &selected_repository.git_repository {
+            Some(repository) => {
+                // When deleting a remote branch, the remote is the one of the branch:
+                let remote_name = if action_type == ActionType::DeleteRemoteBranch {
+                    match RepositoryUtils::split_remote_branch_name(repository, remote_branch_name)
+                    {
+                        Ok((remote_name, _)) => remote_name,
+                        Err(error) => {
+                            self.emit_by_name::<()>("error", &[&error.message().to_string()]);
+                            return;
+                        }
+                    }
+                } else {
+                    remote_name.to_string()
+                };
+
+                // Submodules are reached through their own URL, like the remote of a publication:
+                let clone_mode = if action_type == ActionType::UpdateSubmodules {
+                    RepositoryUtils::get_clone_mode_of_submodule(&repository, remote_branch_name)
+                } else if action_type == ActionType::Publish {
+                    Ok(RepositoryUtils::get_clone_mode_of_url(remote_branch_name))
+                } else {
+                    RepositoryUtils::get_clone_mode_of_remote(&repository, &remote_name)
+                };
+
+                match clone_mode {
+                    Ok(clone_mode) => match profile_mode {
+                        ProfileMode::SelectedProfile(mut profile) => {
+                            // The password of the profile cannot be read while the credential store is locked:
+                            if matches!(clone_mode, CloneMode::HTTPS)
+                                && !SecretStoreUtils::is_open()
+                            {
+                                let toast = adw::Toast::new(&gettext("_Credential store locked"));
+                                self.imp().toast_overlay.add_toast(toast);
+                                self.toggle_git_action_button(true);
+                                self.try_to_find_correct_git_button_action();
+                                SecretStoreUtils::request_unlock();
+                                return;
+                            }
+
+                            // The password is only retrieved from the secret store when it is needed:
+                            if matches!(clone_mode, CloneMode::HTTPS) {
+                                profile.password =
+                                    AppDatabase::retrieve_password_of_git_profile(&profile);
+                            }
+
+                            if !profile.does_profile_has_information_for_actions(&clone_mode) {
+                                match clone_mode {
+                                    CloneMode::SSH => self.emit_by_name::<()>(
+                                        "missing-ssh-information",
+                                        &[
+                                            &profile.username,
+                                            &profile.private_key_path,
+                                            &action_type,
+                                            &remote_branch_name,
+                                            &remote_name,
+                                        ],
+                                    ),
+                                    CloneMode::HTTPS => self.emit_by_name::<()>(
+                                        "missing-https-information",
+                                        &[
+                                            &profile.username,
+                                            &profile.password,
+                                            &action_type,
+                                            &remote_branch_name,
+                                            &remote_name,
+                                        ],
+                                    ),
+                                };
+                            } else {
+                                // TODO: MARKER
+                                match clone_mode {
+                                    CloneMode::SSH => {
+                                        match self.retrieve_saved_ssh_passphrase(
+                                            &profile.private_key_path,
+                                        ) {
+                                            Some(passphrase) => self
+                                                .do_git_action_with_information(
+                                                    profile.username,
+                                                    profile.password,
+                                                    profile.private_key_path,
+                                                    passphrase.to_owned(),
+                                                    action_type,
+                                                    remote_branch_name.to_string(),
+                                                    remote_name.clone(),
+                                                ),
+                                            None => self.emit_by_name::<()>(
+                                                "ssh-passphrase-dialog",
+                                                &[
+                                                    &profile.username,
+                                                    &profile.private_key_path,
+                                                    &action_type,
+                                                    &remote_branch_name,
+                                                    &remote_name,
+                                                ],
+                                            ),
+                                        }
+                                    }
+                                    CloneMode::HTTPS => self.do_git_action_with_information(
+                                        profile.username,
+                                        profile.password,
+                                        profile.private_key_path,
+                                        "".to_string(),
+                                        action_type,
+                                        String::from(remote_branch_name),
+                                        remote_name.clone(),
+                                    ),
+                                };
+                            }
+                        }
+                        _ => {
+                            match clone_mode {
+                                CloneMode::SSH => self.emit_by_name::<()>(
+                                    "missing-ssh-information",
+                                    &[
+                                        &self.imp().commit_view.imp().author_row.text().trim(),
+                                        &"",
+                                        &action_type,
+                                        &remote_branch_name,
+                                        &remote_name,
+                                    ],
+                                ),
+                                CloneMode::HTTPS => self.emit_by_name::<()>(
+                                    "missing-https-information",
+                                    &[
+                                        &self.imp().commit_view.imp().author_row.text().trim(),
+                                        &"",
+                                        &action_type,
+                                        &remote_branch_name,
+                                        &remote_name,
+                                    ],
+                                ),
+                            };
+                        }
+                    },
+                    Err(error) => self.emit_by_name::<()>("error", &[&error.to_string()]),
+                };
+            }
+            None => {}
+        }
+    }
+
+    /// Used to push and update ui.
+    /// An empty remote name means that the default remote is used.
+    pub fn push_and_update_ui(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+        remote_name: String,
+    ) {
+        let selected_repository = self.get_selected_repository();
+
+        let (error_sender, error_receiver) = MainContext::channel::<String>(Priority::default());
+        let (result_sender, result_receiver) = MainContext::channel::<()>(Priority::default());
+
+        self.toggle_git_action_button(false);
+
+        thread::spawn(move || {
+            let error_sender = error_sender.clone();
+            let result_sender = result_sender.clone();
+
+            match RepositoryUtils::push(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                &remote_name,
+                username,
+                password,
+                private_key_path,
+                passphrase,
+            ) {
+                Ok(_) => result_sender
+                    .send(())
+                    .expect("Could not send result through channel"),
+                Err(error) => error_sender
+                    .send(error.to_string())
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        error_receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+                        move |error| {
+                            win.emit_by_name::<()>("error", &[&error.to_string()]);
+
+                            win.try_to_find_correct_git_button_action();
+                            win.toggle_git_action_button(true);
+
+                            win.update_repository_page();
+                            Continue(true)
+                        }
+            ),
+        );
+
+        result_receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |_| {
+                            let toast = adw::Toast::new(&gettext("_Commits pushed"));
+                            win.imp().toast_overlay.add_toast(toast);
+
+                            win.update_push_indication_box(0);
+                            win.try_to_find_correct_git_button_action();
+                            win.toggle_git_action_button(true);
+
+                            win.imp().sidebar.imp().first_commit_oid_of_commit_list.take();
+                            win.update_repository_page();
+                            Continue(true)
+                        }
+            ),
+        );
+    }
+
+    /// Used to publish the repository to a new remote and update UI.
+    pub fn publish_and_update_ui(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+        remote_name: String,
+        url: String,
+    ) {
+        let selected_repository = self.get_selected_repository();
+        let (result_sender, result_receiver) =
+            MainContext::channel::<Result<(), String>>(Priority::default());
+
+        self.toggle_git_action_button(false);
+
+        thread::spawn(move || {
+            let result_sender = result_sender.clone();
+
+            match RepositoryUtils::publish(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                &remote_name,
+                &url,
+                username,
+                password,
+                private_key_path,
+                passphrase,
+            ) {
+                Ok(_) => result_sender
+                    .send(Ok(()))
+                    .expect("Could not send result through channel"),
+                Err(error) => result_sender
+                    .send(Err(error.message().to_string()))
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        result_receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |result| {
+                            match result {
+                                Ok(_) => win.show_toast(&gettext("_Repository published")),
+                                Err(error) => win.emit_by_name::<()>("error", &[&error]),
+                            }
+
+                            win.update_push_indication_box(0);
+                            win.try_to_find_correct_git_button_action();
+                            win.toggle_git_action_button(true);
+
+                            win.imp().sidebar.imp().first_commit_oid_of_commit_list.take();
+                            win.update_repository_page();
+                            Continue(true)
+                        }
+            ),
+        );
+    }
+
+    /// Used to push and update ui.
+    pub fn pull_and_update_ui(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) {
+        let selected_repository = self.get_selected_repository();
+
+        let (error_sender, error_receiver) = MainContext::channel::<String>(Priority::default());
+        let (result_sender, result_receiver) = MainContext::channel::<bool>(Priority::default());
+
+        self.toggle_git_action_button(false);
+
+        thread::spawn(move || {
+            let error_sender = error_sender.clone();
+            let result_sender = result_sender.clone();
+
+            match RepositoryUtils::pull(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                username,
+                password,
+                private_key_path,
+                passphrase,
+            ) {
+                Ok(need_integration) => result_sender
+                    .send(need_integration)
+                    .expect("Could not send result through channel"),
+                Err(error) => error_sender
+                    .send(error.to_string())
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        error_receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+                        move |error| {
+                            win.emit_by_name::<()>("error", &[&error.to_string()]);
+
+                            win.try_to_find_correct_git_button_action();
+                            win.toggle_git_action_button(true);
+
+                            win.update_commits_sidebar();
+                            Continue(true)
+                        }
+            ),
+        );
+
+        result_receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |need_integration| {
+                            if need_integration {
+                                win.integrate_fetched_branch();
+                                return Continue(true);
+                            }
+
+                            let toast = adw::Toast::new(&gettext("_Remote branch pulled"));
+                            win.imp().toast_overlay.add_toast(toast);
+
+                            win.update_pull_indication_box(0);
+                            win.try_to_find_correct_git_button_action();
+                            win.toggle_git_action_button(true);
+
+                            win.update_commits_sidebar();
+                            Continue(true)
+                        }
+            ),
+        );
+    }
+
+    pub fn delete_branch_and_update_ui(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+        branch_name: String,
+    ) {
+        let selected_repository = self.get_selected_repository();
+        let (result_sender, result_receiver) =
+            MainContext::channel::<Result<(), String>>(Priority::default());
+
+        self.toggle_git_action_button(false);
+
+        thread::spawn(move || {
+            let result_sender = result_sender.clone();
+
+            match RepositoryUtils::delete_remote_branch(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                &branch_name,
+                username,
+                password,
+                private_key_path,
+                passphrase,
+            ) {
+                Ok(_) => result_sender
+                    .send(Ok(()))
+                    .expect("Could not send result through channel"),
+                Err(error) => result_sender
+                    .send(Err(error.to_string()))
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        result_receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |result| {
+                            match result {
+                                Ok(_) => {
+                                    win.imp()
+                                        .branch_view
+                                        .fetch_all_branches(selected_repository.user_repository.path.clone());
+                                    win.show_toast(&gettext("_Branch deleted"));
+                                    win.imp().sidebar.init_commit_list(selected_repository.user_repository.path.clone());
+                                    win.toggle_git_action_button(true);
+                                },
+                                Err(error) => {
+                                    win.emit_by_name::<()>("error", &[&error.to_string()]);
+                                    win.toggle_git_action_button(true);
+                                }
+                            }
+                            Continue(true)
+                        }
+            ),
+        );
+    }
+
+    /// Used to push and update ui.
+    pub fn try_pull_without_auth_and_update_ui(&self) {
+        let selected_repository = self.get_selected_repository();
+
+        let (error_sender, error_receiver) =
+            MainContext::channel::<git2::Error>(Priority::default());
+        let (result_sender, result_receiver) = MainContext::channel::<bool>(Priority::default());
+
+        self.toggle_git_action_button(false);
+
+        thread::spawn(move || {
+            let error_sender = error_sender.clone();
+            let result_sender = result_sender.clone();
+
+            match RepositoryUtils::pull(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ) {
+                Ok(need_integration) => result_sender
+                    .send(need_integration)
+                    .expect("Could not send result through channel"),
+                Err(error) => error_sender
+                    .send(error)
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        error_receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+                        move |error| {
+                            if (error.class() == git2::ErrorClass::Http)
+                                || (error.class() == git2::ErrorClass::Ssh)
+                            {
+                                win.do_git_action_with_auth_check(ActionType::Pull, &"", &"");
+                            } else {
+                                win.emit_by_name::<()>("error", &[&error.to_string()]);
+
+                                win.try_to_find_correct_git_button_action();
+                                win.toggle_git_action_button(true);
+
+                                win.update_commits_sidebar();
+                            }
+                            Continue(true)
+                        }
+            ),
+        );
+
+        result_receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |need_integration| {
+                            if need_integration {
+                                win.integrate_fetched_branch();
+                                return Continue(true);
+                            }
+
+                            let toast = adw::Toast::new(&gettext("_Remote branch pulled"));
+                            win.imp().toast_overlay.add_toast(toast);
+
+                            win.update_pull_indication_box(0);
+                            win.try_to_find_correct_git_button_action();
+                            win.toggle_git_action_button(true);
+
+                            win.update_commits_sidebar();
+                            Continue(true)
+                        }
+            ),
+        );
+    }
+
+    /// Retrieves the author, author email and signing key to use for the commits created by the application.
+    /// They come from the selected profile, or from the commit view when no profile is selected.
+    fn get_commit_author_information(&self) -> (String, String, String) {
+        let profile_mode = self.imp().commit_view.imp().profile_mode.take();
+
+        self.imp()
+            .commit_view
+            .imp()
+            .profile_mode
+            .replace(profile_mode.clone());
+
+        match profile_mode {
+            ProfileMode::SelectedProfile(profile) => {
+                (profile.username, profile.email, profile.signing_key)
+            }
+            _ => (
+                self.imp()
+                    .commit_view
+                    .imp()
+                    .author_row
+                    .text()
+                    .trim()
+                    .to_string(),
+                self.imp()
+                    .commit_view
+                    .imp()
+                    .author_email_row
+                    .text()
+                    .trim()
+                    .to_string(),
+                self.imp()
+                    .commit_view
+                    .imp()
+                    .signing_key_row
+                    .text()
+                    .trim()
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// Used to integrate the fetched branch when it diverged from the checked-out one.
+    /// The commits created are signed when the selected profile has a signing key.
+    fn integrate_fetched_branch(&self) {
+        let (author, author_email, signing_key) = self.get_commit_author_information();
+
+        if signing_key.is_empty() {
+            self.integrate_fetched_branch_and_update_ui(&author, &author_email, "", "");
+        } else {
+            self.emit_by_name::<()>(
+                "integrate-fetched-branch-with-signing-key",
+                &[&author, &author_email, &signing_key],
+            );
+        }
+    }
+
+    /// Used to give the git actions back when the integration of the fetched branch is cancelled.
+    pub fn cancel_fetched_branch_integration(&self) {
+        self.update_indication_boxes_from_fetched_branch();
+        self.try_to_find_correct_git_button_action();
+        self.toggle_git_action_button(true);
+    }
+
+    /// Used to show the commits to push and to pull while the fetched branch is not integrated.
+    fn update_indication_boxes_from_fetched_branch(&self) {
+        if let Some(repository) = &self.imp().selected_repository.borrow().git_repository {
+            match RepositoryUtils::get_ahead_behind_fetched_branch(repository) {
+                Ok((ahead, behind)) => {
+                    self.update_push_indication_box(ahead as i64);
+                    self.update_pull_indication_box(behind as i64);
+                }
+                Err(error) => {
+                    tracing::warn!("Could not compare with the fetched branch: {}", error)
+                }
+            }
+        }
+    }
+
+    /// Used to merge or rebase the fetched branch, depending on the repository's pull strategy, and update UI.
+    pub fn integrate_fetched_branch_and_update_ui(
+        &self,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) {
+        let selected_repository = self.get_selected_repository();
+
+        let strategy = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::get_pull_strategy(&repository),
+            None => return,
+        };
+
+        let author = String::from(author);
+        let author_email = String::from(author_email);
+        let signing_key = String::from(signing_key);
+        let passphrase = String::from(passphrase);
+
+        let (sender, receiver) = MainContext::channel::<Result<(), String>>(Priority::default());
+
+        self.toggle_git_action_button(false);
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            match RepositoryUtils::integrate_fetched_branch(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                strategy,
+                &author,
+                &author_email,
+                &signing_key,
+                &passphrase,
+            ) {
+                Ok(_) => sender
+                    .send(Ok(()))
+                    .expect("Could not send result through channel"),
+                Err(error) => sender
+                    .send(Err(error.message().to_string()))
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |result| {
+                            match result {
+                                Ok(_) => {
+                                    let toast_message = match strategy {
+                                        PullStrategy::Merge => gettext("_Remote branch merged"),
+                                        PullStrategy::Rebase => gettext("_Remote branch rebased"),
+                                    };
+                                    win.show_toast(&toast_message);
+
+                                    win.update_pull_indication_box(0);
+                                    win.imp().sidebar.imp().first_commit_oid_of_commit_list.take();
+                                }
+                                Err(error) => {
+                                    win.emit_by_name::<()>("error", &[&error]);
+                                    win.update_indication_boxes_from_fetched_branch();
+                                }
+                            }
+
+                            win.try_to_find_correct_git_button_action();
+                            win.toggle_git_action_button(true);
+
+                            win.update_repository_page();
+                            win.show_merge_conflicts_if_merging();
+                            Continue(true)
+                        }
+            ),
+        );
+    }
+
+    /// Used to discard a file and update UI.
+    pub fn discard_file_and_update_ui(&self, file_path: &str) {
+        let selected_repository = self.get_selected_repository();
+        match RepositoryUtils::discard_one_file(
+            &selected_repository.git_repository.unwrap(),
+            file_path,
+        ) {
+            Ok(_) => {
+                self.update_commits_sidebar();
+                // We update the file view because if the changed file was the viewed file, we need to make sure that it is no longer viewable.
+                self.update_file_view_if_necessary();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.to_string()]),
+        };
+    }
+
+    /// Used to change the current branch.
+    pub fn checkout_branch_and_update_ui(&self, branch_to_checkout_to: String, is_remote: bool) {
+        let selected_repository = self.get_selected_repository();
+
+        let (sender, receiver) = MainContext::channel::<Result<(), String>>(Priority::default());
+
+        self.imp().git_action_button.set_sensitive(false);
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            match RepositoryUtils::checkout_branch(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                &branch_to_checkout_to,
+                is_remote,
+            ) {
+                Ok(_) => sender
+                    .send(Ok(()))
+                    .expect("Could not send result through channel"),
+                Err(error) => sender
+                    .send(Err(error.to_string()))
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        let repo_path = selected_repository.user_repository.path;
+
+        receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |result| {
+                            win.imp().git_action_button.set_sensitive(true);
+
+                            match result {
+                                Ok(_) => win.update_pull_indication_box(0),
+                                Err(error) => win.emit_by_name::<()>("error", &[&error.to_string()])
+                            }
+
+                            win.update_commits_sidebar();
+                            win.update_branch_name();
+                            win.imp()
+                                .branch_view
+                                .fetch_all_branches(repo_path.clone());
+                            Continue(true)
+                        }
+            ),
+        );
+    }
+
+    /// Fetches the given remotes, or the default one when none is given, and update ui.
+    fn fetch_repository_checked_out_branch_and_update_ui(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+        remote_names: Vec<String>,
+    ) {
+        let selected_repository = self.get_selected_repository();
+        let selected_repository_path = selected_repository.user_repository.path;
+
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
+        let git_repo = selected_repository.git_repository.unwrap();
+
+        let (sender, receiver) =
+            MainContext::channel::<Result<FetchResult, git2::Error>>(Priority::default());
+
+        self.toggle_git_action_button(false);
+        let (is_cancelled, progress_sender) = self.start_transfer();
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            let fetch = fetch_checked_out_branch(
+                &git_repo,
+                &remote_names,
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                is_cancelled,
+                move |progress| {
+                    let _ = progress_sender.send(progress);
+                },
+            );
+
+            sender.send(fetch).expect("Could not send through channel");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+                    move |fetch| {
+                        let is_cancelled = win.stop_transfer();
+
+                        match fetch {
+                            Ok(fetch_result) => {
+                                win.update_git_action_button(fetch_result);
+                                win.imp().branch_view.fetch_all_branches(selected_repository_path.clone());
+                            },
+                            Err(_) if is_cancelled => {
+                                win.try_to_find_correct_git_button_action();
+                                win.toggle_git_action_button(true);
+                                win.show_toast(&gettext("_Transfer cancelled"));
+                            },
+                            Err(error) => {
+                                win.try_to_find_correct_git_button_action();
+                                win.toggle_git_action_button(true);
+                                win.emit_by_name::<()>("error", &[&error.to_string()])
+                            },
+                        }
+                        Continue(true)
+                    }
+            ),
+        );
+    }
+
+    /// Used to discard a folder and update UI.
+    pub fn discard_folder_and_update_ui(&self, folder_path: &str) {
+        let selected_repository = self.get_selected_repository();
+
+        let folder_files: Vec<ChangedFile>;
+        {
+            let file_tree = self.imp().sidebar.imp().changed_files.borrow();
+            folder_files = file_tree.get_files_of_folder(folder_path);
+        }
+
+        match RepositoryUtils::discard_folder(
+            &selected_repository.git_repository.unwrap(),
+            &folder_files,
+        ) {
+            Ok(_) => {
+                self.update_commits_sidebar();
+                // We update the file view because one of the folder's files was the viewed file, we need to make sure that it is no longer viewable.
+                self.update_file_view_if_necessary();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.to_string()]),
+        };
+    }
+
+    /// Fetches the repository checked out branch, and update ui.
+    fn try_fetch_without_auth_and_update_ui(&self) {
+        let selected_repository = self.get_selected_repository();
+        let selected_repository_path = selected_repository.user_repository.path;
+
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
+        let git_repo = selected_repository.git_repository.unwrap();
+
+        let (sender, receiver) =
+            MainContext::channel::<Result<FetchResult, git2::Error>>(Priority::default());
+
+        self.toggle_git_action_button(false);
+        let (is_cancelled, progress_sender) = self.start_transfer();
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            let fetch = fetch_checked_out_branch(
+                &git_repo,
+                &vec![],
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                is_cancelled,
+                move |progress| {
+                    let _ = progress_sender.send(progress);
+                },
+            );
+
+            sender.send(fetch).expect("Could not send through channel");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+                        move |fetch| {
+                            let is_cancelled = win.stop_transfer();
+
+                            match fetch {
+                                Ok(fetch_result) => {
+                                    win.update_git_action_button(fetch_result);
+                                    win.imp().branch_view.fetch_all_branches(selected_repository_path.clone());
+                                },
+                                Err(_) if is_cancelled => {
+                                    win.update_commits_sidebar();
+                                    win.try_to_find_correct_git_button_action();
+                                    win.toggle_git_action_button(true);
+                                    win.show_toast(&gettext("_Transfer cancelled"));
+                                },
+                                Err(error) => {
+                                    // TODO: Manage errors.
+                                    if (error.class() == git2::ErrorClass::Http)
+                                        || (error.class() == git2::ErrorClass::Ssh)
+                                    {
+                                        win.do_git_action_with_auth_check(ActionType::Fetch, &"", &"");
+                                    } else {
+                                        win.emit_by_name::<()>("error", &[&error.to_string()]);
+                                        win.update_commits_sidebar();
+                                        win.try_to_find_correct_git_button_action();
+                                        win.toggle_git_action_button(true);
+                                    }
+                                },
+                            }
+                            Continue(true)
+                        }
+            ),
         );
     }
 
-    /// Used to initialize the repository page with a selected repository.
-    pub fn init_repository_page(&self, repository: SelectedRepository) {
-        self.create_file_watcher(Path::new(&repository.user_repository.path));
+    /// Show a toast with a given message.
+    pub fn show_toast(&self, toast_message: &str) {
+        let toast = adw::Toast::new(toast_message);
+        self.imp().toast_overlay.add_toast(toast);
+    }
+
+    /// Used to checked if there is changed files in the current branch.
+    /// If an error occurs, it will return true by default.
+    pub fn has_changed_files(&self) -> bool {
+        let selected_repository = self.get_selected_repository();
+        let git_repo = selected_repository.git_repository;
 
-        self.imp()
-            .main_view_stack
-            .set_visible_child_name("hello page");
+        match git_repo {
+            Some(repo) => RepositoryUtils::has_changed_files(&repo),
+            None => false,
+        }
+    }
 
-        let status_page_title = format!(
-            "{} {}.",
-            gettext("_You are on"),
-            repository.user_repository.name
+    /// Retrieves the selected repository.
+    pub fn get_selected_repository(&self) -> SelectedRepository {
+        let selected_repository = self.imp().selected_repository.take();
+        self.imp().selected_repository.replace(
+            SelectedRepository::try_fetching_selected_repository(
+                &selected_repository.user_repository,
+            )
+            .unwrap(),
         );
+        selected_repository
+    }
 
-        self.imp().status_page.set_title(&status_page_title);
-
-        self.init_git_action_button();
-        self.imp().sidebar.init_commits_sidebar();
-        self.imp()
-            .sidebar
-            .init_commit_list(repository.user_repository.path.clone());
-        self.imp().commit_view.init_commit_view();
+    /// Retrieves the path of the repository.
+    pub fn get_selected_repository_path(&self) -> String {
+        let selected_repository = self.get_selected_repository();
 
-        self.imp()
-            .repository_name_label
-            .set_label(&repository.user_repository.name);
+        return selected_repository.user_repository.path;
+    }
 
-        if repository.user_repository.git_profile_id.is_some() {
-            let selected_profile;
+    /// Try to show the content of a file.
+    pub fn try_showing_file_content(&self, parent_folder: &str, file_name: &str) {
+        let relative_path = RepositoryUtils::build_path_of_file(&parent_folder, &file_name);
 
-            let app_database = self.imp().app_database.take();
+        // Conflicted files are resolved in the merge conflict view:
+        let is_conflicted = match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) => match repository.status_file(Path::new(&relative_path)) {
+                Ok(status) => status.is_conflicted(),
+                Err(_) => false,
+            },
+            None => false,
+        };
 
-            match app_database
-                .get_git_profile_from_id(repository.user_repository.git_profile_id.unwrap())
-            {
-                Ok(profile) => selected_profile = profile,
-                Err(error) => {
-                    // TODO: Show error (maybe with a toast).
+        if is_conflicted && self.show_merge_conflicts_if_merging() {
+            self.imp()
+                .merge_conflict_view
+                .show_conflict_of_file(&relative_path);
+            return;
+        }
 
-                    tracing::warn!("Could not get Git profile from ID: {}", error);
+        let selected_repository = self.get_selected_repository();
+        let git_repo = selected_repository.git_repository;
 
-                    return;
-                }
-            }
+        if git_repo.is_none() {
+            return;
+        }
 
-            self.imp().app_database.replace(app_database);
+        let found_repo = git_repo.unwrap();
 
-            match selected_profile {
-                Some(profile) => {
-                    self.imp()
-                        .commit_view
-                        .set_and_show_selected_profile(profile);
-                }
-                None => self.imp().commit_view.show_no_selected_profile(),
+        match self.imp().file_view.define_how_to_show_file_content(
+            &found_repo,
+            &parent_folder,
+            &file_name,
+        ) {
+            Ok(_) => {
+                self.imp()
+                    .main_view_stack
+                    .set_visible_child_name("file view");
+                self.imp()
+                    .file_view
+                    .set_file_information(&parent_folder, &file_name)
             }
-        } else {
-            self.imp().commit_view.show_no_selected_profile();
+            Err(error) => self.emit_by_name("error", &[&error.to_string()]),
         }
+    }
 
-        self.imp().selected_repository.replace(repository);
+    /// Loads the details of a commit and shows them.
+    pub fn show_commit_details(&self, commit_id: &str) {
+        let repository_path = self.get_selected_repository_path();
+        let commit_id = commit_id.to_string();
 
-        self.update_commits_sidebar();
-        self.update_branch_name();
-        self.imp().branch_view.init_branch_view();
-    }
+        let (sender, receiver) =
+            MainContext::channel::<Result<CommitDetails, String>>(Priority::default());
 
-    /// Used to update the repository page information.
-    pub fn update_repository_page(&self) {
-        self.update_commits_sidebar();
-        self.imp().commit_view.update_git_profiles_list();
-        self.update_branch_name();
+        thread::spawn(move || {
+            let sender = sender.clone();
 
-        self.imp()
-            .branch_view
-            .fetch_all_branches(self.get_selected_repository_path());
-    }
+            let details = match git2::Repository::open(repository_path) {
+                Ok(repository) => match get_commit_details(&repository, &commit_id) {
+                    Ok(details) => Ok(details),
+                    Err(error) => Err(error.to_string()),
+                },
+                Err(error) => Err(error.to_string()),
+            };
 
-    /// Updates the changed files and commit history.
-    pub fn update_commits_sidebar(&self) {
-        self.imp()
-            .sidebar
-            .refresh_commit_list_if_needed(self.get_selected_repository_path());
+            sender
+                .send(details)
+                .expect("Could not send through channel");
+        });
 
-        self.update_changed_files();
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+                move |details| {
+                    match details {
+                        Ok(details) => {
+                            win.imp().commit_detail_view.show_commit_details(details);
+                            win.imp()
+                                .main_view_stack
+                                .set_visible_child_name("commit detail view");
+                        }
+                        Err(error) => {
+                            tracing::warn!("Could not get the details of a commit: {}", error);
+                            win.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+                        }
+                    }
+
+                    Continue(true)
+                }
+            ),
+        );
     }
 
-    /**
-     * Used to update changed files list.
-     */
-    fn update_changed_files(&self) {
-        let borrowed_repo = self.imp().selected_repository.borrow();
-        if borrowed_repo.git_repository.is_some() {
-            let repo = borrowed_repo.git_repository.as_ref().unwrap();
-            match repo.statuses(None) {
-                Ok(statuses) => {
-                    self.imp().sidebar.clear_changed_ui_files_list();
-                    let hash_map = self.imp().sidebar.build_hash_map(statuses);
-
-                    for key in hash_map.keys().sorted() {
-                        let value = &hash_map[key];
-                        if key != "" {
-                            let borrowed_changed_folders =
-                                self.imp().sidebar.imp().changed_files.borrow();
-                            let folder = borrowed_changed_folders
-                                .get_changed_folder_from_list(&key)
-                                .unwrap();
-                            self.imp()
-                                .sidebar
-                                .generate_folder(folder, (&value).to_vec());
+    /// Loads the blame of the file shown in a file view and shows it.
+    pub fn show_blame_of_file(&self, file_view: &BagitFileView) {
+        let repository_path = self.get_selected_repository_path();
+        let file_path = file_view.get_file_path();
+        let revision = file_view.get_revision();
+
+        let (sender, receiver) =
+            MainContext::channel::<Result<Vec<BlameLine>, String>>(Priority::default());
+
+        let thread_file_path = file_path.clone();
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            let blame_lines = match git2::Repository::open(repository_path) {
+                Ok(repository) => match RepositoryUtils::get_blame_of_file(
+                    &repository,
+                    &thread_file_path,
+                    revision.as_deref(),
+                ) {
+                    Ok(blame_lines) => Ok(blame_lines),
+                    Err(error) => Err(error.message().to_string()),
+                },
+                Err(error) => Err(error.message().to_string()),
+            };
+
+            sender
+                .send(blame_lines)
+                .expect("Could not send through channel");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win, @weak file_view => @default-return Continue(false),
+                move |blame_lines| {
+                    match blame_lines {
+                        Ok(blame_lines) => file_view.show_blame(&file_path, blame_lines),
+                        Err(error) => {
+                            file_view.show_changes();
+                            win.emit_by_name::<()>("error", &[&error]);
                         }
                     }
-                    if hash_map.contains_key("") {
-                        for file in &hash_map[""] {
-                            let new_row =
-                                self.imp().sidebar.generate_changed_file(file, 4, 8, None);
-                            self.imp().sidebar.imp().menu.append(&new_row.0);
+
+                    Continue(true)
+                }
+            ),
+        );
+    }
+
+    /// Loads the commits that changed the file shown in a file view and shows them.
+    pub fn show_history_of_file(&self, file_view: &BagitFileView) {
+        let repository_path = self.get_selected_repository_path();
+        let file_path = file_view.get_file_path();
+        let revision = file_view.get_revision();
+
+        let (sender, receiver) =
+            MainContext::channel::<Result<Vec<FileHistoryEntry>, String>>(Priority::default());
+
+        let thread_file_path = file_path.clone();
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            let file_history = match git2::Repository::open(repository_path) {
+                Ok(repository) => match RepositoryUtils::get_history_of_file(
+                    &repository,
+                    &thread_file_path,
+                    revision.as_deref(),
+                ) {
+                    Ok(file_history) => Ok(file_history),
+                    Err(error) => Err(error.message().to_string()),
+                },
+                Err(error) => Err(error.message().to_string()),
+            };
+
+            sender
+                .send(file_history)
+                .expect("Could not send through channel");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win, @weak file_view => @default-return Continue(false),
+                move |file_history| {
+                    match file_history {
+                        Ok(file_history) => file_view.show_file_history(&file_path, file_history),
+                        Err(error) => {
+                            file_view.show_changes();
+                            win.emit_by_name::<()>("error", &[&error]);
                         }
                     }
+
+                    Continue(true)
                 }
-                Err(_) => {}
-            };
+            ),
+        );
+    }
+
+    /// Stages or unstages the selected lines of the shown file.
+    pub fn change_lines_staging_and_update_ui(&self, file_view: &BagitFileView, whole_hunks: bool) {
+        let selected_lines = file_view.get_selected_lines(whole_hunks);
+
+        if selected_lines.is_empty() {
+            self.show_toast(&gettext("_No changed lines selected"));
+            return;
         }
 
-        // We now set a listener on the file list for when a file is clicked :
-        self.imp().sidebar.imp().menu.connect_row_selected(clone!(
-            @weak self as win,
-            => move |list, clicked_row| {
-                if let Some(row) = clicked_row {
+        let selected_repository = self.get_selected_repository();
 
-                    list.unselect_all();
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
 
-                    if let Some(label) = row
-                    .child()
-                    .and_downcast::<gtk::Box>()
-                    .expect("The child has to be a `Box`.")
-                    .first_child()
-                    .expect("The box should at least one child.")
-                    .next_sibling()
-                    .and_downcast::<gtk::Label>() {
-                        win.try_showing_file_content("", &label.text());
-                    }
-                }
+        let git_repo = selected_repository.git_repository.unwrap();
+
+        let current_shown_file_info = file_view.get_current_shown_file_information();
+        let relative_path = RepositoryUtils::build_path_of_file(
+            &current_shown_file_info.0,
+            &current_shown_file_info.1,
+        );
+
+        let result = if file_view.is_showing_staged_changes() {
+            RepositoryUtils::unstage_lines_of_file(&git_repo, &relative_path, &selected_lines)
+        } else {
+            RepositoryUtils::stage_lines_of_file(&git_repo, &relative_path, &selected_lines)
+        };
+
+        match result {
+            Ok(_) => {
+                self.update_changed_files();
+                self.try_showing_file_content(
+                    &current_shown_file_info.0,
+                    &current_shown_file_info.1,
+                );
             }
-        ));
+            Err(error) => {
+                tracing::warn!("Could not change the staging of lines: {}", error);
+                self.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+            }
+        }
     }
 
-    pub fn update_branch_name(&self) {
-        let borrowed_repo = self.imp().selected_repository.borrow();
-        match &borrowed_repo.git_repository {
-            Some(repository) => match RepositoryUtils::get_current_branch_name(&repository) {
-                Ok(branch_name) => {
-                    let button_box = self
-                        .imp()
-                        .branch_button
-                        .first_child()
-                        .and_downcast::<gtk::Box>();
+    /// Stages or unstages files and update UI.
+    pub fn change_files_staging_and_update_ui(&self, file_paths: Vec<String>, is_staging: bool) {
+        if file_paths.is_empty() {
+            return;
+        }
+
+        let selected_repository = self.get_selected_repository();
+
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
+
+        let git_repo = selected_repository.git_repository.unwrap();
+
+        let result = if is_staging {
+            RepositoryUtils::stage_files(&git_repo, &file_paths)
+        } else {
+            RepositoryUtils::unstage_files(&git_repo, &file_paths)
+        };
+
+        match result {
+            Ok(_) => {
+                self.update_changed_files();
+                self.update_file_view_if_necessary();
+            }
+            Err(error) => {
+                tracing::warn!("Could not change the staging of files: {}", error);
+                self.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+            }
+        }
+    }
+
+    /// Update the file view if we are on it.
+    /// The update will do the following :
+    /// - Check if the shown file is still present on the changed files. If not, we will go back to the main view.
+    /// - Update the file content if it has changed.
+    pub fn update_file_view_if_necessary(&self) {
+        // If the current view shown in the repository page isn't the file view, we don't go any further.
+        let is_on_file_view = match self.imp().main_view_stack.visible_child_name() {
+            Some(current_view) => current_view == "file view",
+            None => false,
+        };
+
+        if !is_on_file_view {
+            return;
+        }
+
+        let selected_repository = self.get_selected_repository();
+
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
+        let git_repo = selected_repository.git_repository.unwrap();
 
-                    if button_box.is_none() {
-                        return;
-                    }
+        // First, we check if the current shown file is still in the changed files:
+        let current_shown_file_info = self.imp().file_view.get_current_shown_file_information();
+        let relative_path = RepositoryUtils::build_path_of_file(
+            &current_shown_file_info.0,
+            &current_shown_file_info.1,
+        );
+        let path = Path::new(&relative_path);
+        let file_status = git_repo.status_file(&path);
 
-                    let button_label = button_box
-                        .unwrap()
-                        .last_child()
-                        .and_downcast::<gtk::Label>();
+        // If the file is not in the changed files anymore, we go back to the main repository view.
+        let should_go_to_main_view = match file_status {
+            Ok(status) => status == Status::CURRENT,
+            Err(_) => true,
+        };
 
-                    if let Some(label) = button_label {
-                        label.set_text(&branch_name);
-                    }
-                }
-                Err(_) => {}
-            },
-            None => {}
+        if should_go_to_main_view {
+            self.imp()
+                .main_view_stack
+                .set_visible_child_name("hello page");
+            // We remove the current file information as it is not longer useful.
+            self.imp().file_view.set_file_information("", "");
+        } else {
+            self.try_showing_file_content(&current_shown_file_info.0, &current_shown_file_info.1);
         }
     }
 
-    /// Initialize the git action button.
-    pub fn init_git_action_button(&self) {
-        self.update_push_indication_box(0);
-        self.update_pull_indication_box(0);
-        self.update_git_action_button_action(ActionType::Fetch);
-    }
+    /// Shows the merge conflict view when the conflicts of a merge or of a rebase step are being resolved.
+    /// Returns whether there are conflicts being resolved.
+    pub fn show_merge_conflicts_if_merging(&self) -> bool {
+        let is_resolving_conflicts = match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) => RepositoryUtils::is_resolving_conflicts(&repository),
+            None => false,
+        };
 
-    /// Used to try to find the correct git button action.
-    /// The default action is fetch, and the preffered one is push.
-    pub fn try_to_find_correct_git_button_action(&self) {
-        if self.imp().push_indication_box.is_visible() {
-            self.update_git_action_button_action(ActionType::Push);
-            return;
-        }
-        if self.imp().pull_indication_box.is_visible() {
-            self.update_git_action_button_action(ActionType::Pull);
-            return;
+        if is_resolving_conflicts {
+            self.imp()
+                .merge_conflict_view
+                .set_is_rebasing(self.is_rebasing());
+            self.imp()
+                .main_view_stack
+                .set_visible_child_name("merge conflict view");
+            self.update_merge_conflict_view();
         }
-        self.update_git_action_button_action(ActionType::Fetch);
-    }
 
-    /// Used to update the popover menu buttons visibility.
-    fn update_popover_menu_buttons(&self, action_type: ActionType) {
-        self.imp()
-            .fetch_button
-            .set_visible(!(action_type == ActionType::Fetch));
-        self.imp()
-            .pull_button
-            .set_visible(!(action_type == ActionType::Pull));
-        self.imp()
-            .push_button
-            .set_visible(!(action_type == ActionType::Push));
+        return is_resolving_conflicts;
     }
 
-    /// Used to update the git action button text and current action.
-    /// The default git action is the fetch;
-    fn update_git_action_button_action(&self, action_type: ActionType) {
-        match action_type {
-            ActionType::Push => {
-                self.imp().current_git_action.replace(ActionType::Push);
+    /// Updates the conflicted files of the merge conflict view.
+    /// The view is left once the merge is no longer in progress.
+    pub fn update_merge_conflict_view(&self) {
+        let is_on_merge_conflict_view = match self.imp().main_view_stack.visible_child_name() {
+            Some(current_view) => current_view == "merge conflict view",
+            None => false,
+        };
 
-                self.imp()
-                    .git_action_label
-                    .get()
-                    .set_label(&gettext("_Push"));
-            }
-            ActionType::Pull => {
-                self.imp().current_git_action.replace(ActionType::Pull);
+        if !is_on_merge_conflict_view {
+            return;
+        }
 
-                self.imp()
-                    .git_action_label
-                    .get()
-                    .set_label(&gettext("_Pull"));
+        let conflicts = match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) if RepositoryUtils::is_resolving_conflicts(&repository) => {
+                RepositoryUtils::get_merge_conflicts(&repository)
             }
             _ => {
-                self.imp().current_git_action.replace(ActionType::Fetch);
-
                 self.imp()
-                    .git_action_label
-                    .get()
-                    .set_label(&gettext("_Fetch"));
+                    .main_view_stack
+                    .set_visible_child_name("hello page");
+                return;
             }
+        };
+
+        match conflicts {
+            Ok(conflicts) => self.imp().merge_conflict_view.update_conflicts(conflicts),
+            Err(error) => tracing::warn!("Could not get merge conflicts: {}", error),
         }
-        self.update_popover_menu_buttons(action_type);
     }
 
-    /// Used to update the push indication box
-    fn update_push_indication_box(&self, total_commits_to_push: i64) {
-        self.imp()
-            .push_indication_box
-            .set_visible(total_commits_to_push != 0);
+    /// Used to mark a conflicted file as resolved and update UI.
+    pub fn resolve_merge_conflict_and_update_ui(
+        &self,
+        relative_path: &str,
+        content: &str,
+        is_deleting_file: bool,
+    ) {
+        if !is_deleting_file && has_conflict_markers(content) {
+            self.show_toast(&gettext("_Conflict markers remain"));
+            return;
+        }
 
-        self.imp()
-            .git_action_push_number
-            .set_label(&total_commits_to_push.to_string());
-    }
+        let selected_repository = self.get_selected_repository();
 
-    /// Used to update the pull indication box
-    fn update_pull_indication_box(&self, total_commits_to_pull: i64) {
-        self.imp()
-            .pull_indication_box
-            .set_visible(total_commits_to_pull != 0);
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::resolve_merge_conflict(
+                &repository,
+                relative_path,
+                if is_deleting_file {
+                    None
+                } else {
+                    Some(content.as_bytes())
+                },
+            ),
+            None => return,
+        };
 
-        self.imp()
-            .git_action_pull_number
-            .set_label(&total_commits_to_pull.to_string());
+        self.update_ui_after_conflict_resolution(result);
     }
 
-    /// Activates or deactivates the action button.
-    pub fn toggle_git_action_button(&self, is_active: bool) {
-        self.imp().git_action_button.set_sensitive(is_active);
-        self.imp().git_action_spinner.set_visible(!is_active);
-    }
+    /// Used to mark a conflicted file as resolved with one of its versions and update UI.
+    pub fn resolve_merge_conflict_with_side_and_update_ui(
+        &self,
+        relative_path: &str,
+        side: ConflictSide,
+    ) {
+        let selected_repository = self.get_selected_repository();
 
-    /// Updates the git action button.
-    pub fn update_git_action_button(&self, fetch_result: FetchResult) {
-        self.update_push_indication_box(fetch_result.total_commits_to_push);
-        self.update_pull_indication_box(fetch_result.total_commits_to_pull);
+        let result = match &selected_repository.git_repository {
+            Some(repository) => {
+                RepositoryUtils::resolve_merge_conflict_with_side(&repository, relative_path, side)
+            }
+            None => return,
+        };
 
-        if fetch_result.total_commits_to_push == 0 && fetch_result.total_commits_to_pull == 0 {
-            self.update_git_action_button_action(ActionType::Fetch);
-            self.toggle_git_action_button(true);
-            return;
-        }
+        self.update_ui_after_conflict_resolution(result);
+    }
 
-        if fetch_result.total_commits_to_push > 0 {
-            self.update_git_action_button_action(ActionType::Push);
-        } else {
-            self.update_git_action_button_action(ActionType::Pull);
+    /// Used to show the result of the resolution of a conflicted file.
+    fn update_ui_after_conflict_resolution(&self, result: Result<(), git2::Error>) {
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Conflict resolved"));
+                self.update_commits_sidebar();
+                self.update_merge_conflict_view();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
         }
-
-        self.toggle_git_action_button(true);
     }
 
-    /// Commits files and update UI.
-    pub fn commit_files_and_update_ui(
+    /// Used to create the merge commit once all conflicts are resolved and update UI.
+    pub fn conclude_merge_and_update_ui(
         &self,
         author: &str,
         author_email: &str,
-        message: &str,
         signing_key: &str,
         passphrase: &str,
-        description: &str,
-        need_to_save_profile: bool,
     ) {
-        let borrowed_repo = self.imp().selected_repository.take();
-        if borrowed_repo.git_repository.is_some() {
-            let git_repository = borrowed_repo.git_repository.as_ref().unwrap();
-            let selected_files = self
-                .imp()
-                .sidebar
-                .imp()
-                .changed_files
-                .borrow()
-                .get_selected_files();
-
-            // We save the profile if we need to :
-            if need_to_save_profile {
-                let new_profile_id = Uuid::new_v4();
-
-                // We make sure that the profile name is unique:
-                let same_profile_name_number;
-
-                let app_database = self.imp().app_database.take();
-
-                match app_database
-                    .get_number_of_git_profiles_with_name(&author, &new_profile_id.to_string())
-                {
-                    Ok(number) => same_profile_name_number = number,
-                    Err(error) => {
-                        // TODO: Show error (maybe with a toast).
-
-                        tracing::warn!("Could not get number of git profiles with name: {}", error);
-
-                        return;
-                    }
-                }
-
-                let final_profil_name: String = if same_profile_name_number != 0 {
-                    let new_name = format!("{} ({})", author, same_profile_name_number);
-                    new_name
-                } else {
-                    author.to_string()
-                };
-
-                let new_profile = BagitGitProfile::new(
-                    new_profile_id.clone(),
-                    final_profil_name,
-                    author_email.to_string(),
-                    author.to_string(),
-                    String::from(""),
-                    String::from(""),
-                    signing_key.to_string(),
-                );
-
-                if let Err(error) = app_database.add_git_profile(&new_profile) {
-                    tracing::warn!("Could not add Git profile: {}", error);
+        let selected_repository = self.get_selected_repository();
 
-                    let toast = adw::Toast::new(&gettext("_Could not add Git profile"));
-                    self.imp().toast_overlay.add_toast(toast);
-                }
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::conclude_merge(
+                &repository,
+                author,
+                author_email,
+                signing_key,
+                passphrase,
+            ),
+            None => return,
+        };
 
-                // We set the new profile to the repository:
-                if let Err(error) = app_database.change_git_profile_of_repository(
-                    borrowed_repo.user_repository.repository_id,
-                    Some(new_profile_id),
-                ) {
-                    tracing::warn!("Could not change Git profile of repository: {}", error);
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Merge concluded"));
+                self.imp()
+                    .main_view_stack
+                    .set_visible_child_name("hello page");
+                self.imp()
+                    .sidebar
+                    .imp()
+                    .first_commit_oid_of_commit_list
+                    .take();
+                self.update_repository_page();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
 
-                    let toast = adw::Toast::new(&gettext("_Could not change Git profile"));
-                    self.imp().toast_overlay.add_toast(toast);
-                }
+    /// Used to abort the merge in progress and update UI.
+    pub fn abort_merge_and_update_ui(&self) {
+        let selected_repository = self.get_selected_repository();
 
-                self.imp().app_database.replace(app_database);
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::abort_merge(&repository),
+            None => return,
+        };
 
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Merge aborted"));
                 self.imp()
-                    .commit_view
-                    .imp()
-                    .profile_mode
-                    .replace(ProfileMode::SelectedProfile(new_profile));
-
-                // We update the view:
-                self.imp().commit_view.update_git_profiles_list();
+                    .main_view_stack
+                    .set_visible_child_name("hello page");
+                self.update_repository_page();
             }
-            match RepositoryUtils::commit_files(
-                git_repository,
-                selected_files,
-                message,
-                description,
-                author,
-                author_email,
-                signing_key,
-                passphrase,
-            ) {
-                Ok(_) => {
-                    let toast = adw::Toast::new(&gettext("_Commit created successfully"));
-                    self.imp().toast_overlay.add_toast(toast);
-                    // We remove the last commit message:
-                    self.imp().commit_view.imp().message_row.set_text("");
-                    self.imp().commit_view.imp().description_row.set_text("");
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
+
+    /// Checks if an interactive rebase is in progress in the selected repository.
+    pub fn is_rebasing(&self) -> bool {
+        return match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) => RepositoryUtils::is_rebasing(&repository),
+            None => false,
+        };
+    }
 
-                    self.imp().selected_repository.replace(borrowed_repo);
-                    self.update_commits_sidebar();
-                    self.imp().commit_view.update_commit_view(0);
-                }
-                Err(error) => {
-                    self.imp().selected_repository.replace(borrowed_repo);
-                    self.emit_by_name("error", &[&error.to_string()])
-                }
+    /// Shows the rebase view when an interactive rebase is stopped for editing.
+    fn show_stopped_rebase_if_rebasing(&self) {
+        let state = match &self.imp().selected_repository.borrow().git_repository {
+            Some(repository) if RepositoryUtils::is_rebasing(&repository) => {
+                RepositoryUtils::get_interactive_rebase_state(&repository)
+            }
+            _ => return,
+        };
+
+        match state {
+            Ok(state) => {
+                let edited_step = match &state.stopped_step {
+                    Some((step, false)) => Some(step),
+                    _ => None,
+                };
+
+                self.imp().rebase_view.show_stopped_rebase(edited_step);
+                self.imp()
+                    .main_view_stack
+                    .set_visible_child_name("rebase view");
             }
+            Err(error) => tracing::warn!("Could not get interactive rebase state: {}", error),
         }
     }
 
-    /// Check if we can pull (if we have no changed files).
-    fn check_if_can_pull(&self) -> bool {
-        let changed_files_number = self
-            .imp()
-            .sidebar
-            .imp()
-            .changed_files
-            .borrow()
-            .get_number_of_changed_files();
+    /// Opens the editor of an interactive rebase of the checked out branch on a commit.
+    pub fn open_rebase_editor(&self, base_commit_id: &str) {
+        let selected_repository = self.get_selected_repository();
 
-        return changed_files_number == 0;
+        let steps = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::get_rebase_steps(&repository, base_commit_id),
+            None => return,
+        };
+
+        match steps {
+            Ok(steps) if steps.is_empty() => self.show_toast(&gettext("_No commits to rebase")),
+            Ok(steps) => {
+                self.imp()
+                    .rebase_view
+                    .init_rebase_editor(base_commit_id.to_string(), steps);
+                self.imp()
+                    .main_view_stack
+                    .set_visible_child_name("rebase view");
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
     }
 
-    /// Used to define wich git action we need to do:
-    pub fn do_git_action_with_information(
+    /// Used to start the interactive rebase chosen in the rebase view and update UI.
+    pub fn start_interactive_rebase_and_update_ui(
         &self,
-        username: String,
-        password: String,
-        private_key_path: String,
-        passphrase: String,
-        action_type: ActionType,
-        branch_name: String,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
     ) {
-        match action_type {
-            ActionType::Fetch => self.fetch_repository_checked_out_branch_and_update_ui(
-                username,
-                password,
-                private_key_path,
-                passphrase,
-            ),
-            ActionType::Push => {
-                self.push_and_update_ui(username, password, private_key_path, passphrase)
-            }
-            ActionType::Pull => {
-                self.pull_and_update_ui(username, password, private_key_path, passphrase)
-            }
-            ActionType::DeleteRemoteBranch => self.delete_branch_and_update_ui(
-                username,
-                password,
-                private_key_path,
-                passphrase,
-                branch_name,
-            ),
-        };
+        let base_commit_id = self.imp().rebase_view.get_base_commit_id();
+        let steps = self.imp().rebase_view.get_steps();
+        let author = String::from(author);
+        let author_email = String::from(author_email);
+        let signing_key = String::from(signing_key);
+        let passphrase = String::from(passphrase);
+
+        self.run_interactive_rebase_and_update_ui(move |repository| {
+            RepositoryUtils::start_interactive_rebase(
+                repository,
+                &base_commit_id,
+                steps,
+                &author,
+                &author_email,
+                &signing_key,
+                &passphrase,
+            )
+        });
     }
 
-    fn try_do_git_action_without_auth_check(&self, action_type: ActionType) {
-        let profile_mode = self.imp().commit_view.imp().profile_mode.take();
+    /// Continues the interactive rebase with the author information of the commit view.
+    fn continue_interactive_rebase_with_author_information(&self) {
+        let (author, author_email, signing_key) = self.get_commit_author_information();
 
-        self.imp()
-            .commit_view
-            .imp()
-            .profile_mode
-            .replace(profile_mode.clone());
+        if signing_key.is_empty() {
+            self.continue_interactive_rebase_and_update_ui(&author, &author_email, "", "");
+        } else {
+            self.emit_by_name::<()>(
+                "interactive-rebase-with-signing-key",
+                &[&author, &author_email, &signing_key, &true],
+            );
+        }
+    }
 
-        // If we want to pull, we need to check that we don't have changed files:
-        if action_type == ActionType::Pull && !self.check_if_can_pull() {
-            let toast = adw::Toast::new(&gettext("_Changed files when pull"));
-            self.imp().toast_overlay.add_toast(toast);
-            self.toggle_git_action_button(true);
-            self.try_to_find_correct_git_button_action();
+    /// Used to continue the interactive rebase in progress and update UI.
+    pub fn continue_interactive_rebase_and_update_ui(
+        &self,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) {
+        let author = String::from(author);
+        let author_email = String::from(author_email);
+        let signing_key = String::from(signing_key);
+        let passphrase = String::from(passphrase);
+
+        self.run_interactive_rebase_and_update_ui(move |repository| {
+            RepositoryUtils::continue_interactive_rebase(
+                repository,
+                &author,
+                &author_email,
+                &signing_key,
+                &passphrase,
+            )
+        });
+    }
+
+    /// Runs the interactive rebase in another thread, as rebased commits may be signed, then updates UI.
+    fn run_interactive_rebase_and_update_ui<F>(&self, rebase: F)
+    where
+        F: FnOnce(&git2::Repository) -> Result<RebaseStop, git2::Error> + Send + 'static,
+    {
+        let selected_repository = self.get_selected_repository();
+
+        if selected_repository.git_repository.is_none() {
             return;
         }
 
-        match action_type {
-            ActionType::Fetch => return self.try_fetch_without_auth_and_update_ui(),
-            ActionType::Pull => return self.try_pull_without_auth_and_update_ui(),
-            _ => {}
-        };
-    }
+        let (sender, receiver) =
+            MainContext::channel::<Result<RebaseStop, git2::Error>>(Priority::default());
 
-    fn retrieve_saved_ssh_passphrase(&self, private_key_path: &str) -> Option<String> {
-        let ssh_passphrases = self.imp().ssh_passphrases.take();
+        self.imp().rebase_view.set_sensitive(false);
+
+        thread::spawn(move || {
+            sender
+                .send(rebase(selected_repository.git_repository.as_ref().unwrap()))
+                .expect("Could not send result through channel");
+        });
 
-        let res = ssh_passphrases.get(private_key_path).clone();
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false), move |result| {
+                win.imp().rebase_view.set_sensitive(true);
+                win.show_interactive_rebase_result(result);
+                Continue(true)
+            }),
+        );
+    }
 
+    /// Updates UI depending on where the interactive rebase stopped.
+    fn show_interactive_rebase_result(&self, result: Result<RebaseStop, git2::Error>) {
         self.imp()
-            .ssh_passphrases
-            .replace(ssh_passphrases.to_owned());
+            .sidebar
+            .imp()
+            .first_commit_oid_of_commit_list
+            .take();
+        self.update_repository_page();
+
+        match result {
+            Ok(RebaseStop::Finished) => {
+                self.show_toast(&gettext("_Rebase finished"));
+                self.imp()
+                    .main_view_stack
+                    .set_visible_child_name("hello page");
+            }
+            Ok(RebaseStop::Conflicts(_)) => {
+                self.show_merge_conflicts_if_merging();
+            }
+            Ok(RebaseStop::Edit(step)) => {
+                self.imp().rebase_view.show_stopped_rebase(Some(&step));
+                self.imp()
+                    .main_view_stack
+                    .set_visible_child_name("rebase view");
+            }
+            Err(error) => {
+                self.emit_by_name::<()>("error", &[&error.message().to_string()]);
 
-        match res {
-            Some(passphrase) => Some(passphrase.to_owned()),
-            None => None,
+                if !self.show_merge_conflicts_if_merging() {
+                    self.show_stopped_rebase_if_rebasing();
+                }
+            }
         }
     }
 
-    /// Does a git action that need authentification.
-    /// The branch name parameter is used when wanting to delete a remote branch.
-    pub fn do_git_action_with_auth_check(&self, action_type: ActionType, remote_branch_name: &str) {
+    /// Used to abort the interactive rebase in progress and update UI.
+    pub fn abort_interactive_rebase_and_update_ui(&self) {
         let selected_repository = self.get_selected_repository();
-        let profile_mode = self.imp().commit_view.imp().profile_mode.take();
 
-        self.imp()
-            .commit_view
-            .imp()
-            .profile_mode
-            .replace(profile_mode.clone());
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::abort_interactive_rebase(&repository),
+            None => return,
+        };
 
-        // If we want to pull, we need to check that we don't have changed files:
-        if action_type == ActionType::Pull && !self.check_if_can_pull() {
-            let toast = adw::Toast::new(&gettext("_Changed files when pull"));
-            self.imp().toast_overlay.add_toast(toast);
-            self.toggle_git_action_button(true);
-            self.try_to_find_correct_git_button_action();
-            return;
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Rebase aborted"));
+                self.imp()
+                    .main_view_stack
+                    .set_visible_child_name("hello page");
+                self.imp()
+                    .sidebar
+                    .imp()
+                    .first_commit_oid_of_commit_list
+                    .take();
+                self.update_repository_page();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
         }
+    }
 
-        match &selected_repository.git_repository {
-            Some(repository) => {
-                match RepositoryUtils::get_clone_mode_of_repository(&repository) {
-                    Ok(clone_mode) => match profile_mode {
-                        ProfileMode::SelectedProfile(profile) => {
-                            if !profile.does_profile_has_information_for_actions(&clone_mode) {
-                                match clone_mode {
-                                    CloneMode::SSH => self.emit_by_name::<()>(
-                                        "missing-ssh-information",
-                                        &[
-                                            &profile.username,
-                                            &profile.private_key_path,
-                                            &action_type,
-                                            &remote_branch_name,
-                                        ],
-                                    ),
-                                    CloneMode::HTTPS => self.emit_by_name::<()>(
-                                        "missing-https-information",
-                                        &[
-                                            &profile.username,
-                                            &profile.password,
-                                            &action_type,
-                                            &remote_branch_name,
-                                        ],
-                                    ),
-                                };
-                            } else {
-                                // TODO: MARKER
-                                match clone_mode {
-                                    CloneMode::SSH => {
-                                        match self.retrieve_saved_ssh_passphrase(
-                                            &profile.private_key_path,
-                                        ) {
-                                            Some(passphrase) => self
-                                                .do_git_action_with_information(
-                                                    profile.username,
-                                                    profile.password,
-                                                    profile.private_key_path,
-                                                    passphrase.to_owned(),
-                                                    action_type,
-                                                    remote_branch_name.to_string(),
-                                                ),
-                                            None => self.emit_by_name::<()>(
-                                                "ssh-passphrase-dialog",
-                                                &[
-                                                    &profile.username,
-                                                    &profile.private_key_path,
-                                                    &action_type,
-                                                    &remote_branch_name,
-                                                ],
-                                            ),
-                                        }
-                                    }
-                                    CloneMode::HTTPS => self.do_git_action_with_information(
-                                        profile.username,
-                                        profile.password,
-                                        profile.private_key_path,
-                                        "".to_string(),
-                                        action_type,
-                                        String::from(remote_branch_name),
-                                    ),
-                                };
-                            }
-                        }
-                        _ => {
-                            match clone_mode {
-                                CloneMode::SSH => self.emit_by_name::<()>(
-                                    "missing-ssh-information",
-                                    &[
-                                        &self.imp().commit_view.imp().author_row.text().trim(),
-                                        &"",
-                                        &action_type,
-                                        &remote_branch_name,
-                                    ],
-                                ),
-                                CloneMode::HTTPS => self.emit_by_name::<()>(
-                                    "missing-https-information",
-                                    &[
-                                        &self.imp().commit_view.imp().author_row.text().trim(),
-                                        &"",
-                                        &action_type,
-                                        &remote_branch_name,
-                                    ],
-                                ),
-                            };
-                        }
-                    },
-                    Err(error) => self.emit_by_name::<()>("error", &[&error.to_string()]),
-                };
+    /// Cherry-picks or reverts a commit with the author information of the commit view.
+    fn apply_commit_with_author_information(&self, commit_id: &str, is_reverting: bool) {
+        let (author, author_email, signing_key) = self.get_commit_author_information();
+
+        if signing_key.is_empty() {
+            self.apply_commit_and_update_ui(
+                commit_id,
+                is_reverting,
+                &author,
+                &author_email,
+                "",
+                "",
+            );
+        } else {
+            self.emit_by_name::<()>(
+                "apply-commit-with-signing-key",
+                &[
+                    &commit_id,
+                    &author,
+                    &author_email,
+                    &signing_key,
+                    &is_reverting,
+                ],
+            );
+        }
+    }
+
+    /// Used to cherry-pick or revert a commit on the checked out branch and update UI.
+    pub fn apply_commit_and_update_ui(
+        &self,
+        commit_id: &str,
+        is_reverting: bool,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) {
+        let selected_repository = self.get_selected_repository();
+
+        let result = match &selected_repository.git_repository {
+            Some(repository) if is_reverting => RepositoryUtils::revert_commit(
+                &repository,
+                commit_id,
+                author,
+                author_email,
+                signing_key,
+                passphrase,
+            ),
+            Some(repository) => RepositoryUtils::cherry_pick_commit(
+                &repository,
+                commit_id,
+                author,
+                author_email,
+                signing_key,
+                passphrase,
+            ),
+            None => return,
+        };
+
+        match result {
+            Ok(_) => {
+                self.show_toast(&if is_reverting {
+                    gettext("_Commit reverted")
+                } else {
+                    gettext("_Commit cherry-picked")
+                });
+                self.update_commits_sidebar();
             }
-            None => {}
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
         }
     }
 
-    /// Used to push and update ui.
-    pub fn push_and_update_ui(
-        &self,
-        username: String,
-        password: String,
-        private_key_path: String,
-        passphrase: String,
-    ) {
+    /// Used to move the checked out branch to a commit and update UI.
+    pub fn reset_to_commit_and_update_ui(&self, commit_id: &str, reset_type: git2::ResetType) {
         let selected_repository = self.get_selected_repository();
 
-        let (error_sender, error_receiver) = MainContext::channel::<String>(Priority::default());
-        let (result_sender, result_receiver) = MainContext::channel::<()>(Priority::default());
+        let result = match &selected_repository.git_repository {
+            Some(repository) => {
+                RepositoryUtils::reset_to_commit(&repository, commit_id, reset_type)
+            }
+            None => return,
+        };
 
-        self.toggle_git_action_button(false);
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Branch reset"));
+                self.update_repository_page();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
 
-        thread::spawn(move || {
-            let error_sender = error_sender.clone();
-            let result_sender = result_sender.clone();
+    /// Used to create a branch on a commit and to checkout it.
+    pub fn create_branch_from_commit_and_update_ui(&self, commit_id: &str, branch_name: &str) {
+        let selected_repository = self.get_selected_repository();
 
-            match RepositoryUtils::push(
-                &selected_repository.git_repository.as_ref().unwrap(),
-                username,
-                password,
-                private_key_path,
-                passphrase,
-            ) {
-                Ok(_) => result_sender
-                    .send(())
-                    .expect("Could not send result through channel"),
-                Err(error) => error_sender
-                    .send(error.to_string())
-                    .expect("Could not send error through channel"),
-            };
-        });
+        let result = match &selected_repository.git_repository {
+            Some(repository) => {
+                RepositoryUtils::create_branch_from_commit(&repository, branch_name, commit_id)
+            }
+            None => return,
+        };
 
-        error_receiver.attach(
-            None,
-            clone!(@weak self as win => @default-return Continue(false),
-                        move |error| {
-                            win.emit_by_name::<()>("error", &[&error.to_string()]);
+        match result {
+            Ok(_) => self.checkout_branch_and_update_ui(branch_name.to_string(), false),
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
 
-                            win.try_to_find_correct_git_button_action();
-                            win.toggle_git_action_button(true);
+    /// Used to checkout a commit, leaving HEAD detached, and update UI.
+    pub fn checkout_commit_and_update_ui(&self, commit_id: &str) {
+        let selected_repository = self.get_selected_repository();
 
-                            win.update_repository_page();
-                            Continue(true)
-                        }
-            ),
-        );
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::checkout_commit(&repository, commit_id),
+            None => return,
+        };
 
-        result_receiver.attach(
-            None,
-            clone!(
-                @weak self as win => @default-return Continue(false),
-                        move |_| {
-                            let toast = adw::Toast::new(&gettext("_Commits pushed"));
-                            win.imp().toast_overlay.add_toast(toast);
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Commit checked out"));
+                self.update_repository_page();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
 
-                            win.update_push_indication_box(0);
-                            win.try_to_find_correct_git_button_action();
-                            win.toggle_git_action_button(true);
+    /// Updates the stash list of the stash view.
+    pub fn update_stash_view(&self) {
+        let selected_repository = self.get_selected_repository();
 
-                            win.imp().sidebar.imp().first_commit_oid_of_commit_list.take();
-                            win.update_repository_page();
-                            Continue(true)
-                        }
-            ),
-        );
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
+
+        let mut git_repo = selected_repository.git_repository.unwrap();
+
+        match RepositoryUtils::get_stashes(&mut git_repo) {
+            Ok(stashes) => self.imp().stash_view.update_stashes(stashes),
+            Err(error) => {
+                tracing::warn!("Could not get stashes: {}", error);
+                self.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+            }
+        }
     }
 
-    /// Used to push and update ui.
-    pub fn pull_and_update_ui(
-        &self,
-        username: String,
-        password: String,
-        private_key_path: String,
-        passphrase: String,
-    ) {
+    /// Browses the files of the repository at a revision.
+    /// Without a given revision, the previously browsed one is kept.
+    pub fn open_file_browser(&self, revision: Option<String>) {
         let selected_repository = self.get_selected_repository();
 
-        let (error_sender, error_receiver) = MainContext::channel::<String>(Priority::default());
-        let (result_sender, result_receiver) = MainContext::channel::<()>(Priority::default());
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
 
-        self.toggle_git_action_button(false);
+        match RepositoryUtils::get_browsable_revisions(&selected_repository.git_repository.unwrap())
+        {
+            Ok(revisions) => self
+                .imp()
+                .file_browser_view
+                .show_revisions(revisions, revision.as_deref()),
+            Err(error) => {
+                tracing::warn!("Could not get the revisions to browse: {}", error);
+                self.emit_by_name::<()>("error", &[&gettext("_An error has occured")]);
+            }
+        }
+    }
 
-        thread::spawn(move || {
-            let error_sender = error_sender.clone();
-            let result_sender = result_sender.clone();
+    /// Saves the version of a file at a revision to another location.
+    pub fn save_file_version(&self, revision: &str, file_path: &str, destination_path: &str) {
+        let selected_repository = self.get_selected_repository();
 
-            match RepositoryUtils::pull(
-                &selected_repository.git_repository.as_ref().unwrap(),
-                username,
-                password,
-                private_key_path,
-                passphrase,
-            ) {
-                Ok(_) => result_sender
-                    .send(())
-                    .expect("Could not send result through channel"),
-                Err(error) => error_sender
-                    .send(error.to_string())
-                    .expect("Could not send error through channel"),
-            };
-        });
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
 
-        error_receiver.attach(
-            None,
-            clone!(@weak self as win => @default-return Continue(false),
-                        move |error| {
-                            win.emit_by_name::<()>("error", &[&error.to_string()]);
+        match RepositoryUtils::save_file_at_revision(
+            &selected_repository.git_repository.unwrap(),
+            revision,
+            file_path,
+            destination_path,
+        ) {
+            Ok(_) => self.show_toast(&gettext("_File saved")),
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
 
-                            win.try_to_find_correct_git_button_action();
-                            win.toggle_git_action_button(true);
+    /// Used to stash the changes, or only the selected files, and update UI.
+    pub fn save_stash_and_update_ui(
+        &self,
+        message: &str,
+        include_untracked: bool,
+        only_selected_files: bool,
+    ) {
+        let file_paths: Vec<String> = if only_selected_files {
+            self.imp()
+                .sidebar
+                .imp()
+                .changed_files
+                .borrow()
+                .get_files_in_section(true)
+                .iter()
+                .map(|file| RepositoryUtils::build_path_of_file(&file.parent, &file.name))
+                .collect()
+        } else {
+            vec![]
+        };
 
-                            win.update_commits_sidebar();
-                            Continue(true)
-                        }
-            ),
-        );
+        if only_selected_files && file_paths.is_empty() {
+            self.show_toast(&gettext("_No staged file"));
+            return;
+        }
 
-        result_receiver.attach(
-            None,
-            clone!(
-                @weak self as win => @default-return Continue(false),
-                        move |_| {
-                            let toast = adw::Toast::new(&gettext("_Remote branch pulled"));
-                            win.imp().toast_overlay.add_toast(toast);
+        let selected_repository = self.get_selected_repository();
 
-                            win.update_pull_indication_box(0);
-                            win.try_to_find_correct_git_button_action();
-                            win.toggle_git_action_button(true);
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
 
-                            win.update_commits_sidebar();
-                            Continue(true)
-                        }
-            ),
-        );
+        let mut git_repo = selected_repository.git_repository.unwrap();
+        let (author, author_email, _) = self.get_commit_author_information();
+
+        match RepositoryUtils::save_stash(
+            &mut git_repo,
+            message,
+            include_untracked,
+            &file_paths,
+            &author,
+            &author_email,
+        ) {
+            Ok(_) => {
+                self.show_toast(&gettext("_Changes stashed"));
+                self.imp().stash_view.clear_stash_message();
+                self.update_commits_sidebar();
+                self.update_stash_view();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
     }
 
-    pub fn delete_branch_and_update_ui(
-        &self,
-        username: String,
-        password: String,
-        private_key_path: String,
-        passphrase: String,
-        branch_name: String,
-    ) {
+    /// Used to apply a stash, removing it from the list when popping it, and update UI.
+    pub fn apply_stash_and_update_ui(&self, index: usize, is_popping: bool) {
         let selected_repository = self.get_selected_repository();
-        let (result_sender, result_receiver) =
-            MainContext::channel::<Result<(), String>>(Priority::default());
 
-        self.toggle_git_action_button(false);
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
 
-        thread::spawn(move || {
-            let result_sender = result_sender.clone();
+        let mut git_repo = selected_repository.git_repository.unwrap();
 
-            match RepositoryUtils::delete_remote_branch(
-                &selected_repository.git_repository.as_ref().unwrap(),
-                &branch_name,
-                username,
-                password,
-                private_key_path,
-                passphrase,
-            ) {
-                Ok(_) => result_sender
-                    .send(Ok(()))
-                    .expect("Could not send result through channel"),
-                Err(error) => result_sender
-                    .send(Err(error.to_string()))
-                    .expect("Could not send error through channel"),
-            };
-        });
+        let result = if is_popping {
+            RepositoryUtils::pop_stash(&mut git_repo, index)
+        } else {
+            RepositoryUtils::apply_stash(&mut git_repo, index)
+        };
 
-        result_receiver.attach(
-            None,
-            clone!(
-                @weak self as win => @default-return Continue(false),
-                        move |result| {
-                            match result {
-                                Ok(_) => {
-                                    win.imp()
-                                        .branch_view
-                                        .fetch_all_branches(selected_repository.user_repository.path.clone());
-                                    win.show_toast(&gettext("_Branch deleted"));
-                                    win.imp().sidebar.init_commit_list(selected_repository.user_repository.path.clone());
-                                    win.toggle_git_action_button(true);
-                                },
-                                Err(error) => {
-                                    win.emit_by_name::<()>("error", &[&error.to_string()]);
-                                    win.toggle_git_action_button(true);
-                                }
-                            }
-                            Continue(true)
-                        }
-            ),
-        );
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Stash applied"));
+                self.update_commits_sidebar();
+                self.update_stash_view();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
+
+    /// Used to remove a stash from the list and update UI.
+    pub fn drop_stash_and_update_ui(&self, index: usize) {
+        let selected_repository = self.get_selected_repository();
+
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
+
+        let mut git_repo = selected_repository.git_repository.unwrap();
+
+        match RepositoryUtils::drop_stash(&mut git_repo, index) {
+            Ok(_) => {
+                self.show_toast(&gettext("_Stash dropped"));
+                self.update_stash_view();
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
     }
 
-    /// Used to push and update ui.
-    pub fn try_pull_without_auth_and_update_ui(&self) {
+    /// Used to stash all the changes before changing the current branch.
+    pub fn stash_and_checkout_branch_and_update_ui(
+        &self,
+        branch_to_checkout_to: String,
+        is_remote: bool,
+    ) {
         let selected_repository = self.get_selected_repository();
 
-        let (error_sender, error_receiver) =
-            MainContext::channel::<git2::Error>(Priority::default());
-        let (result_sender, result_receiver) = MainContext::channel::<()>(Priority::default());
+        if selected_repository.git_repository.is_none() {
+            return;
+        }
 
-        self.toggle_git_action_button(false);
+        let mut git_repo = selected_repository.git_repository.unwrap();
+        let (author, author_email, _) = self.get_commit_author_information();
+        let message = format!(
+            "{} {}",
+            gettext("_Stashed before switching to"),
+            branch_to_checkout_to
+        );
 
-        thread::spawn(move || {
-            let error_sender = error_sender.clone();
-            let result_sender = result_sender.clone();
+        match RepositoryUtils::save_stash(
+            &mut git_repo,
+            &message,
+            true,
+            &vec![],
+            &author,
+            &author_email,
+        ) {
+            Ok(_) => {
+                self.show_toast(&gettext("_Changes stashed"));
+                self.checkout_branch_and_update_ui(branch_to_checkout_to, is_remote);
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
 
-            match RepositoryUtils::pull(
-                &selected_repository.git_repository.as_ref().unwrap(),
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-            ) {
-                Ok(_) => result_sender
-                    .send(())
-                    .expect("Could not send result through channel"),
-                Err(error) => error_sender
-                    .send(error)
-                    .expect("Could not send error through channel"),
-            };
-        });
+    /// Used to create a tag on a commit.
+    /// A tag with a message is an annotated tag, which can be signed with the signing key of the profile.
+    pub fn create_tag(&self, commit_id: &str, tag_name: &str, message: &str, is_signing: bool) {
+        if message.trim().is_empty() {
+            let selected_repository = self.get_selected_repository();
 
-        error_receiver.attach(
-            None,
-            clone!(@weak self as win => @default-return Continue(false),
-                        move |error| {
-                            if (error.class() == git2::ErrorClass::Http)
-                                || (error.class() == git2::ErrorClass::Ssh)
-                            {
-                                win.do_git_action_with_auth_check(ActionType::Pull, &"");
-                            } else {
-                                win.emit_by_name::<()>("error", &[&error.to_string()]);
+            let result = match &selected_repository.git_repository {
+                Some(repository) => {
+                    RepositoryUtils::create_lightweight_tag(&repository, tag_name, commit_id)
+                }
+                None => return,
+            };
 
-                                win.try_to_find_correct_git_button_action();
-                                win.toggle_git_action_button(true);
+            match result {
+                Ok(_) => self.show_toast(&gettext("_Tag created")),
+                Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+            }
+            return;
+        }
 
-                                win.update_commits_sidebar();
-                            }
-                            Continue(true)
-                        }
-            ),
-        );
+        let (author, author_email, signing_key) = self.get_commit_author_information();
 
-        result_receiver.attach(
-            None,
-            clone!(
-                @weak self as win => @default-return Continue(false),
-                        move |_| {
-                            let toast = adw::Toast::new(&gettext("_Remote branch pulled"));
-                            win.imp().toast_overlay.add_toast(toast);
+        if !is_signing {
+            self.create_annotated_tag_and_update_ui(
+                commit_id,
+                tag_name,
+                message,
+                &author,
+                &author_email,
+                "",
+                "",
+            );
+        } else if signing_key.is_empty() {
+            self.show_toast(&gettext("_No signing key"));
+        } else {
+            self.emit_by_name::<()>(
+                "create-tag-with-signing-key",
+                &[
+                    &commit_id,
+                    &tag_name,
+                    &message,
+                    &author,
+                    &author_email,
+                    &signing_key,
+                ],
+            );
+        }
+    }
 
-                            win.update_pull_indication_box(0);
-                            win.try_to_find_correct_git_button_action();
-                            win.toggle_git_action_button(true);
+    /// Used to create an annotated tag and update UI.
+    pub fn create_annotated_tag_and_update_ui(
+        &self,
+        commit_id: &str,
+        tag_name: &str,
+        message: &str,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) {
+        let selected_repository = self.get_selected_repository();
 
-                            win.update_commits_sidebar();
-                            Continue(true)
-                        }
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::create_annotated_tag(
+                &repository,
+                tag_name,
+                commit_id,
+                message,
+                author,
+                author_email,
+                signing_key,
+                passphrase,
             ),
-        );
+            None => return,
+        };
+
+        match result {
+            Ok(_) => self.show_toast(&gettext("_Tag created")),
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
     }
 
-    /// Used to discard a file and update UI.
-    pub fn discard_file_and_update_ui(&self, file_path: &str) {
+    /// Used to delete a tag of the repository and update UI.
+    pub fn delete_local_tag_and_update_ui(&self, tag_name: &str) {
         let selected_repository = self.get_selected_repository();
-        match RepositoryUtils::discard_one_file(
-            &selected_repository.git_repository.unwrap(),
-            file_path,
-        ) {
+
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::delete_local_tag(&repository, tag_name),
+            None => return,
+        };
+
+        match result {
             Ok(_) => {
-                self.update_commits_sidebar();
-                // We update the file view because if the changed file was the viewed file, we need to make sure that it is no longer viewable.
-                self.update_file_view_if_necessary();
+                self.show_toast(&gettext("_Tag deleted"));
+                self.imp()
+                    .tag_view
+                    .fetch_all_tags(selected_repository.user_repository.path);
             }
-            Err(error) => self.emit_by_name::<()>("error", &[&error.to_string()]),
-        };
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
     }
 
-    /// Used to change the current branch.
-    pub fn checkout_branch_and_update_ui(&self, branch_to_checkout_to: String, is_remote: bool) {
+    /// Used to add a remote and update UI.
+    pub fn add_remote_and_update_ui(&self, remote_name: &str, remote_url: &str) {
         let selected_repository = self.get_selected_repository();
 
-        let (sender, receiver) = MainContext::channel::<Result<(), String>>(Priority::default());
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::add_remote(&repository, remote_name, remote_url),
+            None => return,
+        };
 
-        self.imp().git_action_button.set_sensitive(false);
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Remote added"));
+                self.try_to_find_correct_git_button_action();
+                self.imp().remote_view.clear_remote_information();
+                self.imp()
+                    .remote_view
+                    .fetch_all_remotes(selected_repository.user_repository.path);
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
 
-        thread::spawn(move || {
-            let sender = sender.clone();
+    /// Used to rename a remote and change its URL, and update UI.
+    pub fn update_remote_and_update_ui(
+        &self,
+        remote_name: &str,
+        new_remote_name: &str,
+        new_remote_url: &str,
+    ) {
+        let selected_repository = self.get_selected_repository();
 
-            match RepositoryUtils::checkout_branch(
-                &selected_repository.git_repository.as_ref().unwrap(),
-                &branch_to_checkout_to,
-                is_remote,
-            ) {
-                Ok(_) => sender
-                    .send(Ok(()))
-                    .expect("Could not send result through channel"),
-                Err(error) => sender
-                    .send(Err(error.to_string()))
-                    .expect("Could not send error through channel"),
-            };
-        });
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::update_remote(
+                &repository,
+                remote_name,
+                new_remote_name,
+                new_remote_url,
+            ),
+            None => return,
+        };
 
-        let repo_path = selected_repository.user_repository.path;
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Remote updated"));
+                self.imp()
+                    .remote_view
+                    .fetch_all_remotes(selected_repository.user_repository.path.clone());
+                self.imp()
+                    .branch_view
+                    .fetch_all_branches(selected_repository.user_repository.path);
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
+    }
 
-        receiver.attach(
-            None,
-            clone!(
-                @weak self as win => @default-return Continue(false),
-                        move |result| {
-                            win.imp().git_action_button.set_sensitive(true);
+    /// Used to delete a remote and update UI.
+    pub fn delete_remote_and_update_ui(&self, remote_name: &str) {
+        let selected_repository = self.get_selected_repository();
 
-                            match result {
-                                Ok(_) => win.update_pull_indication_box(0),
-                                Err(error) => win.emit_by_name::<()>("error", &[&error.to_string()])
-                            }
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::delete_remote(&repository, remote_name),
+            None => return,
+        };
 
-                            win.update_commits_sidebar();
-                            win.update_branch_name();
-                            win.imp()
-                                .branch_view
-                                .fetch_all_branches(repo_path.clone());
-                            Continue(true)
-                        }
-            ),
-        );
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Remote deleted"));
+                self.try_to_find_correct_git_button_action();
+                self.imp()
+                    .remote_view
+                    .fetch_all_remotes(selected_repository.user_repository.path.clone());
+                self.imp()
+                    .branch_view
+                    .fetch_all_branches(selected_repository.user_repository.path);
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
     }
 
-    /// Fetches the repository checked out branch, and update ui.
-    fn fetch_repository_checked_out_branch_and_update_ui(
+    /// Used to push a tag, or all tags when the tag name is empty, and update UI.
+    pub fn push_tags_and_update_ui(
         &self,
         username: String,
         password: String,
         private_key_path: String,
         passphrase: String,
+        tag_name: String,
+        remote_name: String,
     ) {
         let selected_repository = self.get_selected_repository();
-        let selected_repository_path = selected_repository.user_repository.path;
-
-        if selected_repository.git_repository.is_none() {
-            return;
-        }
-        let git_repo = selected_repository.git_repository.unwrap();
-
-        let (sender, receiver) =
-            MainContext::channel::<Result<FetchResult, git2::Error>>(Priority::default());
+        let (result_sender, result_receiver) =
+            MainContext::channel::<Result<(), String>>(Priority::default());
 
         self.toggle_git_action_button(false);
 
         thread::spawn(move || {
-            let sender = sender.clone();
+            let result_sender = result_sender.clone();
 
-            let fetch = fetch_checked_out_branch(
-                &git_repo,
+            match RepositoryUtils::push_tags(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                &remote_name,
+                &tag_name,
                 username,
                 password,
                 private_key_path,
                 passphrase,
-            );
-
-            sender.send(fetch).expect("Could not send through channel");
+            ) {
+                Ok(_) => result_sender
+                    .send(Ok(()))
+                    .expect("Could not send result through channel"),
+                Err(error) => result_sender
+                    .send(Err(error.to_string()))
+                    .expect("Could not send error through channel"),
+            };
         });
 
-        receiver.attach(
+        result_receiver.attach(
             None,
-            clone!(@weak self as win => @default-return Continue(false),
-                    move |fetch| {
-                        match fetch {
-                            Ok(fetch_result) => {
-                                win.update_git_action_button(fetch_result);
-                                win.imp().branch_view.fetch_all_branches(selected_repository_path.clone());
-                            },
-                            Err(error) => {
-                                win.try_to_find_correct_git_button_action();
-                                win.toggle_git_action_button(true);
-                                win.emit_by_name::<()>("error", &[&error.to_string()])
-                            },
-                        }
-                        Continue(true)
-                    }
-            ),
-        );
-    }
-
-    /// Used to discard a folder and update UI.
-    pub fn discard_folder_and_update_ui(&self, folder_path: &str) {
-        let selected_repository = self.get_selected_repository();
-
-        let folder_files: Vec<ChangedFile>;
-        {
-            let file_tree = self.imp().sidebar.imp().changed_files.borrow();
-            folder_files = file_tree.get_files_of_folder(folder_path);
-        }
-
-        match RepositoryUtils::discard_folder(
-            &selected_repository.git_repository.unwrap(),
-            &folder_files,
-        ) {
-            Ok(_) => {
-                self.update_commits_sidebar();
-                // We update the file view because one of the folder's files was the viewed file, we need to make sure that it is no longer viewable.
-                self.update_file_view_if_necessary();
-            }
-            Err(error) => self.emit_by_name::<()>("error", &[&error.to_string()]),
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |result| {
+                            match result {
+                                Ok(_) => win.show_toast(&gettext("_Tags pushed")),
+                                Err(error) => win.emit_by_name::<()>("error", &[&error.to_string()]),
+                            }
+                            win.toggle_git_action_button(true);
+                            Continue(true)
+                        }
+            ),
+        );
+    }
+
+    /// Used to show the history deepening action only in a shallow repository.
+    fn update_deepen_history_button(&self) {
+        let is_shallow = match self.get_selected_repository().git_repository {
+            Some(repository) => repository.is_shallow(),
+            None => false,
         };
+
+        self.imp().deepen_history_button.set_visible(is_shallow);
     }
 
-    /// Fetches the repository checked out branch, and update ui.
-    fn try_fetch_without_auth_and_update_ui(&self) {
-        let selected_repository = self.get_selected_repository();
-        let selected_repository_path = selected_repository.user_repository.path;
+    /// Used to fetch more history in the shallow repository.
+    /// The history is deepened by the given number of commits, or completely fetched for 0.
+    pub fn deepen_history(&self, depth_increment: u32) {
+        self.imp().history_deepening.set(depth_increment);
+        self.do_git_action_with_auth_check(ActionType::DeepenHistory, &"", &"");
+    }
 
-        if selected_repository.git_repository.is_none() {
-            return;
-        }
-        let git_repo = selected_repository.git_repository.unwrap();
+    /// Used to fetch more history with the given authentication information, and update UI.
+    fn deepen_history_and_update_ui(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) {
+        let selected_repository = self.get_selected_repository();
+        let selected_repository_path = selected_repository.user_repository.path.clone();
+        let depth_increment = self.imp().history_deepening.get();
 
-        let (sender, receiver) =
-            MainContext::channel::<Result<FetchResult, git2::Error>>(Priority::default());
+        let (result_sender, result_receiver) =
+            MainContext::channel::<Result<(), String>>(Priority::default());
 
         self.toggle_git_action_button(false);
+        let (is_cancelled, progress_sender) = self.start_transfer();
 
         thread::spawn(move || {
-            let sender = sender.clone();
-
-            let fetch = fetch_checked_out_branch(
-                &git_repo,
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-            );
+            let result_sender = result_sender.clone();
 
-            sender.send(fetch).expect("Could not send through channel");
+            match RepositoryUtils::deepen_history(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                depth_increment,
+                username,
+                password,
+                private_key_path,
+                passphrase,
+                is_cancelled,
+                move |progress| {
+                    let _ = progress_sender.send(progress);
+                },
+            ) {
+                Ok(_) => result_sender
+                    .send(Ok(()))
+                    .expect("Could not send result through channel"),
+                Err(error) => result_sender
+                    .send(Err(error.to_string()))
+                    .expect("Could not send error through channel"),
+            };
         });
 
-        receiver.attach(
+        result_receiver.attach(
             None,
-            clone!(@weak self as win => @default-return Continue(false),
-                        move |fetch| {
-                            match fetch {
-                                Ok(fetch_result) => {
-                                    win.update_git_action_button(fetch_result);
-                                    win.imp().branch_view.fetch_all_branches(selected_repository_path.clone());
-                                },
-                                Err(error) => {
-                                    // TODO: Manage errors.
-                                    if (error.class() == git2::ErrorClass::Http)
-                                        || (error.class() == git2::ErrorClass::Ssh)
-                                    {
-                                        win.do_git_action_with_auth_check(ActionType::Fetch, &"");
-                                    } else {
-                                        win.emit_by_name::<()>("error", &[&error.to_string()]);
-                                        win.update_commits_sidebar();
-                                        win.try_to_find_correct_git_button_action();
-                                        win.toggle_git_action_button(true);
-                                    }
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |result| {
+                            let is_cancelled = win.stop_transfer();
+
+                            match result {
+                                Ok(_) => {
+                                    win.imp().sidebar.init_commit_list(selected_repository_path.clone());
+                                    win.show_toast(&gettext("_History deepened"));
                                 },
+                                Err(_) if is_cancelled => win.show_toast(&gettext("_Transfer cancelled")),
+                                Err(error) => win.emit_by_name::<()>("error", &[&error.to_string()]),
                             }
+                            win.update_deepen_history_button();
+                            win.toggle_git_action_button(true);
                             Continue(true)
                         }
             ),
         );
     }
 
-    /// Show a toast with a given message.
-    pub fn show_toast(&self, toast_message: &str) {
-        let toast = adw::Toast::new(toast_message);
-        self.imp().toast_overlay.add_toast(toast);
-    }
-
-    /// Used to checked if there is changed files in the current branch.
-    /// If an error occurs, it will return true by default.
-    pub fn has_changed_files(&self) -> bool {
+    /// Used to initialize submodules, an empty name initializing all of them, and update UI.
+    pub fn init_submodules_and_update_ui(&self, submodule_name: &str) {
         let selected_repository = self.get_selected_repository();
-        let git_repo = selected_repository.git_repository;
-
-        match git_repo {
-            Some(repo) => RepositoryUtils::has_changed_files(&repo),
-            None => false,
-        }
-    }
-
-    /// Retrieves the selected repository.
-    pub fn get_selected_repository(&self) -> SelectedRepository {
-        let selected_repository = self.imp().selected_repository.take();
-        self.imp().selected_repository.replace(
-            SelectedRepository::try_fetching_selected_repository(
-                &selected_repository.user_repository,
-            )
-            .unwrap(),
-        );
-        selected_repository
-    }
 
-    /// Retrieves the path of the repository.
-    pub fn get_selected_repository_path(&self) -> String {
-        let selected_repository = self.get_selected_repository();
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::init_submodules(&repository, submodule_name),
+            None => return,
+        };
 
-        return selected_repository.user_repository.path;
+        match result {
+            Ok(_) => {
+                self.show_toast(&gettext("_Submodule initialized"));
+                self.imp()
+                    .submodule_view
+                    .fetch_all_submodules(selected_repository.user_repository.path);
+            }
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
+        }
     }
 
-    /// Try to show the content of a file.
-    pub fn try_showing_file_content(&self, parent_folder: &str, file_name: &str) {
+    /// Used to synchronize the URL of submodules, an empty name synchronizing all of them, and update UI.
+    pub fn sync_submodules_and_update_ui(&self, submodule_name: &str) {
         let selected_repository = self.get_selected_repository();
-        let git_repo = selected_repository.git_repository;
-
-        if git_repo.is_none() {
-            return;
-        }
 
-        let found_repo = git_repo.unwrap();
+        let result = match &selected_repository.git_repository {
+            Some(repository) => RepositoryUtils::sync_submodules(&repository, submodule_name),
+            None => return,
+        };
 
-        match self.imp().file_view.define_how_to_show_file_content(
-            &selected_repository.user_repository.path,
-            &found_repo,
-            &parent_folder,
-            &file_name,
-        ) {
+        match result {
             Ok(_) => {
+                self.show_toast(&gettext("_Submodules synchronized"));
                 self.imp()
-                    .main_view_stack
-                    .set_visible_child_name("file view");
-                self.imp()
-                    .file_view
-                    .set_file_information(&parent_folder, &file_name)
+                    .submodule_view
+                    .fetch_all_submodules(selected_repository.user_repository.path);
             }
-            Err(error) => self.emit_by_name("error", &[&error.to_string()]),
+            Err(error) => self.emit_by_name::<()>("error", &[&error.message().to_string()]),
         }
     }
 
-    /// Update the file view if we are on it.
-    /// The update will do the following :
-    /// - Check if the shown file is still present on the changed files. If not, we will go back to the main view.
-    /// - Update the file content if it has changed.
-    pub fn update_file_view_if_necessary(&self) {
-        // If the current view shown in the repository page isn't the file view, we don't go any further.
-        let is_on_file_view = match self.imp().main_view_stack.visible_child_name() {
-            Some(current_view) => current_view == "file view",
-            None => false,
-        };
+    /// Used to update submodules recursively, an empty name updating all of them, and update UI.
+    fn update_submodules_and_update_ui(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+        submodule_name: String,
+    ) {
+        let selected_repository = self.get_selected_repository();
+        let selected_repository_path = selected_repository.user_repository.path.clone();
 
-        if !is_on_file_view {
-            return;
-        }
+        let (result_sender, result_receiver) =
+            MainContext::channel::<Result<(), String>>(Priority::default());
 
-        let selected_repository = self.get_selected_repository();
+        self.toggle_git_action_button(false);
 
-        if selected_repository.git_repository.is_none() {
-            return;
-        }
-        let git_repo = selected_repository.git_repository.unwrap();
+        thread::spawn(move || {
+            let result_sender = result_sender.clone();
 
-        // First, we check if the current shown file is still in the changed files:
-        let current_shown_file_info = self.imp().file_view.get_current_shown_file_information();
-        let relative_path = RepositoryUtils::build_path_of_file(
-            &current_shown_file_info.0,
-            &current_shown_file_info.1,
+            match RepositoryUtils::update_submodules(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                &submodule_name,
+                true,
+                username,
+                password,
+                private_key_path,
+                passphrase,
+            ) {
+                Ok(_) => result_sender
+                    .send(Ok(()))
+                    .expect("Could not send result through channel"),
+                Err(error) => result_sender
+                    .send(Err(error.to_string()))
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        result_receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |result| {
+                            match result {
+                                Ok(_) => win.show_toast(&gettext("_Submodules updated")),
+                                Err(error) => win.emit_by_name::<()>("error", &[&error.to_string()]),
+                            }
+                            win.imp().submodule_view.fetch_all_submodules(selected_repository_path.clone());
+                            win.update_commits_sidebar();
+                            win.toggle_git_action_button(true);
+                            Continue(true)
+                        }
+            ),
         );
-        let path = Path::new(&relative_path);
-        let file_status = git_repo.status_file(&path);
+    }
 
-        // If the file is not in the changed files anymore, we go back to the main repository view.
-        let should_go_to_main_view = match file_status {
-            Ok(status) => status == Status::CURRENT,
-            Err(_) => true,
-        };
+    /// Used to delete a tag from the remote and from the repository, and update UI.
+    pub fn delete_remote_tag_and_update_ui(
+        &self,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+        tag_name: String,
+        remote_name: String,
+    ) {
+        let selected_repository = self.get_selected_repository();
+        let (result_sender, result_receiver) =
+            MainContext::channel::<Result<(), String>>(Priority::default());
 
-        if should_go_to_main_view {
-            self.imp()
-                .main_view_stack
-                .set_visible_child_name("hello page");
-            // We remove the current file information as it is not longer useful.
-            self.imp().file_view.set_file_information("", "");
-        } else {
-            self.try_showing_file_content(&current_shown_file_info.0, &current_shown_file_info.1);
-        }
+        self.toggle_git_action_button(false);
+
+        let cloned_tag_name = tag_name.clone();
+
+        thread::spawn(move || {
+            let result_sender = result_sender.clone();
+
+            match RepositoryUtils::delete_remote_tag(
+                &selected_repository.git_repository.as_ref().unwrap(),
+                &remote_name,
+                &tag_name,
+                username,
+                password,
+                private_key_path,
+                passphrase,
+            ) {
+                Ok(_) => result_sender
+                    .send(Ok(()))
+                    .expect("Could not send result through channel"),
+                Err(error) => result_sender
+                    .send(Err(error.to_string()))
+                    .expect("Could not send error through channel"),
+            };
+        });
+
+        result_receiver.attach(
+            None,
+            clone!(
+                @weak self as win => @default-return Continue(false),
+                        move |result| {
+                            match result {
+                                Ok(_) => win.delete_local_tag_and_update_ui(&cloned_tag_name),
+                                Err(error) => win.emit_by_name::<()>("error", &[&error.to_string()]),
+                            }
+                            win.toggle_git_action_button(true);
+                            Continue(true)
+                        }
+            ),
+        );
     }
 }
@@ -24,7 +24,13 @@ use gtk::glib;
 pub enum ActionType {
     #[default]
     Fetch,
+    FetchAllRemotes,
     Push,
     Pull,
     DeleteRemoteBranch,
+    PushTags,
+    DeleteRemoteTag,
+    DeepenHistory,
+    UpdateSubmodules,
+    Publish,
 }
@@ -0,0 +1,54 @@
+/* agent_utils.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::fs;
+use std::process::Command;
+
+pub struct AgentUtils {}
+
+impl AgentUtils {
+    /// Used to know if an SSH private key is loaded in the running ssh-agent.
+    /// The public key next to the private key is looked for in the keys listed by the agent.
+    pub fn is_ssh_key_in_agent(private_key_path: &str) -> bool {
+        if private_key_path.is_empty() || std::env::var_os("SSH_AUTH_SOCK").is_none() {
+            return false;
+        }
+
+        let public_key = match fs::read_to_string(format!("{}.pub", private_key_path)) {
+            Ok(public_key) => public_key,
+            Err(_) => return false,
+        };
+
+        // A public key is "<type> <key> [comment]", only the key is compared:
+        let key = match public_key.split_whitespace().nth(1) {
+            Some(key) => key.to_string(),
+            None => return false,
+        };
+
+        match Command::new("ssh-add").arg("-L").output() {
+            Ok(output) => String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|line| line.split_whitespace().nth(1) == Some(key.as_str())),
+            Err(error) => {
+                tracing::warn!("Could not list the keys of the ssh-agent: {}", error);
+                false
+            }
+        }
+    }
+}
@@ -24,9 +24,13 @@ use std::fmt;
 pub struct ChangedFile {
     pub parent: String,
     pub name: String,
+    /// The status of the staged changes for a staged file, of the unstaged changes otherwise.
     pub status: Status,
-    pub is_selected: bool,
+    /// Whether the file is shown with the staged changes, a file can have both staged and unstaged changes.
+    pub is_staged: bool,
     pub is_opened: bool,
+    /// Whether the changed path is a submodule, whose recorded commit or content changed.
+    pub is_submodule: bool,
 }
 
 impl fmt::Debug for ChangedFile {
@@ -41,15 +45,16 @@ impl Default for ChangedFile {
             parent: String::new(),
             name: String::new(),
             status: Status::WT_MODIFIED,
-            is_selected: false,
+            is_staged: false,
             is_opened: false,
+            is_submodule: false,
         };
     }
 }
 
 impl fmt::Display for ChangedFile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {}", self.name, self.is_selected, self.is_opened)
+        write!(f, "{} {} {}", self.name, self.is_staged, self.is_opened)
     }
 }
 
@@ -61,15 +66,16 @@ impl ChangedFile {
         parent: String,
         name: String,
         status: Status,
-        is_selected: bool,
+        is_staged: bool,
         is_opened: bool,
     ) -> ChangedFile {
         return ChangedFile {
             parent,
             name,
             status,
-            is_selected,
+            is_staged,
             is_opened,
+            is_submodule: false,
         };
     }
 
@@ -79,6 +85,33 @@ impl ChangedFile {
     pub fn is_same_element(&self, changed_file: &ChangedFile) -> bool {
         return self.parent == changed_file.parent
             && self.name == changed_file.name
-            && self.status == changed_file.status;
+            && self.status == changed_file.status
+            && self.is_staged == changed_file.is_staged;
+    }
+
+    /**
+     * Used to retrieve the part of a status describing the changes written to the index.
+     */
+    pub fn get_staged_status(status: Status) -> Status {
+        return status
+            & (Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE);
+    }
+
+    /**
+     * Used to retrieve the part of a status describing the changes of the working directory.
+     * Conflicts are part of it, since they have to be resolved before being staged.
+     */
+    pub fn get_unstaged_status(status: Status) -> Status {
+        return status
+            & (Status::WT_NEW
+                | Status::WT_MODIFIED
+                | Status::WT_DELETED
+                | Status::WT_RENAMED
+                | Status::WT_TYPECHANGE
+                | Status::CONFLICTED);
     }
 }
@@ -0,0 +1,31 @@
+/* clone_options.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+/// Options restricting what is cloned from a remote.
+#[derive(Debug, Clone, Default)]
+pub struct CloneOptions {
+    /// The number of commits of history cloned, 0 for the whole history.
+    pub depth: u32,
+    /// The only branch cloned, empty to clone all branches.
+    pub branch_name: String,
+    /// Whether the repository is cloned without working directory.
+    pub is_bare: bool,
+    /// Whether the submodules are initialized and cloned recursively.
+    pub is_recursive: bool,
+}
@@ -0,0 +1,45 @@
+/* commit_details.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use git2::Delta;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    #[default]
+    NotSigned,
+    Valid,
+    UnknownKey,
+    Invalid,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CommitDetails {
+    pub commit_id: String,
+    pub message: String,
+    pub author: String,
+    pub author_date: String,
+    pub committer: String,
+    pub committer_date: String,
+    pub parent_ids: Vec<String>,
+    pub signature_status: SignatureStatus,
+    /// Fingerprint of the key used to sign the commit, empty if unknown.
+    pub signer: String,
+    /// Path and kind of change of every file changed compared to the first parent.
+    pub changed_files: Vec<(String, Delta)>,
+}
@@ -0,0 +1,201 @@
+/* commit_graph.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use git2::Oid;
+
+/// A reference pointing to a commit, shown as a label in the commit graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefLabel {
+    Head,
+    LocalBranch(String),
+    RemoteBranch(String),
+    Tag(String),
+}
+
+impl RefLabel {
+    /// Used to retrieve the text of the label.
+    pub fn get_name(&self) -> String {
+        return match self {
+            RefLabel::Head => String::from("HEAD"),
+            RefLabel::LocalBranch(name) | RefLabel::RemoteBranch(name) | RefLabel::Tag(name) => {
+                name.clone()
+            }
+        };
+    }
+
+    /// Used to retrieve the style class of the label.
+    pub fn get_css_class(&self) -> &'static str {
+        return match self {
+            RefLabel::Head => "ref-label-head",
+            RefLabel::LocalBranch(_) => "ref-label-branch",
+            RefLabel::RemoteBranch(_) => "ref-label-remote",
+            RefLabel::Tag(_) => "ref-label-tag",
+        };
+    }
+}
+
+/// The place of a commit in the commit graph, with the lines drawn next to it.
+/// Lines go from a lane to another, between the top, the middle and the bottom of the row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitGraphRow {
+    /// The lane of the commit dot.
+    pub commit_lane: usize,
+    /// Lines from a lane at the top of the row to a lane at its middle.
+    pub top_lines: Vec<(usize, usize)>,
+    /// Lines from a lane at the middle of the row to a lane at its bottom.
+    pub bottom_lines: Vec<(usize, usize)>,
+    /// The number of lanes used by the row.
+    pub lane_count: usize,
+    pub ref_labels: Vec<RefLabel>,
+}
+
+/// The history shown by the commit graph, walked once and then added to the graph page by page.
+#[derive(Debug, Clone, Default)]
+pub struct CommitGraphHistory {
+    /// The commits not added to the graph yet, from the newest.
+    pub commit_ids: VecDeque<Oid>,
+    pub ref_labels: HashMap<Oid, Vec<RefLabel>>,
+    pub unpushed_commit_ids: HashSet<Oid>,
+    /// The commits pointed by the references when the history was walked.
+    pub ref_targets: Vec<(String, Oid)>,
+}
+
+/// Assigns lanes to the commits of the history, given one after another from the newest.
+/// Each lane waits for the next commit of a line of history, so the graph can be built page by page.
+#[derive(Debug, Clone, Default)]
+pub struct CommitGraph {
+    lanes: Vec<Option<Oid>>,
+    /// None until the history is walked for the first page.
+    history: Option<CommitGraphHistory>,
+}
+
+impl CommitGraph {
+    pub fn get_history(&self) -> Option<&CommitGraphHistory> {
+        return self.history.as_ref();
+    }
+
+    pub fn set_history(&mut self, history: CommitGraphHistory) {
+        self.history = Some(history);
+    }
+
+    /// Used to take the next commits of the history to add to the graph.
+    pub fn take_next_commit_ids(&mut self, nb_commits: usize) -> Vec<Oid> {
+        return match self.history.as_mut() {
+            Some(history) => {
+                let nb_commits = nb_commits.min(history.commit_ids.len());
+                history.commit_ids.drain(..nb_commits).collect()
+            }
+            None => vec![],
+        };
+    }
+
+    /// Used to know if a commit of the history is on the remote.
+    pub fn is_commit_pushed(&self, commit_id: &Oid) -> bool {
+        return match &self.history {
+            Some(history) => !history.unpushed_commit_ids.contains(commit_id),
+            None => true,
+        };
+    }
+
+    /// Used to add the next commit of the history to the graph.
+    /// Parents are expected to come after their children.
+    pub fn add_commit(&mut self, commit_id: Oid, parent_ids: &[Oid]) -> CommitGraphRow {
+        let lanes_before = self.lanes.clone();
+
+        // A commit nobody waits for is the tip of a new line of history:
+        let commit_lane = match self.lanes.iter().position(|lane| *lane == Some(commit_id)) {
+            Some(lane) => lane,
+            None => self.take_free_lane(),
+        };
+
+        let top_lines: Vec<(usize, usize)> = lanes_before
+            .iter()
+            .enumerate()
+            .filter_map(|(lane, waited_commit)| match waited_commit {
+                Some(id) if *id == commit_id => Some((lane, commit_lane)),
+                Some(_) => Some((lane, lane)),
+                None => None,
+            })
+            .collect();
+
+        // Every line waiting for the commit merges into it:
+        for lane in self.lanes.iter_mut() {
+            if *lane == Some(commit_id) {
+                *lane = None;
+            }
+        }
+
+        let mut bottom_lines: Vec<(usize, usize)> = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter_map(|(lane, waited_commit)| waited_commit.map(|_| (lane, lane)))
+            .collect();
+
+        for (index, parent_id) in parent_ids.iter().enumerate() {
+            let parent_lane = match self.lanes.iter().position(|lane| *lane == Some(*parent_id)) {
+                Some(lane) => lane,
+                // The first parent continues the lane of the commit:
+                None if index == 0 => {
+                    self.lanes[commit_lane] = Some(*parent_id);
+                    commit_lane
+                }
+                None => {
+                    let lane = self.take_free_lane();
+                    self.lanes[lane] = Some(*parent_id);
+                    lane
+                }
+            };
+
+            bottom_lines.push((commit_lane, parent_lane));
+        }
+
+        while let Some(None) = self.lanes.last() {
+            self.lanes.pop();
+        }
+
+        return CommitGraphRow {
+            commit_lane,
+            top_lines,
+            bottom_lines,
+            lane_count: lanes_before
+                .len()
+                .max(self.lanes.len())
+                .max(commit_lane + 1),
+            ref_labels: self
+                .history
+                .as_mut()
+                .and_then(|history| history.ref_labels.remove(&commit_id))
+                .unwrap_or_default(),
+        };
+    }
+
+    /// Used to find a lane waiting for no commit, a new one being added when there is none.
+    fn take_free_lane(&mut self) -> usize {
+        return match self.lanes.iter().position(|lane| lane.is_none()) {
+            Some(lane) => lane,
+            None => {
+                self.lanes.push(None);
+                self.lanes.len() - 1
+            }
+        };
+    }
+}
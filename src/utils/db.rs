@@ -26,7 +26,7 @@ use uuid::Uuid;
 
 use crate::models::{bagit_git_profile::BagitGitProfile, bagit_repository::BagitRepository};
 
-use super::migrations::migrations;
+use super::{migrations::migrations, secret_store::SecretStoreUtils};
 
 #[derive(Debug)]
 pub struct AppDatabase {
@@ -230,12 +230,17 @@ impl AppDatabase {
                 None
             };
 
+            let profile_uuid = profile_uuid.unwrap();
+
             Ok(BagitGitProfile {
-                profile_id: profile_uuid.unwrap(),
+                profile_id: profile_uuid,
                 profile_name: row.get("profileName")?,
                 email: row.get("email")?,
                 username: row.get("username")?,
-                password: row.get("password")?,
+                // The password of the secret store is only retrieved when needed:
+                password: row
+                    .get::<_, Option<String>>("password")?
+                    .unwrap_or_default(),
                 private_key_path: row.get("privateKeyPath")?,
                 signing_key: row.get("signingKey")?,
             })
@@ -335,13 +340,13 @@ impl AppDatabase {
     }
 
     /// Checks whether a Git profile already exists with the same information.
+    /// The password is not compared, as it is kept in the secret store.
     pub fn does_git_profile_exist_from_information(
         &self,
         profile_id: &str,
         profile_name: &str,
         email: &str,
         username: &str,
-        password: &str,
         private_key_path: &str,
         signing_key: &str,
     ) -> Result<bool, rusqlite::Error> {
@@ -357,16 +362,14 @@ impl AppDatabase {
             AND profileName=?2
             AND email=?3
             AND username=?4
-            AND password=?5
-            AND privateKeyPath=?6
-            AND signingKey=?7;";
+            AND privateKeyPath=?5
+            AND signingKey=?6;";
 
         let parameters = [
             profile_id,
             profile_name,
             email,
             username,
-            password,
             private_key_path,
             signing_key,
         ];
@@ -446,7 +449,10 @@ impl AppDatabase {
                     profile_name: row.get("profileName")?,
                     email: row.get("email")?,
                     username: row.get("username")?,
-                    password: row.get("password")?,
+                    // The password of the secret store is only retrieved when needed:
+                    password: row
+                        .get::<_, Option<String>>("password")?
+                        .unwrap_or_default(),
                     private_key_path: row.get("privateKeyPath")?,
                     signing_key: row.get("signingKey")?,
                 })
@@ -485,7 +491,10 @@ impl AppDatabase {
                     profile_name: profile_name.to_string(),
                     email: row.get("email")?,
                     username: row.get("username")?,
-                    password: row.get("password")?,
+                    // The password of the secret store is only retrieved when needed:
+                    password: row
+                        .get::<_, Option<String>>("password")?
+                        .unwrap_or_default(),
                     private_key_path: row.get("privateKeyPath")?,
                     signing_key: row.get("signingKey")?,
                 })
@@ -505,14 +514,17 @@ impl AppDatabase {
             return Err(rusqlite::Error::InvalidPath(PathBuf::from("")));
         }
 
-        let query = "INSERT INTO gitProfile VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);";
+        SecretStoreUtils::store_password(&profile.profile_id.to_string(), &profile.password)
+            .map_err(|error| rusqlite::Error::ToSqlConversionFailure(error.into()))?;
+
+        // The password is kept in the secret store, not in the database:
+        let query = "INSERT INTO gitProfile VALUES (?1, ?2, ?3, ?4, '', ?5, ?6);";
 
         let parameters = [
             profile.profile_id.to_string(),
             profile.profile_name.to_owned(),
             profile.email.to_owned(),
             profile.username.to_owned(),
-            profile.password.to_owned(),
             profile.private_key_path.to_owned(),
             profile.signing_key.to_owned(),
         ];
@@ -532,20 +544,23 @@ impl AppDatabase {
             return Err(rusqlite::Error::InvalidPath(PathBuf::from("")));
         }
 
+        SecretStoreUtils::store_password(&profile.profile_id.to_string(), &profile.password)
+            .map_err(|error| rusqlite::Error::ToSqlConversionFailure(error.into()))?;
+
+        // The password is kept in the secret store, not in the database:
         let query = "UPDATE gitProfile SET 
             profileName=?1,
             email=?2,
             username=?3,
-            password=?4,
-            privateKeyPath=?5,
-            signingKey=?6
-            WHERE profileId=?7;";
+            password='',
+            privateKeyPath=?4,
+            signingKey=?5
+            WHERE profileId=?6;";
 
         let parameters = [
             profile.profile_name.to_owned(),
             profile.email.to_owned(),
             profile.username.to_owned(),
-            profile.password.to_owned(),
             profile.private_key_path.to_owned(),
             profile.signing_key.to_owned(),
             profile.profile_id.to_string(),
@@ -572,9 +587,72 @@ impl AppDatabase {
 
         connection.execute(query, parameters)?;
 
+        if let Err(error) = SecretStoreUtils::delete_password(profile_id) {
+            tracing::warn!("Could not delete password of Git profile: {}", error);
+        }
+
         Ok(())
     }
 
+    /// Moves the passwords still written in the database to the secret store.
+    /// Returns the number of moved passwords.
+    pub fn move_passwords_to_secret_store(&self) -> Result<usize, rusqlite::Error> {
+        let connection;
+
+        if let Some(conn) = &self.connection {
+            connection = conn
+        } else {
+            return Err(rusqlite::Error::InvalidPath(PathBuf::from("")));
+        }
+
+        let query = "SELECT profileId, password FROM gitProfile WHERE password IS NOT NULL AND password!='';";
+
+        let mut statement: rusqlite::Statement = connection.prepare(query)?;
+
+        let profiles = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>("profileId")?,
+                    row.get::<_, String>("password")?,
+                ))
+            })?
+            .collect::<Result<Vec<(String, String)>, rusqlite::Error>>()?;
+
+        for (profile_id, password) in &profiles {
+            SecretStoreUtils::store_password(profile_id, password)
+                .map_err(|error| rusqlite::Error::ToSqlConversionFailure(error.into()))?;
+
+            connection.execute(
+                "UPDATE gitProfile SET password='' WHERE profileId=?1;",
+                [profile_id],
+            )?;
+        }
+
+        // The database is rebuilt so that the removed passwords do not stay in its free pages:
+        if !profiles.is_empty() {
+            connection.execute("VACUUM;", [])?;
+        }
+
+        return Ok(profiles.len());
+    }
+
+    /// Retrieves the password of a Git profile from the secret store.
+    /// It may block while the store answers, so the profiles are read without it.
+    /// A password still written in the database is used until it is moved to the secret store.
+    pub fn retrieve_password_of_git_profile(profile: &BagitGitProfile) -> String {
+        if !profile.password.is_empty() {
+            return profile.password.clone();
+        }
+
+        match SecretStoreUtils::retrieve_password(&profile.profile_id.to_string()) {
+            Ok(password) => password.unwrap_or_default(),
+            Err(error) => {
+                tracing::warn!("Could not retrieve password of Git profile: {}", error);
+                String::new()
+            }
+        }
+    }
+
     /// Deletes a repository.
     pub fn delete_repository(&self, repository_id: &str) -> Result<(), rusqlite::Error> {
         let connection;
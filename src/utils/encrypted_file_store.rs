@@ -0,0 +1,253 @@
+/* encrypted_file_store.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::fs;
+use std::path::PathBuf;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use gettextrs::gettext;
+use scrypt::Params;
+
+use super::secret_store::SecretStore;
+
+/// The first line of the file, used to recognize its format.
+const FILE_HEADER: &str = "bagit-credentials 1";
+
+/// The value encrypted in the file to check the master passphrase.
+const CHECK_VALUE: &[u8] = b"bagit-desktop";
+
+/// The size of a nonce of AES-256-GCM.
+const NONCE_LENGTH: usize = 12;
+
+/// Stores passwords in a file encrypted with a key derived from a master passphrase.
+///
+/// The file is made of lines of text: the header, the key derivation parameters,
+/// a check value encrypted with the key, then one line per Git profile with its encrypted password.
+/// Each encrypted value is bound to its profile ID, so that values cannot be swapped between profiles.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    /// The lines of the file describing how the key is derived and checked.
+    key_lines: Vec<String>,
+    key: [u8; 32],
+}
+
+impl EncryptedFileStore {
+    /// Opens the encrypted file with the master passphrase, creating the file when it does not exist.
+    pub fn open(path: PathBuf, master_passphrase: &str) -> Result<EncryptedFileStore, String> {
+        if !path.exists() {
+            return EncryptedFileStore::create(path, master_passphrase);
+        }
+
+        let content = fs::read_to_string(&path).map_err(|error| error.to_string())?;
+        let mut lines = content.lines();
+
+        if lines.next() != Some(FILE_HEADER) {
+            return Err(gettext("_Invalid credential file"));
+        }
+
+        let kdf_line = lines.next().unwrap_or("");
+        let check_line = lines.next().unwrap_or("");
+
+        // The key derivation line is "scrypt <log_n> <r> <p> <salt>":
+        let kdf_parts: Vec<&str> = kdf_line.split(' ').collect();
+        if kdf_parts.len() != 5 || kdf_parts[0] != "scrypt" {
+            return Err(gettext("_Invalid credential file"));
+        }
+        let parse_error = |_| gettext("_Invalid credential file");
+        let params = Params::new(
+            kdf_parts[1].parse().map_err(parse_error)?,
+            kdf_parts[2].parse().map_err(parse_error)?,
+            kdf_parts[3].parse().map_err(parse_error)?,
+            32,
+        )
+        .map_err(|error| error.to_string())?;
+        let salt = hex::decode(kdf_parts[4]).map_err(|error| error.to_string())?;
+
+        let key = EncryptedFileStore::derive_key(master_passphrase, &salt, &params)?;
+
+        let store = EncryptedFileStore {
+            path,
+            key_lines: vec![kdf_line.to_string(), check_line.to_string()],
+            key,
+        };
+
+        // A wrong passphrase gives a key that cannot decrypt the check value:
+        let check_value = check_line.strip_prefix("check ").unwrap_or("");
+        match store.decrypt("check", check_value) {
+            Ok(value) if value.as_bytes() == CHECK_VALUE => Ok(store),
+            _ => Err(gettext("_Wrong master passphrase")),
+        }
+    }
+
+    /// Creates the encrypted file, without any password.
+    fn create(path: PathBuf, master_passphrase: &str) -> Result<EncryptedFileStore, String> {
+        let params = Params::recommended();
+        let salt = EncryptedFileStore::generate_salt();
+
+        let key = EncryptedFileStore::derive_key(master_passphrase, &salt, &params)?;
+
+        let mut store = EncryptedFileStore {
+            path,
+            key_lines: vec![format!(
+                "scrypt {} {} {} {}",
+                params.log_n(),
+                params.r(),
+                params.p(),
+                hex::encode(salt)
+            )],
+            key,
+        };
+
+        let check_value = store.encrypt("check", CHECK_VALUE)?;
+        store.key_lines.push(format!("check {}", check_value));
+
+        store.write_entries(&vec![])?;
+
+        Ok(store)
+    }
+
+    /// Generates a random salt for the key derivation.
+    fn generate_salt() -> [u8; 16] {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+
+        return salt;
+    }
+
+    fn derive_key(
+        master_passphrase: &str,
+        salt: &[u8],
+        params: &Params,
+    ) -> Result<[u8; 32], String> {
+        let mut key = [0u8; 32];
+        scrypt::scrypt(master_passphrase.as_bytes(), salt, params, &mut key)
+            .map_err(|error| error.to_string())?;
+
+        Ok(key)
+    }
+
+    /// Encrypts a value, returning the nonce followed by the encrypted value, in hexadecimal.
+    fn encrypt(&self, profile_id: &str, value: &[u8]) -> Result<String, String> {
+        let cipher = Aes256Gcm::new_from_slice(&self.key).map_err(|error| error.to_string())?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let encrypted_value = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: value,
+                    aad: profile_id.as_bytes(),
+                },
+            )
+            .map_err(|error| error.to_string())?;
+
+        let mut result = nonce.to_vec();
+        result.extend(encrypted_value);
+
+        Ok(hex::encode(result))
+    }
+
+    /// Decrypts a value encrypted by `encrypt`.
+    fn decrypt(&self, profile_id: &str, encrypted_value: &str) -> Result<String, String> {
+        let bytes = hex::decode(encrypted_value).map_err(|error| error.to_string())?;
+        if bytes.len() < NONCE_LENGTH {
+            return Err(gettext("_Invalid credential file"));
+        }
+
+        let cipher = Aes256Gcm::new_from_slice(&self.key).map_err(|error| error.to_string())?;
+        let (nonce, encrypted_value) = bytes.split_at(NONCE_LENGTH);
+
+        let value = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: encrypted_value,
+                    aad: profile_id.as_bytes(),
+                },
+            )
+            .map_err(|_| gettext("_Invalid credential file"))?;
+
+        return String::from_utf8(value).map_err(|error| error.to_string());
+    }
+
+    /// Reads the encrypted passwords of the file, with their profile ID.
+    fn read_entries(&self) -> Result<Vec<(String, String)>, String> {
+        let content = fs::read_to_string(&self.path).map_err(|error| error.to_string())?;
+
+        return Ok(content
+            .lines()
+            .skip(1 + self.key_lines.len())
+            .filter_map(|line| line.split_once(' '))
+            .map(|(profile_id, value)| (profile_id.to_string(), value.to_string()))
+            .collect());
+    }
+
+    /// Writes the file with the given encrypted passwords.
+    /// The file is replaced at once, so that it is never left half written.
+    fn write_entries(&self, entries: &Vec<(String, String)>) -> Result<(), String> {
+        let mut content = format!("{}\n", FILE_HEADER);
+        for line in &self.key_lines {
+            content.push_str(&format!("{}\n", line));
+        }
+        for (profile_id, value) in entries {
+            content.push_str(&format!("{} {}\n", profile_id, value));
+        }
+
+        let temporary_path = self.path.with_extension("tmp");
+        fs::write(&temporary_path, content).map_err(|error| error.to_string())?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&temporary_path, fs::Permissions::from_mode(0o600))
+                .map_err(|error| error.to_string())?;
+        }
+
+        return fs::rename(&temporary_path, &self.path).map_err(|error| error.to_string());
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn store_password(&self, profile_id: &str, password: &str) -> Result<(), String> {
+        let mut entries = self.read_entries()?;
+        entries.retain(|(id, _)| id != profile_id);
+        entries.push((
+            profile_id.to_string(),
+            self.encrypt(profile_id, password.as_bytes())?,
+        ));
+
+        return self.write_entries(&entries);
+    }
+
+    fn retrieve_password(&self, profile_id: &str) -> Result<Option<String>, String> {
+        match self.read_entries()?.iter().find(|(id, _)| id == profile_id) {
+            Some((_, value)) => Ok(Some(self.decrypt(profile_id, value)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn delete_password(&self, profile_id: &str) -> Result<(), String> {
+        let mut entries = self.read_entries()?;
+        entries.retain(|(id, _)| id != profile_id);
+
+        return self.write_entries(&entries);
+    }
+}
@@ -0,0 +1,192 @@
+/* file_diff.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use git2::{Diff, Patch};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineType {
+    Context,
+    Addition,
+    Deletion,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    pub line_type: DiffLineType,
+    pub old_line_number: Option<u32>,
+    pub new_line_number: Option<u32>,
+    pub content: String,
+    /// The content of the line as written in the file, line ending included.
+    pub raw_content: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffHunk {
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FileDiff {
+    pub hunks: Vec<DiffHunk>,
+    pub is_binary: bool,
+}
+
+impl FileDiff {
+    /**
+     * Used to build a FileDiff from the first delta of a git diff.
+     * The diff is expected to be restricted to a single file.
+     */
+    pub fn from_diff(diff: &Diff) -> Result<FileDiff, git2::Error> {
+        let mut file_diff = FileDiff::default();
+
+        if diff.deltas().len() == 0 {
+            return Ok(file_diff);
+        }
+
+        let patch = match Patch::from_diff(diff, 0)? {
+            Some(patch) => patch,
+            None => {
+                // libgit2 does not build a patch for binary files.
+                file_diff.is_binary = true;
+                return Ok(file_diff);
+            }
+        };
+
+        if patch.delta().flags().is_binary() {
+            file_diff.is_binary = true;
+            return Ok(file_diff);
+        }
+
+        for hunk_index in 0..patch.num_hunks() {
+            let (hunk, total_lines) = patch.hunk(hunk_index)?;
+
+            let mut diff_hunk = DiffHunk {
+                header: String::from_utf8_lossy(hunk.header())
+                    .trim_end()
+                    .to_string(),
+                old_start: hunk.old_start(),
+                old_lines: hunk.old_lines(),
+                new_start: hunk.new_start(),
+                new_lines: hunk.new_lines(),
+                lines: Vec::new(),
+            };
+
+            for line_index in 0..total_lines {
+                let line = patch.line_in_hunk(hunk_index, line_index)?;
+
+                let line_type = match line.origin() {
+                    '+' => DiffLineType::Addition,
+                    '-' => DiffLineType::Deletion,
+                    ' ' => DiffLineType::Context,
+                    // "No newline at end of file" markers are not shown.
+                    _ => continue,
+                };
+
+                let content = String::from_utf8_lossy(line.content())
+                    .trim_end_matches(&['\n', '\r'][..])
+                    .to_string();
+
+                diff_hunk.lines.push(DiffLine {
+                    line_type,
+                    old_line_number: line.old_lineno(),
+                    new_line_number: line.new_lineno(),
+                    content,
+                    raw_content: line.content().to_vec(),
+                });
+            }
+
+            file_diff.hunks.push(diff_hunk);
+        }
+
+        return Ok(file_diff);
+    }
+
+    /**
+     * Used to check if there is nothing to show.
+     */
+    pub fn is_empty(&self) -> bool {
+        return self.hunks.is_empty();
+    }
+
+    /**
+     * Used to build a new version of a content from the old one, only applying some changed lines.
+     * The old content must be the one used as the old side of this diff.
+     * A line is identified by the index of its hunk and its index in the hunk.
+     * Context lines are always kept, unapplied deletions are kept and unapplied additions are skipped.
+     */
+    pub fn apply_lines<F>(&self, old_content: &[u8], is_line_applied: F) -> Vec<u8>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let old_lines: Vec<&[u8]> = old_content.split_inclusive(|byte| *byte == b'\n').collect();
+        let mut new_content: Vec<u8> = Vec::with_capacity(old_content.len());
+
+        let mut old_line_index: usize = 0;
+
+        for (hunk_index, hunk) in self.hunks.iter().enumerate() {
+            // When a hunk does not contain any old line, its start is the line after which lines are added.
+            let hunk_first_line = if hunk.old_lines == 0 {
+                hunk.old_start as usize
+            } else {
+                (hunk.old_start as usize).saturating_sub(1)
+            };
+
+            while old_line_index < hunk_first_line && old_line_index < old_lines.len() {
+                new_content.extend_from_slice(old_lines[old_line_index]);
+                old_line_index += 1;
+            }
+
+            for (line_index, line) in hunk.lines.iter().enumerate() {
+                match line.line_type {
+                    DiffLineType::Context => {
+                        if let Some(old_line) = old_lines.get(old_line_index) {
+                            new_content.extend_from_slice(old_line);
+                        }
+                        old_line_index += 1;
+                    }
+                    DiffLineType::Deletion => {
+                        if !is_line_applied(hunk_index, line_index) {
+                            if let Some(old_line) = old_lines.get(old_line_index) {
+                                new_content.extend_from_slice(old_line);
+                            }
+                        }
+                        old_line_index += 1;
+                    }
+                    DiffLineType::Addition => {
+                        if is_line_applied(hunk_index, line_index) {
+                            new_content.extend_from_slice(&line.raw_content);
+                        }
+                    }
+                }
+            }
+        }
+
+        while old_line_index < old_lines.len() {
+            new_content.extend_from_slice(old_lines[old_line_index]);
+            old_line_index += 1;
+        }
+
+        return new_content;
+    }
+}
@@ -0,0 +1,29 @@
+/* file_history_entry.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+/// A commit that changed a file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileHistoryEntry {
+    pub commit_id: String,
+    pub title: String,
+    pub author: String,
+    pub date: String,
+    /// The path of the file in this commit, which differs from the current one when it has been renamed since.
+    pub path: String,
+}
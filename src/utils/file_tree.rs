@@ -51,31 +51,6 @@ impl FileTree {
         return None;
     }
 
-    /**
-     * Update changed files list whith new value.
-     */
-    pub fn change_file_information(&mut self, new_value: &ChangedFile) {
-        for i in 0..self.tree.len() {
-            if self.tree[i].is_same_element(new_value) {
-                self.tree[i] = new_value.clone();
-            }
-        }
-    }
-
-    pub fn set_selection_of_files_in_folder(&mut self, parent: &str, selected: bool) {
-        for i in 0..self.tree.len() {
-            if self.tree[i].parent == parent {
-                self.tree[i] = ChangedFile::new(
-                    parent.to_string(),
-                    self.tree[i].name.clone(),
-                    self.tree[i].status,
-                    selected,
-                    self.tree[i].is_opened,
-                )
-            }
-        }
-    }
-
     /**
      * Update expanded value of folder.
      */
@@ -88,24 +63,11 @@ impl FileTree {
     }
 
     /**
-     * Used to know if all files in a folder are selected.
-     */
-    pub fn are_all_files_in_folder_selected(&self, folder_path: &str) -> bool {
-        for file in &self.tree {
-            if file.parent == folder_path && !file.is_selected {
-                return false;
-            }
-        }
-
-        return true;
-    }
-
-    /**
-     * Used to know if all files are selected.
+     * Used to know if all changes are staged.
      */
-    pub fn are_all_files_selected(&self) -> bool {
+    pub fn are_all_files_staged(&self) -> bool {
         for file in &self.tree {
-            if file.is_selected == false {
+            if !file.is_staged {
                 return false;
             }
         }
@@ -113,12 +75,12 @@ impl FileTree {
         return true;
     }
 
-    /// Used to get all selected files.
-    pub fn get_selected_files(&self) -> Vec<ChangedFile> {
+    /// Used to get the staged files, or the files with unstaged changes.
+    pub fn get_files_in_section(&self, is_staged: bool) -> Vec<ChangedFile> {
         let mut changed_files: Vec<ChangedFile> = vec![];
 
         for file in &self.tree {
-            if file.is_selected {
+            if file.is_staged == is_staged {
                 changed_files.push(file.clone());
             }
         }
@@ -140,13 +102,13 @@ impl FileTree {
     }
 
     /**
-     * Used to retrieve the number of selected files.
+     * Used to retrieve the number of staged files.
      */
-    pub fn get_number_of_selected_files(&self) -> i32 {
+    pub fn get_number_of_staged_files(&self) -> i32 {
         let mut count = 0;
 
         for file in &self.tree {
-            if file.is_selected {
+            if file.is_staged {
                 count += 1;
             }
         }
@@ -17,15 +17,32 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-use chrono::NaiveDateTime;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use chrono::{FixedOffset, NaiveDateTime, TimeZone};
 use gettextrs::gettext;
-use git2::{Branch, Error, FetchOptions, Oid, Reference, RemoteCallbacks, Repository};
+use git2::{
+    Branch, DiffFindOptions, Error, FetchOptions, Oid, Reference, ReferenceType, Repository,
+};
 
 use crate::widgets::repository::CommitObject;
 
-use super::{clone_mode::CloneMode, fetch_result::FetchResult, repository_utils::RepositoryUtils};
-
-fn commit_to_commit_object(commit: git2::Commit, is_pushed: bool) -> CommitObject {
+use super::{
+    commit_details::{CommitDetails, SignatureStatus},
+    commit_graph::{CommitGraph, CommitGraphHistory, RefLabel},
+    fetch_result::FetchResult,
+    gpg_utils::GpgUtils,
+    history_search::{HistorySearch, HistorySearchResult},
+    repository_utils::RepositoryUtils,
+    transfer_progress::TransferProgress,
+};
+
+/// Gets the title, the description and the subtitle shown in the history for a commit.
+fn get_commit_texts(commit: &git2::Commit) -> (String, String, String) {
     let commit_datetime: NaiveDateTime =
         NaiveDateTime::from_timestamp_opt(commit.time().seconds(), 0).unwrap();
 
@@ -46,6 +63,12 @@ fn commit_to_commit_object(commit: git2::Commit, is_pushed: bool) -> CommitObjec
         None => (message, String::from("")),
     };
 
+    return (title, description, commit_subtitle);
+}
+
+fn commit_to_commit_object(commit: git2::Commit, is_pushed: bool) -> CommitObject {
+    let (title, description, commit_subtitle) = get_commit_texts(&commit);
+
     return CommitObject::new(
         commit.id().to_string(),
         title,
@@ -236,13 +259,343 @@ pub fn load_commit_history(
     return commit_object_vector;
 }
 
+/// Loads n commits reachable from a detached HEAD, either the first n commits or
+/// the next n commits after the starting one.
+pub fn load_detached_head_history(
+    repository: &Repository,
+    starting_commit_id: String,
+    nb_commits_to_load: i32,
+) -> Result<Vec<CommitObject>, Error> {
+    let mut revwalk: git2::Revwalk<'_> = repository.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+    revwalk.push_head()?;
+
+    let unpushed_commit_ids = get_unpushed_commit_ids(repository);
+
+    // The commits up to the starting one are already loaded:
+    if !starting_commit_id.is_empty() {
+        let starting_commit_oid = Oid::from_str(&starting_commit_id)?;
+        for commit_id in revwalk.by_ref() {
+            if commit_id? == starting_commit_oid {
+                break;
+            }
+        }
+    }
+
+    let mut commit_object_vector: Vec<CommitObject> = Vec::new();
+
+    for commit_id in revwalk.take(nb_commits_to_load as usize) {
+        let commit: git2::Commit<'_> = repository.find_commit(commit_id?)?;
+        let is_pushed = !unpushed_commit_ids.contains(&commit.id());
+
+        commit_object_vector.push(commit_to_commit_object(commit, is_pushed));
+    }
+
+    return Ok(commit_object_vector);
+}
+
+/// Loads the next commits of the commit graph, which shows the history of every branch.
+/// The history is walked when the graph is started, then its commits are given page by page.
+pub fn load_commit_graph_history(
+    repository: &Repository,
+    commit_graph: &mut CommitGraph,
+    nb_commits_to_load: usize,
+) -> Result<Vec<CommitObject>, Error> {
+    if commit_graph.get_history().is_none() {
+        commit_graph.set_history(walk_commit_graph_history(repository)?);
+    }
+
+    let mut commit_object_vector: Vec<CommitObject> = Vec::new();
+
+    for commit_id in commit_graph.take_next_commit_ids(nb_commits_to_load) {
+        let commit: git2::Commit<'_> = repository.find_commit(commit_id)?;
+        let parent_ids: Vec<Oid> = commit.parent_ids().collect();
+
+        let graph_row = commit_graph.add_commit(commit.id(), &parent_ids);
+
+        let is_pushed = commit_graph.is_commit_pushed(&commit.id());
+        let commit_object: CommitObject = commit_to_commit_object(commit, is_pushed);
+        commit_object.set_graph_row(graph_row);
+
+        commit_object_vector.push(commit_object);
+    }
+
+    return Ok(commit_object_vector);
+}
+
+/// Used to know if references moved since the history of the commit graph was walked.
+/// The graph must then be started again, as its lanes would not match the next commits.
+pub fn is_commit_graph_outdated(repository: &Repository, commit_graph: &CommitGraph) -> bool {
+    return match (commit_graph.get_history(), get_ref_targets(repository)) {
+        (Some(history), Ok(ref_targets)) => history.ref_targets != ref_targets,
+        (Some(_), Err(_)) => true,
+        (None, _) => false,
+    };
+}
+
+/// Walks the history of every branch, from the newest commit.
+fn walk_commit_graph_history(repository: &Repository) -> Result<CommitGraphHistory, Error> {
+    let ref_targets = get_ref_targets(repository)?;
+
+    let mut revwalk: git2::Revwalk<'_> = repository.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+    revwalk.push_glob("refs/heads")?;
+    revwalk.push_glob("refs/remotes")?;
+
+    // A detached HEAD may not be on any branch:
+    if let Some(head_oid) = repository.head().ok().and_then(|head| head.target()) {
+        revwalk.push(head_oid)?;
+    }
+
+    return Ok(CommitGraphHistory {
+        commit_ids: revwalk.collect::<Result<VecDeque<Oid>, Error>>()?,
+        ref_labels: get_ref_labels(repository)?,
+        unpushed_commit_ids: get_unpushed_commit_ids(repository),
+        ref_targets,
+    });
+}
+
+/// Searches the commits matching a search in the history of the checked out branch,
+/// or of every branch when searching the commit graph.
+///
+/// The found commits are given to `on_found` by groups, so that they can be shown while the
+/// search goes on. The search stops as soon as it is cancelled.
+pub fn search_commit_history<F: Fn(Vec<HistorySearchResult>)>(
+    repository: &Repository,
+    search: &HistorySearch,
+    is_searching_every_branch: bool,
+    is_cancelled: Arc<AtomicBool>,
+    on_found: F,
+) -> Result<(), Error> {
+    let mut revwalk: git2::Revwalk<'_> = repository.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+    if is_searching_every_branch {
+        revwalk.push_glob("refs/heads")?;
+        revwalk.push_glob("refs/remotes")?;
+    }
+
+    if let Some(head_oid) = repository.head().ok().and_then(|head| head.target()) {
+        revwalk.push(head_oid)?;
+    }
+
+    let unpushed_commit_ids = get_unpushed_commit_ids(repository);
+
+    let mut found_commits: Vec<HistorySearchResult> = Vec::new();
+
+    for commit_id in revwalk {
+        if is_cancelled.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let commit: git2::Commit<'_> = repository.find_commit(commit_id?)?;
+
+        if !search.matches(repository, &commit)? {
+            continue;
+        }
+
+        let (title, description, subtitle) = get_commit_texts(&commit);
+
+        found_commits.push(HistorySearchResult {
+            commit_id: commit.id().to_string(),
+            title,
+            description,
+            subtitle,
+            is_pushed: !unpushed_commit_ids.contains(&commit.id()),
+        });
+
+        if found_commits.len() == 25 {
+            on_found(std::mem::take(&mut found_commits));
+        }
+    }
+
+    if !found_commits.is_empty() {
+        on_found(found_commits);
+    }
+
+    return Ok(());
+}
+
+/// Gets the commits pointed by HEAD and by the references, sorted by reference name.
+fn get_ref_targets(repository: &Repository) -> Result<Vec<(String, Oid)>, Error> {
+    let mut ref_targets: Vec<(String, Oid)> = Vec::new();
+
+    if let Some(head_oid) = repository.head().ok().and_then(|head| head.target()) {
+        ref_targets.push((String::from("HEAD"), head_oid));
+    }
+
+    for reference in repository.references()? {
+        let reference: Reference<'_> = reference?;
+
+        if let (Some(name), Some(target)) = (reference.name(), reference.target()) {
+            ref_targets.push((name.to_string(), target));
+        }
+    }
+
+    ref_targets.sort();
+
+    return Ok(ref_targets);
+}
+
+/// Gets the labels of the references pointing to each commit.
+fn get_ref_labels(repository: &Repository) -> Result<HashMap<Oid, Vec<RefLabel>>, Error> {
+    let mut ref_labels: HashMap<Oid, Vec<RefLabel>> = HashMap::new();
+
+    if let Some(head_oid) = repository.head().ok().and_then(|head| head.target()) {
+        ref_labels.entry(head_oid).or_default().push(RefLabel::Head);
+    }
+
+    for reference in repository.references()? {
+        let reference: Reference<'_> = reference?;
+
+        // Symbolic references, like the HEAD of a remote, point to a branch already labelled:
+        if reference.kind() == Some(ReferenceType::Symbolic) {
+            continue;
+        }
+
+        let name = match reference.shorthand() {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        let label = if reference.is_branch() {
+            RefLabel::LocalBranch(name)
+        } else if reference.is_remote() {
+            RefLabel::RemoteBranch(name)
+        } else if reference.is_tag() {
+            RefLabel::Tag(name)
+        } else {
+            continue;
+        };
+
+        match reference.peel_to_commit() {
+            Ok(commit) => ref_labels.entry(commit.id()).or_default().push(label),
+            Err(_) => continue,
+        }
+    }
+
+    return Ok(ref_labels);
+}
+
+/// Gets the commits of the checked out branch that are not on its upstream branch.
+/// Every commit of the branch is unpushed when it has no upstream branch.
+fn get_unpushed_commit_ids(repository: &Repository) -> HashSet<Oid> {
+    let mut unpushed_commit_ids: HashSet<Oid> = HashSet::new();
+
+    let head: Reference<'_> = match repository.head() {
+        Ok(head) => head,
+        Err(_) => return unpushed_commit_ids,
+    };
+
+    let mut revwalk: git2::Revwalk<'_> = match repository.revwalk() {
+        Ok(revwalk) => revwalk,
+        Err(_) => return unpushed_commit_ids,
+    };
+
+    if let Some(head_oid) = head.target() {
+        let _ = revwalk.push(head_oid);
+    }
+
+    if !head.is_branch() {
+        // A detached HEAD has no upstream, its commits are pushed once they are on a remote branch:
+        let _ = revwalk.hide_glob("refs/remotes");
+    } else if let Some(upstream_oid) = Branch::wrap(head)
+        .upstream()
+        .ok()
+        .and_then(|upstream| upstream.get().target())
+    {
+        let _ = revwalk.hide(upstream_oid);
+    }
+
+    unpushed_commit_ids.extend(revwalk.flatten());
+
+    return unpushed_commit_ids;
+}
+
+/// Formats the time of a git signature with its own time zone.
+fn format_git_time(time: git2::Time) -> String {
+    let date = FixedOffset::east_opt(time.offset_minutes() * 60)
+        .and_then(|offset| offset.timestamp_opt(time.seconds(), 0).single());
+
+    match date {
+        Some(date) => date.format(&gettext("_Commit date format")).to_string(),
+        None => String::new(),
+    }
+}
+
+/// Formats the name and email of a git signature.
+fn format_git_signature(signature: &git2::Signature) -> String {
+    return format!(
+        "{} <{}>",
+        signature.name().unwrap_or_default(),
+        signature.email().unwrap_or_default()
+    );
+}
+
+/// Gets all the details of a commit.
+/// Changed files are the ones that differ from the first parent.
+pub fn get_commit_details(
+    repository: &Repository,
+    commit_id: &str,
+) -> Result<CommitDetails, Error> {
+    let oid = Oid::from_str(commit_id)?;
+    let commit = repository.find_commit(oid)?;
+
+    let (signature_status, signer) = match repository.extract_signature(&oid, None) {
+        Ok((signature, signed_data)) => GpgUtils::verify_commit_signature(&signature, &signed_data),
+        Err(_) => (SignatureStatus::NotSigned, String::new()),
+    };
+
+    let parent_tree = match commit.parents().next() {
+        Some(parent) => Some(parent.tree()?),
+        None => None,
+    };
+
+    let mut diff =
+        repository.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    let changed_files = diff
+        .deltas()
+        .filter_map(|delta| {
+            let file = match delta.status() {
+                git2::Delta::Deleted => delta.old_file(),
+                _ => delta.new_file(),
+            };
+            file.path()
+                .map(|path| (path.to_string_lossy().to_string(), delta.status()))
+        })
+        .collect();
+
+    return Ok(CommitDetails {
+        commit_id: commit.id().to_string(),
+        message: String::from_utf8_lossy(commit.message_bytes())
+            .trim()
+            .to_string(),
+        author: format_git_signature(&commit.author()),
+        author_date: format_git_time(commit.author().when()),
+        committer: format_git_signature(&commit.committer()),
+        committer_date: format_git_time(commit.committer().when()),
+        parent_ids: commit.parent_ids().map(|id| id.to_string()).collect(),
+        signature_status,
+        signer,
+        changed_files,
+    });
+}
+
 /// Gets repository checked out branch.
 pub fn get_repository_checked_out_branch(repository: &Repository) -> Result<Reference<'_>, Error> {
     repository.head()
 }
 
 /// Gets repository checked out branch name.
+/// A detached HEAD is named "HEAD", as it is not on any branch.
 pub fn get_repository_checked_out_branch_name(repository: &Repository) -> Result<String, Error> {
+    // The checked out branch has no reference yet before its first commit:
+    if RepositoryUtils::is_head_unborn(repository) {
+        return RepositoryUtils::get_current_branch_name(repository);
+    }
+
     let head = repository.head()?;
 
     let checked_out_branch: &str = match head.shorthand() {
@@ -259,53 +612,71 @@ pub fn get_repository_checked_out_branch_name(repository: &Repository) -> Result
     return Ok(checked_out_branch.to_string());
 }
 
-/// Fetches the checked out branch.
-pub fn fetch_checked_out_branch(
+/// Fetches the given remotes and compares the checked out branch with its upstream.
+/// The default remote is fetched when no remote is given.
+/// The progress is given to `report_progress` and the fetch is aborted once `is_cancelled` is set.
+pub fn fetch_checked_out_branch<F: Fn(TransferProgress) + Clone + 'static>(
     repository: &Repository,
+    remote_names: &Vec<String>,
     username: String,
     password: String,
     private_key_path: String,
     passphrase: String,
+    is_cancelled: Arc<AtomicBool>,
+    report_progress: F,
 ) -> Result<FetchResult, git2::Error> {
-    let head = repository.head()?;
-
-    let checked_out_branch: &str = match head.shorthand() {
-        Some(it) => it,
-        None => {
-            return Err(git2::Error::new(
-                git2::ErrorCode::GenericError,
-                git2::ErrorClass::Reference,
-                "Could not get the full shorthand of reference.",
-            ))
-        }
+    // A detached HEAD is not on any branch, so it has no upstream to compare with:
+    let branch = if RepositoryUtils::is_head_detached(repository) {
+        None
+    } else {
+        Some(repository.find_branch(
+            &RepositoryUtils::get_current_branch_name(repository)?,
+            git2::BranchType::Local,
+        )?)
     };
 
-    let branch = repository.find_branch(checked_out_branch, git2::BranchType::Local)?;
+    let remote_names = if remote_names.is_empty() {
+        vec![RepositoryUtils::get_default_remote_name(repository)?]
+    } else {
+        remote_names.clone()
+    };
 
-    let callback: RemoteCallbacks<'_>;
+    for remote_name in remote_names {
+        let mut callback = RepositoryUtils::get_remote_callbacks(
+            repository,
+            &remote_name,
+            username.clone(),
+            password.clone(),
+            private_key_path.clone(),
+            passphrase.clone(),
+        )?;
+        RepositoryUtils::add_transfer_callbacks(
+            &mut callback,
+            is_cancelled.clone(),
+            report_progress.clone(),
+        );
+
+        let mut fetch_options = FetchOptions::new();
+
+        fetch_options.remote_callbacks(callback);
+
+        // An empty refspec list uses the fetch refspecs configured for the remote:
+        let refspecs: [&str; 0] = [];
+        repository
+            .find_remote(&remote_name)?
+            .fetch(&refspecs, Some(&mut fetch_options), None)?;
+    }
 
-    match RepositoryUtils::get_clone_mode_of_repository(&repository) {
-        Ok(clone_mode) => {
-            callback = match clone_mode {
-                CloneMode::SSH => {
-                    RepositoryUtils::ssh_callback(username, passphrase, private_key_path)
-                }
-                CloneMode::HTTPS => RepositoryUtils::https_callback(username, password),
-            }
+    let branch = match branch {
+        Some(branch) => branch,
+        None => {
+            return Ok(FetchResult {
+                total_commits_to_push: get_unpushed_commit_ids(repository).len() as i64,
+                total_commits_to_pull: 0,
+            })
         }
-        Err(error) => return Err(error),
     };
 
-    let mut fetch_options = FetchOptions::new();
-
-    fetch_options.remote_callbacks(callback);
-
-    repository.find_remote("origin")?.fetch(
-        &["refs/heads/*:refs/remotes/origin/*"],
-        Some(&mut fetch_options),
-        None,
-    )?;
-
     let upstream_branch = branch.upstream()?;
     let upstream_commit = upstream_branch.into_reference().peel_to_commit()?;
     let commit_local = branch.into_reference().peel_to_commit()?;
@@ -319,6 +690,7 @@ pub fn fetch_checked_out_branch(
 }
 
 /// Gets first commit of checked out branch.
+/// There is none when the checked out branch has no commit yet.
 pub fn get_first_commit_id_of_checked_out_branch(repository: &Repository) -> Option<git2::Oid> {
     let checked_out_branch;
 
@@ -327,15 +699,7 @@ pub fn get_first_commit_id_of_checked_out_branch(repository: &Repository) -> Opt
         Err(_) => return None,
     }
 
-    let branch_commit: git2::AnnotatedCommit<'_> = repository
-        .reference_to_annotated_commit(&checked_out_branch)
-        .unwrap();
-
-    let branch_commit_id = branch_commit.id();
-
-    let first_commit = repository.find_commit(branch_commit_id);
-
-    match first_commit {
+    match checked_out_branch.peel_to_commit() {
         Ok(commit) => return Some(commit.id()),
         Err(_) => {
             return None;
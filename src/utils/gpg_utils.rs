@@ -19,14 +19,16 @@
 
 use std::io::Write;
 
-use gpgme::{PassphraseRequest, PinentryMode};
+use gpgme::{PassphraseRequest, PinentryMode, SignatureSummary};
+
+use super::commit_details::SignatureStatus;
 
 pub struct GpgUtils {}
 
 impl GpgUtils {
     /// Used to sign a string representation of a commit using a signing key.
-    /*
-    pub fn sign_commit_string(commit_string: &str, signing_key: &str) -> Result<String, String> {
+    /// The passphrase is asked by the gpg-agent, when it does not already know it.
+    fn sign_commit_string(commit_string: &str, signing_key: &str) -> Result<String, String> {
         let mut ctx = match gpgme::Context::from_protocol(gpgme::Protocol::OpenPgp) {
             Ok(c) => c,
             Err(error) => return Err(error.to_string()),
@@ -42,7 +44,7 @@ impl GpgUtils {
 
         let mut output = Vec::new();
 
-        match ctx.sign_detached(commit_string.clone(), &mut output) {
+        match ctx.sign_detached(commit_string, &mut output) {
             Ok(_) => match String::from_utf8(output) {
                 Ok(string) => return Ok(string),
                 Err(error) => return Err(error.to_string()),
@@ -50,14 +52,18 @@ impl GpgUtils {
             Err(error) => return Err(error.to_string()),
         };
     }
-    */
 
     /// Used to sign a string representation of a commit using a signing key and its corresponding passphrase.
+    /// An empty passphrase leaves the passphrase to the gpg-agent.
     pub fn sign_commit_string_with_passphrase(
         commit_string: &str,
         signing_key: &str,
         passphrase: &str,
     ) -> Result<String, String> {
+        if passphrase.is_empty() {
+            return GpgUtils::sign_commit_string(commit_string, signing_key);
+        }
+
         let mut ctx = match gpgme::Context::from_protocol(gpgme::Protocol::OpenPgp) {
             Ok(c) => c,
             Err(error) => return Err(error.to_string()),
@@ -95,4 +101,63 @@ impl GpgUtils {
             },
         )
     }
+
+    /// Used to know if the gpg-agent can sign with a signing key without being given its passphrase,
+    /// because it remembers it or asks it itself. Must not be called on the main thread.
+    pub fn can_agent_sign(signing_key: &str) -> bool {
+        match GpgUtils::sign_commit_string("bagit-desktop", signing_key) {
+            Ok(_) => true,
+            Err(error) => {
+                tracing::warn!("The gpg-agent could not sign: {}", error);
+                false
+            }
+        }
+    }
+
+    /// Used to verify the detached signature of a commit.
+    /// Returns the status of the signature and the fingerprint of the signing key.
+    pub fn verify_commit_signature(
+        signature: &[u8],
+        signed_data: &[u8],
+    ) -> (SignatureStatus, String) {
+        let mut ctx = match gpgme::Context::from_protocol(gpgme::Protocol::OpenPgp) {
+            Ok(c) => c,
+            Err(error) => {
+                tracing::warn!("Could not create GPG context: {}", error);
+                return (SignatureStatus::UnknownKey, String::new());
+            }
+        };
+
+        let result = match ctx.verify_detached(signature, signed_data) {
+            Ok(result) => result,
+            Err(error) => {
+                tracing::warn!("Could not verify commit signature: {}", error);
+                return (SignatureStatus::Invalid, String::new());
+            }
+        };
+
+        let found_signature = result.signatures().next();
+
+        match found_signature {
+            Some(found_signature) => {
+                let fingerprint = found_signature
+                    .fingerprint()
+                    .unwrap_or_default()
+                    .to_string();
+
+                if found_signature
+                    .summary()
+                    .contains(SignatureSummary::KEY_MISSING)
+                {
+                    return (SignatureStatus::UnknownKey, fingerprint);
+                }
+
+                match found_signature.status() {
+                    Ok(_) => (SignatureStatus::Valid, fingerprint),
+                    Err(_) => (SignatureStatus::Invalid, fingerprint),
+                }
+            }
+            None => (SignatureStatus::Invalid, String::new()),
+        }
+    }
 }
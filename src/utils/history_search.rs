@@ -0,0 +1,177 @@
+/* history_search.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use git2::{Commit, DiffOptions, Error, Repository};
+
+use crate::widgets::repository::CommitObject;
+
+/// The filters of a search in the commit history.
+///
+/// Every filter that is set must match for a commit to be found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistorySearch {
+    /// A text contained in the message of the commit.
+    pub message: String,
+    /// A text contained in the name or the email of the author of the commit.
+    pub author: String,
+    /// The first day on which the commit can have been made.
+    pub since: Option<NaiveDate>,
+    /// The last day on which the commit can have been made.
+    pub until: Option<NaiveDate>,
+    /// A file or a folder changed by the commit.
+    pub path: String,
+    /// The start of the id of the commit.
+    pub commit_id_prefix: String,
+}
+
+impl HistorySearch {
+    /// Whether no filter is set.
+    pub fn is_empty(&self) -> bool {
+        return self.message.is_empty()
+            && self.author.is_empty()
+            && self.since.is_none()
+            && self.until.is_none()
+            && self.path.is_empty()
+            && self.commit_id_prefix.is_empty();
+    }
+
+    /// Whether a commit matches every filter of the search.
+    ///
+    /// The cheapest filters are checked first, the changed files are only compared when all
+    /// the others match.
+    pub fn matches(&self, repository: &Repository, commit: &Commit) -> Result<bool, Error> {
+        if !self.commit_id_prefix.is_empty()
+            && !commit
+                .id()
+                .to_string()
+                .starts_with(&self.commit_id_prefix.to_lowercase())
+        {
+            return Ok(false);
+        }
+
+        if !self.message.is_empty()
+            && !commit
+                .message()
+                .unwrap_or("")
+                .to_lowercase()
+                .contains(&self.message.to_lowercase())
+        {
+            return Ok(false);
+        }
+
+        if !self.author.is_empty() {
+            let author = commit.author();
+            let searched_author = self.author.to_lowercase();
+
+            if !author
+                .name()
+                .unwrap_or("")
+                .to_lowercase()
+                .contains(&searched_author)
+                && !author
+                    .email()
+                    .unwrap_or("")
+                    .to_lowercase()
+                    .contains(&searched_author)
+            {
+                return Ok(false);
+            }
+        }
+
+        if self.since.is_some() || self.until.is_some() {
+            // The day of the commit is the one of its author, in their own time zone:
+            let time = commit.author().when();
+            let commit_day = match FixedOffset::east_opt(time.offset_minutes() * 60) {
+                Some(offset) => match NaiveDateTime::from_timestamp_opt(time.seconds(), 0) {
+                    Some(datetime) => offset.from_utc_datetime(&datetime).date_naive(),
+                    None => return Ok(false),
+                },
+                None => return Ok(false),
+            };
+
+            if self.since.map_or(false, |since| commit_day < since)
+                || self.until.map_or(false, |until| commit_day > until)
+            {
+                return Ok(false);
+            }
+        }
+
+        if !self.path.is_empty() && !self.is_path_changed(repository, commit)? {
+            return Ok(false);
+        }
+
+        return Ok(true);
+    }
+
+    /// Whether a commit changes the searched path, compared to its first parent.
+    fn is_path_changed(&self, repository: &Repository, commit: &Commit) -> Result<bool, Error> {
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+
+        let mut diff_options = DiffOptions::new();
+        diff_options.pathspec(self.path.trim_matches('/'));
+
+        let diff = repository.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit.tree()?),
+            Some(&mut diff_options),
+        )?;
+
+        return Ok(diff.deltas().len() > 0);
+    }
+}
+
+/// A commit found by a search in the history.
+///
+/// Unlike a `CommitObject`, it can be sent from the thread running the search.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistorySearchResult {
+    pub commit_id: String,
+    pub title: String,
+    pub description: String,
+    pub subtitle: String,
+    pub is_pushed: bool,
+}
+
+impl HistorySearchResult {
+    /// Creates the object shown in the history list for this commit.
+    pub fn to_commit_object(&self) -> CommitObject {
+        return CommitObject::new(
+            self.commit_id.clone(),
+            self.title.clone(),
+            self.description.clone(),
+            self.subtitle.clone(),
+            self.is_pushed,
+        );
+    }
+}
+
+/// The progress of a search in the history, sent by the thread running it.
+#[derive(Debug, Clone)]
+pub enum HistorySearchProgress {
+    /// New commits were found.
+    Found(Vec<HistorySearchResult>),
+    /// Every commit has been searched.
+    Finished,
+    /// The search stopped because of an error.
+    Failed(String),
+}
@@ -0,0 +1,214 @@
+/* interactive_rebase.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use gettextrs::gettext;
+
+/// What is done with a commit during an interactive rebase.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    /**
+     * Used to retrieve all actions, in the order they are offered to the user.
+     */
+    pub fn all() -> [RebaseAction; 6] {
+        return [
+            RebaseAction::Pick,
+            RebaseAction::Reword,
+            RebaseAction::Edit,
+            RebaseAction::Squash,
+            RebaseAction::Fixup,
+            RebaseAction::Drop,
+        ];
+    }
+
+    /**
+     * Used to get the translated name of the action.
+     */
+    pub fn get_name(&self) -> String {
+        return match self {
+            RebaseAction::Pick => gettext("_Pick"),
+            RebaseAction::Reword => gettext("_Reword"),
+            RebaseAction::Edit => gettext("_Edit"),
+            RebaseAction::Squash => gettext("_Squash"),
+            RebaseAction::Fixup => gettext("_Fixup"),
+            RebaseAction::Drop => gettext("_Drop"),
+        };
+    }
+
+    /**
+     * Used to get the keyword of the action, as written in a rebase todo list.
+     */
+    pub fn get_keyword(&self) -> &'static str {
+        return match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Edit => "edit",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        };
+    }
+
+    /**
+     * Used to find the action of a keyword of a rebase todo list.
+     */
+    pub fn from_keyword(keyword: &str) -> Option<RebaseAction> {
+        return RebaseAction::all()
+            .into_iter()
+            .find(|action| action.get_keyword() == keyword);
+    }
+
+    /**
+     * Check whether the commit is melded into the previous one.
+     */
+    pub fn is_melding(&self) -> bool {
+        return *self == RebaseAction::Squash || *self == RebaseAction::Fixup;
+    }
+}
+
+/// A commit of an interactive rebase with what is done with it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RebaseStep {
+    pub action: RebaseAction,
+    pub commit_id: String,
+    /// The message of the commit, only used for the rewritten commit when rewording.
+    pub message: String,
+}
+
+impl RebaseStep {
+    /**
+     * Used to get the first line of the message.
+     */
+    pub fn get_summary(&self) -> String {
+        return self.message.lines().next().unwrap_or("").to_string();
+    }
+}
+
+/// Why an interactive rebase stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebaseStop {
+    Finished,
+    /// The step has conflicts to resolve before its commit is created.
+    Conflicts(RebaseStep),
+    /// The commit of the step is created and can be amended.
+    Edit(RebaseStep),
+}
+
+/// The progress of an interactive rebase, kept in the git directory between its stops.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InteractiveRebaseState {
+    /// The rebased branch.
+    pub branch_name: String,
+    /// The commit of the branch before the rebase, restored when aborting.
+    pub original_head_id: String,
+    /// The step where the rebase stopped, with whether it stopped on conflicts.
+    pub stopped_step: Option<(RebaseStep, bool)>,
+    /// The steps left to do.
+    pub remaining_steps: Vec<RebaseStep>,
+}
+
+impl InteractiveRebaseState {
+    /**
+     * Used to write the state as lines of text.
+     * Messages are hex encoded, as they may span several lines.
+     */
+    pub fn to_text(&self) -> String {
+        let mut lines: Vec<String> = vec![
+            format!("branch {}", self.branch_name),
+            format!("head {}", self.original_head_id),
+        ];
+
+        if let Some((step, has_conflicts)) = &self.stopped_step {
+            lines.push(format!(
+                "{} {}",
+                if *has_conflicts {
+                    "conflicts"
+                } else {
+                    "stopped"
+                },
+                InteractiveRebaseState::step_to_text(step)
+            ));
+        }
+
+        for step in &self.remaining_steps {
+            lines.push(InteractiveRebaseState::step_to_text(step));
+        }
+
+        return lines.join("\n");
+    }
+
+    /**
+     * Used to read the state written by `to_text`.
+     */
+    pub fn from_text(text: &str) -> Option<InteractiveRebaseState> {
+        let mut state = InteractiveRebaseState::default();
+
+        for line in text.lines() {
+            let (keyword, value) = line.split_once(' ')?;
+
+            match keyword {
+                "branch" => state.branch_name = value.to_string(),
+                "head" => state.original_head_id = value.to_string(),
+                "conflicts" | "stopped" => {
+                    state.stopped_step = Some((
+                        InteractiveRebaseState::step_from_text(value)?,
+                        keyword == "conflicts",
+                    ))
+                }
+                _ => state
+                    .remaining_steps
+                    .push(InteractiveRebaseState::step_from_text(line)?),
+            }
+        }
+
+        return Some(state);
+    }
+
+    fn step_to_text(step: &RebaseStep) -> String {
+        return format!(
+            "{} {} {}",
+            step.action.get_keyword(),
+            step.commit_id,
+            hex::encode(&step.message)
+        );
+    }
+
+    fn step_from_text(text: &str) -> Option<RebaseStep> {
+        let mut parts = text.splitn(3, ' ');
+
+        let action = RebaseAction::from_keyword(parts.next()?)?;
+        let commit_id = parts.next()?.to_string();
+        let message = String::from_utf8(hex::decode(parts.next().unwrap_or("")).ok()?).ok()?;
+
+        return Some(RebaseStep {
+            action,
+            commit_id,
+            message,
+        });
+    }
+}
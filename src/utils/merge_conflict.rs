@@ -0,0 +1,149 @@
+/* merge_conflict.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+/// A conflicted file with its content in each version.
+/// A missing content means that the file does not exist in this version.
+/// The contents are kept as they are, as the file may not be a text.
+#[derive(Debug, Clone, Default)]
+pub struct MergeConflict {
+    pub path: String,
+    /// The content of the working directory, where git wrote the conflict markers.
+    pub working_content: Option<Vec<u8>>,
+    pub base_content: Option<Vec<u8>>,
+    pub our_content: Option<Vec<u8>>,
+    pub their_content: Option<Vec<u8>>,
+}
+
+impl MergeConflict {
+    /**
+     * Used to retrieve the content of a side of the conflict.
+     */
+    pub fn get_content_of_side(&self, side: ConflictSide) -> Option<Vec<u8>> {
+        return match side {
+            ConflictSide::Ours => self.our_content.clone(),
+            ConflictSide::Theirs => self.their_content.clone(),
+        };
+    }
+
+    /**
+     * Used to check if a version of the file cannot be edited as text,
+     * like a binary file or a text that is not encoded in UTF-8.
+     */
+    pub fn is_binary(&self) -> bool {
+        return [
+            &self.working_content,
+            &self.base_content,
+            &self.our_content,
+            &self.their_content,
+        ]
+        .iter()
+        .any(|content| match content {
+            Some(content) => content.contains(&0) || std::str::from_utf8(content).is_err(),
+            None => false,
+        });
+    }
+}
+
+/// Indexes of the marker lines of a conflict hunk.
+struct ConflictHunkMarkers {
+    start: usize,
+    base_separator: Option<usize>,
+    separator: usize,
+    end: usize,
+}
+
+/**
+ * Used to find the conflict hunks written by git in a content.
+ */
+fn find_conflict_hunks(lines: &[&str]) -> Vec<ConflictHunkMarkers> {
+    let mut hunks: Vec<ConflictHunkMarkers> = Vec::new();
+
+    let mut start: Option<usize> = None;
+    let mut base_separator: Option<usize> = None;
+    let mut separator: Option<usize> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.starts_with("<<<<<<<") {
+            start = Some(index);
+            base_separator = None;
+            separator = None;
+        } else if start.is_some() && separator.is_none() && line.starts_with("|||||||") {
+            base_separator = Some(index);
+        } else if start.is_some() && line.trim_end() == "=======" {
+            separator = Some(index);
+        } else if line.starts_with(">>>>>>>") {
+            if let (Some(hunk_start), Some(hunk_separator)) = (start, separator) {
+                hunks.push(ConflictHunkMarkers {
+                    start: hunk_start,
+                    base_separator,
+                    separator: hunk_separator,
+                    end: index,
+                });
+            }
+            start = None;
+            base_separator = None;
+            separator = None;
+        }
+    }
+
+    return hunks;
+}
+
+/**
+ * Used to check if a content still contains conflict hunks.
+ */
+pub fn has_conflict_markers(content: &str) -> bool {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+
+    return !find_conflict_hunks(&lines).is_empty();
+}
+
+/**
+ * Used to replace the conflict hunk containing a line by one of its sides.
+ * Lines are counted from 0. Nothing is returned when the line is not in a conflict hunk.
+ */
+pub fn pick_side_of_conflict_hunk(
+    content: &str,
+    line: usize,
+    side: ConflictSide,
+) -> Option<String> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+
+    let hunk = find_conflict_hunks(&lines)
+        .into_iter()
+        .find(|hunk| hunk.start <= line && line <= hunk.end)?;
+
+    let picked_lines = match side {
+        ConflictSide::Ours => &lines[hunk.start + 1..hunk.base_separator.unwrap_or(hunk.separator)],
+        ConflictSide::Theirs => &lines[hunk.separator + 1..hunk.end],
+    };
+
+    let mut new_content = String::with_capacity(content.len());
+    new_content.push_str(&lines[..hunk.start].concat());
+    new_content.push_str(&picked_lines.concat());
+    new_content.push_str(&lines[hunk.end + 1..].concat());
+
+    return Some(new_content);
+}
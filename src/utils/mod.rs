@@ -18,20 +18,42 @@
  */
 
 pub mod action_type;
+pub mod agent_utils;
+pub mod blame_line;
 pub mod changed_file;
 pub mod changed_folder;
 pub mod clone_mode;
+pub mod clone_options;
 pub mod clone_page_profile_mode_type;
+pub mod commit_details;
+pub mod commit_graph;
 pub mod commit_view_profile_mode_type;
 pub mod create_page_profile_mode_type;
 pub mod db;
+pub mod encrypted_file_store;
 pub mod fetch_result;
+pub mod file_diff;
+pub mod file_history_entry;
 pub mod file_tree;
 pub mod git;
 pub mod git_profile_utils;
 pub mod gpg_utils;
+pub mod history_search;
+pub mod interactive_rebase;
+pub mod merge_conflict;
 pub mod migrations;
+pub mod passphrase_cache;
 pub mod profile_mode;
+pub mod pull_strategy;
+pub mod remote_entry;
+pub mod repository_template;
 pub mod repository_utils;
+pub mod secret_service_store;
+pub mod secret_store;
 pub mod selected_repository;
 pub mod settings;
+pub mod stash_entry;
+pub mod submodule_entry;
+pub mod tag_entry;
+pub mod transfer_progress;
+pub mod tree_entry;
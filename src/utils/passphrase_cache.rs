@@ -0,0 +1,242 @@
+/* passphrase_cache.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use gettextrs::gettext;
+use once_cell::sync::Lazy;
+
+use super::secret_store::SecretStoreUtils;
+
+/// The ID under which the remembered passphrases are kept in the secret store.
+const SECRET_STORE_ID: &str = "passphrase-cache";
+
+/// How long a passphrase is remembered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassphraseRetention {
+    /// The passphrase is asked each time.
+    Never,
+    /// The passphrase is remembered until the application is closed.
+    Session,
+    /// The passphrase is remembered for a number of minutes, even when the application is restarted.
+    Minutes(u32),
+}
+
+impl PassphraseRetention {
+    /// Labels of the retention choices offered to the user, in the order used by `from_choice`.
+    pub fn choices(minutes: u32) -> Vec<String> {
+        return vec![
+            gettext("_Never remember"),
+            gettext("_Remember for this session"),
+            gettext("_Remember for minutes").replace("{}", &minutes.to_string()),
+        ];
+    }
+
+    /// Used to get the retention from the position of the choice of the user.
+    pub fn from_choice(position: u32, minutes: u32) -> Self {
+        return match position {
+            0 => PassphraseRetention::Never,
+            2 => PassphraseRetention::Minutes(minutes),
+            _ => PassphraseRetention::Session,
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedPassphrase {
+    passphrase: String,
+    /// The timestamp after which the passphrase is forgotten, none for the session.
+    expiry: Option<i64>,
+}
+
+static CACHE: Lazy<Mutex<HashMap<String, CachedPassphrase>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static IS_AGENT_DELEGATION_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Remembers the passphrases of SSH and GPG keys.
+pub struct PassphraseCache {}
+
+impl PassphraseCache {
+    /// Loads the passphrases remembered for a number of minutes from the secret store.
+    /// Must be called once the secret store is open.
+    pub fn load() {
+        let content = match SecretStoreUtils::retrieve_password(SECRET_STORE_ID) {
+            Ok(Some(content)) => content,
+            Ok(None) => return,
+            Err(error) => {
+                tracing::warn!("Could not load remembered passphrases: {}", error);
+                return;
+            }
+        };
+
+        let now = Utc::now().timestamp();
+        let mut cache = CACHE.lock().unwrap();
+
+        // Each line is "<key> <passphrase> <expiry>", with the key and the passphrase in hexadecimal:
+        for line in content.lines() {
+            let parts: Vec<&str> = line.split(' ').collect();
+            if parts.len() != 3 {
+                continue;
+            }
+
+            let (key, passphrase, expiry) = match (
+                hex::decode(parts[0])
+                    .ok()
+                    .and_then(|key| String::from_utf8(key).ok()),
+                hex::decode(parts[1])
+                    .ok()
+                    .and_then(|passphrase| String::from_utf8(passphrase).ok()),
+                parts[2].parse::<i64>().ok(),
+            ) {
+                (Some(key), Some(passphrase), Some(expiry)) => (key, passphrase, expiry),
+                _ => continue,
+            };
+
+            if expiry > now && !cache.contains_key(&key) {
+                cache.insert(
+                    key,
+                    CachedPassphrase {
+                        passphrase,
+                        expiry: Some(expiry),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Remembers the passphrase of an SSH private key.
+    pub fn save_ssh_passphrase(
+        private_key_path: &str,
+        passphrase: &str,
+        retention: PassphraseRetention,
+    ) {
+        PassphraseCache::save(&format!("ssh:{}", private_key_path), passphrase, retention);
+    }
+
+    /// Remembers the passphrase of a GPG signing key.
+    pub fn save_gpg_passphrase(
+        signing_key: &str,
+        passphrase: &str,
+        retention: PassphraseRetention,
+    ) {
+        PassphraseCache::save(&format!("gpg:{}", signing_key), passphrase, retention);
+    }
+
+    /// Retrieves the remembered passphrase of an SSH private key.
+    pub fn get_ssh_passphrase(private_key_path: &str) -> Option<String> {
+        return PassphraseCache::get(&format!("ssh:{}", private_key_path));
+    }
+
+    /// Retrieves the remembered passphrase of a GPG signing key.
+    pub fn get_gpg_passphrase(signing_key: &str) -> Option<String> {
+        return PassphraseCache::get(&format!("gpg:{}", signing_key));
+    }
+
+    /// Forgets all remembered passphrases.
+    pub fn forget_all() {
+        let mut cache = CACHE.lock().unwrap();
+        cache.clear();
+        PassphraseCache::persist(&cache);
+    }
+
+    /// Used to know if passphrases are left to the ssh-agent and the gpg-agent when they can handle them.
+    pub fn is_agent_delegation_enabled() -> bool {
+        return IS_AGENT_DELEGATION_ENABLED.load(Ordering::Relaxed);
+    }
+
+    pub fn set_agent_delegation_enabled(is_enabled: bool) {
+        IS_AGENT_DELEGATION_ENABLED.store(is_enabled, Ordering::Relaxed);
+    }
+
+    fn save(key: &str, passphrase: &str, retention: PassphraseRetention) {
+        let mut cache = CACHE.lock().unwrap();
+
+        let expiry = match retention {
+            PassphraseRetention::Never => {
+                if cache.remove(key).is_some() {
+                    PassphraseCache::persist(&cache);
+                }
+                return;
+            }
+            PassphraseRetention::Session => None,
+            PassphraseRetention::Minutes(minutes) => {
+                Some(Utc::now().timestamp() + i64::from(minutes) * 60)
+            }
+        };
+
+        let previous = cache.insert(
+            key.to_string(),
+            CachedPassphrase {
+                passphrase: passphrase.to_string(),
+                expiry,
+            },
+        );
+
+        if expiry.is_some() || previous.map_or(false, |previous| previous.expiry.is_some()) {
+            PassphraseCache::persist(&cache);
+        }
+    }
+
+    fn get(key: &str) -> Option<String> {
+        let mut cache = CACHE.lock().unwrap();
+
+        let cached_passphrase = cache.get(key)?.clone();
+
+        if let Some(expiry) = cached_passphrase.expiry {
+            if expiry <= Utc::now().timestamp() {
+                cache.remove(key);
+                PassphraseCache::persist(&cache);
+                return None;
+            }
+        }
+
+        return Some(cached_passphrase.passphrase);
+    }
+
+    /// Writes the passphrases remembered for a number of minutes to the secret store,
+    /// those remembered for the session are only kept in memory.
+    fn persist(cache: &HashMap<String, CachedPassphrase>) {
+        let content: Vec<String> = cache
+            .iter()
+            .filter_map(|(key, cached_passphrase)| {
+                cached_passphrase.expiry.map(|expiry| {
+                    format!(
+                        "{} {} {}",
+                        hex::encode(key),
+                        hex::encode(&cached_passphrase.passphrase),
+                        expiry
+                    )
+                })
+            })
+            .collect();
+
+        // Nothing is persisted while the secret store is locked:
+        if !SecretStoreUtils::is_open() {
+            return;
+        }
+
+        if let Err(error) = SecretStoreUtils::store_password(SECRET_STORE_ID, &content.join("\n")) {
+            tracing::warn!("Could not save remembered passphrases: {}", error);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/* repository_template.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use gtk::{gio, glib};
+
+/// Where the bundled templates are found in the resources.
+const TEMPLATES_RESOURCE_PATH: &str = "/com/skilldary/bagit/desktop/templates";
+
+/// The bundled .gitignore templates, as (shown name, resource name).
+pub const GITIGNORE_TEMPLATES: [(&str, &str); 7] = [
+    ("C", "C"),
+    ("C++", "Cpp"),
+    ("Go", "Go"),
+    ("Java", "Java"),
+    ("Node", "Node"),
+    ("Python", "Python"),
+    ("Rust", "Rust"),
+];
+
+/// The bundled licenses, by SPDX identifier.
+pub const LICENSES: [&str; 6] = [
+    "AGPL-3.0",
+    "Apache-2.0",
+    "BSD-3-Clause",
+    "GPL-3.0",
+    "MIT",
+    "MPL-2.0",
+];
+
+/// The initial content of a new repository.
+#[derive(Debug, Clone, Default)]
+pub struct RepositoryTemplate {
+    /// The name of the branch checked out in the new repository, empty for the default one.
+    pub initial_branch: String,
+    /// The resource name of the .gitignore template, empty for no .gitignore.
+    pub gitignore_template: String,
+    /// The identifier of the license, empty for no license.
+    pub license: String,
+    /// Whether a README skeleton is added.
+    pub has_readme: bool,
+    /// Whether the initial files are committed once the repository is created.
+    pub has_initial_commit: bool,
+}
+
+impl RepositoryTemplate {
+    /// Builds the initial files of the repository, as (relative path, content).
+    /// The author and the year are written in the license when it has a copyright line for them.
+    pub fn build_files(
+        &self,
+        repository_name: &str,
+        author: &str,
+        year: i32,
+    ) -> Result<Vec<(String, String)>, glib::Error> {
+        let mut files: Vec<(String, String)> = Vec::new();
+
+        if !self.gitignore_template.is_empty() {
+            let gitignore = RepositoryTemplate::load_resource(&format!(
+                "{}/gitignore/{}.gitignore",
+                TEMPLATES_RESOURCE_PATH, self.gitignore_template
+            ))?;
+
+            files.push((String::from(".gitignore"), gitignore));
+        }
+
+        if !self.license.is_empty() {
+            let license = RepositoryTemplate::load_resource(&format!(
+                "{}/licenses/{}.txt",
+                TEMPLATES_RESOURCE_PATH, self.license
+            ))?
+            .replace("{year}", &year.to_string())
+            .replace("{author}", author);
+
+            files.push((String::from("LICENSE"), license));
+        }
+
+        if self.has_readme {
+            files.push((
+                String::from("README.md"),
+                self.build_readme(repository_name),
+            ));
+        }
+
+        return Ok(files);
+    }
+
+    /// Builds the README skeleton, pointing to the license when there is one.
+    fn build_readme(&self, repository_name: &str) -> String {
+        let mut readme = format!(
+            "# {}\n\nA short description of the project.\n\n## Installation\n\n## Usage\n",
+            repository_name
+        );
+
+        if !self.license.is_empty() {
+            readme.push_str(&format!(
+                "\n## License\n\nThis project is licensed under the {} license, see [LICENSE](LICENSE).\n",
+                self.license
+            ));
+        }
+
+        return readme;
+    }
+
+    /// Reads a bundled template.
+    fn load_resource(path: &str) -> Result<String, glib::Error> {
+        let bytes = gio::resources_lookup_data(path, gio::ResourceLookupFlags::NONE)?;
+
+        return Ok(String::from_utf8_lossy(&bytes).to_string());
+    }
+}
@@ -17,19 +17,60 @@
  * SPDX-License-Identifier: AGPL-3.0-only
  */
 
-use std::{env, path::Path, str::from_utf8};
+use std::{
+    cell::{Cell, RefCell},
+    env,
+    path::Path,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
+use chrono::NaiveDateTime;
 use gettextrs::gettext;
 use git2::{
-    build::CheckoutBuilder, BranchType, Commit, Cred, Delta, DiffOptions, ErrorClass, ErrorCode,
-    FetchOptions, Index, ObjectType, Oid, PushOptions, RemoteCallbacks, Repository, Signature,
-    Status,
+    build::CheckoutBuilder, BlameOptions, Branch, BranchType, Commit, Cred, Delta, DiffFindOptions,
+    DiffOptions, ErrorClass, ErrorCode, FetchOptions, Index, IndexEntry, IndexTime, ObjectType,
+    Oid, PushOptions, RemoteCallbacks, Repository, RepositoryInitOptions, RepositoryState,
+    Signature, StashFlags, Submodule, SubmoduleIgnore, SubmoduleStatus, SubmoduleUpdateOptions,
+    Tree,
 };
 use regex::Regex;
 
-use crate::{models::bagit_git_profile::BagitGitProfile, utils::gpg_utils::GpgUtils};
+use crate::{
+    models::bagit_git_profile::BagitGitProfile,
+    utils::{agent_utils::AgentUtils, gpg_utils::GpgUtils, passphrase_cache::PassphraseCache},
+};
+
+use super::{
+    blame_line::BlameLine,
+    changed_file::ChangedFile,
+    clone_mode::CloneMode,
+    clone_options::CloneOptions,
+    file_diff::FileDiff,
+    file_history_entry::FileHistoryEntry,
+    interactive_rebase::{InteractiveRebaseState, RebaseAction, RebaseStep, RebaseStop},
+    merge_conflict::{ConflictSide, MergeConflict},
+    pull_strategy::PullStrategy,
+    remote_entry::RemoteEntry,
+    stash_entry::StashEntry,
+    submodule_entry::SubmoduleEntry,
+    tag_entry::TagEntry,
+    transfer_progress::TransferProgress,
+    tree_entry::TreeEntry,
+};
+
+/// The minimal time between two reports of the progress of a transfer.
+const TRANSFER_REPORT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The fetch depth asking libgit2 to fetch the whole history of a shallow repository.
+const UNSHALLOW_DEPTH: i32 = i32::MAX;
 
-use super::{changed_file::ChangedFile, clone_mode::CloneMode};
+/// The file of the git directory keeping the progress of an interactive rebase.
+const INTERACTIVE_REBASE_STATE_FILE: &str = "bagit-rebase-todo";
 
 pub struct RepositoryUtils {}
 
@@ -92,6 +133,7 @@ impl RepositoryUtils {
 
     /**
      * Used to create callback for ssh clone.
+     * Without passphrase, the ssh-agent is used when it holds the private key and delegation is enabled.
      */
     pub fn ssh_callback(
         profile_username: String,
@@ -102,7 +144,19 @@ impl RepositoryUtils {
 
         let private_key_path_clone = profile_private_key_path.clone();
 
+        let is_using_agent = profile_passphrase.is_empty()
+            && PassphraseCache::is_agent_delegation_enabled()
+            && AgentUtils::is_ssh_key_in_agent(&profile_private_key_path);
+
         callback.credentials(move |_url, username, _allowed_type| {
+            if is_using_agent {
+                return Cred::ssh_key_from_agent(if username.is_some() {
+                    username.unwrap()
+                } else {
+                    &profile_username
+                });
+            }
+
             if profile_username.is_empty() {
                 // No cred will be used :
                 return Cred::ssh_key(
@@ -136,6 +190,68 @@ impl RepositoryUtils {
         return callback;
     }
 
+    /// Used to report the progress of the transfer made with the callbacks and to cancel it.
+    /// The transfer is aborted once `is_cancelled` is set, the reports are limited to a few per second.
+    pub fn add_transfer_callbacks<F: Fn(TransferProgress) + 'static>(
+        callback: &mut RemoteCallbacks<'static>,
+        is_cancelled: Arc<AtomicBool>,
+        report_progress: F,
+    ) {
+        let progress = Rc::new(RefCell::new(TransferProgress::default()));
+        let last_report = Rc::new(Cell::new(Instant::now() - TRANSFER_REPORT_INTERVAL));
+        let report_progress = Rc::new(report_progress);
+
+        let report = {
+            let progress = progress.clone();
+            let report_progress = report_progress.clone();
+            move |is_forced: bool| {
+                if is_forced || last_report.get().elapsed() >= TRANSFER_REPORT_INTERVAL {
+                    last_report.set(Instant::now());
+                    report_progress(progress.borrow().clone());
+                }
+            }
+        };
+        let report = Rc::new(report);
+
+        let cloned_is_cancelled = is_cancelled.clone();
+        let cloned_progress = progress.clone();
+        let cloned_report = report.clone();
+        callback.transfer_progress(move |stats| {
+            {
+                let mut progress = cloned_progress.borrow_mut();
+                progress.received_objects = stats.received_objects();
+                progress.indexed_objects = stats.indexed_objects();
+                progress.total_objects = stats.total_objects();
+                progress.received_bytes = stats.received_bytes();
+                progress.indexed_deltas = stats.indexed_deltas();
+                progress.total_deltas = stats.total_deltas();
+            }
+            // The end of the transfer is always reported:
+            cloned_report(
+                stats.received_objects() == stats.total_objects()
+                    && stats.indexed_deltas() == stats.total_deltas(),
+            );
+
+            return !cloned_is_cancelled.load(Ordering::Relaxed);
+        });
+
+        callback.sideband_progress(move |data| {
+            // The remote rewrites its line with carriage returns, only the last state is kept:
+            let message = String::from_utf8_lossy(data);
+            if let Some(line) = message
+                .split(|character| character == '\r' || character == '\n')
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .last()
+            {
+                progress.borrow_mut().remote_message = line.to_string();
+                report(false);
+            }
+
+            return !is_cancelled.load(Ordering::Relaxed);
+        });
+    }
+
     /**
      * Used to get the folder name of a path from OS information.
      */
@@ -196,25 +312,162 @@ impl RepositoryUtils {
         Ok(())
     }
 
-    /// Used to get the clone mode of a repository.
-    pub fn get_clone_mode_of_repository(repository: &Repository) -> Result<CloneMode, git2::Error> {
+    /// Used to get the clone mode of a remote of a repository.
+    /// An empty remote name means that the default remote is used.
+    pub fn get_clone_mode_of_remote(
+        repository: &Repository,
+        remote_name: &str,
+    ) -> Result<CloneMode, git2::Error> {
+        let remote_name = RepositoryUtils::resolve_remote_name(repository, remote_name)?;
+
         let config = match repository.config() {
             Ok(config) => config,
             Err(error) => return Err(error),
         };
 
-        match config.get_entry("remote.origin.url") {
+        match config.get_entry(&format!("remote.{}.url", remote_name)) {
             Ok(url) => {
-                return Ok(if RepositoryUtils::is_using_https(url.value().unwrap()) {
-                    CloneMode::HTTPS
-                } else {
-                    CloneMode::SSH
-                });
+                return Ok(RepositoryUtils::get_clone_mode_of_url(url.value().unwrap()));
             }
             Err(error) => return Err(error),
         };
     }
 
+    /// Used to get the clone mode used to reach a URL.
+    pub fn get_clone_mode_of_url(url: &str) -> CloneMode {
+        return if RepositoryUtils::is_using_https(url) {
+            CloneMode::HTTPS
+        } else {
+            CloneMode::SSH
+        };
+    }
+
+    /// Used to create the callbacks of a remote, depending on its clone mode.
+    pub fn get_remote_callbacks(
+        repository: &Repository,
+        remote_name: &str,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) -> Result<RemoteCallbacks<'static>, git2::Error> {
+        match RepositoryUtils::get_clone_mode_of_remote(repository, remote_name)? {
+            CloneMode::SSH => Ok(RepositoryUtils::ssh_callback(
+                username,
+                passphrase,
+                private_key_path,
+            )),
+            CloneMode::HTTPS => Ok(RepositoryUtils::https_callback(username, password)),
+        }
+    }
+
+    /// Retrieves the remotes of the repository with their URL.
+    pub fn get_remotes(repository: &Repository) -> Result<Vec<RemoteEntry>, git2::Error> {
+        let mut remotes: Vec<RemoteEntry> = vec![];
+
+        for remote_name in repository.remotes()?.iter().flatten() {
+            let remote = repository.find_remote(remote_name)?;
+            remotes.push(RemoteEntry {
+                name: remote_name.to_string(),
+                url: remote.url().unwrap_or("").to_string(),
+            });
+        }
+
+        Ok(remotes)
+    }
+
+    /// Retrieves the remote used when none is chosen.
+    /// It is the remote tracked by the checked-out branch, then "origin", then the first remote.
+    pub fn get_default_remote_name(repository: &Repository) -> Result<String, git2::Error> {
+        if let Ok(head) = repository.head() {
+            if let Some(reference_name) = head.name() {
+                if let Ok(remote_name) = repository.branch_upstream_remote(reference_name) {
+                    if let Some(remote_name) = remote_name.as_str() {
+                        return Ok(remote_name.to_string());
+                    }
+                }
+            }
+        }
+
+        let remotes = repository.remotes()?;
+
+        if remotes.iter().flatten().any(|name| name == "origin") {
+            return Ok(String::from("origin"));
+        }
+
+        match remotes.iter().flatten().next() {
+            Some(remote_name) => Ok(remote_name.to_string()),
+            None => Err(git2::Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Config,
+                gettext("_No remote"),
+            )),
+        }
+    }
+
+    /// Used to get the name of a remote, the default one being used when the name is empty.
+    fn resolve_remote_name(
+        repository: &Repository,
+        remote_name: &str,
+    ) -> Result<String, git2::Error> {
+        if remote_name.is_empty() {
+            return RepositoryUtils::get_default_remote_name(repository);
+        }
+        return Ok(remote_name.to_string());
+    }
+
+    /// Used to add a remote to the repository.
+    pub fn add_remote(
+        repository: &Repository,
+        remote_name: &str,
+        url: &str,
+    ) -> Result<(), git2::Error> {
+        repository.remote(remote_name, url)?;
+        Ok(())
+    }
+
+    /// Used to rename a remote and change its URL.
+    /// Remote-tracking branches and the branches tracking them follow the new name.
+    pub fn update_remote(
+        repository: &Repository,
+        remote_name: &str,
+        new_remote_name: &str,
+        new_url: &str,
+    ) -> Result<(), git2::Error> {
+        if remote_name != new_remote_name {
+            let problems = repository.remote_rename(remote_name, new_remote_name)?;
+            for problem in problems.iter().flatten() {
+                tracing::warn!("Refspec not updated while renaming remote: {}", problem);
+            }
+        }
+
+        repository.remote_set_url(new_remote_name, new_url)
+    }
+
+    /// Used to remove a remote, with its remote-tracking branches.
+    pub fn delete_remote(repository: &Repository, remote_name: &str) -> Result<(), git2::Error> {
+        repository.remote_delete(remote_name)
+    }
+
+    /// Splits the name of a remote branch, like "upstream/main", into its remote name and branch name.
+    pub fn split_remote_branch_name(
+        repository: &Repository,
+        remote_branch_name: &str,
+    ) -> Result<(String, String), git2::Error> {
+        let remote_name = repository
+            .branch_remote_name(&format!("refs/remotes/{}", remote_branch_name))?
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+
+        let branch_name = match remote_branch_name.strip_prefix(&format!("{}/", remote_name)) {
+            Some(branch_name) => branch_name.to_string(),
+            None => remote_branch_name.to_string(),
+        };
+
+        Ok((remote_name, branch_name))
+    }
+
     /// Used to write profile information to git config.
     pub fn override_git_config(
         repository: &Repository,
@@ -240,73 +493,288 @@ impl RepositoryUtils {
         Ok(())
     }
 
+    /// Used to create a repository, with the given branch checked out when there is one.
+    pub fn create_repository(
+        repository_path: &str,
+        initial_branch: &str,
+    ) -> Result<Repository, git2::Error> {
+        let mut init_options = RepositoryInitOptions::new();
+
+        if !initial_branch.is_empty() {
+            if !Branch::name_is_valid(initial_branch)? {
+                return Err(git2::Error::new(
+                    ErrorCode::InvalidSpec,
+                    ErrorClass::Reference,
+                    gettext("_Invalid initial branch name"),
+                ));
+            }
+
+            init_options.initial_head(initial_branch);
+        }
+
+        return Repository::init_opts(repository_path, &init_options);
+    }
+
+    /// Used to write the initial files of a new repository and to stage them.
+    pub fn add_initial_files(
+        repository: &Repository,
+        files: &[(String, String)],
+    ) -> Result<(), git2::Error> {
+        let workdir = RepositoryUtils::get_workdir_of_repository(repository)?;
+
+        for (relative_path, content) in files {
+            if let Err(error) = std::fs::write(Path::new(&workdir).join(relative_path), content) {
+                return Err(git2::Error::new(
+                    ErrorCode::GenericError,
+                    ErrorClass::Os,
+                    error.to_string(),
+                ));
+            }
+        }
+
+        let file_paths: Vec<String> = files
+            .iter()
+            .map(|(relative_path, _)| relative_path.clone())
+            .collect();
+
+        return RepositoryUtils::stage_files(repository, &file_paths);
+    }
+
     /// Used to clone a repository and return the result.
     pub fn clone_repository(
         url: &str,
         new_folder_path: &str,
         callback: RemoteCallbacks,
+        clone_options: &CloneOptions,
     ) -> Result<Repository, git2::Error> {
         let mut fo = git2::FetchOptions::new();
         fo.remote_callbacks(callback);
 
+        if clone_options.depth > 0 {
+            fo.depth(clone_options.depth as i32);
+        }
+
         let mut builder = git2::build::RepoBuilder::new();
         builder.fetch_options(fo);
+        builder.bare(clone_options.is_bare);
+
+        if !clone_options.branch_name.is_empty() {
+            let branch_name = clone_options.branch_name.clone();
+            builder.branch(&branch_name);
+
+            // Only the chosen branch is fetched, now and later:
+            builder.remote_create(move |repository, name, url| {
+                return repository.remote_with_fetch(
+                    name,
+                    url,
+                    &format!(
+                        "+refs/heads/{}:refs/remotes/{}/{}",
+                        branch_name, name, branch_name
+                    ),
+                );
+            });
+        }
 
         return builder.clone(&url.trim(), Path::new(&new_folder_path));
     }
 
+    /// Used to clone the submodules of a freshly cloned repository when asked by the clone options.
+    /// A failure is only logged, as the submodules can still be updated from the repository page.
+    pub fn update_cloned_submodules(
+        repository: &Repository,
+        clone_options: &CloneOptions,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) {
+        if !clone_options.is_recursive || clone_options.is_bare {
+            return;
+        }
+
+        if let Err(error) = RepositoryUtils::update_submodules(
+            repository,
+            "",
+            true,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        ) {
+            tracing::warn!(
+                "Could not update submodules of cloned repository: {}",
+                error
+            );
+        }
+    }
+
+    /// Used to list the branches of a remote repository before cloning it.
+    /// The default branch of the remote comes first.
+    pub fn get_remote_branch_names(
+        url: &str,
+        callback: RemoteCallbacks,
+    ) -> Result<Vec<String>, git2::Error> {
+        let mut remote = git2::Remote::create_detached(url.trim())?;
+        let connection = remote.connect_auth(git2::Direction::Fetch, Some(callback), None)?;
+
+        let default_branch = match connection.default_branch() {
+            Ok(buf) => buf.as_str().unwrap_or("").to_string(),
+            Err(_) => String::new(),
+        };
+
+        let mut branch_names: Vec<String> = vec![];
+
+        for head in connection.list()? {
+            if let Some(branch_name) = head.name().strip_prefix("refs/heads/") {
+                if head.name() == default_branch {
+                    branch_names.insert(0, branch_name.to_string());
+                } else {
+                    branch_names.push(branch_name.to_string());
+                }
+            }
+        }
+
+        return Ok(branch_names);
+    }
+
+    /// Used to get the number of commits of the history of the checked out branch,
+    /// in a shallow repository it is the depth of the cloned history.
+    pub fn get_history_depth(repository: &Repository) -> Result<u32, git2::Error> {
+        let mut revwalk = repository.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.simplify_first_parent()?;
+
+        return Ok(revwalk.count() as u32);
+    }
+
+    /// Used to fetch more history in a shallow repository.
+    /// The history is deepened by the given number of commits, or completely fetched for 0.
+    pub fn deepen_history<F: Fn(TransferProgress) + 'static>(
+        repository: &Repository,
+        depth_increment: u32,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+        is_cancelled: Arc<AtomicBool>,
+        report_progress: F,
+    ) -> Result<(), git2::Error> {
+        let remote_name = RepositoryUtils::get_default_remote_name(repository)?;
+
+        let depth = if depth_increment == 0 {
+            UNSHALLOW_DEPTH
+        } else {
+            (RepositoryUtils::get_history_depth(repository)? + depth_increment) as i32
+        };
+
+        let mut callback = RepositoryUtils::get_remote_callbacks(
+            repository,
+            &remote_name,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        )?;
+        RepositoryUtils::add_transfer_callbacks(&mut callback, is_cancelled, report_progress);
+
+        let mut fetch_options = FetchOptions::new();
+        fetch_options.remote_callbacks(callback);
+        fetch_options.depth(depth);
+
+        // An empty refspec list uses the fetch refspecs configured for the remote:
+        let refspecs: [&str; 0] = [];
+        return repository.find_remote(&remote_name)?.fetch(
+            &refspecs,
+            Some(&mut fetch_options),
+            None,
+        );
+    }
+
     /// Used to find latest commit of a repository.
     pub fn find_last_commit(repo: &Repository) -> Result<Commit, git2::Error> {
+        if RepositoryUtils::is_head_unborn(repo) {
+            return Err(git2::Error::new(
+                ErrorCode::UnbornBranch,
+                ErrorClass::Reference,
+                gettext("_There is no commit yet"),
+            ));
+        }
+
         let obj = repo.head()?.resolve()?.peel(ObjectType::Commit)?;
         obj.into_commit()
             .map_err(|_| git2::Error::from_str("Couldn't find commit"))
     }
 
-    /// Used to update a repository's index with the selected files.
-    pub fn update_repository_index(
-        repository: &Repository,
-        selected_files: Vec<ChangedFile>,
-    ) -> Result<Index, String> {
-        let mut index = repository.index().ok().unwrap();
+    /// Used to check if the checked out branch has no commit yet, like in a new repository.
+    pub fn is_head_unborn(repository: &Repository) -> bool {
+        return match repository.head() {
+            Ok(_) => false,
+            Err(error) => error.code() == ErrorCode::UnbornBranch,
+        };
+    }
 
-        for selected_file in &selected_files {
-            let selected_file_path_string =
-                RepositoryUtils::build_path_of_file(&selected_file.parent, &selected_file.name);
+    /// Used to check if HEAD points directly to a commit instead of a branch.
+    pub fn is_head_detached(repository: &Repository) -> bool {
+        return repository.head_detached().unwrap_or(false);
+    }
+
+    /// Used to move the checked out branch, or HEAD itself when detached, to a commit.
+    /// The checked out branch is created if it has no commit yet.
+    fn move_head_to_commit(
+        repository: &Repository,
+        commit_oid: Oid,
+        log_message: &str,
+    ) -> Result<(), git2::Error> {
+        let head = repository.find_reference("HEAD")?;
 
-            let selected_file_path = Path::new(&selected_file_path_string);
+        match head.symbolic_target() {
+            Some(branch_reference_name) => {
+                repository.reference(branch_reference_name, commit_oid, true, log_message)?;
+            }
+            None => repository.set_head_detached(commit_oid)?,
+        }
 
-            match selected_file.status {
-                Status::WT_DELETED | Status::INDEX_DELETED => {
-                    if let Err(error) = index.remove_path(selected_file_path) {
-                        tracing::warn!(
-                            "Could not remove file from index while committing: {}",
-                            error
-                        );
+        return Ok(());
+    }
 
-                        return Err(gettext("_An error has occured"));
-                    }
-                }
+    /// Used to stage files: their changes in the working directory are written to the index.
+    pub fn stage_files(repository: &Repository, file_paths: &[String]) -> Result<(), git2::Error> {
+        let workdir = RepositoryUtils::get_workdir_of_repository(repository)?;
+        let mut index = repository.index()?;
 
-                _ => {
-                    if let Err(error) = index.add_path(selected_file_path) {
-                        tracing::warn!("Could not add file in index while committing: {}", error);
+        for file_path in file_paths {
+            let path = Path::new(file_path);
 
-                        return Err(gettext("_An error has occured"));
-                    }
-                }
+            // A file missing from the working directory is staged as deleted:
+            if Path::new(&workdir).join(path).symlink_metadata().is_ok() {
+                index.add_path(path)?;
+            } else {
+                index.remove_path(path)?;
             }
         }
 
-        match index.write() {
-            Ok(_) => Ok(index),
-            Err(_) => Err(gettext("_An error has occured")),
-        }
+        return index.write();
+    }
+
+    /// Used to unstage files: their entries in the index are reset to the checked out commit.
+    /// Files that are not in the checked out commit, or when there is no commit yet, are removed from the index.
+    pub fn unstage_files(
+        repository: &Repository,
+        file_paths: &[String],
+    ) -> Result<(), git2::Error> {
+        let head_commit = match repository.head() {
+            Ok(head) => Some(head.peel(ObjectType::Commit)?),
+            Err(error) if error.code() == ErrorCode::UnbornBranch => None,
+            Err(error) => return Err(error),
+        };
+
+        return repository.reset_default(head_commit.as_ref(), file_paths);
     }
 
-    /// Used to commit files.
+    /// Used to commit the staged files.
+    /// On a branch without any commit yet, the commit is the first one of the branch.
     pub fn commit_files(
         repository: &Repository,
-        selected_files: Vec<ChangedFile>,
         message: &str,
         description: &str,
         author: &str,
@@ -314,21 +782,17 @@ impl RepositoryUtils {
         signing_key: &str,
         passphrase: &str,
     ) -> Result<Oid, git2::Error> {
-        let mut index = match RepositoryUtils::update_repository_index(repository, selected_files) {
-            Ok(idx) => idx,
-            Err(error_message) => {
-                return Err(git2::Error::new(
-                    ErrorCode::GenericError,
-                    ErrorClass::Invalid,
-                    error_message,
-                ))
-            }
-        };
+        let mut index = repository.index()?;
 
-        let oid = index.write_tree()?;
+        let tree = repository.find_tree(index.write_tree()?)?;
         let author_signature = Signature::now(author, author_email)?;
-        let parent_commit = RepositoryUtils::find_last_commit(&repository)?;
-        let tree = repository.find_tree(oid).ok().unwrap();
+
+        let parent_commit = if RepositoryUtils::is_head_unborn(repository) {
+            None
+        } else {
+            Some(RepositoryUtils::find_last_commit(repository)?)
+        };
+        let parents: Vec<&Commit> = parent_commit.iter().collect();
 
         let final_message = if description.is_empty() {
             message.to_string()
@@ -336,130 +800,114 @@ impl RepositoryUtils {
             format!("{}\n{}", message, description)
         };
 
-        if signing_key.is_empty() {
-            match repository.commit(
-                Some("HEAD"),
-                &author_signature,
-                &author_signature,
-                &final_message,
-                &tree,
-                &[&parent_commit],
-            ) {
-                Ok(commit_oid) => {
-                    return Ok(commit_oid);
-                }
-                Err(error) => {
-                    match repository.reset(&parent_commit.as_object(), git2::ResetType::Soft, None)
-                    {
-                        Ok(_) => Err(error),
-                        Err(e) => Err(e),
-                    }
-                }
-            }
+        let commit_oid = RepositoryUtils::create_commit(
+            repository,
+            &author_signature,
+            &author_signature,
+            &final_message,
+            &tree,
+            &parents,
+            signing_key,
+            passphrase,
+        )?;
+
+        RepositoryUtils::move_head_to_commit(repository, commit_oid, message)?;
+
+        return Ok(commit_oid);
+    }
+
+    /// Retrieves the title and the description of the last commit.
+    pub fn get_last_commit_message(
+        repository: &Repository,
+    ) -> Result<(String, String), git2::Error> {
+        let last_commit = RepositoryUtils::find_last_commit(repository)?;
+        let message = String::from_utf8_lossy(last_commit.message_bytes())
+            .trim()
+            .to_string();
+
+        return Ok(match message.split_once("\n") {
+            Some((title, description)) => (title.to_string(), description.trim().to_string()),
+            None => (message, String::from("")),
+        });
+    }
+
+    /// Used to rewrite the last commit with the staged files added to it and a new message.
+    /// The author of the last commit is kept unless the author is reset to the given one.
+    pub fn amend_last_commit(
+        repository: &Repository,
+        message: &str,
+        description: &str,
+        author: &str,
+        author_email: &str,
+        is_resetting_author: bool,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        let mut index = repository.index()?;
+
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let last_commit = RepositoryUtils::find_last_commit(repository)?;
+        let committer_signature = Signature::now(author, author_email)?;
+        let author_signature = if is_resetting_author {
+            committer_signature.clone()
         } else {
-            match repository.commit_create_buffer(
-                &author_signature,
-                &author_signature,
-                &final_message,
-                &tree,
-                &[&parent_commit],
-            ) {
-                Ok(buffer) => {
-                    let commit_as_str = std::str::from_utf8(&buffer).unwrap().to_string();
-
-                    let sig = GpgUtils::sign_commit_string_with_passphrase(
-                        &commit_as_str,
-                        signing_key,
-                        passphrase,
-                    );
-
-                    match sig {
-                        Ok(string_sig) => {
-                            match repository.commit_signed(&commit_as_str, &string_sig, None) {
-                                Ok(commit_oid) => {
-                                    // Strangely, commit_signed will not update the HEAD. We need to do it manually
-                                    let head = repository.head()?;
-                                    let branch = head.shorthand();
-                                    match branch {
-                                        Some(branch) => {
-                                            repository.reference(
-                                                &format!("refs/heads/{}", branch),
-                                                commit_oid,
-                                                true,
-                                                &message,
-                                            )?;
-                                        }
-                                        None => {
-                                            return Err(git2::Error::new(
-                                                ErrorCode::GenericError,
-                                                ErrorClass::Invalid,
-                                                gettext("_An error has occured"),
-                                            ));
-                                        }
-                                    }
-
-                                    return Ok(commit_oid);
-                                }
-                                Err(error) => {
-                                    match repository.reset(
-                                        &parent_commit.as_object(),
-                                        git2::ResetType::Soft,
-                                        None,
-                                    ) {
-                                        Ok(_) => Err(error),
-                                        Err(e) => Err(e),
-                                    }
-                                }
-                            }
-                        }
-                        Err(error) => Err(git2::Error::new(
-                            ErrorCode::GenericError,
-                            ErrorClass::Invalid,
-                            error,
-                        )),
-                    }
-                }
-                Err(e) => Err(e),
-            }
-        }
+            last_commit.author().to_owned()
+        };
+
+        let final_message = if description.is_empty() {
+            message.to_string()
+        } else {
+            format!("{}\n{}", message, description)
+        };
+
+        let parents: Vec<Commit> = last_commit.parents().collect();
+        let new_commit_oid = RepositoryUtils::create_commit(
+            repository,
+            &author_signature,
+            &committer_signature,
+            &final_message,
+            &tree,
+            &parents.iter().collect::<Vec<&Commit>>(),
+            signing_key,
+            passphrase,
+        )?;
+
+        // The reference pointed by HEAD, or HEAD itself when detached, is moved to the new commit:
+        repository
+            .head()?
+            .set_target(new_commit_oid, "Amend last commit")?;
+
+        return Ok(new_commit_oid);
     }
 
     /// Used to push changes.
+    /// An empty remote name means that the default remote is used.
     pub fn push(
         repository: &Repository,
+        remote_name: &str,
         username: String,
         password: String,
         private_key_path: String,
         passphrase: String,
     ) -> Result<(), git2::Error> {
-        let head = repository.head()?;
-
-        let checked_out_branch = head
-            .shorthand()
-            .expect("Could not retrieve name of checked-out branch.");
+        let checked_out_branch = &RepositoryUtils::get_current_branch_name(repository)?;
+        let branch = repository.find_branch(checked_out_branch, git2::BranchType::Local)?;
 
-        let branch = repository
-            .find_branch(checked_out_branch, git2::BranchType::Local)
-            .unwrap();
+        let remote_name = RepositoryUtils::resolve_remote_name(repository, remote_name)?;
 
-        let mut remote = match repository.find_remote("origin") {
+        let mut remote = match repository.find_remote(&remote_name) {
             Ok(remote) => remote,
             Err(error) => return Err(error),
         };
 
-        let callback: RemoteCallbacks<'_>;
-
-        match RepositoryUtils::get_clone_mode_of_repository(&repository) {
-            Ok(clone_mode) => {
-                callback = match clone_mode {
-                    CloneMode::SSH => {
-                        RepositoryUtils::ssh_callback(username, passphrase, private_key_path)
-                    }
-                    CloneMode::HTTPS => RepositoryUtils::https_callback(username, password),
-                }
-            }
-            Err(error) => return Err(error),
-        };
+        let callback = RepositoryUtils::get_remote_callbacks(
+            repository,
+            &remote_name,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        )?;
 
         let mut push_options = PushOptions::new();
         push_options.remote_callbacks(callback);
@@ -475,19 +923,10 @@ impl RepositoryUtils {
         ) {
             Ok(_) => {
                 if upstream_branch_reference.is_none() {
-                    let binding = repository
-                        .find_branch(checked_out_branch, git2::BranchType::Local)
-                        .unwrap();
-
-                    let remote_name = format!(
-                        "{}/{}",
-                        remote.name().unwrap(),
-                        binding.name().unwrap().unwrap()
-                    );
+                    let remote_name = format!("{}/{}", remote.name().unwrap(), checked_out_branch);
 
                     repository
-                        .find_branch(checked_out_branch, git2::BranchType::Local)
-                        .unwrap()
+                        .find_branch(checked_out_branch, git2::BranchType::Local)?
                         .set_upstream(Some(&remote_name))?;
                 }
                 return Ok(());
@@ -496,44 +935,149 @@ impl RepositoryUtils {
         }
     }
 
-    /// Used to pull a repository's remote branch.
-    pub fn pull(
+    /// Used to publish the repository to a new remote, reached with the given credentials.
+    /// The remote repository must be empty. The checked out branch is pushed to it and tracks it.
+    /// The new remote is removed when the publication fails, so that it can be tried again.
+    pub fn publish(
         repository: &Repository,
+        remote_name: &str,
+        url: &str,
         username: String,
         password: String,
         private_key_path: String,
         passphrase: String,
     ) -> Result<(), git2::Error> {
-        let head = repository.head()?;
+        if RepositoryUtils::is_head_unborn(repository) {
+            return Err(git2::Error::new(
+                ErrorCode::UnbornBranch,
+                ErrorClass::Reference,
+                gettext("_There is no commit yet"),
+            ));
+        }
 
-        let checked_out_branch = head
-            .shorthand()
-            .expect("Could not retrieve name of checked-out branch.");
+        if repository.find_remote(remote_name).is_ok() {
+            return Err(git2::Error::new(
+                ErrorCode::Exists,
+                ErrorClass::Config,
+                gettext("_A remote with this name already exists"),
+            ));
+        }
 
-        let branch = repository
-            .find_branch(checked_out_branch, git2::BranchType::Local)
-            .unwrap();
+        RepositoryUtils::add_remote(repository, remote_name, url)?;
+
+        let result = RepositoryUtils::push_to_empty_remote(
+            repository,
+            remote_name,
+            url,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        );
+
+        if result.is_err() {
+            if let Err(error) = repository.remote_delete(remote_name) {
+                tracing::warn!(
+                    "Could not remove the remote of a failed publication: {}",
+                    error
+                );
+            }
+        }
 
-        let callback: RemoteCallbacks<'_>;
+        return result;
+    }
 
-        match RepositoryUtils::get_clone_mode_of_repository(&repository) {
-            Ok(clone_mode) => {
-                callback = match clone_mode {
-                    CloneMode::SSH => {
-                        RepositoryUtils::ssh_callback(username, passphrase, private_key_path)
-                    }
-                    CloneMode::HTTPS => RepositoryUtils::https_callback(username, password),
-                }
-            }
-            Err(error) => return Err(error),
+    /// Used to push the checked out branch to a remote, once checked that it has no branch yet.
+    fn push_to_empty_remote(
+        repository: &Repository,
+        remote_name: &str,
+        url: &str,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) -> Result<(), git2::Error> {
+        // Fetching checks that the remote is reachable, and brings its branches if it has any:
+        let mut fetch_options = FetchOptions::new();
+        fetch_options.remote_callbacks(RepositoryUtils::find_correct_callback(
+            url.to_string(),
+            username.clone(),
+            password.clone(),
+            passphrase.clone(),
+            private_key_path.clone(),
+        ));
+
+        let refspecs: [&str; 0] = [];
+        repository
+            .find_remote(remote_name)?
+            .fetch(&refspecs, Some(&mut fetch_options), None)?;
+
+        if repository
+            .references_glob(&format!("refs/remotes/{}/*", remote_name))?
+            .next()
+            .is_some()
+        {
+            return Err(git2::Error::new(
+                ErrorCode::Exists,
+                ErrorClass::Net,
+                gettext("_The remote repository is not empty"),
+            ));
+        }
+
+        return RepositoryUtils::push(
+            repository,
+            remote_name,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        );
+    }
+
+    /// Used to pull a repository's remote branch.
+    /// Returns true when the remote branch diverged and still needs to be integrated.
+    pub fn pull(
+        repository: &Repository,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) -> Result<bool, git2::Error> {
+        let checked_out_branch = RepositoryUtils::get_current_branch_name(repository)?;
+
+        // The branch is pulled from the remote branch it tracks:
+        let branch_reference_name = format!("refs/heads/{}", checked_out_branch);
+        let (remote_name, remote_reference_name) = match (
+            repository.branch_upstream_remote(&branch_reference_name),
+            repository.config().and_then(|config| {
+                config.get_string(&format!("branch.{}.merge", checked_out_branch))
+            }),
+        ) {
+            (Ok(remote_name), Ok(merge_reference_name)) => (
+                remote_name.as_str().unwrap_or("").to_string(),
+                merge_reference_name,
+            ),
+            _ => (
+                RepositoryUtils::get_default_remote_name(repository)?,
+                branch_reference_name.clone(),
+            ),
         };
 
+        let callback = RepositoryUtils::get_remote_callbacks(
+            repository,
+            &remote_name,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        )?;
+
         let mut fetch_options = FetchOptions::new();
 
         fetch_options.remote_callbacks(callback);
 
-        repository.find_remote("origin")?.fetch(
-            &[branch.name().as_mut().unwrap().unwrap()],
+        repository.find_remote(&remote_name)?.fetch(
+            &[remote_reference_name],
             Some(&mut fetch_options),
             None,
         )?;
@@ -543,349 +1087,2720 @@ impl RepositoryUtils {
         let analysis = repository.merge_analysis(&[&fetch_commit])?;
 
         if analysis.0.is_up_to_date() {
-            Ok(())
+            Ok(false)
         } else if analysis.0.is_fast_forward() {
-            let refname = format!("refs/heads/{}", branch.name().as_mut().unwrap().unwrap());
-            let mut reference = repository.find_reference(&refname)?;
+            // The local changes are kept, unless they are on files changed by the fetched commits:
+            repository.checkout_tree(
+                &repository.find_object(fetch_commit.id(), None)?,
+                Some(git2::build::CheckoutBuilder::default().safe()),
+            )?;
+            let mut reference = repository.find_reference(&branch_reference_name)?;
             reference.set_target(fetch_commit.id(), "Fast-Forward")?;
-            repository.set_head(&refname)?;
-            repository.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+            repository.set_head(&branch_reference_name)?;
+            Ok(false)
+        } else if analysis.0.is_normal() {
+            Ok(true)
         } else {
             Err(git2::Error::from_str(&gettext("_An error has occured")))
         }
     }
 
-    /// Retrieves all branches (name, type, head status) of a repository.
-    pub fn get_all_branches(
+    /// Used to retrieve the strategy used when a pulled branch diverged.
+    /// It is read from the "pull.rebase" entry of the git config, like git does.
+    pub fn get_pull_strategy(repository: &Repository) -> PullStrategy {
+        let config = match repository.config() {
+            Ok(config) => config,
+            Err(_) => return PullStrategy::default(),
+        };
+
+        match config.get_string("pull.rebase") {
+            Ok(value) => match value.to_lowercase().as_str() {
+                "true" | "merges" | "interactive" => PullStrategy::Rebase,
+                _ => PullStrategy::Merge,
+            },
+            Err(_) => PullStrategy::default(),
+        }
+    }
+
+    /// Used to save the strategy used when a pulled branch diverged in the repository's git config.
+    pub fn set_pull_strategy(
         repository: &Repository,
-    ) -> Result<Vec<(String, BranchType, bool)>, git2::Error> {
-        let mut res: Vec<(String, BranchType, bool)> = vec![];
+        strategy: PullStrategy,
+    ) -> Result<(), git2::Error> {
+        let mut config = repository.config()?;
 
-        let branches = repository.branches(None)?;
-        for branch_result in branches {
-            if let Err(_) = branch_result {
+        config.set_bool("pull.rebase", strategy == PullStrategy::Rebase)
+    }
+
+    /// Used to integrate the fetched branch into the checked-out one once they diverged.
+    /// An empty author means that the one of the git config is used.
+    pub fn integrate_fetched_branch(
+        repository: &Repository,
+        strategy: PullStrategy,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        // The local changes would be lost or mixed with the integrated ones:
+        if RepositoryUtils::has_changed_tracked_files(repository)? {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Merge,
+                gettext("_Changed files when pulling"),
+            ));
+        }
+
+        let signature = RepositoryUtils::get_signature(repository, author, author_email)?;
+
+        match strategy {
+            PullStrategy::Merge => RepositoryUtils::merge_fetched_branch(
+                repository,
+                &signature,
+                signing_key,
+                passphrase,
+            ),
+            PullStrategy::Rebase => RepositoryUtils::rebase_on_fetched_branch(
+                repository,
+                &signature,
+                signing_key,
+                passphrase,
+            ),
+        }
+    }
+
+    /// Used to count the commits of the checked-out branch and of the fetched one that are not in the other.
+    pub fn get_ahead_behind_fetched_branch(
+        repository: &Repository,
+    ) -> Result<(usize, usize), git2::Error> {
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+        let fetch_commit = repository.find_reference("FETCH_HEAD")?.peel_to_commit()?;
+
+        return repository.graph_ahead_behind(head_commit.id(), fetch_commit.id());
+    }
+
+    /// Used to check if tracked files are changed in the index or in the working directory.
+    fn has_changed_tracked_files(repository: &Repository) -> Result<bool, git2::Error> {
+        let mut binding = git2::StatusOptions::new();
+        let statuses_options = binding.include_ignored(false).include_untracked(false);
+
+        return Ok(!repository.statuses(Some(statuses_options))?.is_empty());
+    }
+
+    /// Used to merge the fetched branch into the checked-out one with a merge commit.
+    /// When there are conflicts, the repository is left in a merging state.
+    fn merge_fetched_branch(
+        repository: &Repository,
+        signature: &Signature,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        let branch_name = RepositoryUtils::get_current_branch_name(repository)?;
+        let remote_url = repository
+            .find_remote(&RepositoryUtils::get_default_remote_name(repository)?)?
+            .url()
+            .unwrap_or_default()
+            .to_string();
+
+        let message = format!("Merge branch '{}' of {}", branch_name, remote_url);
+
+        let fetch_head = repository.find_reference("FETCH_HEAD")?;
+        let fetch_commit = fetch_head.peel_to_commit()?;
+
+        repository.merge(
+            &[&repository.reference_to_annotated_commit(&fetch_head)?],
+            None,
+            None,
+        )?;
+
+        let mut index = repository.index()?;
+
+        if index.has_conflicts() {
+            // The message is kept to conclude the merge once conflicts are resolved.
+            let _ = std::fs::write(repository.path().join("MERGE_MSG"), &message);
+
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Merge,
+                gettext("_Merge conflicts"),
+            ));
+        }
+
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+
+        let merge_commit_oid = RepositoryUtils::create_commit(
+            repository,
+            signature,
+            signature,
+            &message,
+            &tree,
+            &[&head_commit, &fetch_commit],
+            signing_key,
+            passphrase,
+        )?;
+
+        repository.reference(
+            &format!("refs/heads/{}", branch_name),
+            merge_commit_oid,
+            true,
+            &message,
+        )?;
+        // The merge already updated the index and the working directory.
+        repository.cleanup_state()?;
+
+        return Ok(merge_commit_oid);
+    }
+
+    /// Used to replay the local commits of the checked-out branch on top of the fetched one.
+    /// Merge commits are dropped, like git does by default.
+    /// Nothing is changed when a commit cannot be replayed without conflicts.
+    fn rebase_on_fetched_branch(
+        repository: &Repository,
+        signature: &Signature,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        let branch_name = RepositoryUtils::get_current_branch_name(repository)?;
+        let fetch_commit = repository.find_reference("FETCH_HEAD")?.peel_to_commit()?;
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+
+        let mut revwalk = repository.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        revwalk.push(head_commit.id())?;
+        revwalk.hide(fetch_commit.id())?;
+
+        let mut onto_commit = fetch_commit;
+
+        for oid in revwalk {
+            let commit = repository.find_commit(oid?)?;
+
+            if commit.parent_count() > 1 {
                 continue;
             }
-            let branch_info = branch_result.unwrap();
-            res.push((
-                branch_info.0.name().as_ref().unwrap().unwrap().to_string(),
-                branch_info.1,
-                branch_info.0.is_head(),
-            ))
+
+            let mut index = repository.cherrypick_commit(&commit, &onto_commit, 0, None)?;
+
+            if index.has_conflicts() {
+                return Err(git2::Error::new(
+                    ErrorCode::GenericError,
+                    ErrorClass::Rebase,
+                    gettext("_Rebase conflicts"),
+                ));
+            }
+
+            let tree_oid = index.write_tree_to(repository)?;
+
+            // Commits whose changes are already in the fetched branch are skipped.
+            if tree_oid == onto_commit.tree_id() {
+                continue;
+            }
+
+            let tree = repository.find_tree(tree_oid)?;
+
+            let new_commit_oid = RepositoryUtils::create_commit(
+                repository,
+                &commit.author(),
+                signature,
+                &String::from_utf8_lossy(commit.message_bytes()),
+                &tree,
+                &[&onto_commit],
+                signing_key,
+                passphrase,
+            )?;
+
+            onto_commit = repository.find_commit(new_commit_oid)?;
         }
 
-        Ok(res)
+        // The working directory is updated before the branch moves, as a safe checkout compares it with HEAD:
+        repository.checkout_tree(
+            onto_commit.as_object(),
+            Some(CheckoutBuilder::default().safe()),
+        )?;
+        repository.reference(
+            &format!("refs/heads/{}", branch_name),
+            onto_commit.id(),
+            true,
+            "Rebase",
+        )?;
+
+        return Ok(onto_commit.id());
     }
 
-    /// Used to get branches (name and head status) of a repository.
-    /// If we want to find the remotes ones, it will return only the untracked ones.
-    pub fn get_branches(
+    /// Used to list the commits rebased by an interactive rebase on a base commit, oldest first.
+    /// Merge commits cannot be rebased this way.
+    pub fn get_rebase_steps(
         repository: &Repository,
-        branch_type: BranchType,
-    ) -> Result<Vec<(String, bool)>, git2::Error> {
-        let mut res: Vec<(String, bool)> = vec![];
+        base_commit_id: &str,
+    ) -> Result<Vec<RebaseStep>, git2::Error> {
+        let base_commit = repository.find_commit(Oid::from_str(base_commit_id)?)?;
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+
+        if !repository.graph_descendant_of(head_commit.id(), base_commit.id())? {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Rebase,
+                gettext("_Base commit not in branch"),
+            ));
+        }
 
-        match repository.branches(Some(branch_type)) {
-            Ok(branches) => {
-                for branch_result in branches {
-                    let branch = branch_result.unwrap().0;
+        let mut revwalk = repository.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        revwalk.push(head_commit.id())?;
+        revwalk.hide(base_commit.id())?;
 
-                    if branch_type == BranchType::Remote
-                        && RepositoryUtils::find_tracking_branch(
-                            repository,
-                            branch.name().as_ref().unwrap().unwrap(),
-                        )
-                        .is_some()
-                    {
-                        continue;
-                    }
-                    res.push((
-                        branch.name().as_ref().unwrap().unwrap().to_string(),
-                        branch.is_head(),
-                    ));
+        let mut steps: Vec<RebaseStep> = vec![];
+
+        for oid in revwalk {
+            let commit = repository.find_commit(oid?)?;
+
+            if commit.parent_count() > 1 {
+                return Err(git2::Error::new(
+                    ErrorCode::GenericError,
+                    ErrorClass::Rebase,
+                    gettext("_Cannot rebase merge commits"),
+                ));
+            }
+
+            steps.push(RebaseStep {
+                action: RebaseAction::Pick,
+                commit_id: commit.id().to_string(),
+                message: String::from_utf8_lossy(commit.message_bytes()).to_string(),
+            });
+        }
+
+        return Ok(steps);
+    }
+
+    /// Checks if an interactive rebase is in progress.
+    pub fn is_rebasing(repository: &Repository) -> bool {
+        return repository
+            .path()
+            .join(INTERACTIVE_REBASE_STATE_FILE)
+            .exists();
+    }
+
+    /// Used to read the progress of the interactive rebase in progress.
+    pub fn get_interactive_rebase_state(
+        repository: &Repository,
+    ) -> Result<InteractiveRebaseState, git2::Error> {
+        let state_path = repository.path().join(INTERACTIVE_REBASE_STATE_FILE);
+
+        let text = match std::fs::read_to_string(state_path) {
+            Ok(text) => text,
+            Err(error) => {
+                return Err(git2::Error::new(
+                    ErrorCode::GenericError,
+                    ErrorClass::Os,
+                    error.to_string(),
+                ))
+            }
+        };
+
+        match InteractiveRebaseState::from_text(&text) {
+            Some(state) => Ok(state),
+            None => Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Rebase,
+                gettext("_Invalid rebase state"),
+            )),
+        }
+    }
+
+    /// Used to save the progress of the interactive rebase, or to forget it when there is none.
+    fn save_interactive_rebase_state(
+        repository: &Repository,
+        state: Option<&InteractiveRebaseState>,
+    ) -> Result<(), git2::Error> {
+        let state_path = repository.path().join(INTERACTIVE_REBASE_STATE_FILE);
+
+        let result = match state {
+            Some(state) => std::fs::write(state_path, state.to_text()),
+            None => std::fs::remove_file(state_path),
+        };
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(error) => Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Os,
+                error.to_string(),
+            )),
+        }
+    }
+
+    /// Used to start an interactive rebase of the checked out branch on a base commit.
+    /// The steps are done in the given order, rewritten commits are signed when a signing key is given.
+    /// The rebase stops on conflicts and on commits to edit, and is then continued or aborted.
+    pub fn start_interactive_rebase(
+        repository: &Repository,
+        base_commit_id: &str,
+        steps: Vec<RebaseStep>,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<RebaseStop, git2::Error> {
+        if RepositoryUtils::is_rebasing(repository) {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Rebase,
+                gettext("_Rebase already in progress"),
+            ));
+        }
+
+        if RepositoryUtils::has_changed_tracked_files(repository)? {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Rebase,
+                gettext("_Changed files when rebasing"),
+            ));
+        }
+
+        // Commits can only be melded into a previous commit of the rebase:
+        if let Some(first_step) = steps.iter().find(|step| step.action != RebaseAction::Drop) {
+            if first_step.action.is_melding() {
+                return Err(git2::Error::new(
+                    ErrorCode::GenericError,
+                    ErrorClass::Rebase,
+                    gettext("_Cannot meld first commit"),
+                ));
+            }
+        }
+
+        let signature = RepositoryUtils::get_signature(repository, author, author_email)?;
+        let base_commit = repository.find_commit(Oid::from_str(base_commit_id)?)?;
+
+        let state = InteractiveRebaseState {
+            branch_name: RepositoryUtils::get_current_branch_name(repository)?,
+            original_head_id: RepositoryUtils::find_last_commit(repository)?
+                .id()
+                .to_string(),
+            stopped_step: None,
+            remaining_steps: steps,
+        };
+        RepositoryUtils::save_interactive_rebase_state(repository, Some(&state))?;
+
+        // The rebased commits are created on a detached HEAD, the branch is only moved at the end:
+        repository.set_head_detached(base_commit.id())?;
+        repository.checkout_head(Some(CheckoutBuilder::default().force()))?;
+
+        return RepositoryUtils::run_rebase_steps(
+            repository,
+            state,
+            &signature,
+            signing_key,
+            passphrase,
+        );
+    }
+
+    /// Used to continue the interactive rebase once conflicts are resolved or the commit is edited.
+    /// Changes made while editing a commit are added to it.
+    pub fn continue_interactive_rebase(
+        repository: &Repository,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<RebaseStop, git2::Error> {
+        let mut state = RepositoryUtils::get_interactive_rebase_state(repository)?;
+        let signature = RepositoryUtils::get_signature(repository, author, author_email)?;
+
+        match state.stopped_step.take() {
+            Some((step, true)) => {
+                RepositoryUtils::commit_rebase_step(
+                    repository,
+                    &step,
+                    &signature,
+                    signing_key,
+                    passphrase,
+                )?;
+
+                // A commit to edit is only created once its conflicts are resolved:
+                if step.action == RebaseAction::Edit {
+                    state.stopped_step = Some((step.clone(), false));
+                    RepositoryUtils::save_interactive_rebase_state(repository, Some(&state))?;
+
+                    return Ok(RebaseStop::Edit(step));
                 }
-                return Ok(res);
             }
-            Err(error) => return Err(error),
+            Some((_, false)) => RepositoryUtils::amend_edited_commit(
+                repository,
+                &signature,
+                signing_key,
+                passphrase,
+            )?,
+            None => {}
         }
+
+        RepositoryUtils::save_interactive_rebase_state(repository, Some(&state))?;
+
+        return RepositoryUtils::run_rebase_steps(
+            repository,
+            state,
+            &signature,
+            signing_key,
+            passphrase,
+        );
     }
 
-    /// Used to get the current branch.
-    pub fn get_current_branch_name(repository: &Repository) -> Result<String, git2::Error> {
-        let head = repository.head()?;
-        Ok(head.shorthand().unwrap().to_string())
+    /// Used to abort the interactive rebase and bring the branch back to its state before the rebase.
+    pub fn abort_interactive_rebase(repository: &Repository) -> Result<(), git2::Error> {
+        let state = RepositoryUtils::get_interactive_rebase_state(repository)?;
+
+        // A step stopped on conflicts is still in progress for git:
+        if let Ok(mut rebase) = repository.open_rebase(None) {
+            rebase.abort()?;
+        }
+
+        let original_head = repository.find_commit(Oid::from_str(&state.original_head_id)?)?;
+        repository.reference(
+            &format!("refs/heads/{}", state.branch_name),
+            original_head.id(),
+            true,
+            "Abort interactive rebase",
+        )?;
+        repository.set_head(&format!("refs/heads/{}", state.branch_name))?;
+        repository.reset(original_head.as_object(), git2::ResetType::Hard, None)?;
+
+        return RepositoryUtils::save_interactive_rebase_state(repository, None);
+    }
+
+    /// Used to do the remaining steps of an interactive rebase, until it stops or finishes.
+    fn run_rebase_steps(
+        repository: &Repository,
+        mut state: InteractiveRebaseState,
+        signature: &Signature,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<RebaseStop, git2::Error> {
+        while !state.remaining_steps.is_empty() {
+            let step = state.remaining_steps.remove(0);
+
+            if step.action == RebaseAction::Drop {
+                continue;
+            }
+
+            let has_conflicts = RepositoryUtils::apply_rebase_step(repository, &step)?;
+
+            if has_conflicts {
+                state.stopped_step = Some((step.clone(), true));
+                RepositoryUtils::save_interactive_rebase_state(repository, Some(&state))?;
+
+                return Ok(RebaseStop::Conflicts(step));
+            }
+
+            if let Err(error) = RepositoryUtils::commit_rebase_step(
+                repository,
+                &step,
+                signature,
+                signing_key,
+                passphrase,
+            ) {
+                // The applied step is kept to be committed again when continuing the rebase:
+                state.stopped_step = Some((step, true));
+                RepositoryUtils::save_interactive_rebase_state(repository, Some(&state))?;
+
+                return Err(error);
+            }
+
+            if step.action == RebaseAction::Edit {
+                state.stopped_step = Some((step.clone(), false));
+                RepositoryUtils::save_interactive_rebase_state(repository, Some(&state))?;
+
+                return Ok(RebaseStop::Edit(step));
+            }
+
+            RepositoryUtils::save_interactive_rebase_state(repository, Some(&state))?;
+        }
+
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+        repository.reference(
+            &format!("refs/heads/{}", state.branch_name),
+            head_commit.id(),
+            true,
+            "Interactive rebase",
+        )?;
+        repository.set_head(&format!("refs/heads/{}", state.branch_name))?;
+        RepositoryUtils::save_interactive_rebase_state(repository, None)?;
+
+        return Ok(RebaseStop::Finished);
+    }
+
+    /// Used to apply the changes of the commit of a step on HEAD, with git's rebase.
+    /// Returns whether there are conflicts, the rebase of the step then staying in progress.
+    fn apply_rebase_step(repository: &Repository, step: &RebaseStep) -> Result<bool, git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(&step.commit_id)?)?;
+        let parent_commit = commit.parent(0)?;
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+
+        let branch = repository.find_annotated_commit(commit.id())?;
+        let upstream = repository.find_annotated_commit(parent_commit.id())?;
+        let onto = repository.find_annotated_commit(head_commit.id())?;
+
+        let mut rebase = repository.rebase(Some(&branch), Some(&upstream), Some(&onto), None)?;
+
+        if let Some(operation) = rebase.next() {
+            operation?;
+        }
+
+        let mut index = repository.index()?;
+        index.read(false)?;
+
+        return Ok(index.has_conflicts());
+    }
+
+    /// Used to create the commit of a step from the index, and end the rebase of the step.
+    /// A melded commit replaces HEAD, a commit without changes is skipped.
+    fn commit_rebase_step(
+        repository: &Repository,
+        step: &RebaseStep,
+        signature: &Signature,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<(), git2::Error> {
+        let mut index = repository.index()?;
+        index.read(false)?;
+
+        if index.has_conflicts() {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Rebase,
+                gettext("_Unresolved conflicts"),
+            ));
+        }
+
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+        let commit = repository.find_commit(Oid::from_str(&step.commit_id)?)?;
+
+        let commit_message = if step.action == RebaseAction::Reword {
+            step.message.clone()
+        } else {
+            String::from_utf8_lossy(commit.message_bytes()).to_string()
+        };
+
+        let new_commit_oid = if step.action.is_melding() {
+            let head_message = String::from_utf8_lossy(head_commit.message_bytes()).to_string();
+            let message = if step.action == RebaseAction::Squash {
+                format!("{}\n\n{}", head_message.trim_end(), commit_message)
+            } else {
+                head_message
+            };
+            let parents: Vec<Commit> = head_commit.parents().collect();
+
+            Some(RepositoryUtils::create_commit(
+                repository,
+                &head_commit.author(),
+                signature,
+                &message,
+                &tree,
+                &parents.iter().collect::<Vec<&Commit>>(),
+                signing_key,
+                passphrase,
+            )?)
+        } else if tree.id() == head_commit.tree_id() {
+            // The changes of the commit are already in HEAD:
+            None
+        } else {
+            Some(RepositoryUtils::create_commit(
+                repository,
+                &commit.author(),
+                signature,
+                &commit_message,
+                &tree,
+                &[&head_commit],
+                signing_key,
+                passphrase,
+            )?)
+        };
+
+        if let Some(new_commit_oid) = new_commit_oid {
+            repository.set_head_detached(new_commit_oid)?;
+        }
+
+        if let Ok(mut rebase) = repository.open_rebase(None) {
+            rebase.finish(None)?;
+        }
+        repository.checkout_head(Some(CheckoutBuilder::default().force()))?;
+
+        Ok(())
+    }
+
+    /// Used to add the changes made while a rebase stopped on a commit to edit to this commit.
+    /// Only the tracked files are added, untracked ones are left out as with `git commit --amend -a`.
+    fn amend_edited_commit(
+        repository: &Repository,
+        signature: &Signature,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<(), git2::Error> {
+        if !RepositoryUtils::has_changed_tracked_files(repository)? {
+            return Ok(());
+        }
+
+        let mut index = repository.index()?;
+        index.update_all(["*"].iter(), None)?;
+        index.write()?;
+
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+        let parents: Vec<Commit> = head_commit.parents().collect();
+
+        let new_commit_oid = RepositoryUtils::create_commit(
+            repository,
+            &head_commit.author(),
+            signature,
+            &String::from_utf8_lossy(head_commit.message_bytes()),
+            &tree,
+            &parents.iter().collect::<Vec<&Commit>>(),
+            signing_key,
+            passphrase,
+        )?;
+
+        return repository.set_head_detached(new_commit_oid);
+    }
+
+    /// Used to create a commit without updating any reference.
+    /// The commit is signed when a signing key is given.
+    pub fn create_commit(
+        repository: &Repository,
+        author: &Signature,
+        committer: &Signature,
+        message: &str,
+        tree: &Tree,
+        parents: &[&Commit],
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        if signing_key.is_empty() {
+            return repository.commit(None, author, committer, message, tree, parents);
+        }
+
+        let buffer = repository.commit_create_buffer(author, committer, message, tree, parents)?;
+        let commit_as_str = String::from_utf8_lossy(&buffer).to_string();
+
+        match GpgUtils::sign_commit_string_with_passphrase(&commit_as_str, signing_key, passphrase)
+        {
+            Ok(signature) => repository.commit_signed(&commit_as_str, &signature, None),
+            Err(error) => Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Invalid,
+                error,
+            )),
+        }
+    }
+
+    /// Used to check if a merge is in progress.
+    pub fn is_merging(repository: &Repository) -> bool {
+        return repository.state() == RepositoryState::Merge;
+    }
+
+    /// Used to check if the conflicts of a merge, or of a step of an interactive rebase, are being resolved.
+    pub fn is_resolving_conflicts(repository: &Repository) -> bool {
+        return RepositoryUtils::is_merging(repository)
+            || (RepositoryUtils::is_rebasing(repository)
+                && repository.state() == RepositoryState::RebaseMerge);
+    }
+
+    /// Retrieves the conflicted files of the index with the content of each of their versions.
+    pub fn get_merge_conflicts(repository: &Repository) -> Result<Vec<MergeConflict>, git2::Error> {
+        let workdir = RepositoryUtils::get_workdir_of_repository(repository)?;
+        let mut index = repository.index()?;
+        // The repository may be kept open while the index changes on disk:
+        index.read(false)?;
+
+        let mut merge_conflicts: Vec<MergeConflict> = Vec::new();
+
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+
+            let path = match conflict
+                .our
+                .as_ref()
+                .or(conflict.their.as_ref())
+                .or(conflict.ancestor.as_ref())
+            {
+                Some(entry) => String::from_utf8_lossy(&entry.path).to_string(),
+                None => continue,
+            };
+
+            let working_content = std::fs::read(Path::new(&workdir).join(&path)).ok();
+
+            merge_conflicts.push(MergeConflict {
+                path,
+                working_content,
+                base_content: RepositoryUtils::get_content_of_index_entry(
+                    repository,
+                    conflict.ancestor.as_ref(),
+                )?,
+                our_content: RepositoryUtils::get_content_of_index_entry(
+                    repository,
+                    conflict.our.as_ref(),
+                )?,
+                their_content: RepositoryUtils::get_content_of_index_entry(
+                    repository,
+                    conflict.their.as_ref(),
+                )?,
+            });
+        }
+
+        return Ok(merge_conflicts);
+    }
+
+    /// Retrieves the content of the blob of an index entry.
+    fn get_content_of_index_entry(
+        repository: &Repository,
+        entry: Option<&IndexEntry>,
+    ) -> Result<Option<Vec<u8>>, git2::Error> {
+        match entry {
+            Some(entry) => {
+                let blob = repository.find_blob(entry.id)?;
+                Ok(Some(blob.content().to_vec()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Used to mark a conflicted file as resolved with one of its versions, kept byte for byte.
+    /// The file is deleted when it does not exist in this version.
+    pub fn resolve_merge_conflict_with_side(
+        repository: &Repository,
+        relative_path: &str,
+        side: ConflictSide,
+    ) -> Result<(), git2::Error> {
+        let conflict = RepositoryUtils::get_merge_conflicts(repository)?
+            .into_iter()
+            .find(|conflict| conflict.path == relative_path);
+
+        match conflict {
+            Some(conflict) => RepositoryUtils::resolve_merge_conflict(
+                repository,
+                relative_path,
+                conflict.get_content_of_side(side).as_deref(),
+            ),
+            None => Err(git2::Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Merge,
+                gettext("_File not conflicted"),
+            )),
+        }
+    }
+
+    /// Used to mark a conflicted file as resolved with its final content.
+    /// Without content, the file is deleted.
+    pub fn resolve_merge_conflict(
+        repository: &Repository,
+        relative_path: &str,
+        content: Option<&[u8]>,
+    ) -> Result<(), git2::Error> {
+        let workdir = RepositoryUtils::get_workdir_of_repository(repository)?;
+        let file_path = Path::new(&workdir).join(relative_path);
+
+        let mut index = repository.index()?;
+
+        match content {
+            Some(content) => {
+                if let Err(error) = std::fs::write(&file_path, content) {
+                    return Err(git2::Error::new(
+                        ErrorCode::GenericError,
+                        ErrorClass::Os,
+                        error.to_string(),
+                    ));
+                }
+                index.add_path(Path::new(relative_path))?;
+            }
+            None => {
+                if file_path.exists() {
+                    if let Err(error) = std::fs::remove_file(&file_path) {
+                        return Err(git2::Error::new(
+                            ErrorCode::GenericError,
+                            ErrorClass::Os,
+                            error.to_string(),
+                        ));
+                    }
+                }
+                index.remove_path(Path::new(relative_path))?;
+            }
+        }
+
+        index.write()
+    }
+
+    /// Used to conclude a merge once all conflicts are resolved, creating the merge commit.
+    /// An empty author means that the one of the git config is used.
+    pub fn conclude_merge(
+        repository: &Repository,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        let mut index = repository.index()?;
+        index.read(false)?;
+
+        if index.has_conflicts() {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Merge,
+                gettext("_Unresolved conflicts"),
+            ));
+        }
+
+        let merge_heads = match std::fs::read_to_string(repository.path().join("MERGE_HEAD")) {
+            Ok(merge_heads) => merge_heads,
+            Err(error) => {
+                return Err(git2::Error::new(
+                    ErrorCode::GenericError,
+                    ErrorClass::Merge,
+                    error.to_string(),
+                ))
+            }
+        };
+
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+        let mut parents: Vec<Commit> = vec![head_commit];
+        for merge_head in merge_heads.lines().filter(|line| !line.trim().is_empty()) {
+            parents.push(repository.find_commit(Oid::from_str(merge_head.trim())?)?);
+        }
+
+        let message = match repository.message() {
+            Ok(message) => message,
+            Err(_) => String::from("Merge"),
+        };
+
+        let signature = RepositoryUtils::get_signature(repository, author, author_email)?;
+        let tree = repository.find_tree(index.write_tree()?)?;
+
+        let merge_commit_oid = RepositoryUtils::create_commit(
+            repository,
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            &parents.iter().collect::<Vec<&Commit>>(),
+            signing_key,
+            passphrase,
+        )?;
+
+        RepositoryUtils::move_head_to_commit(repository, merge_commit_oid, &message)?;
+        repository.cleanup_state()?;
+
+        return Ok(merge_commit_oid);
+    }
+
+    /// Used to abort a merge and restore the state of the repository before it.
+    /// Like git, only the files changed by the merge are restored, the other changes are kept.
+    pub fn abort_merge(repository: &Repository) -> Result<(), git2::Error> {
+        let head_tree = RepositoryUtils::find_last_commit(repository)?.tree()?;
+        let mut index = repository.index()?;
+        index.read(false)?;
+
+        let mut merged_paths: Vec<String> = RepositoryUtils::get_merge_conflicts(repository)?
+            .into_iter()
+            .map(|conflict| conflict.path)
+            .collect();
+
+        for delta in repository
+            .diff_tree_to_index(Some(&head_tree), Some(&index), None)?
+            .deltas()
+        {
+            if let Some(path) = delta.new_file().path().or(delta.old_file().path()) {
+                merged_paths.push(path.to_string_lossy().to_string());
+            }
+        }
+
+        if !merged_paths.is_empty() {
+            let mut checkout_builder = CheckoutBuilder::new();
+            checkout_builder.force();
+            for path in &merged_paths {
+                checkout_builder.path(path);
+            }
+
+            repository.checkout_tree(head_tree.as_object(), Some(&mut checkout_builder))?;
+        }
+
+        repository.cleanup_state()
+    }
+
+    /// Used to stash the changes of the working directory.
+    /// When file paths are given, only these files are stashed.
+    /// An empty author means that the one of the git config is used.
+    pub fn save_stash(
+        repository: &mut Repository,
+        message: &str,
+        include_untracked: bool,
+        file_paths: &Vec<String>,
+        author: &str,
+        author_email: &str,
+    ) -> Result<Oid, git2::Error> {
+        let signature = RepositoryUtils::get_signature(repository, author, author_email)?;
+
+        let flags = if include_untracked {
+            StashFlags::INCLUDE_UNTRACKED
+        } else {
+            StashFlags::DEFAULT
+        };
+
+        if file_paths.is_empty() {
+            let message = if message.is_empty() {
+                None
+            } else {
+                Some(message)
+            };
+            return repository.stash_save2(&signature, message, Some(flags));
+        }
+
+        return RepositoryUtils::save_stash_of_files(
+            repository,
+            &signature,
+            message,
+            include_untracked,
+            file_paths,
+        );
+    }
+
+    /**
+     * Used to stash only some files.
+     * The stash commits are built the same way git does: a commit for the index,
+     * an optional one for the untracked files and the stash commit holding the working directory.
+     * The stashed files are then restored to their state in HEAD.
+     */
+    fn save_stash_of_files(
+        repository: &Repository,
+        signature: &Signature,
+        message: &str,
+        include_untracked: bool,
+        file_paths: &Vec<String>,
+    ) -> Result<Oid, git2::Error> {
+        let workdir = RepositoryUtils::get_workdir_of_repository(repository)?;
+        // Like git, a stash made on a detached HEAD is described as made on no branch:
+        let branch_name = if RepositoryUtils::is_head_detached(repository) {
+            String::from("(no branch)")
+        } else {
+            RepositoryUtils::get_current_branch_name(repository)?
+        };
+        let head_commit = repository.head()?.peel_to_commit()?;
+        let head_tree = head_commit.tree()?;
+        let head_description = format!(
+            "{} {}",
+            &head_commit.id().to_string()[..7],
+            head_commit.summary().unwrap_or("")
+        );
+
+        let index = repository.index()?;
+        let mut stashed_index = Index::new()?;
+        stashed_index.read_tree(&head_tree)?;
+        let mut stashed_workdir = Index::new()?;
+        stashed_workdir.read_tree(&head_tree)?;
+        let mut stashed_untracked = Index::new()?;
+
+        let mut tracked_paths: Vec<&str> = Vec::new();
+        let mut untracked_paths: Vec<&str> = Vec::new();
+
+        for file_path in file_paths {
+            let path = Path::new(file_path);
+            let status = repository.status_file(path)?;
+
+            if status.is_wt_new() {
+                if include_untracked {
+                    let full_path = format!("{}/{}", workdir, file_path);
+                    let (blob_oid, mode) =
+                        RepositoryUtils::create_blob_of_file(repository, Path::new(&full_path))?;
+                    let mut entry = RepositoryUtils::build_new_index_entry(file_path, mode);
+                    entry.id = blob_oid;
+                    stashed_untracked.add(&entry)?;
+                    untracked_paths.push(file_path);
+                }
+                continue;
+            }
+
+            match index.get_path(path, 0) {
+                Some(entry) => stashed_index.add(&entry)?,
+                None => {
+                    if stashed_index.get_path(path, 0).is_some() {
+                        stashed_index.remove_path(path)?;
+                    }
+                }
+            }
+
+            if status.is_wt_deleted() || (status.is_index_deleted() && !status.is_wt_new()) {
+                if stashed_workdir.get_path(path, 0).is_some() {
+                    stashed_workdir.remove_path(path)?;
+                }
+            } else {
+                let full_path = format!("{}/{}", workdir, file_path);
+                let (blob_oid, mode) =
+                    RepositoryUtils::create_blob_of_file(repository, Path::new(&full_path))?;
+                let mut entry = RepositoryUtils::build_new_index_entry(file_path, mode);
+                entry.id = blob_oid;
+                stashed_workdir.add(&entry)?;
+            }
+            tracked_paths.push(file_path);
+        }
+
+        if tracked_paths.is_empty() && untracked_paths.is_empty() {
+            return Err(git2::Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Stash,
+                gettext("_No changes to stash"),
+            ));
+        }
+
+        let index_tree = repository.find_tree(stashed_index.write_tree_to(repository)?)?;
+        let index_commit = repository.find_commit(repository.commit(
+            None,
+            signature,
+            signature,
+            &format!("index on {}: {}", branch_name, head_description),
+            &index_tree,
+            &[&head_commit],
+        )?)?;
+
+        let mut parents = vec![&head_commit, &index_commit];
+        let untracked_commit;
+        if !untracked_paths.is_empty() {
+            let untracked_tree =
+                repository.find_tree(stashed_untracked.write_tree_to(repository)?)?;
+            untracked_commit = repository.find_commit(repository.commit(
+                None,
+                signature,
+                signature,
+                &format!("untracked files on {}: {}", branch_name, head_description),
+                &untracked_tree,
+                &[],
+            )?)?;
+            parents.push(&untracked_commit);
+        }
+
+        let stash_message = if message.is_empty() {
+            format!("WIP on {}: {}", branch_name, head_description)
+        } else {
+            format!("On {}: {}", branch_name, message)
+        };
+
+        let workdir_tree = repository.find_tree(stashed_workdir.write_tree_to(repository)?)?;
+        let stash_oid = repository.commit(
+            None,
+            signature,
+            signature,
+            &stash_message,
+            &workdir_tree,
+            &parents,
+        )?;
+
+        repository.reference_ensure_log("refs/stash")?;
+        repository.reference("refs/stash", stash_oid, true, &stash_message)?;
+
+        // The stashed files are now restored to their state in HEAD:
+        if !tracked_paths.is_empty() {
+            repository.reset_default(Some(head_commit.as_object()), tracked_paths.iter())?;
+
+            let mut checkout_builder = CheckoutBuilder::new();
+            checkout_builder.force();
+            for file_path in &tracked_paths {
+                checkout_builder.path(file_path);
+            }
+            repository.checkout_head(Some(&mut checkout_builder))?;
+        }
+
+        for file_path in tracked_paths.iter().chain(untracked_paths.iter()) {
+            if head_tree.get_path(Path::new(file_path)).is_err() {
+                let _ = std::fs::remove_file(format!("{}/{}", workdir, file_path));
+            }
+        }
+
+        return Ok(stash_oid);
+    }
+
+    /// Retrieves the stashed states, from the most recent one.
+    pub fn get_stashes(repository: &mut Repository) -> Result<Vec<StashEntry>, git2::Error> {
+        let mut stashes: Vec<StashEntry> = Vec::new();
+
+        repository.stash_foreach(|index, message, stash_id| {
+            stashes.push(StashEntry {
+                index,
+                message: message.to_string(),
+                stash_id: stash_id.to_string(),
+            });
+            true
+        })?;
+
+        return Ok(stashes);
+    }
+
+    /// Used to apply a stashed state without removing it from the stash list.
+    pub fn apply_stash(repository: &mut Repository, index: usize) -> Result<(), git2::Error> {
+        repository.stash_apply(index, None)
+    }
+
+    /// Used to apply a stashed state and remove it from the stash list.
+    pub fn pop_stash(repository: &mut Repository, index: usize) -> Result<(), git2::Error> {
+        repository.stash_pop(index, None)
+    }
+
+    /// Used to remove a stashed state from the stash list.
+    pub fn drop_stash(repository: &mut Repository, index: usize) -> Result<(), git2::Error> {
+        repository.stash_drop(index)
+    }
+
+    /// Retrieves the files changed by a stash.
+    /// Stashed untracked files are stored in a third parent of the stash commit.
+    pub fn get_stash_changed_files(
+        repository: &Repository,
+        stash_id: &str,
+    ) -> Result<Vec<(String, Delta)>, git2::Error> {
+        let stash_commit = repository.find_commit(Oid::from_str(stash_id)?)?;
+
+        let mut diffs = vec![repository.diff_tree_to_tree(
+            Some(&stash_commit.parent(0)?.tree()?),
+            Some(&stash_commit.tree()?),
+            None,
+        )?];
+
+        if stash_commit.parent_count() > 2 {
+            diffs.push(repository.diff_tree_to_tree(
+                None,
+                Some(&stash_commit.parent(2)?.tree()?),
+                None,
+            )?);
+        }
+
+        let mut changed_files: Vec<(String, Delta)> = Vec::new();
+        for diff in diffs {
+            for delta in diff.deltas() {
+                let path = match delta.new_file().path().or(delta.old_file().path()) {
+                    Some(path) => path.to_string_lossy().to_string(),
+                    None => continue,
+                };
+                changed_files.push((path, delta.status()));
+            }
+        }
+
+        return Ok(changed_files);
+    }
+
+    /// Retrieves the differences a stash brings to a file.
+    pub fn get_diff_of_file_in_stash(
+        repository: &Repository,
+        stash_id: &str,
+        relative_path: &str,
+    ) -> Result<FileDiff, git2::Error> {
+        let stash_commit = repository.find_commit(Oid::from_str(stash_id)?)?;
+
+        let mut diff_options = DiffOptions::new();
+        diff_options
+            .pathspec(relative_path)
+            .disable_pathspec_match(true);
+
+        let diff = repository.diff_tree_to_tree(
+            Some(&stash_commit.parent(0)?.tree()?),
+            Some(&stash_commit.tree()?),
+            Some(&mut diff_options),
+        )?;
+
+        // The file may be a stashed untracked file:
+        if diff.deltas().len() == 0 && stash_commit.parent_count() > 2 {
+            let untracked_diff = repository.diff_tree_to_tree(
+                None,
+                Some(&stash_commit.parent(2)?.tree()?),
+                Some(&mut diff_options),
+            )?;
+            return FileDiff::from_diff(&untracked_diff);
+        }
+
+        return FileDiff::from_diff(&diff);
+    }
+
+    /// Retrieves the tags of the repository, sorted by name.
+    pub fn get_all_tags(repository: &Repository) -> Result<Vec<TagEntry>, git2::Error> {
+        let mut tags: Vec<TagEntry> = Vec::new();
+
+        for tag_name in repository.tag_names(None)?.iter().flatten() {
+            let reference = repository.find_reference(&format!("refs/tags/{}", tag_name))?;
+            let commit = match reference.peel_to_commit() {
+                Ok(commit) => commit,
+                // Tags that do not point to a commit are ignored:
+                Err(_) => continue,
+            };
+
+            let (message, is_annotated) = match reference.peel_to_tag() {
+                Ok(tag) => {
+                    // The signature of a signed tag is part of its message:
+                    let message = tag.message().unwrap_or("");
+                    let message = match message.split_once("-----BEGIN PGP SIGNATURE-----") {
+                        Some((message, _signature)) => message,
+                        None => message,
+                    };
+                    (message.trim().to_string(), true)
+                }
+                Err(_) => (String::new(), false),
+            };
+
+            tags.push(TagEntry {
+                name: tag_name.to_string(),
+                commit_id: commit.id().to_string(),
+                message,
+                is_annotated,
+            });
+        }
+
+        tags.sort_by(|first, second| first.name.cmp(&second.name));
+
+        return Ok(tags);
+    }
+
+    /// Used to create a lightweight tag on a commit.
+    pub fn create_lightweight_tag(
+        repository: &Repository,
+        tag_name: &str,
+        commit_id: &str,
+    ) -> Result<Oid, git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(commit_id)?)?;
+
+        return repository.tag_lightweight(tag_name, commit.as_object(), false);
+    }
+
+    /// Used to create an annotated tag on a commit.
+    /// The tag is signed when a signing key is given.
+    pub fn create_annotated_tag(
+        repository: &Repository,
+        tag_name: &str,
+        commit_id: &str,
+        message: &str,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(commit_id)?)?;
+        let tagger = RepositoryUtils::get_signature(repository, author, author_email)?;
+
+        if signing_key.is_empty() {
+            return repository.tag(tag_name, commit.as_object(), &tagger, message, false);
+        }
+
+        let tag_reference_name = format!("refs/tags/{}", tag_name);
+        if repository.find_reference(&tag_reference_name).is_ok() {
+            return Err(git2::Error::new(
+                ErrorCode::Exists,
+                ErrorClass::Tag,
+                gettext("_Tag already exists"),
+            ));
+        }
+
+        // libgit2 cannot sign tags, so the tag object is written the same way git does:
+        let offset = tagger.when().offset_minutes();
+        let mut tag_content = format!(
+            "object {}\ntype commit\ntag {}\ntagger {} <{}> {} {}{:02}{:02}\n\n{}\n",
+            commit.id(),
+            tag_name,
+            tagger.name().unwrap_or(""),
+            tagger.email().unwrap_or(""),
+            tagger.when().seconds(),
+            if offset < 0 { '-' } else { '+' },
+            offset.abs() / 60,
+            offset.abs() % 60,
+            message.trim_end()
+        );
+
+        match GpgUtils::sign_commit_string_with_passphrase(&tag_content, signing_key, passphrase) {
+            Ok(signature) => tag_content.push_str(&signature),
+            Err(error) => {
+                return Err(git2::Error::new(
+                    ErrorCode::GenericError,
+                    ErrorClass::Invalid,
+                    error,
+                ))
+            }
+        };
+
+        let tag_oid = repository
+            .odb()?
+            .write(ObjectType::Tag, tag_content.as_bytes())?;
+        repository.reference(
+            &tag_reference_name,
+            tag_oid,
+            false,
+            &format!("tag: {}", tag_name),
+        )?;
+
+        return Ok(tag_oid);
+    }
+
+    /// Used to delete a tag of the repository.
+    pub fn delete_local_tag(repository: &Repository, tag_name: &str) -> Result<(), git2::Error> {
+        return repository.tag_delete(tag_name);
+    }
+
+    /// Used to push a tag to a remote.
+    /// All tags are pushed when the tag name is empty.
+    pub fn push_tags(
+        repository: &Repository,
+        remote_name: &str,
+        tag_name: &str,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) -> Result<(), git2::Error> {
+        let tag_names: Vec<String> = if tag_name.is_empty() {
+            repository
+                .tag_names(None)?
+                .iter()
+                .flatten()
+                .map(|name| name.to_string())
+                .collect()
+        } else {
+            vec![tag_name.to_string()]
+        };
+
+        let refspecs: Vec<String> = tag_names
+            .iter()
+            .map(|name| format!("refs/tags/{}:refs/tags/{}", name, name))
+            .collect();
+
+        return RepositoryUtils::push_refspecs(
+            repository,
+            remote_name,
+            &refspecs,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        );
+    }
+
+    /// Used to delete a tag from a remote.
+    pub fn delete_remote_tag(
+        repository: &Repository,
+        remote_name: &str,
+        tag_name: &str,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) -> Result<(), git2::Error> {
+        return RepositoryUtils::push_refspecs(
+            repository,
+            remote_name,
+            &vec![format!(":refs/tags/{}", tag_name)],
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        );
+    }
+
+    /// Used to push refspecs to a remote.
+    /// An empty remote name means that the default remote is used.
+    fn push_refspecs(
+        repository: &Repository,
+        remote_name: &str,
+        refspecs: &Vec<String>,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) -> Result<(), git2::Error> {
+        let remote_name = RepositoryUtils::resolve_remote_name(repository, remote_name)?;
+        let mut remote = repository.find_remote(&remote_name)?;
+
+        let callback = RepositoryUtils::get_remote_callbacks(
+            repository,
+            &remote_name,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        )?;
+
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(callback);
+
+        return remote.push(refspecs, Some(&mut push_options));
+    }
+
+    /// Retrieves the signature to use for new commits.
+    /// An empty author means that the one of the git config is used.
+    fn get_signature<'a>(
+        repository: &Repository,
+        author: &str,
+        author_email: &str,
+    ) -> Result<Signature<'a>, git2::Error> {
+        if author.is_empty() {
+            repository.signature()
+        } else {
+            Signature::now(author, author_email)
+        }
+    }
+
+    /// Retrieves all branches (name, type, head status) of a repository.
+    pub fn get_all_branches(
+        repository: &Repository,
+    ) -> Result<Vec<(String, BranchType, bool)>, git2::Error> {
+        let mut res: Vec<(String, BranchType, bool)> = vec![];
+
+        let branches = repository.branches(None)?;
+        for branch_result in branches {
+            if let Err(_) = branch_result {
+                continue;
+            }
+            let branch_info = branch_result.unwrap();
+            res.push((
+                branch_info.0.name().as_ref().unwrap().unwrap().to_string(),
+                branch_info.1,
+                branch_info.0.is_head(),
+            ))
+        }
+
+        Ok(res)
+    }
+
+    /// Used to get branches (name and head status) of a repository.
+    /// If we want to find the remotes ones, it will return only the untracked ones.
+    pub fn get_branches(
+        repository: &Repository,
+        branch_type: BranchType,
+    ) -> Result<Vec<(String, bool)>, git2::Error> {
+        let mut res: Vec<(String, bool)> = vec![];
+
+        match repository.branches(Some(branch_type)) {
+            Ok(branches) => {
+                for branch_result in branches {
+                    let branch = branch_result.unwrap().0;
+
+                    if branch_type == BranchType::Remote
+                        && RepositoryUtils::find_tracking_branch(
+                            repository,
+                            branch.name().as_ref().unwrap().unwrap(),
+                        )
+                        .is_some()
+                    {
+                        continue;
+                    }
+                    res.push((
+                        branch.name().as_ref().unwrap().unwrap().to_string(),
+                        branch.is_head(),
+                    ));
+                }
+                return Ok(res);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    /// Used to get the current branch.
+    /// Fails when HEAD is detached, as no branch is checked out then.
+    pub fn get_current_branch_name(repository: &Repository) -> Result<String, git2::Error> {
+        // Without any commit, HEAD points to a branch that does not exist yet:
+        if RepositoryUtils::is_head_unborn(repository) {
+            if let Some(branch_reference_name) =
+                repository.find_reference("HEAD")?.symbolic_target()
+            {
+                return Ok(branch_reference_name
+                    .trim_start_matches("refs/heads/")
+                    .to_string());
+            }
+        }
+
+        if RepositoryUtils::is_head_detached(repository) {
+            return Err(git2::Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Reference,
+                gettext("_No branch is checked out"),
+            ));
+        }
+
+        let head = repository.head()?;
+        Ok(head.shorthand().unwrap().to_string())
+    }
+
+    /// Used to find a branch tracking a remote one.
+    pub fn find_tracking_branch(
+        repository: &Repository,
+        remote_branch_name: &str,
+    ) -> Option<String> {
+        match repository.branches(Some(BranchType::Local)) {
+            Ok(branches) => {
+                for branch_result in branches {
+                    let branch = branch_result.unwrap().0;
+
+                    let upstream_branch = match branch.upstream() {
+                        Ok(branch) => Some(branch),
+                        Err(_) => None,
+                    };
+
+                    match upstream_branch {
+                        Some(upstream) => {
+                            if upstream.name().unwrap().unwrap() == remote_branch_name {
+                                return Some(branch.name().unwrap().unwrap().to_string());
+                            }
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Err(_) => return None,
+        }
+        None
+    }
+
+    /// Used to create a new branch that tracks a remote branch.
+    pub fn track_remote_branch(
+        repository: &Repository,
+        remote_branch_name: &str,
+        local_branch_name: &str,
+    ) -> Result<(), git2::Error> {
+        let mut remote_branches = repository.branches(Some(BranchType::Remote))?;
+
+        // The tracked remote branch.
+        let tracked_branch = remote_branches.find(|branch| match branch {
+            Ok(element) => element.0.name().unwrap().unwrap() == remote_branch_name,
+            Err(_) => false,
+        });
+
+        match tracked_branch {
+            Some(branch) => {
+                // We create our local branch, tracking the remote one.
+                match repository.branch(
+                    local_branch_name,
+                    &branch.unwrap().0.into_reference().peel_to_commit()?,
+                    false,
+                ) {
+                    Ok(mut branch) => branch.set_upstream(Some(remote_branch_name))?,
+                    Err(error) => return Err(error),
+                }
+            }
+            None => {
+                return Err(git2::Error::new(
+                    git2::ErrorCode::NotFound,
+                    git2::ErrorClass::Checkout,
+                    &gettext("_Remote branch not found"),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * Create a branch from a given name.
+     */
+    pub fn create_branch(repository: &Repository, branch_name: &str) -> Result<(), git2::Error> {
+        let last_commit = RepositoryUtils::find_last_commit(repository)?;
+        repository.branch(branch_name, &last_commit, true)?;
+        Ok(())
+    }
+
+    /// Used to create a branch on a given commit.
+    pub fn create_branch_from_commit(
+        repository: &Repository,
+        branch_name: &str,
+        commit_id: &str,
+    ) -> Result<(), git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(commit_id)?)?;
+        repository.branch(branch_name, &commit, false)?;
+
+        return Ok(());
+    }
+
+    /// Used to checkout a commit, leaving HEAD detached.
+    pub fn checkout_commit(repository: &Repository, commit_id: &str) -> Result<(), git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(commit_id)?)?;
+
+        repository.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+        repository.set_head_detached(commit.id())?;
+
+        return Ok(());
+    }
+
+    /// Used to move the checked out branch to a commit.
+    /// A soft reset keeps the index and the working directory, a mixed one only keeps the working directory
+    /// and a hard one discards every change.
+    pub fn reset_to_commit(
+        repository: &Repository,
+        commit_id: &str,
+        reset_type: git2::ResetType,
+    ) -> Result<(), git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(commit_id)?)?;
+
+        return repository.reset(commit.as_object(), reset_type, None);
+    }
+
+    /// Used to apply the changes of a commit on top of HEAD with a new commit keeping its author and message.
+    pub fn cherry_pick_commit(
+        repository: &Repository,
+        commit_id: &str,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(commit_id)?)?;
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+
+        if commit.parent_count() > 1 {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::CherryPick,
+                gettext("_Cannot cherry-pick merge commits"),
+            ));
+        }
+
+        let index = repository.cherrypick_commit(&commit, &head_commit, 0, None)?;
+
+        return RepositoryUtils::commit_index_on_head(
+            repository,
+            index,
+            &commit.author(),
+            &Signature::now(author, author_email)?,
+            &String::from_utf8_lossy(commit.message_bytes()),
+            signing_key,
+            passphrase,
+        );
+    }
+
+    /// Used to undo the changes of a commit with a new revert commit on top of HEAD.
+    pub fn revert_commit(
+        repository: &Repository,
+        commit_id: &str,
+        author: &str,
+        author_email: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(commit_id)?)?;
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+
+        if commit.parent_count() > 1 {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Revert,
+                gettext("_Cannot revert merge commits"),
+            ));
+        }
+
+        let index = repository.revert_commit(&commit, &head_commit, 0, None)?;
+        let signature = Signature::now(author, author_email)?;
+        let message = format!(
+            "Revert \"{}\"\n\nThis reverts commit {}.",
+            commit.summary().unwrap_or_default(),
+            commit.id()
+        );
+
+        return RepositoryUtils::commit_index_on_head(
+            repository,
+            index,
+            &signature,
+            &signature,
+            &message,
+            signing_key,
+            passphrase,
+        );
+    }
+
+    /// Used to commit an index computed in memory on top of HEAD and to update the working directory with it.
+    /// Nothing is changed when the index has conflicts or when local changes would be overwritten.
+    fn commit_index_on_head(
+        repository: &Repository,
+        mut index: Index,
+        author: &Signature,
+        committer: &Signature,
+        message: &str,
+        signing_key: &str,
+        passphrase: &str,
+    ) -> Result<Oid, git2::Error> {
+        if index.has_conflicts() {
+            return Err(git2::Error::new(
+                ErrorCode::Conflict,
+                ErrorClass::Index,
+                gettext("_Commit cannot be applied without conflicts"),
+            ));
+        }
+
+        let head_commit = RepositoryUtils::find_last_commit(repository)?;
+        let tree = repository.find_tree(index.write_tree_to(repository)?)?;
+
+        if tree.id() == head_commit.tree_id() {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Index,
+                gettext("_Commit already applied"),
+            ));
+        }
+
+        let new_commit_oid = RepositoryUtils::create_commit(
+            repository,
+            author,
+            committer,
+            message,
+            &tree,
+            &[&head_commit],
+            signing_key,
+            passphrase,
+        )?;
+
+        repository.checkout_tree(tree.as_object(), Some(CheckoutBuilder::new().safe()))?;
+        repository.head()?.set_target(new_commit_oid, message)?;
+
+        return Ok(new_commit_oid);
+    }
+
+    /// Delete a remote branch.
+    pub fn delete_remote_branch(
+        repository: &Repository,
+        remote_branch_name: &str,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) -> Result<(), git2::Error> {
+        // The remote branch name starts with the name of its remote.
+        // We must remove this part when creating the delete ref:
+        let (remote_name, clean_branch_name) =
+            RepositoryUtils::split_remote_branch_name(repository, remote_branch_name)?;
+        let remote_delete_ref = format!(":refs/heads/{}", clean_branch_name);
+
+        let mut remote = match repository.find_remote(&remote_name) {
+            Ok(remote) => remote,
+            Err(error) => return Err(error),
+        };
+
+        let callback = RepositoryUtils::get_remote_callbacks(
+            repository,
+            &remote_name,
+            username,
+            password,
+            private_key_path,
+            passphrase,
+        )?;
+
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(callback);
+
+        match remote.push(&[remote_delete_ref], Some(&mut push_options)) {
+            Ok(_) => {
+                return Ok(());
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    /// Delete a branch from its name.
+    pub fn delete_local_branch(repo: &Repository, branch_name: &str) -> Result<(), git2::Error> {
+        let mut branch = repo.find_branch(branch_name, BranchType::Local)?;
+        branch.delete()?;
+        Ok(())
+    }
+
+    /// Used to checkout to another branch.
+    pub fn checkout_branch(
+        repository: &Repository,
+        branch_to_checkout_to: &str,
+        is_remote: bool,
+    ) -> Result<(), git2::Error> {
+        let mut binding = CheckoutBuilder::new();
+        let checkout_builder = binding.safe();
+        let mut final_branch_name_to_checkout_to = branch_to_checkout_to.to_string();
+
+        // If the selected branch is remote and untracked, we need to create a local branch tracking it.
+        if is_remote {
+            let tracking_branch =
+                RepositoryUtils::find_tracking_branch(repository, &branch_to_checkout_to);
+
+            if tracking_branch.is_some() {
+                final_branch_name_to_checkout_to = tracking_branch.unwrap();
+            } else {
+                let (_, local_branch_name) =
+                    RepositoryUtils::split_remote_branch_name(repository, branch_to_checkout_to)?;
+                let local_branch_name = local_branch_name.as_str();
+                tracing::info!(
+                    "No local branch is tracking {}. We will create the local branch: {}.",
+                    branch_to_checkout_to,
+                    local_branch_name
+                );
+                match RepositoryUtils::track_remote_branch(
+                    repository,
+                    &branch_to_checkout_to,
+                    local_branch_name,
+                ) {
+                    Ok(_) => final_branch_name_to_checkout_to = local_branch_name.to_string(),
+                    Err(error) => return Err(error),
+                };
+            }
+        }
+
+        let tree = repository.revparse_single(&final_branch_name_to_checkout_to)?;
+
+        repository.checkout_tree(&tree, Some(checkout_builder))?;
+
+        let full_branch_name = format!("refs/heads/{}", final_branch_name_to_checkout_to);
+        repository.set_head(&full_branch_name)?;
+
+        Ok(())
+    }
+
+    /// Used to discard one file.
+    /// A submodule is brought back to its recorded commit instead of being force checked out.
+    pub fn discard_one_file(repository: &Repository, file_path: &str) -> Result<(), git2::Error> {
+        if RepositoryUtils::get_submodule_paths(repository).contains(&file_path.to_string()) {
+            return RepositoryUtils::reset_submodule(repository, file_path);
+        }
+
+        let mut binding = CheckoutBuilder::new();
+        let checkout = binding.force().remove_untracked(true).path(file_path);
+
+        let head = repository.head()?;
+        let head_commit = head.peel_to_commit()?;
+
+        let head_tree = head_commit.tree()?;
+
+        repository.checkout_tree(head_tree.as_object(), Some(checkout))?;
+
+        Ok(())
+    }
+
+    /// Used to discard folder.
+    /// Submodules of the folder are brought back to their recorded commit instead of being force checked out.
+    pub fn discard_folder(
+        repository: &Repository,
+        folder_files: &Vec<ChangedFile>,
+    ) -> Result<(), git2::Error> {
+        let submodule_paths = RepositoryUtils::get_submodule_paths(repository);
+
+        let mut binding = CheckoutBuilder::new();
+        let checkout = binding.force().remove_untracked(true);
+        let mut has_files_to_checkout = false;
+
+        for file in folder_files {
+            let file_path = if file.parent.is_empty() {
+                file.name.clone()
+            } else {
+                RepositoryUtils::build_path_of_file(&file.parent, &file.name)
+            };
+
+            if submodule_paths.contains(&file_path) {
+                RepositoryUtils::reset_submodule(repository, &file_path)?;
+            } else {
+                checkout.path(file_path);
+                has_files_to_checkout = true;
+            }
+        }
+
+        // Without any path, the checkout would apply to the whole repository:
+        if !has_files_to_checkout {
+            return Ok(());
+        }
+
+        let head = repository.head()?;
+        let head_commit = head.peel_to_commit()?;
+
+        let head_tree = head_commit.tree()?;
+
+        repository.checkout_tree(head_tree.as_object(), Some(checkout))?;
+
+        Ok(())
+    }
+
+    /// Used to retrieve the submodules of the repository with their state.
+    pub fn get_submodules(repository: &Repository) -> Result<Vec<SubmoduleEntry>, git2::Error> {
+        let mut submodules: Vec<SubmoduleEntry> = vec![];
+
+        for submodule in repository.submodules()? {
+            let name = submodule.name().unwrap_or("").to_string();
+            let status = repository.submodule_status(&name, SubmoduleIgnore::None)?;
+
+            submodules.push(SubmoduleEntry {
+                name,
+                path: submodule.path().to_str().unwrap_or("").to_string(),
+                url: submodule.url().unwrap_or("").to_string(),
+                recorded_commit_id: match submodule.index_id().or(submodule.head_id()) {
+                    Some(oid) => oid.to_string(),
+                    None => String::new(),
+                },
+                checked_out_commit_id: match submodule.workdir_id() {
+                    Some(oid) => oid.to_string(),
+                    None => String::new(),
+                },
+                is_initialized: !status.contains(SubmoduleStatus::WD_UNINITIALIZED)
+                    && submodule.workdir_id().is_some(),
+                is_dirty: status.intersects(
+                    SubmoduleStatus::WD_INDEX_MODIFIED
+                        | SubmoduleStatus::WD_WD_MODIFIED
+                        | SubmoduleStatus::WD_UNTRACKED,
+                ),
+            });
+        }
+
+        return Ok(submodules);
+    }
+
+    /// Used to retrieve the paths of the submodules of the repository.
+    /// If an error occurs, no path is returned.
+    pub fn get_submodule_paths(repository: &Repository) -> Vec<String> {
+        match repository.submodules() {
+            Ok(submodules) => submodules
+                .iter()
+                .map(|submodule| submodule.path().to_str().unwrap_or("").to_string())
+                .collect(),
+            Err(error) => {
+                tracing::warn!("Could not retrieve submodules: {}", error);
+                vec![]
+            }
+        }
+    }
+
+    /// Used to find the submodules targeted by an action, an empty name targeting all of them.
+    fn find_submodules<'a>(
+        repository: &'a Repository,
+        submodule_name: &str,
+    ) -> Result<Vec<Submodule<'a>>, git2::Error> {
+        if submodule_name.is_empty() {
+            return repository.submodules();
+        }
+        return Ok(vec![repository.find_submodule(submodule_name)?]);
+    }
+
+    /// Used to get the clone mode used to reach a submodule, an empty name meaning the first submodule.
+    /// A submodule with a relative URL is reached like the default remote of the repository.
+    pub fn get_clone_mode_of_submodule(
+        repository: &Repository,
+        submodule_name: &str,
+    ) -> Result<CloneMode, git2::Error> {
+        let submodules = RepositoryUtils::find_submodules(repository, submodule_name)?;
+
+        let url = match submodules.first() {
+            Some(submodule) => submodule.url().unwrap_or("").to_string(),
+            None => return Err(git2::Error::from_str(&gettext("_No submodules"))),
+        };
+
+        if url.starts_with("./") || url.starts_with("../") {
+            return RepositoryUtils::get_clone_mode_of_remote(repository, "");
+        }
+
+        return Ok(RepositoryUtils::get_clone_mode_of_url(&url));
+    }
+
+    /// Used to copy the submodule information of .gitmodules into the configuration of the repository.
+    /// An empty name initializes all submodules.
+    pub fn init_submodules(
+        repository: &Repository,
+        submodule_name: &str,
+    ) -> Result<(), git2::Error> {
+        for mut submodule in RepositoryUtils::find_submodules(repository, submodule_name)? {
+            submodule.init(false)?;
+        }
+
+        Ok(())
+    }
+
+    /// Used to update the URL of submodules after it changed in .gitmodules.
+    /// An empty name synchronizes all submodules.
+    pub fn sync_submodules(
+        repository: &Repository,
+        submodule_name: &str,
+    ) -> Result<(), git2::Error> {
+        for mut submodule in RepositoryUtils::find_submodules(repository, submodule_name)? {
+            submodule.sync()?;
+        }
+
+        Ok(())
+    }
+
+    /// Used to clone or fetch submodules and check out the commit recorded for them.
+    /// An empty name updates all submodules, uninitialized submodules are initialized first.
+    /// The credentials used depend on the URL of each submodule.
+    pub fn update_submodules(
+        repository: &Repository,
+        submodule_name: &str,
+        is_recursive: bool,
+        username: String,
+        password: String,
+        private_key_path: String,
+        passphrase: String,
+    ) -> Result<(), git2::Error> {
+        for mut submodule in RepositoryUtils::find_submodules(repository, submodule_name)? {
+            submodule.init(false)?;
+
+            // Relative URLs of .gitmodules are resolved in the configuration once initialized:
+            let url = match repository
+                .config()?
+                .get_string(&format!("submodule.{}.url", submodule.name().unwrap_or("")))
+            {
+                Ok(url) => url,
+                Err(_) => submodule.url().unwrap_or("").to_string(),
+            };
+
+            let mut fetch_options = FetchOptions::new();
+            fetch_options.remote_callbacks(RepositoryUtils::find_correct_callback(
+                url,
+                username.clone(),
+                password.clone(),
+                passphrase.clone(),
+                private_key_path.clone(),
+            ));
+
+            let mut update_options = SubmoduleUpdateOptions::new();
+            update_options.fetch(fetch_options);
+
+            submodule.update(true, Some(&mut update_options))?;
+
+            if is_recursive {
+                RepositoryUtils::update_submodules(
+                    &submodule.open()?,
+                    "",
+                    true,
+                    username.clone(),
+                    password.clone(),
+                    private_key_path.clone(),
+                    passphrase.clone(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Used to check out the commit recorded for a submodule, without fetching.
+    /// Changes made in the submodule are kept, as a safe checkout is used.
+    fn reset_submodule(repository: &Repository, submodule_path: &str) -> Result<(), git2::Error> {
+        for mut submodule in repository.submodules()? {
+            if submodule.path() == Path::new(submodule_path) {
+                if submodule.workdir_id().is_none() {
+                    return Ok(());
+                }
+
+                let mut update_options = SubmoduleUpdateOptions::new();
+                update_options.allow_fetch(false);
+
+                return submodule.update(false, Some(&mut update_options));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Used to checked if there is changed files in the current branch.
+    /// If an error occurs, it will return true by default.
+    pub fn has_changed_files(repository: &Repository) -> bool {
+        let mut binding = git2::StatusOptions::new();
+        let statuses_options = binding.include_ignored(false).include_untracked(true);
+        if let Ok(statuses) = repository.statuses(Some(statuses_options)) {
+            return !statuses.is_empty();
+        }
+        return false;
+    }
+
+    /// Retrieve the differences of a file.
+    /// Staged differences are between HEAD and the index, unstaged ones between the index and the working directory.
+    pub fn get_diff_of_file(
+        repository: &Repository,
+        relative_path: &str,
+        is_staged: bool,
+    ) -> Result<FileDiff, git2::Error> {
+        let mut diff_options = DiffOptions::new();
+        diff_options
+            .pathspec(relative_path)
+            .disable_pathspec_match(true)
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+
+        let diff = if is_staged {
+            // On an unborn branch, there is no HEAD tree to compare to.
+            let head_tree = match repository.head() {
+                Ok(head) => Some(head.peel_to_tree()?),
+                Err(_) => None,
+            };
+            repository.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut diff_options))?
+        } else {
+            repository.diff_index_to_workdir(None, Some(&mut diff_options))?
+        };
+
+        return FileDiff::from_diff(&diff);
+    }
+
+    /// Retrieve the differences a commit brought to a file, compared to its first parent.
+    pub fn get_diff_of_file_in_commit(
+        repository: &Repository,
+        commit_id: &str,
+        relative_path: &str,
+    ) -> Result<FileDiff, git2::Error> {
+        let commit = repository.find_commit(Oid::from_str(commit_id)?)?;
+
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+
+        let mut diff_options = DiffOptions::new();
+        diff_options
+            .pathspec(relative_path)
+            .disable_pathspec_match(true);
+
+        let diff = repository.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit.tree()?),
+            Some(&mut diff_options),
+        )?;
+
+        return FileDiff::from_diff(&diff);
+    }
+
+    /// Retrieve the revisions whose files can be browsed: the checked out commit, the branches and the tags.
+    pub fn get_browsable_revisions(repository: &Repository) -> Result<Vec<String>, git2::Error> {
+        let mut revisions: Vec<String> = vec![String::from("HEAD")];
+
+        for branch_type in [BranchType::Local, BranchType::Remote] {
+            for branch in repository.branches(Some(branch_type))? {
+                let (branch, _) = branch?;
+
+                // The HEAD of a remote is the same as one of its branches:
+                if branch.get().symbolic_target().is_some() {
+                    continue;
+                }
+
+                if let Some(name) = branch.name()? {
+                    revisions.push(name.to_string());
+                }
+            }
+        }
+
+        for tag_name in repository.tag_names(None)?.iter().flatten() {
+            revisions.push(tag_name.to_string());
+        }
+
+        return Ok(revisions);
+    }
+
+    /// Retrieve the files and the folders of a folder at a revision, the folders being first.
+    /// The root of the repository is retrieved with an empty folder path.
+    pub fn get_tree_entries(
+        repository: &Repository,
+        revision: &str,
+        folder_path: &str,
+    ) -> Result<Vec<TreeEntry>, git2::Error> {
+        let root_tree = repository.revparse_single(revision)?.peel_to_tree()?;
+
+        let tree = if folder_path.is_empty() {
+            root_tree
+        } else {
+            root_tree
+                .get_path(Path::new(folder_path))?
+                .to_object(repository)?
+                .peel_to_tree()?
+        };
+
+        let mut tree_entries: Vec<TreeEntry> = vec![];
+
+        for entry in tree.iter() {
+            let is_folder = match entry.kind() {
+                Some(ObjectType::Tree) => true,
+                Some(ObjectType::Blob) => false,
+                // Submodules only record a commit of another repository:
+                _ => continue,
+            };
+
+            let name = String::from_utf8_lossy(entry.name_bytes()).to_string();
+
+            // The paths of the trees of git always use slashes:
+            let path = if folder_path.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", folder_path, name)
+            };
+
+            tree_entries.push(TreeEntry {
+                path,
+                name,
+                is_folder,
+            });
+        }
+
+        tree_entries.sort_by(|first_entry, second_entry| {
+            second_entry
+                .is_folder
+                .cmp(&first_entry.is_folder)
+                .then_with(|| first_entry.name.cmp(&second_entry.name))
+        });
+
+        return Ok(tree_entries);
     }
 
-    /// Used to find a branch tracking a remote one.
-    pub fn find_tracking_branch(
+    /// Retrieve the content of a file at a revision.
+    pub fn get_file_content_at_revision(
         repository: &Repository,
-        remote_branch_name: &str,
-    ) -> Option<String> {
-        match repository.branches(Some(BranchType::Local)) {
-            Ok(branches) => {
-                for branch_result in branches {
-                    let branch = branch_result.unwrap().0;
+        revision: &str,
+        relative_path: &str,
+    ) -> Result<Vec<u8>, git2::Error> {
+        let blob = repository
+            .revparse_single(revision)?
+            .peel_to_tree()?
+            .get_path(Path::new(relative_path))?
+            .to_object(repository)?
+            .peel_to_blob()?;
+
+        return Ok(blob.content().to_vec());
+    }
 
-                    let upstream_branch = match branch.upstream() {
-                        Ok(branch) => Some(branch),
-                        Err(_) => None,
-                    };
+    /// Write the content of a file at a revision to another file.
+    pub fn save_file_at_revision(
+        repository: &Repository,
+        revision: &str,
+        relative_path: &str,
+        destination_path: &str,
+    ) -> Result<(), git2::Error> {
+        let content =
+            RepositoryUtils::get_file_content_at_revision(repository, revision, relative_path)?;
 
-                    match upstream_branch {
-                        Some(upstream) => {
-                            if upstream.name().unwrap().unwrap() == remote_branch_name {
-                                return Some(branch.name().unwrap().unwrap().to_string());
-                            }
-                        }
-                        None => {}
-                    }
-                }
+        match std::fs::write(destination_path, content) {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                tracing::warn!("Could not save the file: {}", error);
+
+                Err(git2::Error::new(
+                    ErrorCode::GenericError,
+                    ErrorClass::Os,
+                    gettext("_Could not save the file"),
+                ))
             }
-            Err(_) => return None,
         }
-        None
     }
 
-    /// Used to create a new branch that tracks a remote branch.
-    pub fn track_remote_branch(
+    /// Retrieve the last commit that changed each line of a file, either at a revision or in the
+    /// working directory.
+    /// The lines changed in the working directory are not linked to any commit.
+    pub fn get_blame_of_file(
         repository: &Repository,
-        remote_branch_name: &str,
-        local_branch_name: &str,
-    ) -> Result<(), git2::Error> {
-        let mut remote_branches = repository.branches(Some(BranchType::Remote))?;
+        relative_path: &str,
+        revision: Option<&str>,
+    ) -> Result<Vec<BlameLine>, git2::Error> {
+        let (content, newest_commit_id) = match revision {
+            Some(revision) => {
+                let commit = repository.revparse_single(revision)?.peel_to_commit()?;
+                let blob = commit
+                    .tree()?
+                    .get_path(Path::new(relative_path))?
+                    .to_object(repository)?
+                    .peel_to_blob()?;
+
+                (blob.content().to_vec(), Some(commit.id()))
+            }
+            None => {
+                let workdir = RepositoryUtils::get_workdir_of_repository(repository)?;
+
+                let content = match std::fs::read(Path::new(&workdir).join(relative_path)) {
+                    Ok(content) => content,
+                    Err(error) => {
+                        tracing::warn!("Could not read the file to blame: {}", error);
+
+                        return Err(git2::Error::new(
+                            ErrorCode::NotFound,
+                            ErrorClass::Os,
+                            gettext("_File not found"),
+                        ));
+                    }
+                };
 
-        // The tracked remote branch.
-        let tracked_branch = remote_branches.find(|branch| match branch {
-            Ok(element) => element.0.name().unwrap().unwrap() == remote_branch_name,
-            Err(_) => false,
-        });
+                // On an unborn branch, there is no commit to blame.
+                (
+                    content,
+                    repository.head().ok().and_then(|head| head.target()),
+                )
+            }
+        };
 
-        match tracked_branch {
-            Some(branch) => {
-                // We create our local branch, tracking the remote one.
-                match repository.branch(
-                    local_branch_name,
-                    &branch.unwrap().0.into_reference().peel_to_commit()?,
-                    false,
-                ) {
-                    Ok(mut branch) => branch.set_upstream(Some(remote_branch_name))?,
+        if content.contains(&0) {
+            return Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Object,
+                gettext("_Binary file, blame cannot be shown"),
+            ));
+        }
+
+        let blame = match newest_commit_id {
+            Some(commit_id) => {
+                let mut blame_options = BlameOptions::new();
+                blame_options.newest_commit(commit_id);
+
+                match repository.blame_file(Path::new(relative_path), Some(&mut blame_options)) {
+                    Ok(blame) => Some(blame),
+                    // A file that has never been committed has no blame.
+                    Err(error) if error.code() == ErrorCode::NotFound => None,
                     Err(error) => return Err(error),
                 }
             }
-            None => {
-                return Err(git2::Error::new(
-                    git2::ErrorCode::NotFound,
-                    git2::ErrorClass::Checkout,
-                    &gettext("_Remote branch not found"),
-                ))
+            None => None,
+        };
+
+        let text = String::from_utf8_lossy(&content);
+
+        // The lines of the working directory keep the blame of the committed lines they come from:
+        let blamed_line_numbers: Vec<Option<usize>> = match (&blame, newest_commit_id) {
+            (Some(_), Some(commit_id)) if revision.is_none() => {
+                let committed_blob = repository
+                    .find_commit(commit_id)?
+                    .tree()?
+                    .get_path(Path::new(relative_path))?
+                    .to_object(repository)?
+                    .peel_to_blob()?;
+
+                RepositoryUtils::get_origin_line_numbers(committed_blob.content(), &content)?
             }
+            _ => (1..=text.lines().count()).map(Some).collect(),
+        };
+
+        let mut blame_lines: Vec<BlameLine> = vec![];
+
+        for (line_index, line) in text.lines().enumerate() {
+            let blame_hunk = match (&blame, blamed_line_numbers.get(line_index)) {
+                (Some(blame), Some(Some(line_number))) => blame.get_line(*line_number),
+                _ => None,
+            };
+
+            blame_lines.push(match blame_hunk {
+                Some(blame_hunk) if !blame_hunk.final_commit_id().is_zero() => {
+                    let signature = blame_hunk.final_signature();
+
+                    BlameLine {
+                        commit_id: blame_hunk.final_commit_id().to_string(),
+                        author: signature.name().unwrap_or_default().to_string(),
+                        date: RepositoryUtils::format_short_date(signature.when()),
+                        content: line.to_string(),
+                    }
+                }
+                _ => BlameLine {
+                    content: line.to_string(),
+                    ..Default::default()
+                },
+            });
         }
-        Ok(())
-    }
 
-    /**
-     * Create a branch from a given name.
-     */
-    pub fn create_branch(repository: &Repository, branch_name: &str) -> Result<(), git2::Error> {
-        let last_commit = RepositoryUtils::find_last_commit(repository)?;
-        repository.branch(branch_name, &last_commit, true)?;
-        Ok(())
+        return Ok(blame_lines);
     }
 
-    /// Delete a remote branch.
-    pub fn delete_remote_branch(
-        repository: &Repository,
-        remote_branch_name: &str,
-        username: String,
-        password: String,
-        private_key_path: String,
-        passphrase: String,
-    ) -> Result<(), git2::Error> {
-        let mut remote = match repository.find_remote("origin") {
-            Ok(remote) => remote,
-            Err(error) => return Err(error),
-        };
+    /// Retrieve, for each line of a new content, the number of the line it comes from in an old content.
+    /// The lines that are not in the old content have no number.
+    fn get_origin_line_numbers(
+        old_content: &[u8],
+        new_content: &[u8],
+    ) -> Result<Vec<Option<usize>>, git2::Error> {
+        let nb_new_lines = String::from_utf8_lossy(new_content).lines().count();
+        let nb_old_lines = String::from_utf8_lossy(old_content).lines().count();
 
-        // The remote branch name should start with "origin/".
-        // We must remove this part when creating the delete ref:
-        let clean_branch_name = remote_branch_name.replacen("origin/", "", 1);
-        let remote_delete_ref = format!(":refs/heads/{}", clean_branch_name);
+        let mut origin_line_numbers: Vec<Option<usize>> = (1..=nb_new_lines).map(Some).collect();
 
-        let callback: RemoteCallbacks<'_>;
+        // With enough context, every line of the new content is in the differences:
+        let mut diff_options = DiffOptions::new();
+        diff_options.context_lines(nb_new_lines.max(nb_old_lines) as u32);
+
+        let patch = git2::Patch::from_buffers(
+            old_content,
+            None,
+            new_content,
+            None,
+            Some(&mut diff_options),
+        )?;
+
+        for hunk_index in 0..patch.num_hunks() {
+            for line_index in 0..patch.num_lines_in_hunk(hunk_index)? {
+                let line = patch.line_in_hunk(hunk_index, line_index)?;
 
-        match RepositoryUtils::get_clone_mode_of_repository(&repository) {
-            Ok(clone_mode) => {
-                callback = match clone_mode {
-                    CloneMode::SSH => {
-                        RepositoryUtils::ssh_callback(username, passphrase, private_key_path)
+                if let Some(new_line_number) = line.new_lineno() {
+                    if let Some(origin_line_number) =
+                        origin_line_numbers.get_mut(new_line_number as usize - 1)
+                    {
+                        *origin_line_number = line.old_lineno().map(|number| number as usize);
                     }
-                    CloneMode::HTTPS => RepositoryUtils::https_callback(username, password),
                 }
             }
-            Err(error) => return Err(error),
-        };
+        }
 
-        let mut push_options = PushOptions::new();
-        push_options.remote_callbacks(callback);
+        return Ok(origin_line_numbers);
+    }
 
-        match remote.push(&[remote_delete_ref], Some(&mut push_options)) {
-            Ok(_) => {
-                return Ok(());
+    /// Retrieve the commits that changed a file, from the newest to the oldest, following its renames.
+    /// The history starts either at a revision or at the checked out commit.
+    pub fn get_history_of_file(
+        repository: &Repository,
+        relative_path: &str,
+        revision: Option<&str>,
+    ) -> Result<Vec<FileHistoryEntry>, git2::Error> {
+        let mut revwalk = repository.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        match revision {
+            Some(revision) => {
+                revwalk.push(repository.revparse_single(revision)?.peel_to_commit()?.id())?
             }
-            Err(error) => return Err(error),
+            None => match repository.head() {
+                Ok(head) => revwalk.push(head.peel_to_commit()?.id())?,
+                // On an unborn branch, there is no history yet.
+                Err(_) => return Ok(vec![]),
+            },
         }
-    }
 
-    /// Delete a branch from its name.
-    pub fn delete_local_branch(repo: &Repository, branch_name: &str) -> Result<(), git2::Error> {
-        let mut branch = repo.find_branch(branch_name, BranchType::Local)?;
-        branch.delete()?;
-        Ok(())
-    }
+        let mut current_path = relative_path.to_string();
+        let mut file_history: Vec<FileHistoryEntry> = vec![];
 
-    /// Used to checkout to another branch.
-    pub fn checkout_branch(
-        repository: &Repository,
-        branch_to_checkout_to: &str,
-        is_remote: bool,
-    ) -> Result<(), git2::Error> {
-        let mut binding = CheckoutBuilder::new();
-        let checkout_builder = binding.safe();
-        let mut final_branch_name_to_checkout_to = branch_to_checkout_to.to_string();
+        for commit_id in revwalk {
+            let commit = repository.find_commit(commit_id?)?;
 
-        // If the selected branch is remote and untracked, we need to create a local branch tracking it.
-        if is_remote {
-            let tracking_branch =
-                RepositoryUtils::find_tracking_branch(repository, &branch_to_checkout_to);
+            let entry_id = RepositoryUtils::get_entry_id_in_commit(&commit, &current_path);
 
-            if tracking_branch.is_some() {
-                final_branch_name_to_checkout_to = tracking_branch.unwrap();
-            } else {
-                let local_branch_name = branch_to_checkout_to.split("origin/").last().unwrap();
-                tracing::info!(
-                    "No local branch is tracking {}. We will create the local branch: {}.",
-                    branch_to_checkout_to,
-                    local_branch_name
-                );
-                match RepositoryUtils::track_remote_branch(
-                    repository,
-                    &branch_to_checkout_to,
-                    local_branch_name,
-                ) {
-                    Ok(_) => final_branch_name_to_checkout_to = local_branch_name.to_string(),
-                    Err(error) => return Err(error),
-                };
+            if entry_id.is_none() {
+                continue;
             }
-        }
 
-        let tree = repository.revparse_single(&final_branch_name_to_checkout_to)?;
+            let parent_entry_ids: Vec<Option<Oid>> = commit
+                .parents()
+                .map(|parent| RepositoryUtils::get_entry_id_in_commit(&parent, &current_path))
+                .collect();
 
-        repository.checkout_tree(&tree, Some(checkout_builder))?;
+            // A commit keeping the file of one of its parents, like most merges, did not change it:
+            if parent_entry_ids.contains(&entry_id) {
+                continue;
+            }
 
-        let full_branch_name = format!("refs/heads/{}", final_branch_name_to_checkout_to);
-        repository.set_head(&full_branch_name)?;
+            let path_in_commit = current_path.clone();
 
-        Ok(())
+            // A file missing from the first parent has either been added or renamed:
+            if parent_entry_ids.first() == Some(&None) {
+                if let Some(old_path) = RepositoryUtils::find_old_path_of_renamed_file(
+                    repository,
+                    &commit,
+                    &current_path,
+                )? {
+                    current_path = old_path;
+                }
+            }
+
+            let author = commit.author();
+
+            file_history.push(FileHistoryEntry {
+                commit_id: commit.id().to_string(),
+                title: commit.summary().unwrap_or_default().to_string(),
+                author: author.name().unwrap_or_default().to_string(),
+                date: RepositoryUtils::format_short_date(author.when()),
+                path: path_in_commit,
+            });
+        }
+
+        return Ok(file_history);
     }
 
-    /// Used to discard one file.
-    pub fn discard_one_file(repository: &Repository, file_path: &str) -> Result<(), git2::Error> {
-        let mut binding = CheckoutBuilder::new();
-        let checkout = binding.force().remove_untracked(true).path(file_path);
+    /// Retrieve the id of the blob or the tree at a path of a commit, if there is one.
+    fn get_entry_id_in_commit(commit: &Commit, relative_path: &str) -> Option<Oid> {
+        return match commit.tree() {
+            Ok(tree) => match tree.get_path(Path::new(relative_path)) {
+                Ok(entry) => Some(entry.id()),
+                Err(_) => None,
+            },
+            Err(_) => None,
+        };
+    }
 
-        let head = repository.head()?;
-        let head_commit = head.peel_to_commit()?;
+    /// Retrieve the path a file had before a commit renamed it.
+    fn find_old_path_of_renamed_file(
+        repository: &Repository,
+        commit: &Commit,
+        relative_path: &str,
+    ) -> Result<Option<String>, git2::Error> {
+        let mut diff = repository.diff_tree_to_tree(
+            Some(&commit.parent(0)?.tree()?),
+            Some(&commit.tree()?),
+            None,
+        )?;
 
-        let head_tree = head_commit.tree()?;
+        let mut find_options = DiffFindOptions::new();
+        find_options.renames(true);
+        diff.find_similar(Some(&mut find_options))?;
+
+        for delta in diff.deltas() {
+            if delta.status() == Delta::Renamed
+                && delta.new_file().path() == Some(Path::new(relative_path))
+            {
+                return Ok(delta
+                    .old_file()
+                    .path()
+                    .map(|old_path| old_path.to_string_lossy().to_string()));
+            }
+        }
 
-        repository.checkout_tree(head_tree.as_object(), Some(checkout))?;
+        return Ok(None);
+    }
 
-        Ok(())
+    /// Format the day of a git time, as shown in the blame and in the history of a file.
+    fn format_short_date(time: git2::Time) -> String {
+        return match NaiveDateTime::from_timestamp_opt(time.seconds(), 0) {
+            Some(datetime) => datetime.format(&gettext("_Short date format")).to_string(),
+            None => String::new(),
+        };
     }
 
-    /// Used to discard folder.
-    pub fn discard_folder(
+    /// Stage some lines of a file.
+    /// A line is identified by the index of its hunk and its index in the hunk of the unstaged diff.
+    pub fn stage_lines_of_file(
         repository: &Repository,
-        folder_files: &Vec<ChangedFile>,
+        relative_path: &str,
+        selected_lines: &Vec<(usize, usize)>,
     ) -> Result<(), git2::Error> {
-        let mut binding = CheckoutBuilder::new();
-        let checkout = binding.force().remove_untracked(true);
+        let file_diff = RepositoryUtils::get_diff_of_file(repository, relative_path, false)?;
 
-        for file in folder_files {
-            let file_path = if file.parent.is_empty() {
-                file.name.clone()
-            } else {
-                RepositoryUtils::build_path_of_file(&file.parent, &file.name)
-            };
-            checkout.path(file_path);
-        }
+        let mut index = repository.index()?;
+        let path = Path::new(relative_path);
 
-        let head = repository.head()?;
-        let head_commit = head.peel_to_commit()?;
+        let index_entry = index.get_path(path, 0);
 
-        let head_tree = head_commit.tree()?;
+        let old_content = match &index_entry {
+            Some(entry) => repository.find_blob(entry.id)?.content().to_vec(),
+            None => vec![],
+        };
 
-        repository.checkout_tree(head_tree.as_object(), Some(checkout))?;
+        let new_content = file_diff.apply_lines(&old_content, |hunk_index, line_index| {
+            selected_lines.contains(&(hunk_index, line_index))
+        });
 
-        Ok(())
-    }
+        let is_deleted_from_workdir = !Path::new(&RepositoryUtils::build_path_of_file(
+            &RepositoryUtils::get_workdir_of_repository(repository)?,
+            relative_path,
+        ))
+        .exists();
 
-    /// Used to checked if there is changed files in the current branch.
-    /// If an error occurs, it will return true by default.
-    pub fn has_changed_files(repository: &Repository) -> bool {
-        let mut binding = git2::StatusOptions::new();
-        let statuses_options = binding.include_ignored(false).include_untracked(true);
-        if let Ok(statuses) = repository.statuses(Some(statuses_options)) {
-            return !statuses.is_empty();
+        if is_deleted_from_workdir && new_content.is_empty() {
+            index.remove_path(path)?;
+        } else {
+            let entry = match index_entry {
+                Some(entry) => entry,
+                None => {
+                    // Adding the whole file first gives us an entry with the right file mode.
+                    index.add_path(path)?;
+                    match index.get_path(path, 0) {
+                        Some(entry) => entry,
+                        None => RepositoryUtils::build_new_index_entry(relative_path, 0o100644),
+                    }
+                }
+            };
+            index.add_frombuffer(&entry, &new_content)?;
         }
-        return false;
+
+        index.write()?;
+
+        return Ok(());
     }
 
-    /// Retrieve the content of a file.
-    pub fn get_content_of_deleted_file(
+    /// Unstage some lines of a file.
+    /// A line is identified by the index of its hunk and its index in the hunk of the staged diff.
+    pub fn unstage_lines_of_file(
         repository: &Repository,
         relative_path: &str,
-    ) -> Result<String, git2::Error> {
-        let head_tree = repository.head()?.peel_to_commit()?.tree()?;
+        selected_lines: &Vec<(usize, usize)>,
+    ) -> Result<(), git2::Error> {
+        let file_diff = RepositoryUtils::get_diff_of_file(repository, relative_path, true)?;
 
-        let mut diff_options = DiffOptions::new();
-        diff_options
-            .include_untracked(true)
-            .recurse_ignored_dirs(true);
+        let mut index = repository.index()?;
+        let path = Path::new(relative_path);
+
+        let head_entry = match repository.head() {
+            Ok(head) => head.peel_to_tree()?.get_path(path).ok(),
+            Err(_) => None,
+        };
 
-        let diff_result = repository
-            .diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut diff_options))?;
+        let old_content = match &head_entry {
+            Some(entry) => entry
+                .to_object(repository)?
+                .peel_to_blob()?
+                .content()
+                .to_vec(),
+            None => vec![],
+        };
 
-        let diff_deltas: Vec<_> = diff_result.deltas().collect();
+        // Every staged change that is not selected stays in the index:
+        let new_content = file_diff.apply_lines(&old_content, |hunk_index, line_index| {
+            !selected_lines.contains(&(hunk_index, line_index))
+        });
 
-        if diff_deltas.is_empty() {
-            return Ok(String::new());
+        if head_entry.is_none() && new_content.is_empty() {
+            // The file is not known by HEAD, it becomes untracked again.
+            index.remove_path(path)?;
+        } else {
+            let entry = match index.get_path(path, 0) {
+                Some(entry) => entry,
+                None => RepositoryUtils::build_new_index_entry(
+                    relative_path,
+                    match &head_entry {
+                        Some(entry) => entry.filemode() as u32,
+                        None => 0o100644,
+                    },
+                ),
+            };
+            index.add_frombuffer(&entry, &new_content)?;
         }
 
-        let mut file_content = String::new();
+        index.write()?;
+
+        return Ok(());
+    }
 
-        for diff_delta in diff_deltas {
-            let delta = diff_delta.status();
+    /// Build an index entry for a file that is not in the index yet.
+    fn build_new_index_entry(relative_path: &str, mode: u32) -> IndexEntry {
+        return IndexEntry {
+            ctime: IndexTime::new(0, 0),
+            mtime: IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode,
+            uid: 0,
+            gid: 0,
+            file_size: 0,
+            id: Oid::zero(),
+            flags: 0,
+            flags_extended: 0,
+            path: relative_path.as_bytes().to_vec(),
+        };
+    }
 
-            let diff_file = match delta {
-                Delta::Deleted => diff_delta.old_file(),
-                _ => diff_delta.new_file(),
-            };
+    /// Writes the blob of a file of the working directory, and retrieves the mode git gives to it:
+    /// a symbolic link, an executable or a regular file.
+    fn create_blob_of_file(
+        repository: &Repository,
+        file_path: &Path,
+    ) -> Result<(Oid, u32), git2::Error> {
+        let to_git_error = |error: std::io::Error| {
+            git2::Error::new(ErrorCode::GenericError, ErrorClass::Os, error.to_string())
+        };
 
-            let diff_file_path = diff_file.path().unwrap().to_str().unwrap().to_string();
+        let metadata = std::fs::symlink_metadata(file_path).map_err(to_git_error)?;
 
-            if diff_file_path == relative_path {
-                let file_oid = diff_file.id();
-                let blob = repository.find_blob(file_oid)?;
+        // The blob of a symbolic link holds its target:
+        if metadata.file_type().is_symlink() {
+            let target = std::fs::read_link(file_path).map_err(to_git_error)?;
+            let blob_oid = repository.blob(target.to_string_lossy().as_bytes())?;
+            return Ok((blob_oid, 0o120000));
+        }
 
-                let file_bytes_content = blob.content();
-                file_content = String::from(match from_utf8(file_bytes_content) {
-                    Ok(v) => v,
-                    Err(_) => "",
-                });
+        let blob_oid = repository.blob_path(file_path)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if metadata.permissions().mode() & 0o111 != 0 {
+                return Ok((blob_oid, 0o100755));
             }
         }
 
-        Ok(file_content)
+        return Ok((blob_oid, 0o100644));
+    }
+
+    /// Retrieve the path of the working directory of a repository.
+    fn get_workdir_of_repository(repository: &Repository) -> Result<String, git2::Error> {
+        match repository.workdir() {
+            Some(workdir) => Ok(workdir.to_string_lossy().trim_end_matches('/').to_string()),
+            None => Err(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Invalid,
+                gettext("_An error has occured"),
+            )),
+        }
     }
 }
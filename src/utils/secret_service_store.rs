@@ -0,0 +1,141 @@
+/* secret_service_store.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::collections::HashMap;
+
+use gettextrs::gettext;
+use secret_service::blocking::{Collection, Item, SecretService};
+use secret_service::EncryptionType;
+
+use super::secret_store::SecretStore;
+
+/// The attributes identifying the password of a Git profile in the Secret Service.
+fn get_attributes(profile_id: &str) -> HashMap<&str, &str> {
+    return HashMap::from([
+        ("application", "com.skilldary.bagit.desktop"),
+        ("profile-id", profile_id),
+    ]);
+}
+
+/// Stores passwords with the freedesktop Secret Service (GNOME Keyring, KWallet...).
+///
+/// Nothing is unlocked when reading or saving a password, as the unlock prompt would block
+/// the main thread: the store must be unlocked from another thread with `unlock`.
+pub struct SecretServiceStore {
+    service: SecretService<'static>,
+}
+
+impl SecretServiceStore {
+    /// Connects to the Secret Service provider, which must have a default collection.
+    pub fn connect() -> Result<SecretServiceStore, String> {
+        let service =
+            SecretService::connect(EncryptionType::Dh).map_err(|error| error.to_string())?;
+        service
+            .get_default_collection()
+            .map_err(|error| error.to_string())?;
+
+        Ok(SecretServiceStore { service })
+    }
+
+    /// Retrieves the default collection, failing when it is locked.
+    fn get_unlocked_collection(&self) -> Result<Collection<'_>, String> {
+        let collection = self
+            .service
+            .get_default_collection()
+            .map_err(|error| error.to_string())?;
+
+        if collection.is_locked().map_err(|error| error.to_string())? {
+            return Err(gettext("_Credential store locked"));
+        }
+
+        Ok(collection)
+    }
+
+    /// Retrieves the items holding the password of a Git profile, failing when one of them is locked.
+    fn find_items(&self, profile_id: &str) -> Result<Vec<Item<'_>>, String> {
+        let result = self
+            .service
+            .search_items(get_attributes(profile_id))
+            .map_err(|error| error.to_string())?;
+
+        if !result.locked.is_empty() {
+            return Err(gettext("_Credential store locked"));
+        }
+
+        Ok(result.unlocked)
+    }
+}
+
+impl SecretStore for SecretServiceStore {
+    fn store_password(&self, profile_id: &str, password: &str) -> Result<(), String> {
+        let collection = self.get_unlocked_collection()?;
+
+        collection
+            .create_item(
+                "Bagit Desktop Git profile password",
+                get_attributes(profile_id),
+                password.as_bytes(),
+                true,
+                "text/plain",
+            )
+            .map_err(|error| error.to_string())?;
+
+        Ok(())
+    }
+
+    fn retrieve_password(&self, profile_id: &str) -> Result<Option<String>, String> {
+        let items = self.find_items(profile_id)?;
+
+        let password = match items.first() {
+            Some(item) => {
+                let secret = item.get_secret().map_err(|error| error.to_string())?;
+                Some(String::from_utf8(secret).map_err(|error| error.to_string())?)
+            }
+            None => None,
+        };
+
+        Ok(password)
+    }
+
+    fn delete_password(&self, profile_id: &str) -> Result<(), String> {
+        for item in self.find_items(profile_id)? {
+            item.delete().map_err(|error| error.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    fn is_locked(&self) -> bool {
+        return match self.service.get_default_collection() {
+            Ok(collection) => collection.is_locked().unwrap_or(true),
+            Err(_) => true,
+        };
+    }
+
+    fn unlock(&self) -> Result<(), String> {
+        let collection = self
+            .service
+            .get_default_collection()
+            .map_err(|error| error.to_string())?;
+
+        return collection
+            .ensure_unlocked()
+            .map_err(|error| error.to_string());
+    }
+}
@@ -0,0 +1,188 @@
+/* secret_store.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use directories::ProjectDirs;
+use gettextrs::gettext;
+use gtk::glib;
+use once_cell::sync::Lazy;
+
+use super::{encrypted_file_store::EncryptedFileStore, secret_service_store::SecretServiceStore};
+
+/// A store keeping the passwords of the Git profiles out of the database.
+pub trait SecretStore: Send + Sync {
+    /// Saves the password of a Git profile, replacing the previous one.
+    fn store_password(&self, profile_id: &str, password: &str) -> Result<(), String>;
+
+    /// Retrieves the password of a Git profile, if one was saved.
+    fn retrieve_password(&self, profile_id: &str) -> Result<Option<String>, String>;
+
+    /// Deletes the password of a Git profile.
+    fn delete_password(&self, profile_id: &str) -> Result<(), String>;
+
+    /// Used to know if the store must be unlocked before its passwords can be read and saved.
+    fn is_locked(&self) -> bool {
+        return false;
+    }
+
+    /// Unlocks the store. The user may be prompted, so it must not be called on the main thread.
+    fn unlock(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// The store used by the application, none until the Secret Service or the encrypted file is opened.
+static SECRET_STORE: Lazy<Mutex<Option<Arc<dyn SecretStore>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Used to ask the window to unlock the store when a password is saved while it is locked.
+static UNLOCK_REQUEST_SENDER: Lazy<Mutex<Option<glib::Sender<()>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+pub struct SecretStoreUtils {}
+
+impl SecretStoreUtils {
+    /// Tries to use the Secret Service as store.
+    /// Returns false when it is not available, the encrypted file must then be opened with a master passphrase.
+    pub fn open_secret_service() -> bool {
+        match SecretServiceStore::connect() {
+            Ok(store) => {
+                SecretStoreUtils::set_store(Arc::new(store));
+                return true;
+            }
+            Err(error) => {
+                tracing::warn!("Secret Service is not available: {}", error);
+                return false;
+            }
+        }
+    }
+
+    /// Used to know if a master passphrase was already chosen for the encrypted file.
+    pub fn does_encrypted_file_exist() -> bool {
+        return SecretStoreUtils::get_encrypted_file_path().exists();
+    }
+
+    /// Uses the encrypted file as store, creating it when it does not exist yet.
+    pub fn open_encrypted_file(master_passphrase: &str) -> Result<(), String> {
+        let store = EncryptedFileStore::open(
+            SecretStoreUtils::get_encrypted_file_path(),
+            master_passphrase,
+        )?;
+        SecretStoreUtils::set_store(Arc::new(store));
+
+        Ok(())
+    }
+
+    /// Used to know if the master passphrase of the encrypted file must be asked to open a store.
+    pub fn needs_master_passphrase() -> bool {
+        return SECRET_STORE.lock().unwrap().is_none();
+    }
+
+    /// Used to know if passwords can be read and saved.
+    pub fn is_open() -> bool {
+        return match SecretStoreUtils::get_store() {
+            Some(store) => !store.is_locked(),
+            None => false,
+        };
+    }
+
+    /// Unlocks the opened store, waiting for the user to answer the prompt of the Secret Service.
+    /// Must not be called on the main thread.
+    pub fn unlock() -> Result<(), String> {
+        return match SecretStoreUtils::get_store() {
+            Some(store) => store.unlock(),
+            None => Err(gettext("_Credential store locked")),
+        };
+    }
+
+    /// Sets where the requests to unlock the store are sent.
+    pub fn set_unlock_request_sender(sender: glib::Sender<()>) {
+        UNLOCK_REQUEST_SENDER.lock().unwrap().replace(sender);
+    }
+
+    /// Asks the user to unlock the store.
+    pub fn request_unlock() {
+        if let Some(sender) = UNLOCK_REQUEST_SENDER.lock().unwrap().as_ref() {
+            if let Err(error) = sender.send(()) {
+                tracing::warn!(
+                    "Could not request to unlock the credential store: {}",
+                    error
+                );
+            }
+        }
+    }
+
+    /// Saves the password of a Git profile. An empty password deletes the saved one.
+    pub fn store_password(profile_id: &str, password: &str) -> Result<(), String> {
+        if password.is_empty() {
+            return SecretStoreUtils::delete_password(profile_id);
+        }
+
+        let store = SecretStoreUtils::get_open_store_or_request_unlock()?;
+
+        return store.store_password(profile_id, password);
+    }
+
+    /// Retrieves the password of a Git profile.
+    /// Fails while the store is locked.
+    pub fn retrieve_password(profile_id: &str) -> Result<Option<String>, String> {
+        return match SecretStoreUtils::get_store() {
+            Some(store) if !store.is_locked() => store.retrieve_password(profile_id),
+            _ => Err(gettext("_Credential store locked")),
+        };
+    }
+
+    /// Deletes the password of a Git profile.
+    /// Nothing is done while no store is open or while it is locked, so that profiles without password can be saved.
+    pub fn delete_password(profile_id: &str) -> Result<(), String> {
+        return match SecretStoreUtils::get_store() {
+            Some(store) if !store.is_locked() => store.delete_password(profile_id),
+            _ => Ok(()),
+        };
+    }
+
+    fn set_store(store: Arc<dyn SecretStore>) {
+        SECRET_STORE.lock().unwrap().replace(store);
+    }
+
+    /// The store is cloned so that it is not kept locked while unlocking it or using it.
+    fn get_store() -> Option<Arc<dyn SecretStore>> {
+        return SECRET_STORE.lock().unwrap().clone();
+    }
+
+    /// Retrieves the store when it is open, otherwise the user is asked to unlock it.
+    fn get_open_store_or_request_unlock() -> Result<Arc<dyn SecretStore>, String> {
+        match SecretStoreUtils::get_store() {
+            Some(store) if !store.is_locked() => Ok(store),
+            _ => {
+                SecretStoreUtils::request_unlock();
+                Err(gettext("_Credential store locked"))
+            }
+        }
+    }
+
+    /// The encrypted file is kept next to the database.
+    fn get_encrypted_file_path() -> PathBuf {
+        let project_dir: ProjectDirs =
+            ProjectDirs::from("com", "SkillDary", "Bagit Desktop").unwrap();
+
+        return project_dir.data_dir().join("db").join("credentials");
+    }
+}
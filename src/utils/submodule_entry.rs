@@ -0,0 +1,33 @@
+/* submodule_entry.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+/// A submodule of the repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubmoduleEntry {
+    pub name: String,
+    pub path: String,
+    pub url: String,
+    /// The commit recorded by the superproject, empty if there is none.
+    pub recorded_commit_id: String,
+    /// The commit checked out in the submodule, empty if it is not initialized.
+    pub checked_out_commit_id: String,
+    pub is_initialized: bool,
+    /// Whether the submodule has changes in its working directory or index.
+    pub is_dirty: bool,
+}
@@ -0,0 +1,29 @@
+/* tag_entry.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+/// A tag of the repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagEntry {
+    pub name: String,
+    /// The id of the tagged commit.
+    pub commit_id: String,
+    /// The message of an annotated tag, empty for a lightweight one.
+    pub message: String,
+    pub is_annotated: bool,
+}
@@ -0,0 +1,76 @@
+/* transfer_progress.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use gettextrs::gettext;
+use gtk::glib;
+
+/// Progress of a transfer with a remote, reported while cloning or fetching.
+#[derive(Debug, Clone, Default)]
+pub struct TransferProgress {
+    pub received_objects: usize,
+    pub indexed_objects: usize,
+    pub total_objects: usize,
+    pub received_bytes: usize,
+    pub indexed_deltas: usize,
+    pub total_deltas: usize,
+    /// The last message sent by the remote (e.g. "Counting objects").
+    pub remote_message: String,
+}
+
+impl TransferProgress {
+    /// Used to get the done fraction of the transfer, none while the remote has not sent the number of objects.
+    pub fn get_fraction(&self) -> Option<f64> {
+        if self.total_objects == 0 {
+            return None;
+        }
+
+        // Receiving the objects then resolving the deltas:
+        return Some(
+            (self.received_objects + self.indexed_deltas) as f64
+                / (self.total_objects + self.total_deltas) as f64,
+        );
+    }
+
+    /// Used to describe the current step of the transfer.
+    pub fn get_description(&self) -> String {
+        if self.total_objects == 0 {
+            if self.remote_message.is_empty() {
+                return gettext("_Connecting to remote");
+            }
+            return self.remote_message.clone();
+        }
+
+        if self.received_objects < self.total_objects {
+            return format!(
+                "{} {}/{} ({})",
+                gettext("_Receiving objects"),
+                self.received_objects,
+                self.total_objects,
+                glib::format_size(self.received_bytes as u64)
+            );
+        }
+
+        return format!(
+            "{} {}/{}",
+            gettext("_Resolving deltas"),
+            self.indexed_deltas,
+            self.total_deltas
+        );
+    }
+}
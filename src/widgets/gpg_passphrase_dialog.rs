@@ -20,6 +20,7 @@
 //use adw::prelude::WidgetExt;
 use adw::subclass::prelude::*;
 
+use adw::prelude::ComboRowExt;
 use adw::prelude::EditableExt;
 use adw::prelude::MessageDialogExt;
 use adw::prelude::StaticType;
@@ -29,11 +30,19 @@ use gtk::{
     gio,
     glib::{self},
 };
-use gtk::{glib::subclass::Signal, prelude::ObjectExt, template_callbacks};
+use gtk::{
+    glib::subclass::Signal,
+    prelude::{ObjectExt, SettingsExt},
+    template_callbacks,
+};
 use once_cell::sync::Lazy;
 
+use crate::utils::passphrase_cache::PassphraseRetention;
+
 mod imp {
 
+    use std::cell::Cell;
+
     use super::*;
 
     #[derive(Debug, Default, gtk::CompositeTemplate)]
@@ -41,6 +50,10 @@ mod imp {
     pub struct BagitGpgPassphraseDialog {
         #[template_child]
         pub passphrase_row: TemplateChild<adw::PasswordEntryRow>,
+        #[template_child]
+        pub remember_row: TemplateChild<adw::ComboRow>,
+
+        pub remembering_minutes: Cell<u32>,
     }
 
     #[template_callbacks]
@@ -110,6 +123,7 @@ impl BagitGpgPassphraseDialog {
         let win: BagitGpgPassphraseDialog = Self::default();
         let body_text = format!("{}\n{}", gettext("_Passphrase key"), signing_key);
         win.set_body(&body_text);
+        win.init_remember_row();
 
         /*
         let eventctl = gtk::EventControllerKey::new();
@@ -124,4 +138,26 @@ impl BagitGpgPassphraseDialog {
         */
         win
     }
+
+    /// Used to fill the choices of how long the passphrase is remembered.
+    fn init_remember_row(&self) {
+        let settings = gio::Settings::new("com.skilldary.bagit.desktop");
+        let minutes = settings.uint("passphrase-cache-minutes");
+        self.imp().remembering_minutes.set(minutes);
+
+        let choices = PassphraseRetention::choices(minutes);
+        let choices: Vec<&str> = choices.iter().map(|choice| choice.as_str()).collect();
+        self.imp()
+            .remember_row
+            .set_model(Some(&gtk::StringList::new(&choices)));
+        self.imp().remember_row.set_selected(1);
+    }
+
+    /// Used to get how long the user wants the passphrase to be remembered.
+    pub fn get_retention(&self) -> PassphraseRetention {
+        return PassphraseRetention::from_choice(
+            self.imp().remember_row.selected(),
+            self.imp().remembering_minutes.get(),
+        );
+    }
 }
@@ -18,4 +18,5 @@
  */
 
 pub mod preferences_git_profiles;
+pub mod preferences_passphrases;
 pub mod preferences_sidebar;
@@ -0,0 +1,111 @@
+/* preferences_passphrases.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use adw::subclass::prelude::*;
+use gtk::gio::{Settings, SettingsBindFlags};
+use gtk::glib::subclass::Signal;
+use gtk::template_callbacks;
+use gtk::{glib, prelude::*, CompositeTemplate};
+use once_cell::sync::Lazy;
+
+use crate::utils::passphrase_cache::PassphraseCache;
+
+mod imp {
+
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(
+        resource = "/com/skilldary/bagit/desktop/ui/widgets/preferences/bagit-preferences-passphrases.ui"
+    )]
+    pub struct BagitPreferencesPassphrases {
+        #[template_child]
+        pub minutes_spin_button: TemplateChild<gtk::SpinButton>,
+        #[template_child]
+        pub agent_switch: TemplateChild<gtk::Switch>,
+    }
+
+    #[template_callbacks]
+    impl BagitPreferencesPassphrases {
+        #[template_callback]
+        fn forget_button_clicked(&self, _button: gtk::Button) {
+            self.obj().emit_by_name::<()>("forget-passphrases", &[]);
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitPreferencesPassphrases {
+        const NAME: &'static str = "BagitPreferencesPassphrases";
+        type Type = super::BagitPreferencesPassphrases;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for BagitPreferencesPassphrases {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let settings = Settings::new("com.skilldary.bagit.desktop");
+
+            settings
+                .bind(
+                    "passphrase-cache-minutes",
+                    &self.minutes_spin_button.get(),
+                    "value",
+                )
+                .flags(SettingsBindFlags::DEFAULT)
+                .build();
+            settings
+                .bind(
+                    "is-agent-delegation-enabled",
+                    &self.agent_switch.get(),
+                    "active",
+                )
+                .flags(SettingsBindFlags::DEFAULT)
+                .build();
+
+            self.agent_switch.connect_active_notify(|agent_switch| {
+                PassphraseCache::set_agent_delegation_enabled(agent_switch.is_active());
+            });
+        }
+
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> =
+                Lazy::new(|| vec![Signal::builder("forget-passphrases").build()]);
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitPreferencesPassphrases {}
+    impl BoxImpl for BagitPreferencesPassphrases {}
+}
+
+glib::wrapper! {
+    pub struct BagitPreferencesPassphrases(ObjectSubclass<imp::BagitPreferencesPassphrases>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
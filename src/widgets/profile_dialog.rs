@@ -27,6 +27,7 @@ use gettextrs::gettext;
 use gtk::{gio, glib};
 
 use crate::models::bagit_git_profile::BagitGitProfile;
+use crate::utils::db::AppDatabase;
 
 mod imp {
 
@@ -74,7 +75,12 @@ impl Default for BagitProfileDialog {
 impl BagitProfileDialog {
     pub fn new(profile: &BagitGitProfile) -> Self {
         let win: BagitProfileDialog = Self::default();
-        win.show_profile_information(profile);
+
+        // The password is only retrieved from the secret store when the information is shown:
+        let mut profile = profile.clone();
+        profile.password = AppDatabase::retrieve_password_of_git_profile(&profile);
+
+        win.show_profile_information(&profile);
         win
     }
 
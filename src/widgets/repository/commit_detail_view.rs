@@ -0,0 +1,332 @@
+/* commit_detail_view.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::path::Path;
+
+use adw::prelude::ActionRowExt;
+use adw::subclass::prelude::*;
+use gettextrs::gettext;
+use git2::Delta;
+use gtk::glib;
+use gtk::prelude::*;
+
+use crate::utils::commit_details::{CommitDetails, SignatureStatus};
+use crate::utils::file_diff::FileDiff;
+use crate::widgets::repository::file_view::BagitFileView;
+
+mod imp {
+
+    use std::cell::RefCell;
+
+    use super::*;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::subclass::widget::CompositeTemplateInitializingExt;
+    use gtk::template_callbacks;
+    use gtk::CompositeTemplate;
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(
+        resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-commit-detail-view.ui"
+    )]
+    pub struct BagitCommitDetailView {
+        #[template_child]
+        pub title_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub description_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub commit_id_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub author_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub committer_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub parents_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub signature_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub signature_image: TemplateChild<gtk::Image>,
+        #[template_child]
+        pub changed_files_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub file_view: TemplateChild<BagitFileView>,
+
+        pub commit_details: RefCell<CommitDetails>,
+    }
+
+    #[template_callbacks]
+    impl BagitCommitDetailView {
+        #[template_callback]
+        fn changed_file_activated(&self, row: &gtk::ListBoxRow) {
+            self.obj().select_changed_file(row.index());
+        }
+
+        #[template_callback]
+        fn create_tag(&self, _button: &gtk::Button) {
+            let commit_id = self.obj().get_commit_id();
+            self.obj().emit_by_name::<()>("create-tag", &[&commit_id]);
+        }
+
+        #[template_callback]
+        fn rebase_from_commit(&self, _button: &gtk::Button) {
+            let commit_id = self.obj().get_commit_id();
+            self.obj()
+                .emit_by_name::<()>("rebase-from-commit", &[&commit_id]);
+        }
+
+        #[template_callback]
+        fn browse_files(&self, _button: &gtk::Button) {
+            let commit_id = self.obj().get_commit_id();
+            self.obj()
+                .emit_by_name::<()>("browse-commit-files", &[&commit_id]);
+        }
+    }
+
+    // The central trait for subclassing a GObject
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitCommitDetailView {
+        const NAME: &'static str = "BagitCommitDetailView";
+        type Type = super::BagitCommitDetailView;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+    impl ObjectImpl for BagitCommitDetailView {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            // The differences of a commit cannot be staged:
+            self.file_view.set_staging_controls_visible(false);
+            self.file_view.set_file_modes_visible(false);
+        }
+
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("commit-file-selected")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("create-tag")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("rebase-from-commit")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("browse-commit-files")
+                        .param_types([str::static_type()])
+                        .build(),
+                ]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitCommitDetailView {}
+    impl BoxImpl for BagitCommitDetailView {}
+}
+glib::wrapper! {
+    pub struct BagitCommitDetailView(ObjectSubclass<imp::BagitCommitDetailView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl BagitCommitDetailView {
+    /// Shows the details of a commit.
+    /// The first changed file is selected to show its differences.
+    pub fn show_commit_details(&self, commit_details: CommitDetails) {
+        let (title, description) = match commit_details.message.split_once("\n") {
+            Some((title, description)) => (title.to_string(), description.trim().to_string()),
+            None => (commit_details.message.clone(), String::new()),
+        };
+
+        self.imp().title_label.set_text(&title);
+        self.imp().description_label.set_text(&description);
+        self.imp()
+            .description_label
+            .set_visible(!description.is_empty());
+
+        self.imp()
+            .commit_id_row
+            .set_subtitle(&commit_details.commit_id);
+        self.imp().author_row.set_subtitle(&format!(
+            "{}\n{}",
+            commit_details.author, commit_details.author_date
+        ));
+        self.imp().committer_row.set_subtitle(&format!(
+            "{}\n{}",
+            commit_details.committer, commit_details.committer_date
+        ));
+
+        if commit_details.parent_ids.is_empty() {
+            self.imp().parents_row.set_subtitle(&gettext("_No parent"));
+        } else {
+            self.imp()
+                .parents_row
+                .set_subtitle(&commit_details.parent_ids.join("\n"));
+        }
+
+        self.update_signature_row(commit_details.signature_status, &commit_details.signer);
+
+        self.clear_changed_files_list();
+        for changed_file in &commit_details.changed_files {
+            let row = Self::build_changed_file_row(&changed_file.0, changed_file.1);
+            self.imp().changed_files_list.append(&row);
+        }
+
+        self.imp().commit_details.replace(commit_details);
+
+        self.imp().file_view.show_diff("", FileDiff::default());
+
+        if let Some(first_row) = self.imp().changed_files_list.row_at_index(0) {
+            self.imp().changed_files_list.select_row(Some(&first_row));
+            self.select_changed_file(0);
+        }
+    }
+
+    /// Shows the differences of a file of the commit.
+    pub fn show_file_diff(&self, file_path: &str, file_diff: FileDiff) {
+        self.imp().file_view.show_diff(file_path, file_diff);
+    }
+
+    /// Retrieves the id of the shown commit.
+    pub fn get_commit_id(&self) -> String {
+        return self.imp().commit_details.borrow().commit_id.clone();
+    }
+
+    /// Asks for the differences of a changed file of the commit.
+    fn select_changed_file(&self, index: i32) {
+        let commit_details = self.imp().commit_details.borrow().clone();
+
+        if index < 0 {
+            return;
+        }
+
+        if let Some(changed_file) = commit_details.changed_files.get(index as usize) {
+            self.emit_by_name::<()>(
+                "commit-file-selected",
+                &[&commit_details.commit_id, &changed_file.0],
+            );
+        }
+    }
+
+    /// Updates the signature row depending on the signature status.
+    fn update_signature_row(&self, signature_status: SignatureStatus, signer: &str) {
+        let signature_image = &self.imp().signature_image;
+        for css_class in ["success", "warning", "error"] {
+            signature_image.remove_css_class(css_class);
+        }
+
+        let (subtitle, icon_name, css_class) = match signature_status {
+            SignatureStatus::NotSigned => {
+                (gettext("_Not signed"), "dialog-information-symbolic", "")
+            }
+            SignatureStatus::Valid => (
+                gettext("_Valid signature"),
+                "check-round-outline-symbolic",
+                "success",
+            ),
+            SignatureStatus::UnknownKey => (
+                gettext("_Signed with an unknown key"),
+                "dialog-warning-symbolic",
+                "warning",
+            ),
+            SignatureStatus::Invalid => (
+                gettext("_Invalid signature"),
+                "dialog-error-symbolic",
+                "error",
+            ),
+        };
+
+        if signer.is_empty() {
+            self.imp().signature_row.set_subtitle(&subtitle);
+        } else {
+            self.imp()
+                .signature_row
+                .set_subtitle(&format!("{}\n{}", subtitle, signer));
+        }
+
+        signature_image.set_icon_name(Some(icon_name));
+        if !css_class.is_empty() {
+            signature_image.add_css_class(css_class);
+        }
+    }
+
+    /// Builds the row of a changed file, with an icon depending on its change.
+    pub fn build_changed_file_row(file_path: &str, delta: Delta) -> adw::ActionRow {
+        let path = Path::new(file_path);
+
+        let file_name = match path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => file_path.to_string(),
+        };
+        let parent = match path.parent() {
+            Some(parent) => parent.to_string_lossy().to_string(),
+            None => String::new(),
+        };
+
+        let row = adw::ActionRow::builder()
+            .title(&file_name)
+            .activatable(true)
+            .build();
+        row.set_title_lines(1);
+        row.set_subtitle_lines(1);
+        if !parent.is_empty() {
+            row.set_subtitle(&parent);
+        }
+
+        let (css_class_name, icon_name, icon_tooltip_text) = match delta {
+            Delta::Added => ("success", "list-add-symbolic", gettext("_New file")),
+            Delta::Deleted => ("error", "list-remove-symbolic", gettext("_Deleted file")),
+            Delta::Renamed => (
+                "warning",
+                "panel-modified-symbolic",
+                gettext("_Renamed file"),
+            ),
+            _ => (
+                "warning",
+                "panel-modified-symbolic",
+                gettext("_Modified file"),
+            ),
+        };
+
+        let icon = gtk::Image::from_icon_name(icon_name);
+        icon.set_tooltip_text(Some(&icon_tooltip_text));
+        icon.add_css_class(css_class_name);
+        row.add_prefix(&icon);
+
+        return row;
+    }
+
+    /// Clears the list of changed files.
+    fn clear_changed_files_list(&self) {
+        let mut file_row = self.imp().changed_files_list.row_at_index(0);
+        while file_row != None {
+            self.imp().changed_files_list.remove(&file_row.unwrap());
+            file_row = self.imp().changed_files_list.row_at_index(0);
+        }
+    }
+}
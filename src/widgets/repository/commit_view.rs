@@ -77,6 +77,14 @@ mod imp {
         #[template_child]
         pub save_profile_button: TemplateChild<gtk::CheckButton>,
         #[template_child]
+        pub amend_button: TemplateChild<gtk::CheckButton>,
+        #[template_child]
+        pub amend_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
+        pub reset_author_button: TemplateChild<gtk::CheckButton>,
+        #[template_child]
+        pub pushed_commit_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
         pub commit_button: TemplateChild<gtk::Button>,
 
         pub profile_mode: RefCell<ProfileMode>,
@@ -138,6 +146,26 @@ mod imp {
                 .expect("Could not set setting.");
         }
         #[template_callback]
+        fn amend_button_toggled(&self, amend_button: &gtk::CheckButton) {
+            let is_amending = amend_button.is_active();
+
+            self.amend_revealer.set_reveal_child(is_amending);
+            self.commit_button.set_label(&if is_amending {
+                gettext("_Amend")
+            } else {
+                gettext("_Commit")
+            });
+
+            if !is_amending {
+                self.reset_author_button.set_active(false);
+                self.pushed_commit_revealer.set_reveal_child(false);
+            }
+
+            self.obj()
+                .emit_by_name::<()>("amend-toggled", &[&is_amending]);
+            self.obj().emit_by_name::<()>("toggle-commit-button", &[]);
+        }
+        #[template_callback]
         fn commit_files(&self, _commit_button: &gtk::Button) {
             let profile_mode = self.profile_mode.take();
             self.profile_mode.replace(profile_mode.clone());
@@ -219,6 +247,9 @@ mod imp {
                         ])
                         .build(),
                     Signal::builder("toggle-commit-button").build(),
+                    Signal::builder("amend-toggled")
+                        .param_types([bool::static_type()])
+                        .build(),
                     Signal::builder("select-profile")
                         .param_types([str::static_type()])
                         .build(),
@@ -291,6 +322,32 @@ impl BagitCommitView {
         self.imp().author_email_row.set_text("");
         self.imp().message_row.set_text("");
         self.imp().signing_key_row.set_text("");
+        self.stop_amending();
+    }
+
+    /// Checks if the last commit is amended instead of creating a new commit.
+    pub fn is_amending(&self) -> bool {
+        return self.imp().amend_button.is_active();
+    }
+
+    /// Checks if the author of the amended commit is replaced by the current one.
+    pub fn is_resetting_author(&self) -> bool {
+        return self.is_amending() && self.imp().reset_author_button.is_active();
+    }
+
+    /// Used to fill the fields with the message of the amended commit.
+    /// A warning is shown when the commit has already been pushed.
+    pub fn show_amended_commit(&self, title: &str, description: &str, is_pushed: bool) {
+        self.imp().message_row.set_text(title);
+        self.imp().description_row.set_text(description);
+        self.imp()
+            .pushed_commit_revealer
+            .set_reveal_child(is_pushed);
+    }
+
+    /// Used to go back to the creation of a new commit.
+    pub fn stop_amending(&self) {
+        self.imp().amend_button.set_active(false);
     }
 
     ///Used to update the label of files selected and the commit button of the commit view.
@@ -318,13 +375,13 @@ impl BagitCommitView {
 
         return match self.imp().profile_mode.borrow().get_profile_mode() {
             ProfileMode::SelectedProfile(_) => {
-                is_message_text_filled && (total_selected_files != 0)
+                is_message_text_filled && (total_selected_files != 0 || self.is_amending())
             }
             _ => {
                 is_author_mail_filled
                     && is_message_text_filled
                     && is_author_text_filled
-                    && (total_selected_files != 0)
+                    && (total_selected_files != 0 || self.is_amending())
                     && is_author_mail_valid
             }
         };
@@ -19,17 +19,21 @@
 
 use crate::utils::changed_file::ChangedFile;
 use crate::utils::changed_folder::ChangedFolder;
+use crate::utils::commit_graph::{CommitGraph, CommitGraphRow};
 use crate::utils::file_tree::FileTree;
+use crate::utils::history_search::{HistorySearch, HistorySearchProgress};
 use crate::utils::repository_utils::RepositoryUtils;
+use adw::prelude::ActionRowExt;
 use adw::subclass::prelude::*;
+use chrono::NaiveDate;
 use gettextrs::gettext;
 use git2::Repository;
 use git2::{Status, Statuses};
 use gtk::glib::subclass::Signal;
-use gtk::glib::{clone, SignalHandlerId};
+use gtk::glib::{clone, MainContext, Priority, SignalHandlerId};
 use gtk::pango::EllipsizeMode;
 use gtk::{
-    gio, glib, prelude::*, Align, CompositeTemplate, Label, ListItem, NoSelection,
+    cairo, gdk, gio, glib, prelude::*, Align, CompositeTemplate, Label, ListItem, NoSelection,
     SignalListItemFactory,
 };
 use once_cell::sync::Lazy;
@@ -38,14 +42,82 @@ extern crate chrono;
 
 use crate::utils::git::{
     get_first_commit_id_of_checked_out_branch, get_repository_checked_out_branch_name,
-    load_commit_history,
+    is_commit_graph_outdated, load_commit_graph_history, load_commit_history,
+    load_detached_head_history, search_commit_history,
 };
 
 use super::CommitObject;
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+/// The width of a lane of the commit graph.
+const GRAPH_LANE_WIDTH: f64 = 16.0;
+
+/// The colors of the lanes of the commit graph, used one after another.
+const GRAPH_LANE_COLORS: [(f64, f64, f64); 6] = [
+    (0.21, 0.52, 0.89),
+    (0.2, 0.82, 0.48),
+    (0.96, 0.83, 0.18),
+    (1.0, 0.47, 0.0),
+    (0.88, 0.11, 0.14),
+    (0.57, 0.25, 0.67),
+];
+
+/// Draws the lines of a row of the commit graph and the dot of its commit.
+fn draw_commit_graph_row(graph_row: &CommitGraphRow, context: &cairo::Context, height: f64) {
+    let middle = height / 2.0;
+    let lane_x = |lane: usize| (lane as f64 + 0.5) * GRAPH_LANE_WIDTH;
+    let set_lane_color = |lane: usize| {
+        let (red, green, blue) = GRAPH_LANE_COLORS[lane % GRAPH_LANE_COLORS.len()];
+        context.set_source_rgb(red, green, blue);
+    };
+
+    context.set_line_width(2.0);
+
+    for (top_lane, middle_lane) in &graph_row.top_lines {
+        set_lane_color(*top_lane);
+        context.move_to(lane_x(*top_lane), 0.0);
+        context.curve_to(
+            lane_x(*top_lane),
+            middle / 2.0,
+            lane_x(*middle_lane),
+            middle / 2.0,
+            lane_x(*middle_lane),
+            middle,
+        );
+        let _ = context.stroke();
+    }
+
+    for (middle_lane, bottom_lane) in &graph_row.bottom_lines {
+        set_lane_color(*bottom_lane);
+        context.move_to(lane_x(*middle_lane), middle);
+        context.curve_to(
+            lane_x(*middle_lane),
+            middle + middle / 2.0,
+            lane_x(*bottom_lane),
+            middle + middle / 2.0,
+            lane_x(*bottom_lane),
+            height,
+        );
+        let _ = context.stroke();
+    }
+
+    set_lane_color(graph_row.commit_lane);
+    context.arc(
+        lane_x(graph_row.commit_lane),
+        middle,
+        5.0,
+        0.0,
+        2.0 * std::f64::consts::PI,
+    );
+    let _ = context.fill();
+}
 
 mod imp {
+    use gtk::gio::Settings;
     use gtk::{gio, template_callbacks};
     use std::cell::Cell;
     use std::cell::RefCell;
@@ -75,6 +147,30 @@ mod imp {
         pub commits_sidebar_stack: TemplateChild<gtk::Stack>,
         #[template_child]
         pub scrolled_window_commit_history: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub empty_history_page: TemplateChild<adw::StatusPage>,
+        #[template_child]
+        pub graph_button: TemplateChild<gtk::CheckButton>,
+        #[template_child]
+        pub search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub search_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
+        pub message_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub author_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub since_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub until_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub path_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub commit_id_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub search_spinner: TemplateChild<gtk::Spinner>,
+        #[template_child]
+        pub search_status_label: TemplateChild<gtk::Label>,
 
         pub scroll_handler_id: RefCell<Option<SignalHandlerId>>,
 
@@ -82,9 +178,13 @@ mod imp {
         pub checked_out_branch_name: RefCell<String>,
         pub first_commit_oid_of_commit_list: RefCell<String>,
         pub last_commit_oid_of_commit_list: RefCell<String>,
+        pub selected_repository_path: RefCell<String>,
+        pub commit_graph: RefCell<CommitGraph>,
+        pub history_search: RefCell<HistorySearch>,
+        pub is_history_search_cancelled: RefCell<Arc<AtomicBool>>,
+        pub is_clearing_history_search: Cell<bool>,
 
         pub changed_files: RefCell<FileTree>,
-        pub change_from_file: Cell<bool>,
         pub change_from_user: Cell<bool>,
     }
 
@@ -121,11 +221,10 @@ mod imp {
         }
 
         #[template_callback]
-        fn select_button_changed(&self, _check_button: &gtk::CheckButton) {
+        fn select_button_changed(&self, check_button: &gtk::CheckButton) {
             if self.change_from_user.get() {
-                // We update the changed files list :
-                self.obj().clear_changed_files_list();
-                self.obj().emit_by_name::<()>("update-changed-files", &[]);
+                self.obj()
+                    .emit_by_name::<()>("change-all-files-staging", &[&check_button.is_active()]);
             } else {
                 self.change_from_user.set(true);
             }
@@ -140,6 +239,45 @@ mod imp {
             }
         }
 
+        #[template_callback]
+        fn graph_button_toggled(&self, graph_button: &gtk::CheckButton) {
+            let settings = Settings::new("com.skilldary.bagit.desktop");
+
+            settings
+                .set_boolean("is-commit-graph-enabled", graph_button.is_active())
+                .expect("Could not set setting.");
+
+            let selected_repository_path = self.selected_repository_path.borrow().clone();
+
+            if !selected_repository_path.is_empty() {
+                self.obj().init_commit_list(selected_repository_path);
+            }
+        }
+
+        #[template_callback]
+        fn search_button_toggled(&self, search_button: &gtk::ToggleButton) {
+            self.search_revealer
+                .set_reveal_child(search_button.is_active());
+
+            if search_button.is_active() {
+                self.message_search_entry.grab_focus();
+            } else {
+                // Closing the search shows the whole history again:
+                let selected_repository_path = self.selected_repository_path.borrow().clone();
+
+                if self.obj().clear_history_search() && !selected_repository_path.is_empty() {
+                    self.obj().init_commit_list(selected_repository_path);
+                }
+            }
+        }
+
+        #[template_callback]
+        fn search_changed(&self, _search_entry: &gtk::SearchEntry) {
+            if !self.is_clearing_history_search.get() {
+                self.obj().update_history_search();
+            }
+        }
+
         #[template_callback]
         fn show_commit_view(&self, _button: &gtk::Button) {
             self.obj().emit_by_name::<()>("show-commit-view", &[]);
@@ -186,7 +324,34 @@ mod imp {
                         .param_types([str::static_type()])
                         .build(),
                     Signal::builder("file-selected")
-                        .param_types([str::static_type(), str::static_type()])
+                        .param_types([str::static_type(), str::static_type(), bool::static_type()])
+                        .build(),
+                    Signal::builder("change-file-staging")
+                        .param_types([str::static_type(), bool::static_type()])
+                        .build(),
+                    Signal::builder("change-folder-staging")
+                        .param_types([str::static_type(), bool::static_type()])
+                        .build(),
+                    Signal::builder("change-all-files-staging")
+                        .param_types([bool::static_type()])
+                        .build(),
+                    Signal::builder("commit-selected")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("cherry-pick-commit")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("revert-commit")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("reset-to-commit")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("create-branch-from-commit")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("checkout-commit")
+                        .param_types([str::static_type()])
                         .build(),
                 ]
             });
@@ -197,6 +362,12 @@ mod imp {
             self.parent_constructed();
 
             self.change_from_user.set(true);
+
+            let settings = Settings::new("com.skilldary.bagit.desktop");
+            self.graph_button
+                .set_active(settings.boolean("is-commit-graph-enabled"));
+
+            self.obj().connect_commit_history_activation();
         }
     }
     impl WidgetImpl for BagitCommitsSideBar {}
@@ -223,14 +394,11 @@ impl BagitCommitsSideBar {
     /// Used to initialize the commits sidebar.
     pub fn init_commits_sidebar(&self) {
         self.select_changed_files_stack();
-        self.imp().change_from_file.set(false);
         self.clear_changed_files_list();
 
-        // We force the state of the selection button:
-        if !self.imp().select_by_default_button.is_active() {
-            self.imp().change_from_user.set(false);
-            self.imp().select_by_default_button.set_active(true);
-        }
+        // The search of the previous repository is not kept:
+        self.clear_history_search();
+        self.imp().search_button.set_active(false);
     }
 
     /// Sets up the commit list by creating a new `gio::ListStore` model to hold commit objects.
@@ -245,10 +413,77 @@ impl BagitCommitsSideBar {
             .set_model(Some(&selection_model));
     }
 
+    /// Sends the id of a commit when its row is clicked in the history.
+    fn connect_commit_history_activation(&self) {
+        self.imp()
+            .commit_history_list
+            .set_single_click_activate(true);
+
+        self.imp().commit_history_list.connect_activate(clone!(
+            @weak self as win
+            => move |list_view, position| {
+                let commit_object = match list_view.model() {
+                    Some(model) => model.item(position).and_downcast::<CommitObject>(),
+                    None => None,
+                };
+
+                if let Some(commit_object) = commit_object {
+                    win.emit_by_name::<()>("commit-selected", &[&commit_object.commit_id()]);
+                }
+            }
+        ));
+    }
+
+    /// Shows the actions available on a commit of the history in a menu pointing at a position of its row.
+    fn show_commit_menu(&self, row: &gtk::Box, commit_id: String, x: f64, y: f64) {
+        let popover = gtk::Popover::new();
+        popover.set_has_arrow(false);
+        popover.set_pointing_to(Some(&gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+
+        let menu_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+
+        for (label, signal_name) in [
+            (gettext("_Cherry-pick commit"), "cherry-pick-commit"),
+            (gettext("_Revert commit"), "revert-commit"),
+            (gettext("_Reset branch to commit"), "reset-to-commit"),
+            (
+                gettext("_Create branch from commit"),
+                "create-branch-from-commit",
+            ),
+            (gettext("_Checkout commit"), "checkout-commit"),
+        ] {
+            let button_label = Label::new(Some(&label));
+            button_label.set_halign(Align::Start);
+
+            let button = gtk::Button::new();
+            button.set_child(Some(&button_label));
+            button.add_css_class("flat");
+            button.connect_clicked(clone!(
+                @weak self as win,
+                @weak popover,
+                @strong commit_id
+                => move |_| {
+                    popover.popdown();
+                    win.emit_by_name::<()>(signal_name, &[&commit_id]);
+                }
+            ));
+
+            menu_box.append(&button);
+        }
+
+        popover.set_child(Some(&menu_box));
+        popover.set_parent(row);
+        // The menu is only attached to the row while it is shown:
+        popover.connect_closed(|popover| {
+            popover.unparent();
+        });
+        popover.popup();
+    }
+
     /// Sets up a `SignalListItemFactory` for creating custom commit list item views.
     fn setup_commit_list_factory(&self) {
         let factory: SignalListItemFactory = SignalListItemFactory::new();
-        factory.connect_setup(move |_, list_item: &glib::Object| {
+        factory.connect_setup(clone!(@weak self as win => move |_, list_item: &glib::Object| {
             let title: Label = Label::new(Some("Title"));
             let subtitle: Label = Label::new(Some("Subtitle"));
 
@@ -256,11 +491,15 @@ impl BagitCommitsSideBar {
             row.set_valign(gtk::Align::Center);
             row.set_hexpand(true);
 
-            row.set_margin_top(12);
-            row.set_margin_bottom(12);
+            // The graph takes the whole height of the row, to join the lines of the next rows:
+            let graph_area: gtk::DrawingArea = gtk::DrawingArea::new();
+            graph_area.set_visible(false);
+            row.append(&graph_area);
 
             let text_box: gtk::Box = gtk::Box::new(gtk::Orientation::Vertical, 8);
             text_box.set_hexpand(true);
+            text_box.set_margin_top(12);
+            text_box.set_margin_bottom(12);
 
             title.add_css_class("heading");
             title.set_property("halign", Align::Start);
@@ -269,7 +508,11 @@ impl BagitCommitsSideBar {
             subtitle.set_property("halign", Align::Start);
             subtitle.set_ellipsize(EllipsizeMode::End);
 
+            let labels_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+            labels_box.set_visible(false);
+
             text_box.append(&title);
+            text_box.append(&labels_box);
             text_box.append(&subtitle);
 
             row.append(&text_box);
@@ -283,11 +526,27 @@ impl BagitCommitsSideBar {
             local_image.set_hexpand(true);
             row.append(&local_image);
 
-            list_item
+            let list_item = list_item
                 .downcast_ref::<ListItem>()
-                .expect("Needs to be ListItem")
-                .set_child(Some(&row));
-        });
+                .expect("Needs to be ListItem");
+
+            // The actions on the commit are shown with a right click on its row:
+            let menu_gesture = gtk::GestureClick::new();
+            menu_gesture.set_button(gdk::BUTTON_SECONDARY);
+            menu_gesture.connect_pressed(clone!(
+                @weak win,
+                @weak row,
+                @weak list_item
+                => move |_, _, x, y| {
+                    if let Some(commit_object) = list_item.item().and_downcast::<CommitObject>() {
+                        win.show_commit_menu(&row, commit_object.commit_id(), x, y);
+                    }
+                }
+            ));
+            row.add_controller(menu_gesture);
+
+            list_item.set_child(Some(&row));
+        }));
 
         factory.connect_bind(move |_, list_item: &glib::Object| {
             // Get `CommitObject` from `ListItem`
@@ -298,48 +557,76 @@ impl BagitCommitsSideBar {
                 .and_downcast::<CommitObject>()
                 .expect("The item has to be a `CommitObject`.");
 
-            // Get `title` from `ListItem`
-            let title: Label = list_item
+            // Get the row from `ListItem`
+            let row: gtk::Box = list_item
                 .downcast_ref::<ListItem>()
                 .expect("Needs to be ListItem")
                 .child()
                 .and_downcast::<gtk::Box>()
-                .expect("The child has to be a `Box`.")
+                .expect("The child has to be a `Box`.");
+
+            let graph_area: gtk::DrawingArea = row
                 .first_child()
+                .and_downcast::<gtk::DrawingArea>()
+                .expect("First child of `Box` has to be a `DrawingArea`.");
+
+            let text_box: gtk::Box = graph_area
+                .next_sibling()
                 .and_downcast::<gtk::Box>()
-                .expect("First child of `Box` has to be a `Box`.")
+                .expect("Second child of `Box` has to be a `Box`.");
+
+            let title: Label = text_box
                 .first_child()
                 .and_downcast::<gtk::Label>()
                 .expect("First child of `Box` has to be a `Label`.");
 
-            // Get `subtitle` from `ListItem`
-            let subtitle: Label = list_item
-                .downcast_ref::<ListItem>()
-                .expect("Needs to be ListItem")
-                .child()
+            let labels_box: gtk::Box = title
+                .next_sibling()
                 .and_downcast::<gtk::Box>()
-                .expect("The child has to be a `Box`.")
-                .first_child()
-                .and_downcast::<gtk::Box>()
-                .expect("First child of `Box` has to be a `Box`.")
+                .expect("Second child of `Box` has to be a `Box`.");
+
+            let subtitle: Label = text_box
                 .last_child()
                 .and_downcast::<gtk::Label>()
                 .expect("Last child of `Box` has to be a `Label`.");
 
             // Get 'is_pushed image' from 'ListItem'
-            let is_pushed_image: gtk::Image = list_item
-                .downcast_ref::<ListItem>()
-                .expect("Needs to be ListItem")
-                .child()
-                .and_downcast::<gtk::Box>()
-                .expect("The child has to be a `Box`.")
+            let is_pushed_image: gtk::Image = row
                 .last_child()
                 .and_downcast::<gtk::Image>()
                 .expect("Last child of `Box` has to be an `Image`.");
 
             title.set_label(&commit_object.title());
             subtitle.set_label(&commit_object.subtitle());
-            is_pushed_image.set_visible(!commit_object.is_pushed())
+            is_pushed_image.set_visible(!commit_object.is_pushed());
+
+            while let Some(label) = labels_box.first_child() {
+                labels_box.remove(&label);
+            }
+
+            match commit_object.graph_row() {
+                Some(graph_row) => {
+                    for ref_label in &graph_row.ref_labels {
+                        let label = Label::new(Some(&ref_label.get_name()));
+                        label.set_ellipsize(EllipsizeMode::End);
+                        label.add_css_class("ref-label");
+                        label.add_css_class(ref_label.get_css_class());
+                        labels_box.append(&label);
+                    }
+                    labels_box.set_visible(!graph_row.ref_labels.is_empty());
+
+                    graph_area
+                        .set_content_width((graph_row.lane_count as f64 * GRAPH_LANE_WIDTH) as i32);
+                    graph_area.set_draw_func(move |_, context, _, height| {
+                        draw_commit_graph_row(&graph_row, context, height as f64);
+                    });
+                    graph_area.set_visible(true);
+                }
+                None => {
+                    labels_box.set_visible(false);
+                    graph_area.set_visible(false);
+                }
+            }
         });
 
         // TODO: Unbind.
@@ -355,34 +642,79 @@ impl BagitCommitsSideBar {
     ) {
         let repository: Repository = Repository::open(selected_repository_path).unwrap();
 
-        let checked_out_branch_name;
+        let starting_commit_id: String = self.imp().last_commit_oid_of_commit_list.take();
 
-        match get_repository_checked_out_branch_name(&repository) {
-            Ok(repository_checked_out_branch_name) => {
-                checked_out_branch_name = repository_checked_out_branch_name
+        let newly_loaded_commits: Vec<CommitObject> = if self.imp().graph_button.is_active() {
+            // The graph shows every branch, it is started again when references moved,
+            // as its lanes would not match the next commits:
+            if is_commit_graph_outdated(&repository, &self.imp().commit_graph.borrow()) {
+                self.imp().commit_graph.replace(CommitGraph::default());
+                self.commits().remove_all();
             }
-            Err(error) => {
-                tracing::warn!(
-                    "Could not get the name of the checked out branch: {}",
-                    error
-                );
 
-                return;
+            match load_commit_graph_history(
+                &repository,
+                &mut self.imp().commit_graph.borrow_mut(),
+                nb_commits_to_load as usize,
+            ) {
+                Ok(commits) => commits,
+                Err(error) => {
+                    tracing::warn!("Could not load the commit graph: {}", error);
+
+                    return;
+                }
             }
-        }
+        } else if RepositoryUtils::is_head_unborn(&repository) {
+            // Before the first commit, there is no history to load:
+            Vec::new()
+        } else if RepositoryUtils::is_head_detached(&repository) {
+            // A detached HEAD is not on any branch, its history starts from the commit itself:
+            match load_detached_head_history(
+                &repository,
+                starting_commit_id.to_string(),
+                nb_commits_to_load,
+            ) {
+                Ok(commits) => commits,
+                Err(error) => {
+                    tracing::warn!("Could not load the history of the detached HEAD: {}", error);
 
-        let branch: git2::Branch<'_> = repository
-            .find_branch(&checked_out_branch_name, git2::BranchType::Local)
-            .unwrap();
+                    return;
+                }
+            }
+        } else {
+            let checked_out_branch_name;
 
-        let starting_commit_id: String = self.imp().last_commit_oid_of_commit_list.take();
+            match get_repository_checked_out_branch_name(&repository) {
+                Ok(repository_checked_out_branch_name) => {
+                    checked_out_branch_name = repository_checked_out_branch_name
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        "Could not get the name of the checked out branch: {}",
+                        error
+                    );
 
-        let newly_loaded_commits: Vec<CommitObject> = load_commit_history(
-            &repository,
-            branch,
-            starting_commit_id.to_string(),
-            nb_commits_to_load,
-        );
+                    return;
+                }
+            }
+
+            let branch: git2::Branch<'_> =
+                match repository.find_branch(&checked_out_branch_name, git2::BranchType::Local) {
+                    Ok(branch) => branch,
+                    Err(error) => {
+                        tracing::warn!("Could not find the checked out branch: {}", error);
+
+                        return;
+                    }
+                };
+
+            load_commit_history(
+                &repository,
+                branch,
+                starting_commit_id.to_string(),
+                nb_commits_to_load,
+            )
+        };
 
         if self.commits().n_items() == 0 {
             let commits_to_push: i32 = newly_loaded_commits
@@ -400,6 +732,7 @@ impl BagitCommitsSideBar {
             self.imp()
                 .last_commit_oid_of_commit_list
                 .replace(starting_commit_id);
+            self.update_empty_history_state();
 
             return;
         }
@@ -414,6 +747,19 @@ impl BagitCommitsSideBar {
             .replace(new_starting_commit_id);
 
         self.commits().extend(newly_loaded_commits);
+        self.update_empty_history_state();
+    }
+
+    /// Shows an empty state instead of the commit list when there is no commit to show,
+    /// e.g. before the first commit of a branch.
+    fn update_empty_history_state(&self) {
+        let is_history_empty =
+            self.commits().n_items() == 0 && self.imp().history_search.borrow().is_empty();
+
+        self.imp().empty_history_page.set_visible(is_history_empty);
+        self.imp()
+            .scrolled_window_commit_history
+            .set_visible(!is_history_empty);
     }
 
     /// Sets up the callback for the infinite scroll.
@@ -432,7 +778,10 @@ impl BagitCommitsSideBar {
             .imp()
             .scrolled_window_commit_history
             .connect_edge_reached(move |_, pos: gtk::PositionType| match pos {
-                gtk::PositionType::Bottom => {
+                // The found commits are all added by the search itself:
+                gtk::PositionType::Bottom
+                    if self_clone.imp().history_search.borrow().is_empty() =>
+                {
                     self_clone.add_commits_to_history(25, selected_repository_path.clone());
                 }
                 _ => {}
@@ -472,29 +821,229 @@ impl BagitCommitsSideBar {
 
         let first_commit_id = get_first_commit_id_of_checked_out_branch(&repository);
 
-        match first_commit_id {
-            Some(id) => {
-                self.imp()
-                    .first_commit_oid_of_commit_list
-                    .replace(id.to_string());
-            }
-            None => {}
-        }
+        // A branch without any commit yet has no first commit:
+        self.imp()
+            .first_commit_oid_of_commit_list
+            .replace(first_commit_id.map(|id| id.to_string()).unwrap_or_default());
     }
 
     /// Initialize the commit list.
     pub fn init_commit_list(&self, selected_repository_path: String) {
+        self.imp()
+            .selected_repository_path
+            .replace(selected_repository_path.clone());
+        self.imp().commit_graph.replace(CommitGraph::default());
+        self.cancel_history_search();
+
+        if self.imp().graph_button.is_active() {
+            self.imp().commit_history_list.add_css_class("commit-graph");
+        } else {
+            self.imp()
+                .commit_history_list
+                .remove_css_class("commit-graph");
+        }
+
         self.setup_first_and_last_commit(selected_repository_path.clone());
 
         self.setup_commit_list();
 
         self.setup_commit_list_factory();
 
-        self.add_commits_to_history(25, selected_repository_path.clone());
+        if self.imp().history_search.borrow().is_empty() {
+            self.add_commits_to_history(25, selected_repository_path.clone());
+        } else {
+            self.update_empty_history_state();
+            self.search_history(selected_repository_path.clone());
+        }
 
         self.setup_infinite_scroll(selected_repository_path);
     }
 
+    /// Gets the search fields of the history.
+    fn get_history_search_entries(&self) -> Vec<gtk::SearchEntry> {
+        return vec![
+            self.imp().message_search_entry.get(),
+            self.imp().author_search_entry.get(),
+            self.imp().since_search_entry.get(),
+            self.imp().until_search_entry.get(),
+            self.imp().path_search_entry.get(),
+            self.imp().commit_id_search_entry.get(),
+        ];
+    }
+
+    /// Reads a day of a date range of the history search, and marks its field when it is invalid.
+    fn parse_search_day(search_entry: &gtk::SearchEntry) -> Result<Option<NaiveDate>, ()> {
+        let text = search_entry.text().trim().to_string();
+
+        if text.is_empty() {
+            search_entry.remove_css_class("error");
+            return Ok(None);
+        }
+
+        match NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+            Ok(day) => {
+                search_entry.remove_css_class("error");
+                return Ok(Some(day));
+            }
+            Err(_) => {
+                search_entry.add_css_class("error");
+                return Err(());
+            }
+        }
+    }
+
+    /// Retrieves the search written in the search fields, if its dates are valid.
+    fn get_history_search(&self) -> Option<HistorySearch> {
+        let since = Self::parse_search_day(&self.imp().since_search_entry);
+        let until = Self::parse_search_day(&self.imp().until_search_entry);
+
+        return match (since, until) {
+            (Ok(since), Ok(until)) => Some(HistorySearch {
+                message: self.imp().message_search_entry.text().trim().to_string(),
+                author: self.imp().author_search_entry.text().trim().to_string(),
+                since,
+                until,
+                path: self.imp().path_search_entry.text().trim().to_string(),
+                commit_id_prefix: self.imp().commit_id_search_entry.text().trim().to_string(),
+            }),
+            _ => None,
+        };
+    }
+
+    /// Starts a new search in the history when the search fields changed.
+    /// The whole history is shown again when every field is empty.
+    pub fn update_history_search(&self) {
+        let history_search = match self.get_history_search() {
+            Some(history_search) => history_search,
+            None => return,
+        };
+
+        if *self.imp().history_search.borrow() == history_search {
+            return;
+        }
+
+        self.imp().history_search.replace(history_search);
+
+        let selected_repository_path = self.imp().selected_repository_path.borrow().clone();
+
+        if !selected_repository_path.is_empty() {
+            self.init_commit_list(selected_repository_path);
+        }
+    }
+
+    /// Empties the search fields of the history.
+    /// Returns whether there was a search to clear.
+    fn clear_history_search(&self) -> bool {
+        self.imp().is_clearing_history_search.set(true);
+
+        for search_entry in self.get_history_search_entries() {
+            search_entry.set_text("");
+            search_entry.remove_css_class("error");
+        }
+
+        self.imp().is_clearing_history_search.set(false);
+
+        return !self
+            .imp()
+            .history_search
+            .replace(HistorySearch::default())
+            .is_empty();
+    }
+
+    /// Stops the running search in the history, if any.
+    fn cancel_history_search(&self) {
+        self.imp()
+            .is_history_search_cancelled
+            .borrow()
+            .store(true, Ordering::Relaxed);
+
+        self.imp().search_spinner.stop();
+        self.imp().search_spinner.set_visible(false);
+        self.imp().search_status_label.set_visible(false);
+    }
+
+    /// Searches the history in a background thread, adding the found commits to the list as they come.
+    fn search_history(&self, selected_repository_path: String) {
+        let history_search = self.imp().history_search.borrow().clone();
+        let is_searching_every_branch = self.imp().graph_button.is_active();
+
+        let is_cancelled = Arc::new(AtomicBool::new(false));
+        self.imp()
+            .is_history_search_cancelled
+            .replace(is_cancelled.clone());
+
+        self.imp().search_spinner.set_visible(true);
+        self.imp().search_spinner.start();
+
+        let (sender, receiver) = MainContext::channel::<HistorySearchProgress>(Priority::default());
+
+        let thread_is_cancelled = is_cancelled.clone();
+
+        thread::spawn(move || {
+            let result = match Repository::open(selected_repository_path) {
+                Ok(repository) => search_commit_history(
+                    &repository,
+                    &history_search,
+                    is_searching_every_branch,
+                    thread_is_cancelled,
+                    |found_commits| {
+                        // The receiver is gone when the search has been replaced by another one:
+                        let _ = sender.send(HistorySearchProgress::Found(found_commits));
+                    },
+                ),
+                Err(error) => Err(error),
+            };
+
+            let _ = sender.send(match result {
+                Ok(_) => HistorySearchProgress::Finished,
+                Err(error) => HistorySearchProgress::Failed(error.to_string()),
+            });
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+                move |progress| {
+                    if is_cancelled.load(Ordering::Relaxed) {
+                        return Continue(false);
+                    }
+
+                    let status = match progress {
+                        HistorySearchProgress::Found(found_commits) => {
+                            let commit_objects: Vec<CommitObject> = found_commits
+                                .iter()
+                                .map(|found_commit| found_commit.to_commit_object())
+                                .collect();
+
+                            win.commits().extend(commit_objects);
+
+                            return Continue(true);
+                        }
+                        HistorySearchProgress::Finished => {
+                            match win.commits().n_items() {
+                                0 => gettext("_No commit found"),
+                                nb_found_commits => gettext("_Commits found")
+                                    .replace("{}", &nb_found_commits.to_string()),
+                            }
+                        }
+                        HistorySearchProgress::Failed(error) => {
+                            tracing::warn!("Could not search in the history: {}", error);
+
+                            gettext("_Could not search in the history")
+                        }
+                    };
+
+                    win.imp().search_spinner.stop();
+                    win.imp().search_spinner.set_visible(false);
+                    win.imp().search_status_label.set_label(&status);
+                    win.imp().search_status_label.set_visible(true);
+
+                    Continue(false)
+                }
+            ),
+        );
+    }
+
     /// Checks whether the internal checked out branch matches the actual
     /// checked out branch.
     ///
@@ -541,16 +1090,9 @@ impl BagitCommitsSideBar {
 
         let first_commit_id = get_first_commit_id_of_checked_out_branch(repository);
 
-        match first_commit_id {
-            Some(id) => {
-                if id.to_string() != first_commit_oid_of_commit_list {
-                    return false;
-                }
-            }
-            None => {}
-        }
-
-        return true;
+        // The first commit of a branch which had none makes the list outdated too:
+        return first_commit_id.map(|id| id.to_string()).unwrap_or_default()
+            == first_commit_oid_of_commit_list;
     }
 
     /// Refreshes the commit list if needed.
@@ -573,6 +1115,23 @@ impl BagitCommitsSideBar {
         };
     }
 
+    /// Checks if the last commit of the checked out branch is on the upstream branch.
+    pub fn is_last_commit_pushed(&self) -> bool {
+        let last_commit_id = self.imp().first_commit_oid_of_commit_list.borrow().clone();
+        let commits = self.commits();
+
+        // The last commit of the branch is not always the first of the list when showing the graph:
+        for position in 0..commits.n_items() {
+            if let Some(commit) = commits.item(position).and_downcast::<CommitObject>() {
+                if commit.commit_id() == last_commit_id {
+                    return commit.is_pushed();
+                }
+            }
+        }
+
+        return false;
+    }
+
     /**
      * Used to clear changed files list for UI.
      */
@@ -592,16 +1151,46 @@ impl BagitCommitsSideBar {
     }
 
     /**
-     * Used to generate an add button.
+     * Used to generate an add button, active when the changes are staged.
      */
-    fn generate_add_button(&self, is_selected: bool) -> gtk::CheckButton {
+    fn generate_add_button(&self, is_staged: bool) -> gtk::CheckButton {
         let add_button = gtk::CheckButton::new();
-        add_button.set_active(is_selected);
-        add_button.set_visible(is_selected);
+        add_button.set_active(is_staged);
+        add_button.set_visible(is_staged);
+        add_button.set_tooltip_text(Some(&if is_staged {
+            gettext("_Unstage")
+        } else {
+            gettext("_Stage")
+        }));
 
         return add_button;
     }
 
+    /**
+     * Used to generate the header of the staged or the unstaged changes.
+     */
+    pub fn generate_section_header(&self, is_staged: bool, total_files: usize) {
+        let title = if is_staged {
+            gettext("_Staged changes")
+        } else {
+            gettext("_Unstaged changes")
+        };
+
+        let label = gtk::Label::new(Some(&format!("{} ({})", title, total_files)));
+        label.set_halign(gtk::Align::Start);
+        label.set_margin_top(8);
+        label.set_margin_bottom(4);
+        label.set_margin_start(8);
+        label.add_css_class("heading");
+        label.add_css_class("dim-label");
+
+        let row = gtk::ListBoxRow::new();
+        row.set_activatable(false);
+        row.set_selectable(false);
+        row.set_child(Some(&label));
+        self.imp().menu.append(&row);
+    }
+
     /**
      * Used to generate a default discard button.
      */
@@ -617,7 +1206,7 @@ impl BagitCommitsSideBar {
     /**
      * Used to generate folder with files.
      */
-    pub fn generate_folder(&self, folder: ChangedFolder, files: Vec<ChangedFile>) {
+    pub fn generate_folder(&self, folder: ChangedFolder, files: Vec<ChangedFile>, is_staged: bool) {
         let row = adw::ActionRow::new();
 
         let main_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
@@ -625,8 +1214,8 @@ impl BagitCommitsSideBar {
         let revealer = gtk::Revealer::new();
         revealer.set_reveal_child(folder.is_expanded);
         let file_list = gtk::ListBox::new();
+        file_list.set_selection_mode(gtk::SelectionMode::None);
         file_list.add_css_class("background");
-        let mut file_add_button_list: Vec<gtk::CheckButton> = Vec::new();
 
         let folder_label = gtk::Label::new(Some(&folder.path));
         //folder_label.set_max_width_chars(20);
@@ -661,12 +1250,7 @@ impl BagitCommitsSideBar {
         choice_box.set_halign(gtk::Align::End);
         choice_box.set_margin_end(12);
 
-        let add_button: gtk::CheckButton;
-        {
-            let borrowed_tree = self.imp().changed_files.borrow();
-            add_button = self
-                .generate_add_button(borrowed_tree.are_all_files_in_folder_selected(&folder.path))
-        }
+        let add_button = self.generate_add_button(is_staged);
 
         let discard_folder_path = folder.path.clone();
         let discard_button = self.generate_discard_button();
@@ -706,68 +1290,16 @@ impl BagitCommitsSideBar {
         main_box.append(&folder_box);
 
         for file in &files {
-            let new_file_row = self.generate_changed_file(&file, 30, 6, Some(add_button.clone()));
-            file_list.append(&new_file_row.0);
-            if file.is_opened {
-                file_list.select_row(Some(&new_file_row.0));
-            }
-            file_add_button_list.push(new_file_row.1);
+            let new_file_row = self.generate_changed_file(&file, 30, 6);
+            file_list.append(&new_file_row);
         }
         revealer.set_child(Some(&file_list));
 
-        // We now set a listener on the file list for when a file is clicked :
-        file_list.connect_row_selected(clone!(
-            @weak self as win,
-            @weak folder_label
-            => move |list, clicked_row| {
-                if let Some(row) = clicked_row {
-
-                    list.unselect_all();
-
-                    let parent_folder_name = folder_label.text();
-                    let file_name = row
-                    .child()
-                    .and_downcast::<gtk::Box>()
-                    .expect("The child has to be a `Box`.")
-                    .first_child()
-                    .expect("The box should at least one child.")
-                    .next_sibling()
-                    .and_downcast::<gtk::Label>()
-                    .expect("The second child of the box should be a `Label`.")
-                    .text();
-
-                    win.emit_by_name::<()>("file-selected", &[
-                        &parent_folder_name,
-                        &file_name
-                    ]);
-                }
-            }
-        ));
-
-        let clone_add_button_list = file_add_button_list.clone();
+        let staged_folder_path = folder.path.clone();
         add_button.connect_toggled(clone!(
             @weak self as win
             => move |button| {
-                if !win.imp().change_from_file.get() {
-                    let mut legacy_list = win.imp().changed_files.take();
-
-                    legacy_list.set_selection_of_files_in_folder(&folder.path,button.is_active());
-                    win.emit_by_name::<()>("update-file-information-label", &[&legacy_list.get_number_of_selected_files()]);
-
-                    let are_all_files_selected = legacy_list.are_all_files_selected();
-                    if win.imp().select_by_default_button.is_active() != are_all_files_selected {
-                        win.imp().change_from_user.set(false);
-                        win.imp().select_by_default_button.set_active(are_all_files_selected);
-                    }
-
-                    win.imp().changed_files.replace(legacy_list);
-
-                    for file in &clone_add_button_list {
-                        file.set_active(button.is_active());
-                        file.set_visible(button.is_active());
-                    }
-                    win.imp().change_from_file.set(false);
-                }
+                win.emit_by_name::<()>("change-folder-staging", &[&staged_folder_path, &button.is_active()]);
         }));
 
         main_box.append(&revealer);
@@ -783,9 +1315,9 @@ impl BagitCommitsSideBar {
         file: &ChangedFile,
         margin_start: i32,
         margin_end: i32,
-        parent_folder_add_button: Option<gtk::CheckButton>,
-    ) -> (adw::ActionRow, gtk::CheckButton) {
+    ) -> adw::ActionRow {
         let row = adw::ActionRow::new();
+        row.set_activatable(true);
         let label = gtk::Label::new(Some(&file.name));
         label.set_halign(gtk::Align::Start);
         label.set_margin_top(8);
@@ -802,40 +1334,23 @@ impl BagitCommitsSideBar {
         choice_box.set_halign(gtk::Align::End);
         choice_box.set_margin_end(margin_end);
 
-        let add_button = self.generate_add_button(file.is_selected);
-
-        let file_clone = file.clone();
-        let parent_clone = parent_folder_add_button.clone();
+        let add_button = self.generate_add_button(file.is_staged);
+        let staged_file_path = RepositoryUtils::build_path_of_file(&file.parent, &file.name);
         add_button.connect_toggled(clone!(
             @weak self as win,
             => move |button| {
-                win.imp().change_from_file.set(true);
-                let mut legacy_list = win.imp().changed_files.take();
-
-                let mut new_file_info = file_clone.clone();
-                new_file_info.is_selected = button.is_active();
-
-                legacy_list.change_file_information(&new_file_info);
-                win.emit_by_name::<()>("update-file-information-label", &[&legacy_list.get_number_of_selected_files()]);
-
-                let are_all_files_selected = legacy_list.are_all_files_selected();
-                if win.imp().select_by_default_button.is_active() != are_all_files_selected {
-                    win.imp().change_from_user.set(false);
-                    win.imp().select_by_default_button.set_active(are_all_files_selected);
-                }
-
-                let cloned_list = legacy_list.clone();
+                win.emit_by_name::<()>("change-file-staging", &[&staged_file_path, &button.is_active()]);
+        }));
 
-                win.imp().changed_files.replace(legacy_list);
-                if parent_clone.is_some() {
-                    let parent_unwrap = parent_clone.clone().unwrap();
-                    let new_selection = cloned_list.are_all_files_in_folder_selected(&file_clone.parent);
-                    if parent_unwrap.is_active() != new_selection {
-                        parent_clone.clone().unwrap().set_active(new_selection);
-                        parent_clone.clone().unwrap().set_visible(new_selection);
-                    }
-                }
-                win.imp().change_from_file.set(false);
+        let selected_file = file.clone();
+        row.connect_activated(clone!(
+            @weak self as win,
+            => move |_row| {
+                win.emit_by_name::<()>("file-selected", &[
+                    &selected_file.parent,
+                    &selected_file.name,
+                    &selected_file.is_staged,
+                ]);
         }));
 
         let discard_file_clone = file.clone();
@@ -876,10 +1391,15 @@ impl BagitCommitsSideBar {
         row.add_controller(controller);
 
         let css_class_name: &str;
-        let icon_tooltip_text: String;
-        let icon_name: &str;
+        let mut icon_tooltip_text: String;
+        let mut icon_name: &str;
 
         match file.status.clone() {
+            status if status.is_conflicted() => {
+                css_class_name = "error";
+                icon_name = "dialog-warning-symbolic";
+                icon_tooltip_text = gettext("_Conflicted file");
+            }
             Status::WT_MODIFIED | Status::INDEX_MODIFIED => {
                 css_class_name = "warning";
                 icon_name = "panel-modified-symbolic";
@@ -912,6 +1432,11 @@ impl BagitCommitsSideBar {
             }
         };
 
+        if file.is_submodule {
+            icon_name = "folder-remote-symbolic";
+            icon_tooltip_text = format!("{} {}", gettext("_Submodule"), icon_tooltip_text);
+        }
+
         label.add_css_class(&css_class_name);
         let icon = gtk::Image::from_icon_name(&icon_name);
         icon.set_margin_start(margin_start);
@@ -923,7 +1448,7 @@ impl BagitCommitsSideBar {
         main_box.append(&choice_box);
         row.set_child(Some(&main_box));
 
-        return (row, add_button);
+        return row;
     }
 
     /**
@@ -963,13 +1488,24 @@ impl BagitCommitsSideBar {
     }
 
     /**
-     * Used to build a HashMap of parent with files.
+     * Used to build the HashMaps of parent with files, one for the staged changes and one for the unstaged ones.
+     * A file with both staged and unstaged changes is part of both.
+     * Changed paths found in the submodule paths are marked as submodules.
      */
-    pub fn build_hash_map(&self, statuses: Statuses<'_>) -> HashMap<String, Vec<ChangedFile>> {
-        let mut hash_map: HashMap<String, Vec<ChangedFile>> = HashMap::new();
+    pub fn build_hash_maps(
+        &self,
+        statuses: Statuses<'_>,
+        submodule_paths: &Vec<String>,
+    ) -> (
+        HashMap<String, Vec<ChangedFile>>,
+        HashMap<String, Vec<ChangedFile>>,
+    ) {
+        let mut staged_hash_map: HashMap<String, Vec<ChangedFile>> = HashMap::new();
+        let mut unstaged_hash_map: HashMap<String, Vec<ChangedFile>> = HashMap::new();
         let borrowed_changed_files = self.imp().changed_files.take();
         let mut new_file_list: Vec<ChangedFile> = Vec::new();
         let mut new_folder_list: Vec<ChangedFolder> = Vec::new();
+        let mut total_changed_paths = 0;
 
         for i in 0..statuses.len() {
             let change = statuses.get(i).unwrap();
@@ -979,52 +1515,79 @@ impl BagitCommitsSideBar {
             let status = change.status();
 
             // We only take files and folders that ain't in a gitignore file.
-            if !status.is_ignored() {
+            if status.is_ignored() {
+                continue;
+            }
+            total_changed_paths += 1;
+
+            let mut current_folder = ChangedFolder::new(parent.clone(), true);
+            if let Some(found_folder) = borrowed_changed_files.get_changed_folder_from_list(&parent)
+            {
+                current_folder.is_expanded = found_folder.is_expanded;
+            }
+            if !new_folder_list
+                .iter()
+                .any(|folder| folder.path == current_folder.path)
+            {
+                new_folder_list.push(current_folder);
+            }
+
+            for (section_status, is_staged) in [
+                (ChangedFile::get_staged_status(status), true),
+                (ChangedFile::get_unstaged_status(status), false),
+            ] {
+                if section_status.is_empty() {
+                    continue;
+                }
+
                 let mut current_file = ChangedFile::new(
                     parent.clone(),
-                    filename,
-                    status,
-                    self.imp().select_by_default_button.is_active(),
+                    filename.clone(),
+                    section_status,
+                    is_staged,
                     false,
                 );
-                let mut current_folder = ChangedFolder::new(parent.clone(), true);
+                current_file.is_submodule = submodule_paths.contains(&path.to_string());
 
-                let found_file = borrowed_changed_files.get_changed_file_from_list(&current_file);
-                let found_folder = borrowed_changed_files.get_changed_folder_from_list(&parent);
-
-                if found_file.is_some() {
-                    let unwraped_file = found_file.unwrap();
-                    current_file.is_selected = unwraped_file.is_selected;
-                    current_file.is_opened = unwraped_file.is_opened;
+                if let Some(found_file) =
+                    borrowed_changed_files.get_changed_file_from_list(&current_file)
+                {
+                    current_file.is_opened = found_file.is_opened;
                 }
 
-                if found_folder.is_some() {
-                    current_folder.is_expanded = found_folder.unwrap().is_expanded;
-                }
-                if !hash_map.contains_key(&parent) {
-                    hash_map.insert(parent, vec![current_file.clone()]);
-                    new_folder_list.push(current_folder.clone());
+                let hash_map = if is_staged {
+                    &mut staged_hash_map
                 } else {
-                    hash_map
-                        .get_mut(&parent)
-                        .unwrap()
-                        .push(current_file.clone());
-                }
-                new_file_list.push(current_file.clone());
+                    &mut unstaged_hash_map
+                };
+                hash_map
+                    .entry(parent.clone())
+                    .or_insert_with(Vec::new)
+                    .push(current_file.clone());
+                new_file_list.push(current_file);
             }
         }
-        self.update_changed_files_indicator(new_file_list.len().try_into().unwrap());
+        self.update_changed_files_indicator(total_changed_paths);
 
         let new_file_tree = FileTree::new(new_file_list, new_folder_list);
 
         self.emit_by_name::<()>(
             "update-file-information-label",
-            &[&new_file_tree.get_number_of_selected_files()],
+            &[&new_file_tree.get_number_of_staged_files()],
         );
 
+        // The selection button shows whether every change is staged:
+        let are_all_files_staged = new_file_tree.are_all_files_staged();
+        if self.imp().select_by_default_button.is_active() != are_all_files_staged {
+            self.imp().change_from_user.set(false);
+            self.imp()
+                .select_by_default_button
+                .set_active(are_all_files_staged);
+        }
+
         self.imp().changed_files.replace(new_file_tree);
 
-        return hash_map;
+        return (staged_hash_map, unstaged_hash_map);
     }
 
     // Used to select the changed files stack for initializing the page.
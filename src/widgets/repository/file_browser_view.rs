@@ -0,0 +1,427 @@
+/* file_browser_view.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use adw::subclass::prelude::*;
+use gtk::glib::{self, clone};
+use gtk::prelude::*;
+
+use crate::utils::changed_folder::ChangedFolder;
+use crate::utils::file_diff::FileDiff;
+use crate::utils::tree_entry::TreeEntry;
+use crate::widgets::repository::file_view::BagitFileView;
+
+/// The indentation of the entries of a folder, relatively to the folder.
+const TREE_INDENTATION: i32 = 16;
+
+mod imp {
+
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::subclass::widget::CompositeTemplateInitializingExt;
+    use gtk::template_callbacks;
+    use gtk::CompositeTemplate;
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(
+        resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-file-browser-view.ui"
+    )]
+    pub struct BagitFileBrowserView {
+        #[template_child]
+        pub revision_drop_down: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub save_file_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub tree_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub file_view: TemplateChild<BagitFileView>,
+
+        pub revisions: RefCell<Vec<String>>,
+        pub revision: RefCell<String>,
+        // The entries shown in the tree list, with their depth in the tree.
+        pub shown_entries: RefCell<Vec<(TreeEntry, i32)>>,
+        // The folders that were expanded or collapsed, kept when changing of revision.
+        pub folders: RefCell<Vec<ChangedFolder>>,
+        pub opened_file_path: RefCell<String>,
+        pub is_updating_revisions: Cell<bool>,
+    }
+
+    #[template_callbacks]
+    impl BagitFileBrowserView {
+        #[template_callback]
+        fn tree_entry_activated(&self, row: &gtk::ListBoxRow) {
+            self.obj().activate_tree_entry(row.index());
+        }
+
+        #[template_callback]
+        fn save_file(&self, _button: &gtk::Button) {
+            let revision = self.revision.borrow().clone();
+            let file_path = self.opened_file_path.borrow().clone();
+
+            if file_path.is_empty() {
+                return;
+            }
+
+            self.obj()
+                .emit_by_name::<()>("save-file", &[&revision, &file_path]);
+        }
+    }
+
+    // The central trait for subclassing a GObject
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitFileBrowserView {
+        const NAME: &'static str = "BagitFileBrowserView";
+        type Type = super::BagitFileBrowserView;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+    impl ObjectImpl for BagitFileBrowserView {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            // The files of a revision cannot be staged:
+            self.file_view.set_staging_controls_visible(false);
+
+            let obj = self.obj();
+            self.revision_drop_down.connect_selected_notify(
+                clone!(@weak obj as view => move |drop_down| {
+                    if view.imp().is_updating_revisions.get() {
+                        return;
+                    }
+
+                    let revision = view
+                        .imp()
+                        .revisions
+                        .borrow()
+                        .get(drop_down.selected() as usize)
+                        .cloned();
+
+                    if let Some(revision) = revision {
+                        view.select_revision(&revision);
+                    }
+                }),
+            );
+        }
+
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("folder-requested")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("file-selected")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("save-file")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
+                ]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitFileBrowserView {}
+    impl BoxImpl for BagitFileBrowserView {}
+}
+glib::wrapper! {
+    pub struct BagitFileBrowserView(ObjectSubclass<imp::BagitFileBrowserView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl BagitFileBrowserView {
+    /// Shows the revisions that can be browsed and browses one of them.
+    /// Without a given revision, the browsed revision stays the same if it still exists, otherwise
+    /// the checked out commit is browsed.
+    pub fn show_revisions(&self, mut revisions: Vec<String>, selected_revision: Option<&str>) {
+        let selected_revision = match selected_revision {
+            Some(revision) => revision.to_string(),
+            None => {
+                let current_revision = self.imp().revision.borrow().clone();
+
+                if revisions.contains(&current_revision) {
+                    current_revision
+                } else {
+                    String::from("HEAD")
+                }
+            }
+        };
+
+        // A commit is not part of the branches and the tags:
+        if !revisions.contains(&selected_revision) {
+            revisions.insert(0, selected_revision.clone());
+        }
+
+        let selected_index = revisions
+            .iter()
+            .position(|revision| *revision == selected_revision)
+            .unwrap_or(0);
+
+        let revision_names: Vec<&str> =
+            revisions.iter().map(|revision| revision.as_str()).collect();
+
+        self.imp().is_updating_revisions.set(true);
+        self.imp()
+            .revision_drop_down
+            .set_model(Some(&gtk::StringList::new(&revision_names)));
+        self.imp()
+            .revision_drop_down
+            .set_selected(selected_index as u32);
+        self.imp().revisions.replace(revisions);
+        self.imp().is_updating_revisions.set(false);
+
+        self.select_revision(&selected_revision);
+    }
+
+    /// Shows the files and the folders of a folder of the browsed revision.
+    /// The entries of the root of the repository replace the whole tree.
+    pub fn show_folder_entries(&self, folder_path: &str, entries: Vec<TreeEntry>) {
+        let (insert_index, depth) = if folder_path.is_empty() {
+            self.clear_tree_list();
+            (0, 0)
+        } else {
+            match self.get_shown_entry_index(folder_path) {
+                Some(index) => (index + 1, self.imp().shown_entries.borrow()[index].1 + 1),
+                None => return,
+            }
+        };
+
+        let mut folders_to_expand: Vec<String> = vec![];
+
+        for (offset, entry) in entries.into_iter().enumerate() {
+            let is_expanded = entry.is_folder && self.is_folder_expanded(&entry.path);
+            if is_expanded {
+                folders_to_expand.push(entry.path.clone());
+            }
+
+            let row = self.build_tree_entry_row(&entry, depth, is_expanded);
+            self.imp()
+                .tree_list
+                .insert(&row, (insert_index + offset) as i32);
+            self.imp()
+                .shown_entries
+                .borrow_mut()
+                .insert(insert_index + offset, (entry, depth));
+        }
+
+        let revision = self.imp().revision.borrow().clone();
+        for folder in folders_to_expand {
+            self.emit_by_name::<()>("folder-requested", &[&revision, &folder]);
+        }
+    }
+
+    /// Shows the content of the opened file at the browsed revision.
+    pub fn show_file_content(&self, file_path: &str, content: Vec<u8>) {
+        let revision = self.imp().revision.borrow().clone();
+
+        self.imp().opened_file_path.replace(file_path.to_string());
+        self.imp()
+            .file_view
+            .show_file_content(file_path, &revision, content);
+        self.imp().save_file_button.set_sensitive(true);
+    }
+
+    /// Retrieves the file view showing the opened file.
+    pub fn file_view(&self) -> BagitFileView {
+        return self.imp().file_view.get();
+    }
+
+    /// Browses the files of a revision, starting from the root of the repository.
+    fn select_revision(&self, revision: &str) {
+        self.imp().revision.replace(revision.to_string());
+        self.imp().opened_file_path.replace(String::new());
+        self.imp().save_file_button.set_sensitive(false);
+        self.imp().file_view.show_diff("", FileDiff::default());
+        self.clear_tree_list();
+
+        self.emit_by_name::<()>("folder-requested", &[&revision, &""]);
+    }
+
+    /// Opens a file or expands or collapses a folder of the tree list.
+    fn activate_tree_entry(&self, index: i32) {
+        if index < 0 {
+            return;
+        }
+
+        let entry = match self.imp().shown_entries.borrow().get(index as usize) {
+            Some((entry, _)) => entry.clone(),
+            None => return,
+        };
+        let revision = self.imp().revision.borrow().clone();
+
+        if !entry.is_folder {
+            self.emit_by_name::<()>("file-selected", &[&revision, &entry.path]);
+            return;
+        }
+
+        let is_expanded = self.is_folder_expanded(&entry.path);
+        let changed_folder = ChangedFolder::new(entry.path.clone(), !is_expanded);
+
+        let folder_index = self
+            .imp()
+            .folders
+            .borrow()
+            .iter()
+            .position(|folder| folder.is_same_element(&changed_folder));
+        match folder_index {
+            Some(folder_index) => self.imp().folders.borrow_mut()[folder_index] = changed_folder,
+            None => self.imp().folders.borrow_mut().push(changed_folder),
+        }
+
+        if is_expanded {
+            self.remove_folder_descendants(index as usize, &entry.path);
+        }
+
+        self.update_folder_icon(index, !is_expanded);
+
+        if !is_expanded {
+            self.emit_by_name::<()>("folder-requested", &[&revision, &entry.path]);
+        }
+    }
+
+    /// Removes the rows of the files and the folders inside a shown folder.
+    fn remove_folder_descendants(&self, folder_index: usize, folder_path: &str) {
+        let descendant_prefix = format!("{}/", folder_path);
+        let first_descendant_index = folder_index + 1;
+
+        loop {
+            let is_descendant = match self
+                .imp()
+                .shown_entries
+                .borrow()
+                .get(first_descendant_index)
+            {
+                Some((entry, _)) => entry.path.starts_with(&descendant_prefix),
+                None => false,
+            };
+
+            if !is_descendant {
+                break;
+            }
+
+            self.imp()
+                .shown_entries
+                .borrow_mut()
+                .remove(first_descendant_index);
+            if let Some(row) = self
+                .imp()
+                .tree_list
+                .row_at_index(first_descendant_index as i32)
+            {
+                self.imp().tree_list.remove(&row);
+            }
+        }
+    }
+
+    /// Changes the icon of a folder row to show whether it is expanded.
+    fn update_folder_icon(&self, index: i32, is_expanded: bool) {
+        let icon = self
+            .imp()
+            .tree_list
+            .row_at_index(index)
+            .and_then(|row| row.child())
+            .and_then(|row_box| row_box.first_child())
+            .and_then(|icon| icon.downcast::<gtk::Image>().ok());
+
+        if let Some(icon) = icon {
+            icon.set_icon_name(Some(Self::get_folder_icon_name(is_expanded)));
+        }
+    }
+
+    /// Builds the row of a file or a folder, indented according to its depth in the tree.
+    fn build_tree_entry_row(
+        &self,
+        entry: &TreeEntry,
+        depth: i32,
+        is_expanded: bool,
+    ) -> gtk::ListBoxRow {
+        let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        row_box.set_margin_start(depth * TREE_INDENTATION);
+
+        let icon_name = if entry.is_folder {
+            Self::get_folder_icon_name(is_expanded)
+        } else {
+            "text-x-generic-symbolic"
+        };
+        row_box.append(&gtk::Image::from_icon_name(icon_name));
+
+        let label = gtk::Label::new(Some(&entry.name));
+        label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        label.set_xalign(0.0);
+        row_box.append(&label);
+
+        let row = gtk::ListBoxRow::new();
+        row.set_child(Some(&row_box));
+        row.set_tooltip_text(Some(&entry.path));
+
+        return row;
+    }
+
+    /// Checks if a folder has been expanded.
+    fn is_folder_expanded(&self, folder_path: &str) -> bool {
+        return self
+            .imp()
+            .folders
+            .borrow()
+            .iter()
+            .any(|folder| folder.path == folder_path && folder.is_expanded);
+    }
+
+    /// Retrieves the name of the icon of a folder.
+    fn get_folder_icon_name(is_expanded: bool) -> &'static str {
+        if is_expanded {
+            return "folder-open-symbolic";
+        }
+
+        return "folder-symbolic";
+    }
+
+    /// Retrieves the index of a shown file or folder.
+    fn get_shown_entry_index(&self, path: &str) -> Option<usize> {
+        return self
+            .imp()
+            .shown_entries
+            .borrow()
+            .iter()
+            .position(|(entry, _)| entry.path == path);
+    }
+
+    /// Removes all the rows of the tree list.
+    fn clear_tree_list(&self) {
+        self.imp().shown_entries.replace(vec![]);
+
+        let mut row = self.imp().tree_list.row_at_index(0);
+        while row != None {
+            self.imp().tree_list.remove(&row.unwrap());
+            row = self.imp().tree_list.row_at_index(0);
+        }
+    }
+}
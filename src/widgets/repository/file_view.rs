@@ -17,38 +17,186 @@
 * SPDX-License-Identifier: AGPL-3.0-only
 */
 
-use std::io::Read;
-use std::path::Path;
-
+use adw::prelude::ActionRowExt;
 use adw::subclass::prelude::*;
-use git2::{Repository, Status};
+use gettextrs::gettext;
+use git2::Repository;
+use gtk::gio::Settings;
 use gtk::glib::clone;
 use gtk::glib::subclass::Signal;
-use gtk::prelude::{TextBufferExt, TextViewExt, WidgetExt};
+use gtk::prelude::{
+    ButtonExt, ListBoxRowExt, ObjectExt, SettingsExt, StaticType, TextBufferExt,
+    TextBufferExtManual, TextViewExt, ToggleButtonExt, WidgetExt,
+};
 use gtk::subclass::widget::CompositeTemplateInitializingExt;
-use gtk::{glib, CompositeTemplate};
+use gtk::{glib, template_callbacks, CompositeTemplate};
+use itertools::Itertools;
 use once_cell::sync::Lazy;
-use sourceview5::prelude::BufferExt;
+use sourceview5::prelude::{BufferExt, ViewExt};
 use sourceview5::{Buffer, LanguageManager, StyleScheme, StyleSchemeManager};
 
+use crate::utils::blame_line::BlameLine;
+use crate::utils::file_diff::{DiffHunk, DiffLineType, FileDiff};
+use crate::utils::file_history_entry::FileHistoryEntry;
 use crate::utils::repository_utils::RepositoryUtils;
 
+/// The number of characters of a commit id shown in the blame and in the history of a file.
+const SHORT_COMMIT_ID_LENGTH: usize = 8;
+
+/// The biggest number of characters of an author name shown in the blame.
+const BLAME_AUTHOR_MAX_WIDTH: usize = 20;
+
+/// A line shown on one side of the side-by-side layout.
+#[derive(Debug, Clone)]
+struct SideBySideLine {
+    line_index: usize,
+    line_number: u32,
+    content: String,
+    is_changed: bool,
+}
+
 mod imp {
 
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
 
     use super::*;
 
     #[derive(Debug, Default, CompositeTemplate)]
     #[template(resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-file-view.ui")]
     pub struct BagitFileView {
+        #[template_child]
+        pub file_name_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub file_mode_box: TemplateChild<gtk::Box>,
+        #[template_child]
+        pub changes_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub blame_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub file_history_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub diff_source_box: TemplateChild<gtk::Box>,
+        #[template_child]
+        pub staging_buttons_box: TemplateChild<gtk::Box>,
+        #[template_child]
+        pub unstaged_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub staged_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub stage_hunk_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub stage_lines_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub diff_layout_box: TemplateChild<gtk::Box>,
+        #[template_child]
+        pub unified_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub side_by_side_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub diff_stack: TemplateChild<gtk::Stack>,
         #[template_child]
         pub source_view: TemplateChild<sourceview5::View>,
+        #[template_child]
+        pub old_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub old_source_view: TemplateChild<sourceview5::View>,
+        #[template_child]
+        pub new_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub new_source_view: TemplateChild<sourceview5::View>,
+        #[template_child]
+        pub blame_source_view: TemplateChild<sourceview5::View>,
+        #[template_child]
+        pub file_history_list: TemplateChild<gtk::ListBox>,
 
         pub file_folder: RefCell<String>,
         pub file_name: RefCell<String>,
 
+        // The path of the shown file, and the revision it is shown at when it is not the working directory one.
+        pub file_path: RefCell<String>,
+        pub revision: RefCell<Option<String>>,
+        // The whole content of the file, shown instead of differences when browsing a revision.
+        pub file_content: RefCell<Option<Vec<u8>>>,
+
+        pub are_staging_controls_hidden: Cell<bool>,
+
+        pub file_diff: RefCell<FileDiff>,
+
+        // For each line of a buffer, the hunk index and the line index in the hunk it shows.
+        // Hunk headers do not have a line index, and filler lines are not linked to anything.
+        pub unified_line_mapping: RefCell<Vec<Option<(usize, Option<usize>)>>>,
+        pub old_line_mapping: RefCell<Vec<Option<(usize, Option<usize>)>>>,
+        pub new_line_mapping: RefCell<Vec<Option<(usize, Option<usize>)>>>,
+
+        // For each line of the blame, the id of the commit that last changed it.
+        pub blame_line_mapping: RefCell<Vec<String>>,
+        pub blame_annotation_width: Cell<i32>,
+
+        pub file_history: RefCell<Vec<FileHistoryEntry>>,
+
         pub buffer: RefCell<Buffer>,
+        pub old_buffer: RefCell<Buffer>,
+        pub new_buffer: RefCell<Buffer>,
+        pub blame_buffer: RefCell<Buffer>,
+    }
+
+    #[template_callbacks]
+    impl BagitFileView {
+        #[template_callback]
+        fn diff_layout_toggled(&self, unified_button: &gtk::ToggleButton) {
+            let settings = Settings::new("com.skilldary.bagit.desktop");
+
+            settings
+                .set_boolean("is-diff-side-by-side-enabled", !unified_button.is_active())
+                .expect("Could not set setting.");
+
+            self.obj().show_current_diff();
+        }
+
+        #[template_callback]
+        fn diff_source_toggled(&self, unstaged_button: &gtk::ToggleButton) {
+            if unstaged_button.is_active() {
+                self.stage_hunk_button.set_label(&gettext("_Stage hunk"));
+                self.stage_lines_button.set_label(&gettext("_Stage lines"));
+            } else {
+                self.stage_hunk_button.set_label(&gettext("_Unstage hunk"));
+                self.stage_lines_button
+                    .set_label(&gettext("_Unstage lines"));
+            }
+
+            self.obj().emit_by_name::<()>("diff-source-changed", &[]);
+        }
+
+        #[template_callback]
+        fn file_mode_toggled(&self, mode_button: &gtk::ToggleButton) {
+            // The button of the previous mode is toggled too:
+            if mode_button.is_active() {
+                self.obj().show_current_file_mode();
+            }
+        }
+
+        #[template_callback]
+        fn file_history_row_activated(&self, row: &gtk::ListBoxRow) {
+            let commit_id = match self.file_history.borrow().get(row.index() as usize) {
+                Some(file_history_entry) => file_history_entry.commit_id.clone(),
+                None => return,
+            };
+
+            self.obj()
+                .emit_by_name::<()>("commit-selected", &[&commit_id]);
+        }
+
+        #[template_callback]
+        fn stage_hunk(&self, _stage_hunk_button: &gtk::Button) {
+            self.obj()
+                .emit_by_name::<()>("change-lines-staging", &[&true]);
+        }
+
+        #[template_callback]
+        fn stage_lines(&self, _stage_lines_button: &gtk::Button) {
+            self.obj()
+                .emit_by_name::<()>("change-lines-staging", &[&false]);
+        }
     }
 
     // The central trait for subclassing a GObject
@@ -60,6 +208,7 @@ mod imp {
 
         fn class_init(klass: &mut Self::Class) {
             klass.bind_template();
+            klass.bind_template_callbacks();
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -67,8 +216,41 @@ mod imp {
         }
     }
     impl ObjectImpl for BagitFileView {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            // Both sides of the side-by-side layout scroll together:
+            self.new_scrolled_window
+                .set_vadjustment(Some(&self.old_scrolled_window.vadjustment()));
+
+            let settings = Settings::new("com.skilldary.bagit.desktop");
+
+            self.side_by_side_button
+                .set_active(settings.boolean("is-diff-side-by-side-enabled"));
+
+            self.file_history_list
+                .set_placeholder(Some(&gtk::Label::new(Some(&gettext(
+                    "_No commit changed this file",
+                )))));
+
+            self.obj().connect_blame_click();
+            self.obj().set_color_theme_depending_on_system_theme();
+        }
+
         fn signals() -> &'static [Signal] {
-            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| vec![]);
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("diff-source-changed").build(),
+                    Signal::builder("change-lines-staging")
+                        .param_types([bool::static_type()])
+                        .build(),
+                    Signal::builder("show-blame").build(),
+                    Signal::builder("show-file-history").build(),
+                    Signal::builder("commit-selected")
+                        .param_types([str::static_type()])
+                        .build(),
+                ]
+            });
             SIGNALS.as_ref()
         }
     }
@@ -83,17 +265,610 @@ glib::wrapper! {
 }
 
 impl BagitFileView {
-    /// Shows the content of a file.
-    pub fn show_file(&self, file_name: &str, file_content: &str) {
+    /// Shows the differences of a file.
+    /// The file path is used to guess the language of the file.
+    pub fn show_diff(&self, file_path: &str, file_diff: FileDiff) {
+        let is_other_file = *self.imp().file_path.borrow() != file_path;
+
+        self.imp().file_name_label.set_text(file_path);
+        self.imp().file_path.replace(file_path.to_string());
+        self.imp().file_diff.replace(file_diff);
+        self.imp().file_content.replace(None);
+        self.imp().revision.replace(None);
+        self.imp().changes_button.set_label(&gettext("_Changes"));
+
+        // The blame or the history of the previous file is replaced by the one of the new file:
+        if is_other_file && !self.imp().changes_button.is_active() {
+            self.show_current_file_mode();
+        } else {
+            self.show_current_diff();
+        }
+    }
+
+    /// Shows the content of a file at a revision, read-only.
+    pub fn show_file_content(&self, file_path: &str, revision: &str, content: Vec<u8>) {
+        let is_other_file = *self.imp().file_path.borrow() != file_path
+            || self.imp().revision.borrow().as_deref() != Some(revision);
+
+        self.imp().file_name_label.set_text(file_path);
+        self.imp().file_path.replace(file_path.to_string());
+        self.imp().file_diff.replace(FileDiff::default());
+        self.imp().file_content.replace(Some(content));
+        self.imp().revision.replace(Some(revision.to_string()));
+        self.imp().changes_button.set_label(&gettext("_Content"));
+
+        if is_other_file && !self.imp().changes_button.is_active() {
+            self.show_current_file_mode();
+        } else {
+            self.show_current_diff();
+        }
+    }
+
+    /// Shows the file in the selected mode.
+    /// The blame and the history of the file are asked to be loaded, and shown when received.
+    pub fn show_current_file_mode(&self) {
+        let is_showing_changes = self.imp().changes_button.is_active();
+        let are_staging_controls_visible =
+            is_showing_changes && !self.imp().are_staging_controls_hidden.get();
+
+        self.imp()
+            .diff_layout_box
+            .set_visible(is_showing_changes && self.imp().file_content.borrow().is_none());
+        self.imp()
+            .diff_source_box
+            .set_visible(are_staging_controls_visible);
+        self.imp()
+            .staging_buttons_box
+            .set_visible(are_staging_controls_visible);
+
+        if is_showing_changes || self.imp().file_path.borrow().is_empty() {
+            self.show_current_diff();
+        } else {
+            self.imp().diff_stack.set_visible_child_name("loading page");
+
+            if self.imp().blame_button.is_active() {
+                self.emit_by_name::<()>("show-blame", &[]);
+            } else {
+                self.emit_by_name::<()>("show-file-history", &[]);
+            }
+        }
+    }
+
+    /// Goes back to the differences of the file.
+    pub fn show_changes(&self) {
+        self.imp().changes_button.set_active(true);
+    }
+
+    /// Shows the stored differences with the selected layout.
+    pub fn show_current_diff(&self) {
+        // The differences are shown again when going back to them:
+        if !self.imp().changes_button.is_active() && !self.imp().file_path.borrow().is_empty() {
+            return;
+        }
+
+        if let Some(file_content) = self.imp().file_content.borrow().clone() {
+            self.show_whole_file(&self.imp().file_path.borrow().clone(), &file_content);
+            return;
+        }
+
+        let file_diff = self.imp().file_diff.borrow().clone();
+
+        let file_name = self.imp().file_name_label.text();
+
+        if file_diff.is_binary || file_diff.is_empty() {
+            let message = if file_diff.is_binary {
+                gettext("_Binary file, differences cannot be shown")
+            } else {
+                gettext("_No differences to show")
+            };
+
+            let buffer = Buffer::new(None);
+            buffer.set_text(&message);
+            self.imp().unified_line_mapping.replace(vec![]);
+            self.imp().buffer.replace(buffer.clone());
+            self.imp().source_view.set_show_line_numbers(false);
+            self.imp().source_view.set_buffer(Some(&buffer));
+            self.imp().diff_stack.set_visible_child_name("unified page");
+        } else if self.imp().side_by_side_button.is_active() {
+            self.show_side_by_side_diff(&file_name, &file_diff);
+            self.imp()
+                .diff_stack
+                .set_visible_child_name("side by side page");
+        } else {
+            self.show_unified_diff(&file_name, &file_diff);
+            self.imp().diff_stack.set_visible_child_name("unified page");
+        }
+
+        self.set_color_theme();
+    }
+
+    /// Shows the differences of a file in a single view.
+    /// Each line is prefixed with its old and new line number.
+    fn show_unified_diff(&self, file_name: &str, file_diff: &FileDiff) {
         let buffer = self.get_buffer(file_name);
+        self.create_diff_tags(&buffer);
+
+        let width = self.get_line_number_width(file_diff);
 
-        buffer.set_text(&file_content);
+        let mut text_lines: Vec<String> = vec![];
+        let mut tagged_lines: Vec<(i32, &str)> = vec![];
+        let mut line_mapping: Vec<Option<(usize, Option<usize>)>> = vec![];
+
+        for (hunk_index, hunk) in file_diff.hunks.iter().enumerate() {
+            tagged_lines.push((text_lines.len() as i32, "hunk-header"));
+            text_lines.push(hunk.header.clone());
+            line_mapping.push(Some((hunk_index, None)));
+
+            for (line_index, line) in hunk.lines.iter().enumerate() {
+                line_mapping.push(Some((hunk_index, Some(line_index))));
+
+                let old_number = match line.old_line_number {
+                    Some(number) => number.to_string(),
+                    None => String::new(),
+                };
+                let new_number = match line.new_line_number {
+                    Some(number) => number.to_string(),
+                    None => String::new(),
+                };
+
+                let sign = match line.line_type {
+                    DiffLineType::Addition => {
+                        tagged_lines.push((text_lines.len() as i32, "added-line"));
+                        "+"
+                    }
+                    DiffLineType::Deletion => {
+                        tagged_lines.push((text_lines.len() as i32, "removed-line"));
+                        "-"
+                    }
+                    DiffLineType::Context => " ",
+                };
+
+                text_lines.push(format!(
+                    "{:>width$} {:>width$} {} {}",
+                    old_number,
+                    new_number,
+                    sign,
+                    line.content,
+                    width = width
+                ));
+            }
+        }
+
+        buffer.set_text(&text_lines.join("\n"));
+        self.apply_line_tags(&buffer, tagged_lines);
+        self.imp().unified_line_mapping.replace(line_mapping);
 
         self.imp().buffer.replace(buffer.clone());
+        self.imp().source_view.set_show_line_numbers(false);
+        self.imp().source_view.set_buffer(Some(&buffer));
+    }
 
-        self.set_color_theme_depending_on_system_theme();
+    /// Shows the whole content of a file, with its line numbers.
+    fn show_whole_file(&self, file_name: &str, content: &[u8]) {
+        let buffer = if content.contains(&0) {
+            let buffer = Buffer::new(None);
+            buffer.set_text(&gettext("_Binary file, content cannot be shown"));
+            self.imp().source_view.set_show_line_numbers(false);
+            buffer
+        } else {
+            let buffer = self.get_buffer(file_name);
+            buffer.set_text(&String::from_utf8_lossy(content));
+            self.imp().source_view.set_show_line_numbers(true);
+            buffer
+        };
 
+        // The lines of a file are not changes that can be staged:
+        self.imp().unified_line_mapping.replace(vec![]);
+        self.imp().buffer.replace(buffer.clone());
         self.imp().source_view.set_buffer(Some(&buffer));
+        self.imp().diff_stack.set_visible_child_name("unified page");
+
+        self.set_color_theme();
+    }
+
+    /// Shows the differences of a file with the old version on the left and the new one on the right.
+    /// Empty lines are added so that both sides stay aligned.
+    fn show_side_by_side_diff(&self, file_name: &str, file_diff: &FileDiff) {
+        let old_buffer = self.get_buffer(file_name);
+        let new_buffer = self.get_buffer(file_name);
+        self.create_diff_tags(&old_buffer);
+        self.create_diff_tags(&new_buffer);
+
+        let width = self.get_line_number_width(file_diff);
+
+        let mut old_lines: Vec<String> = vec![];
+        let mut new_lines: Vec<String> = vec![];
+        let mut old_tagged_lines: Vec<(i32, &str)> = vec![];
+        let mut new_tagged_lines: Vec<(i32, &str)> = vec![];
+        let mut old_line_mapping: Vec<Option<(usize, Option<usize>)>> = vec![];
+        let mut new_line_mapping: Vec<Option<(usize, Option<usize>)>> = vec![];
+
+        for (hunk_index, hunk) in file_diff.hunks.iter().enumerate() {
+            old_tagged_lines.push((old_lines.len() as i32, "hunk-header"));
+            new_tagged_lines.push((new_lines.len() as i32, "hunk-header"));
+            old_lines.push(hunk.header.clone());
+            new_lines.push(hunk.header.clone());
+            old_line_mapping.push(Some((hunk_index, None)));
+            new_line_mapping.push(Some((hunk_index, None)));
+
+            for (removed, added) in self.get_side_by_side_rows(hunk) {
+                match removed {
+                    Some(line) => {
+                        if line.is_changed {
+                            old_tagged_lines.push((old_lines.len() as i32, "removed-line"));
+                        }
+                        old_lines.push(format!(
+                            "{:>width$} {}",
+                            line.line_number,
+                            line.content,
+                            width = width
+                        ));
+                        old_line_mapping.push(Some((hunk_index, Some(line.line_index))));
+                    }
+                    None => {
+                        old_tagged_lines.push((old_lines.len() as i32, "filler-line"));
+                        old_lines.push(String::new());
+                        old_line_mapping.push(None);
+                    }
+                }
+
+                match added {
+                    Some(line) => {
+                        if line.is_changed {
+                            new_tagged_lines.push((new_lines.len() as i32, "added-line"));
+                        }
+                        new_lines.push(format!(
+                            "{:>width$} {}",
+                            line.line_number,
+                            line.content,
+                            width = width
+                        ));
+                        new_line_mapping.push(Some((hunk_index, Some(line.line_index))));
+                    }
+                    None => {
+                        new_tagged_lines.push((new_lines.len() as i32, "filler-line"));
+                        new_lines.push(String::new());
+                        new_line_mapping.push(None);
+                    }
+                }
+            }
+        }
+
+        old_buffer.set_text(&old_lines.join("\n"));
+        new_buffer.set_text(&new_lines.join("\n"));
+        self.apply_line_tags(&old_buffer, old_tagged_lines);
+        self.apply_line_tags(&new_buffer, new_tagged_lines);
+        self.imp().old_line_mapping.replace(old_line_mapping);
+        self.imp().new_line_mapping.replace(new_line_mapping);
+
+        self.imp().old_buffer.replace(old_buffer.clone());
+        self.imp().new_buffer.replace(new_buffer.clone());
+        self.imp().old_source_view.set_buffer(Some(&old_buffer));
+        self.imp().new_source_view.set_buffer(Some(&new_buffer));
+    }
+
+    /// Shows the blame of a file, each line being annotated with the last commit that changed it.
+    /// A blame received for another file than the shown one is ignored.
+    pub fn show_blame(&self, file_path: &str, blame_lines: Vec<BlameLine>) {
+        if *self.imp().file_path.borrow() != file_path || !self.imp().blame_button.is_active() {
+            return;
+        }
+
+        let buffer = self.get_buffer(file_path);
+        buffer.create_tag(
+            Some("blame-annotation"),
+            &[("foreground", &"rgba(128, 128, 128, 1)")],
+        );
+        buffer.create_tag(
+            Some("blame-alternate-commit"),
+            &[("paragraph-background", &"rgba(128, 128, 128, 0.1)")],
+        );
+
+        let not_committed_text = gettext("_Not committed yet");
+
+        let author_width = blame_lines
+            .iter()
+            .map(|line| line.author.chars().count())
+            .max()
+            .unwrap_or_default()
+            .min(BLAME_AUTHOR_MAX_WIDTH);
+        let date_width = blame_lines
+            .iter()
+            .map(|line| line.date.chars().count())
+            .max()
+            .unwrap_or_default();
+        let mut annotation_width = SHORT_COMMIT_ID_LENGTH + author_width + date_width + 2;
+
+        if blame_lines.iter().any(|line| line.commit_id.is_empty()) {
+            annotation_width = annotation_width.max(not_committed_text.chars().count());
+        }
+
+        let number_width = blame_lines.len().to_string().len();
+
+        let mut text_lines: Vec<String> = vec![];
+        let mut alternate_lines: Vec<(i32, &str)> = vec![];
+        let mut line_mapping: Vec<String> = vec![];
+        let mut is_alternate_commit = false;
+
+        for (line_index, line) in blame_lines.iter().enumerate() {
+            // The commit is only written on the first of its consecutive lines:
+            let is_first_line_of_commit =
+                line_index == 0 || blame_lines[line_index - 1].commit_id != line.commit_id;
+
+            if is_first_line_of_commit && line_index > 0 {
+                is_alternate_commit = !is_alternate_commit;
+            }
+
+            if is_alternate_commit {
+                alternate_lines.push((line_index as i32, "blame-alternate-commit"));
+            }
+
+            let annotation = if !is_first_line_of_commit {
+                String::new()
+            } else if line.commit_id.is_empty() {
+                not_committed_text.clone()
+            } else {
+                format!(
+                    "{} {:<author_width$} {}",
+                    line.commit_id
+                        .chars()
+                        .take(SHORT_COMMIT_ID_LENGTH)
+                        .collect::<String>(),
+                    line.author
+                        .chars()
+                        .take(BLAME_AUTHOR_MAX_WIDTH)
+                        .collect::<String>(),
+                    line.date,
+                    author_width = author_width
+                )
+            };
+
+            text_lines.push(format!(
+                "{:<annotation_width$} {:>number_width$} {}",
+                annotation,
+                line_index + 1,
+                line.content,
+                annotation_width = annotation_width,
+                number_width = number_width
+            ));
+            line_mapping.push(line.commit_id.clone());
+        }
+
+        buffer.set_text(&text_lines.join("\n"));
+        self.apply_line_tags(&buffer, alternate_lines);
+
+        let annotation_end = (annotation_width + number_width + 1) as i32;
+
+        for line_index in 0..text_lines.len() as i32 {
+            if let (Some(start), Some(end)) = (
+                buffer.iter_at_line(line_index),
+                buffer.iter_at_line_offset(line_index, annotation_end),
+            ) {
+                buffer.apply_tag_by_name("blame-annotation", &start, &end);
+            }
+        }
+
+        self.imp().blame_line_mapping.replace(line_mapping);
+        self.imp()
+            .blame_annotation_width
+            .set(annotation_width as i32);
+
+        self.imp().blame_buffer.replace(buffer.clone());
+        self.imp().blame_source_view.set_buffer(Some(&buffer));
+        self.imp().diff_stack.set_visible_child_name("blame page");
+
+        self.set_color_theme();
+    }
+
+    /// Shows the commit of a line of the blame when its annotation is clicked.
+    fn connect_blame_click(&self) {
+        let click_gesture = gtk::GestureClick::new();
+
+        click_gesture.connect_released(clone!(
+            @weak self as win
+            => move |_, _, x, y| {
+                let blame_source_view = &win.imp().blame_source_view;
+
+                let (buffer_x, buffer_y) = blame_source_view.window_to_buffer_coords(
+                    gtk::TextWindowType::Widget,
+                    x as i32,
+                    y as i32,
+                );
+
+                let clicked_iter = match blame_source_view.iter_at_location(buffer_x, buffer_y) {
+                    Some(clicked_iter) => clicked_iter,
+                    None => return,
+                };
+
+                if clicked_iter.line_offset() >= win.imp().blame_annotation_width.get() {
+                    return;
+                }
+
+                let commit_id = match win
+                    .imp()
+                    .blame_line_mapping
+                    .borrow()
+                    .get(clicked_iter.line() as usize)
+                {
+                    Some(commit_id) => commit_id.clone(),
+                    None => return,
+                };
+
+                if !commit_id.is_empty() {
+                    win.emit_by_name::<()>("commit-selected", &[&commit_id]);
+                }
+            }
+        ));
+
+        self.imp().blame_source_view.add_controller(click_gesture);
+    }
+
+    /// Shows the commits that changed a file, from the newest to the oldest.
+    /// A history received for another file than the shown one is ignored.
+    pub fn show_file_history(&self, file_path: &str, file_history: Vec<FileHistoryEntry>) {
+        if *self.imp().file_path.borrow() != file_path
+            || !self.imp().file_history_button.is_active()
+        {
+            return;
+        }
+
+        while let Some(row) = self.imp().file_history_list.row_at_index(0) {
+            self.imp().file_history_list.remove(&row);
+        }
+
+        for file_history_entry in &file_history {
+            let mut subtitle = format!(
+                "{} {} {}",
+                file_history_entry
+                    .commit_id
+                    .chars()
+                    .take(SHORT_COMMIT_ID_LENGTH)
+                    .collect::<String>(),
+                file_history_entry.author,
+                file_history_entry.date
+            );
+
+            // The path is shown for the commits made before the file was renamed:
+            if file_history_entry.path != file_path {
+                subtitle.push_str("\n");
+                subtitle.push_str(
+                    &gettext("_File path in this commit").replace("{}", &file_history_entry.path),
+                );
+            }
+
+            let row = adw::ActionRow::builder()
+                .title(&file_history_entry.title)
+                .subtitle(&subtitle)
+                .activatable(true)
+                .build();
+            row.set_title_lines(1);
+
+            self.imp().file_history_list.append(&row);
+        }
+
+        self.imp().file_history.replace(file_history);
+        self.imp()
+            .diff_stack
+            .set_visible_child_name("file history page");
+    }
+
+    /// Pairs the lines of a hunk into rows of (old line, new line).
+    /// Removed lines are shown next to the added lines that follow them.
+    fn get_side_by_side_rows(
+        &self,
+        hunk: &DiffHunk,
+    ) -> Vec<(Option<SideBySideLine>, Option<SideBySideLine>)> {
+        let mut rows = vec![];
+
+        let mut removed_lines: Vec<SideBySideLine> = vec![];
+        let mut added_lines: Vec<SideBySideLine> = vec![];
+
+        for (line_index, line) in hunk.lines.iter().enumerate() {
+            match line.line_type {
+                DiffLineType::Deletion => {
+                    // A removal after additions starts a new block of changes:
+                    if !added_lines.is_empty() {
+                        self.flush_changed_lines(&mut rows, &mut removed_lines, &mut added_lines);
+                    }
+                    removed_lines.push(SideBySideLine {
+                        line_index,
+                        line_number: line.old_line_number.unwrap_or_default(),
+                        content: line.content.clone(),
+                        is_changed: true,
+                    });
+                }
+                DiffLineType::Addition => added_lines.push(SideBySideLine {
+                    line_index,
+                    line_number: line.new_line_number.unwrap_or_default(),
+                    content: line.content.clone(),
+                    is_changed: true,
+                }),
+                DiffLineType::Context => {
+                    self.flush_changed_lines(&mut rows, &mut removed_lines, &mut added_lines);
+                    rows.push((
+                        Some(SideBySideLine {
+                            line_index,
+                            line_number: line.old_line_number.unwrap_or_default(),
+                            content: line.content.clone(),
+                            is_changed: false,
+                        }),
+                        Some(SideBySideLine {
+                            line_index,
+                            line_number: line.new_line_number.unwrap_or_default(),
+                            content: line.content.clone(),
+                            is_changed: false,
+                        }),
+                    ));
+                }
+            }
+        }
+
+        self.flush_changed_lines(&mut rows, &mut removed_lines, &mut added_lines);
+
+        return rows;
+    }
+
+    /// Moves pending removed and added lines into rows, side by side.
+    fn flush_changed_lines(
+        &self,
+        rows: &mut Vec<(Option<SideBySideLine>, Option<SideBySideLine>)>,
+        removed_lines: &mut Vec<SideBySideLine>,
+        added_lines: &mut Vec<SideBySideLine>,
+    ) {
+        let total_rows = removed_lines.len().max(added_lines.len());
+
+        let mut removed = removed_lines.drain(..);
+        let mut added = added_lines.drain(..);
+
+        for _ in 0..total_rows {
+            rows.push((removed.next(), added.next()));
+        }
+    }
+
+    /// Retrieves the width needed to show the biggest line number of a diff.
+    fn get_line_number_width(&self, file_diff: &FileDiff) -> usize {
+        let biggest_line_number = file_diff
+            .hunks
+            .iter()
+            .map(|hunk| (hunk.old_start + hunk.old_lines).max(hunk.new_start + hunk.new_lines))
+            .max()
+            .unwrap_or_default();
+
+        return biggest_line_number.to_string().len();
+    }
+
+    /// Creates the tags used to highlight the lines of a diff.
+    fn create_diff_tags(&self, buffer: &Buffer) {
+        buffer.create_tag(
+            Some("hunk-header"),
+            &[
+                ("paragraph-background", &"rgba(53, 132, 228, 0.2)"),
+                ("weight", &700),
+            ],
+        );
+        buffer.create_tag(
+            Some("added-line"),
+            &[("paragraph-background", &"rgba(46, 194, 126, 0.2)")],
+        );
+        buffer.create_tag(
+            Some("removed-line"),
+            &[("paragraph-background", &"rgba(224, 27, 36, 0.2)")],
+        );
+        buffer.create_tag(
+            Some("filler-line"),
+            &[("paragraph-background", &"rgba(128, 128, 128, 0.1)")],
+        );
+    }
+
+    /// Applies tags on whole lines of a buffer.
+    fn apply_line_tags(&self, buffer: &Buffer, tagged_lines: Vec<(i32, &str)>) {
+        for (line, tag_name) in tagged_lines {
+            if let Some(start) = buffer.iter_at_line(line) {
+                let mut end = start.clone();
+                end.forward_line();
+                buffer.apply_tag_by_name(tag_name, &start, &end);
+            }
+        }
     }
 
     /// Sets the text view color theme depending on the one of the system.
@@ -108,14 +883,10 @@ impl BagitFileView {
             }));
     }
 
-    /// Sets the color theme used for the view.
+    /// Sets the color theme used for the views.
     pub fn set_color_theme(&self) {
-        let buffer = self.imp().buffer.take();
-
         let style_scheme_manager = StyleSchemeManager::new();
 
-        let style: Option<&StyleScheme>;
-
         let scheme_id;
 
         match self.settings().is_gtk_application_prefer_dark_theme() {
@@ -123,22 +894,16 @@ impl BagitFileView {
             false => scheme_id = "Adwaita",
         }
 
-        let s: StyleScheme;
+        let style: Option<StyleScheme> = style_scheme_manager.scheme(scheme_id);
 
-        if let Some(style_scheme) = style_scheme_manager.scheme(scheme_id) {
-            s = style_scheme.clone();
-            style = Some(&s);
-        } else {
-            buffer.set_style_scheme(None);
-
-            self.imp().buffer.replace(buffer);
-
-            return;
+        for buffer in [
+            &self.imp().buffer,
+            &self.imp().old_buffer,
+            &self.imp().new_buffer,
+            &self.imp().blame_buffer,
+        ] {
+            buffer.borrow().set_style_scheme(style.as_ref());
         }
-
-        buffer.set_style_scheme(style);
-
-        self.imp().buffer.replace(buffer);
     }
 
     /// Retrieves a buffer with a language given from a file name.
@@ -152,51 +917,26 @@ impl BagitFileView {
         }
     }
 
-    /// Tries to show a file.
-    /// If the file cannot be shown, an error will be returned.
+    /// Tries to show the differences of a file.
+    /// If the differences cannot be retrieved, an error will be returned.
     pub fn define_how_to_show_file_content(
         &self,
-        repository_path: &str,
         repository: &Repository,
         parent_folder: &str,
         file_name: &str,
     ) -> Result<(), String> {
         let relative_path = RepositoryUtils::build_path_of_file(&parent_folder, &file_name);
-        let absolute_path = RepositoryUtils::build_path_of_file(repository_path, &relative_path);
-
-        let path = Path::new(&relative_path);
-
-        let is_file_deleted = match repository.status_file(&path) {
-            Ok(status) => status == Status::WT_DELETED || status == Status::INDEX_DELETED,
-            Err(error) => return Err(error.to_string()),
-        };
 
-        if is_file_deleted {
-            match RepositoryUtils::get_content_of_deleted_file(&repository, &relative_path) {
-                Ok(content) => Ok(self.show_file(&file_name, &content)),
-                Err(error) => Err(error.to_string()),
-            }
-        } else {
-            match self.get_content_of_present_file(&absolute_path) {
-                Ok(content) => Ok(self.show_file(&file_name, &content)),
-                Err(error) => Err(error.to_string()),
-            }
+        match RepositoryUtils::get_diff_of_file(
+            &repository,
+            &relative_path,
+            self.is_showing_staged_changes(),
+        ) {
+            Ok(file_diff) => Ok(self.show_diff(&relative_path, file_diff)),
+            Err(error) => Err(error.to_string()),
         }
     }
 
-    /// Retrieves the content of a file.
-    /// The file must be present on the system.
-    pub fn get_content_of_present_file(
-        &self,
-        absolute_file_path: &str,
-    ) -> Result<String, std::io::Error> {
-        let mut file = std::fs::File::open(absolute_file_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-
-        return Ok(contents);
-    }
-
     /// Defines the information of the current viewed file.
     pub fn set_file_information(&self, file_folder: &str, file_name: &str) {
         self.imp().file_folder.replace(file_folder.to_string());
@@ -214,4 +954,139 @@ impl BagitFileView {
 
         (file_folder, file_name)
     }
+
+    /// Shows or hides the controls used to stage changes.
+    /// They are hidden when showing the differences of a commit.
+    pub fn set_staging_controls_visible(&self, is_visible: bool) {
+        self.imp().are_staging_controls_hidden.set(!is_visible);
+
+        let is_showing_changes = self.imp().changes_button.is_active();
+        self.imp()
+            .diff_source_box
+            .set_visible(is_visible && is_showing_changes);
+        self.imp()
+            .staging_buttons_box
+            .set_visible(is_visible && is_showing_changes);
+    }
+
+    /// Shows or hides the buttons used to see the blame and the history of the file.
+    /// Only the differences can be shown when they are hidden.
+    pub fn set_file_modes_visible(&self, is_visible: bool) {
+        self.imp().file_mode_box.set_visible(is_visible);
+
+        if !is_visible {
+            self.show_changes();
+        }
+    }
+
+    /// Retrieves the path of the shown file.
+    pub fn get_file_path(&self) -> String {
+        return self.imp().file_path.borrow().clone();
+    }
+
+    /// Defines the revision at which the file is shown.
+    /// Without a revision, the file of the working directory is shown.
+    pub fn set_revision(&self, revision: Option<String>) {
+        self.imp().revision.replace(revision);
+    }
+
+    /// Retrieves the revision at which the file is shown, if it is not the working directory one.
+    pub fn get_revision(&self) -> Option<String> {
+        return self.imp().revision.borrow().clone();
+    }
+
+    /// Checks if the shown differences are the staged ones.
+    pub fn is_showing_staged_changes(&self) -> bool {
+        return self.imp().staged_button.is_active();
+    }
+
+    /// Shows the staged or the unstaged differences.
+    /// The shown file is asked to be shown again when the differences change.
+    pub fn set_showing_staged_changes(&self, is_showing_staged_changes: bool) {
+        if is_showing_staged_changes {
+            self.imp().staged_button.set_active(true);
+        } else {
+            self.imp().unstaged_button.set_active(true);
+        }
+    }
+
+    /// Retrieves the changed lines the user has selected, as (hunk index, line index in the hunk).
+    /// If nothing is selected, the line containing the cursor is used.
+    /// With whole hunks, every changed line of the touched hunks is retrieved.
+    pub fn get_selected_lines(&self, whole_hunks: bool) -> Vec<(usize, usize)> {
+        let is_side_by_side = match self.imp().diff_stack.visible_child_name() {
+            Some(page) => page == "side by side page",
+            None => false,
+        };
+
+        let (buffer, line_mapping) = if !is_side_by_side {
+            (
+                self.imp().buffer.borrow().clone(),
+                self.imp().unified_line_mapping.borrow().clone(),
+            )
+        } else if self.imp().old_buffer.borrow().has_selection()
+            && !self.imp().new_buffer.borrow().has_selection()
+        {
+            (
+                self.imp().old_buffer.borrow().clone(),
+                self.imp().old_line_mapping.borrow().clone(),
+            )
+        } else {
+            (
+                self.imp().new_buffer.borrow().clone(),
+                self.imp().new_line_mapping.borrow().clone(),
+            )
+        };
+
+        let (first_line, mut last_line) = match buffer.selection_bounds() {
+            Some((start, end)) => {
+                // A selection ending at the very start of a line does not include it.
+                if end.line() > start.line() && end.starts_line() {
+                    (start.line(), end.line() - 1)
+                } else {
+                    (start.line(), end.line())
+                }
+            }
+            None => {
+                let cursor = buffer.iter_at_mark(&buffer.get_insert());
+                (cursor.line(), cursor.line())
+            }
+        };
+        last_line = last_line.min(line_mapping.len() as i32 - 1);
+
+        let file_diff = self.imp().file_diff.borrow().clone();
+
+        let mut selected_lines: Vec<(usize, usize)> = vec![];
+
+        if first_line < 0 || last_line < first_line {
+            return selected_lines;
+        }
+
+        for mapped_line in &line_mapping[first_line as usize..=last_line as usize] {
+            let (hunk_index, line_index) = match mapped_line {
+                Some(mapped_line) => *mapped_line,
+                None => continue,
+            };
+
+            let hunk = match file_diff.hunks.get(hunk_index) {
+                Some(hunk) => hunk,
+                None => continue,
+            };
+
+            match line_index {
+                Some(line_index) if !whole_hunks => selected_lines.push((hunk_index, line_index)),
+                // A hunk header or a whole hunk selection takes every line of the hunk.
+                _ => selected_lines.extend((0..hunk.lines.len()).map(|index| (hunk_index, index))),
+            }
+        }
+
+        return selected_lines
+            .into_iter()
+            .filter(|(hunk_index, line_index)| {
+                file_diff.hunks[*hunk_index].lines[*line_index].line_type != DiffLineType::Context
+            })
+            .sorted()
+            .dedup()
+            .collect();
+    }
 }
@@ -25,6 +25,8 @@ use gtk::glib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 
+use crate::utils::commit_graph::CommitGraphRow;
+
 // Object holding the state
 #[derive(Properties, Default)]
 #[properties(wrapper_type = super::CommitObject)]
@@ -39,6 +41,8 @@ pub struct CommitObject {
     subtitle: RefCell<String>,
     #[property(get, set)]
     is_pushed: Cell<bool>,
+    /// The place of the commit in the commit graph, when the history is shown as a graph.
+    pub graph_row: RefCell<Option<CommitGraphRow>>,
 }
 
 // The central trait for subclassing a GObject
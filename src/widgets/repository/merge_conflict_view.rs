@@ -0,0 +1,572 @@
+/* merge_conflict_view.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::path::Path;
+
+use adw::prelude::ActionRowExt;
+use adw::subclass::prelude::*;
+use gettextrs::gettext;
+use gtk::glib::clone;
+use gtk::prelude::*;
+use gtk::{glib, template_callbacks};
+use sourceview5::prelude::BufferExt;
+use sourceview5::{Buffer, LanguageManager, StyleScheme, StyleSchemeManager};
+
+use crate::utils::merge_conflict::{pick_side_of_conflict_hunk, ConflictSide, MergeConflict};
+
+mod imp {
+
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::subclass::widget::CompositeTemplateInitializingExt;
+    use gtk::CompositeTemplate;
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(
+        resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-merge-conflict-view.ui"
+    )]
+    pub struct BagitMergeConflictView {
+        #[template_child]
+        pub conflicts_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub conclude_merge_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub abort_merge_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub resolved_status_page: TemplateChild<adw::StatusPage>,
+        #[template_child]
+        pub content_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub file_name_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub result_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub ours_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub theirs_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub base_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub version_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub result_source_view: TemplateChild<sourceview5::View>,
+        #[template_child]
+        pub ours_source_view: TemplateChild<sourceview5::View>,
+        #[template_child]
+        pub theirs_source_view: TemplateChild<sourceview5::View>,
+        #[template_child]
+        pub base_source_view: TemplateChild<sourceview5::View>,
+        #[template_child]
+        pub binary_status_page: TemplateChild<adw::StatusPage>,
+        #[template_child]
+        pub deleted_file_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub use_ours_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub use_theirs_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub use_ours_in_hunk_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub use_theirs_in_hunk_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub mark_as_resolved_button: TemplateChild<gtk::Button>,
+
+        pub conflicts: RefCell<Vec<MergeConflict>>,
+        /// The path of the conflicted file currently shown.
+        pub shown_path: RefCell<String>,
+        /// Whether the chosen resolution is to delete the file.
+        pub is_deleting_file: Cell<bool>,
+        /// The version chosen for the whole file, which is the only resolution of a binary file.
+        pub chosen_side: Cell<Option<ConflictSide>>,
+    }
+
+    #[template_callbacks]
+    impl BagitMergeConflictView {
+        #[template_callback]
+        fn conflict_activated(&self, row: &gtk::ListBoxRow) {
+            let conflict = self.conflicts.borrow().get(row.index() as usize).cloned();
+
+            if let Some(conflict) = conflict {
+                self.obj().show_conflict(conflict);
+            }
+        }
+
+        #[template_callback]
+        fn version_toggled(&self, button: &gtk::ToggleButton) {
+            // Only the newly activated button needs to be handled.
+            if !button.is_active() {
+                return;
+            }
+
+            let page_name = if self.ours_button.is_active() {
+                "ours"
+            } else if self.theirs_button.is_active() {
+                "theirs"
+            } else if self.base_button.is_active() {
+                "base"
+            } else {
+                "result"
+            };
+
+            self.version_stack.set_visible_child_name(page_name);
+        }
+
+        #[template_callback]
+        fn use_ours(&self, _button: &gtk::Button) {
+            self.obj().use_side_for_file(ConflictSide::Ours);
+        }
+
+        #[template_callback]
+        fn use_theirs(&self, _button: &gtk::Button) {
+            self.obj().use_side_for_file(ConflictSide::Theirs);
+        }
+
+        #[template_callback]
+        fn use_ours_in_hunk(&self, _button: &gtk::Button) {
+            self.obj().use_side_for_hunk(ConflictSide::Ours);
+        }
+
+        #[template_callback]
+        fn use_theirs_in_hunk(&self, _button: &gtk::Button) {
+            self.obj().use_side_for_hunk(ConflictSide::Theirs);
+        }
+
+        #[template_callback]
+        fn mark_as_resolved(&self, _button: &gtk::Button) {
+            let shown_path = self.shown_path.borrow().clone();
+
+            // A binary file is not edited, the chosen version is kept as it is:
+            let is_binary = match self.obj().get_shown_conflict() {
+                Some(conflict) => conflict.is_binary(),
+                None => false,
+            };
+            if is_binary {
+                if let Some(side) = self.chosen_side.get() {
+                    self.obj().emit_by_name::<()>(
+                        "resolve-conflict-with-side",
+                        &[&shown_path, &(side == ConflictSide::Ours)],
+                    );
+                }
+                return;
+            }
+
+            let buffer = self.result_source_view.buffer();
+            let content = buffer.text(&buffer.start_iter(), &buffer.end_iter(), true);
+
+            self.obj().emit_by_name::<()>(
+                "resolve-conflict",
+                &[
+                    &shown_path,
+                    &content.to_string(),
+                    &self.is_deleting_file.get(),
+                ],
+            );
+        }
+
+        #[template_callback]
+        fn conclude_merge(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("conclude-merge", &[]);
+        }
+
+        #[template_callback]
+        fn abort_merge(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("abort-merge", &[]);
+        }
+    }
+
+    // The central trait for subclassing a GObject
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitMergeConflictView {
+        const NAME: &'static str = "BagitMergeConflictView";
+        type Type = super::BagitMergeConflictView;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+    impl ObjectImpl for BagitMergeConflictView {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            self.obj().set_color_theme_depending_on_system_theme();
+        }
+
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("resolve-conflict")
+                        .param_types([str::static_type(), str::static_type(), bool::static_type()])
+                        .build(),
+                    Signal::builder("resolve-conflict-with-side")
+                        .param_types([str::static_type(), bool::static_type()])
+                        .build(),
+                    Signal::builder("conclude-merge").build(),
+                    Signal::builder("abort-merge").build(),
+                ]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitMergeConflictView {}
+    impl BoxImpl for BagitMergeConflictView {}
+}
+glib::wrapper! {
+    pub struct BagitMergeConflictView(ObjectSubclass<imp::BagitMergeConflictView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl BagitMergeConflictView {
+    /// Updates the list of conflicted files.
+    /// The shown file is kept if it is still conflicted, so that its resolution is not lost.
+    pub fn update_conflicts(&self, conflicts: Vec<MergeConflict>) {
+        self.clear_conflicts_list();
+        for conflict in &conflicts {
+            let row = self.build_conflict_row(&conflict.path);
+            self.imp().conflicts_list.append(&row);
+        }
+
+        self.imp()
+            .conclude_merge_button
+            .set_sensitive(conflicts.is_empty());
+
+        let shown_path = self.imp().shown_path.borrow().clone();
+        let shown_index = conflicts
+            .iter()
+            .position(|conflict| conflict.path == shown_path);
+        let first_conflict = conflicts.first().cloned();
+
+        self.imp().conflicts.replace(conflicts);
+
+        match (shown_index, first_conflict) {
+            (Some(index), _) => self.select_row(index),
+            (None, Some(conflict)) => self.show_conflict(conflict),
+            (None, None) => {
+                self.imp().shown_path.replace(String::new());
+                self.imp()
+                    .content_stack
+                    .set_visible_child_name("resolved page");
+            }
+        }
+    }
+
+    /// Adapts the view to the conflicts of a rebase step, or of a merge.
+    /// The conclude and abort signals then apply to the rebase.
+    pub fn set_is_rebasing(&self, is_rebasing: bool) {
+        let imp = self.imp();
+
+        if is_rebasing {
+            imp.conclude_merge_button
+                .set_label(&gettext("_Continue rebase"));
+            imp.abort_merge_button.set_label(&gettext("_Abort rebase"));
+            imp.resolved_status_page
+                .set_description(Some(&gettext("_Continue the rebase to create the commit")));
+        } else {
+            imp.conclude_merge_button
+                .set_label(&gettext("_Conclude merge"));
+            imp.abort_merge_button.set_label(&gettext("_Abort merge"));
+            imp.resolved_status_page.set_description(Some(&gettext(
+                "_Conclude the merge to create the merge commit",
+            )));
+        }
+    }
+
+    /// Shows the conflicted file with the given path, if there is one.
+    pub fn show_conflict_of_file(&self, path: &str) {
+        let conflict = self
+            .imp()
+            .conflicts
+            .borrow()
+            .iter()
+            .find(|conflict| conflict.path == path)
+            .cloned();
+
+        if let Some(conflict) = conflict {
+            self.show_conflict(conflict);
+        }
+    }
+
+    /// Checks if a file is in the list of conflicted files.
+    pub fn is_file_conflicted(&self, path: &str) -> bool {
+        return self
+            .imp()
+            .conflicts
+            .borrow()
+            .iter()
+            .any(|conflict| conflict.path == path);
+    }
+
+    /// Shows the versions of a conflicted file.
+    /// The result starts from the content written by git, with its conflict markers.
+    /// A binary file cannot be edited, one of its versions is chosen instead.
+    fn show_conflict(&self, conflict: MergeConflict) {
+        let imp = self.imp();
+        let is_binary = conflict.is_binary();
+
+        imp.file_name_label.set_text(&conflict.path);
+        imp.shown_path.replace(conflict.path.clone());
+        imp.is_deleting_file.set(false);
+        imp.deleted_file_label.set_visible(false);
+        imp.chosen_side.set(None);
+        imp.binary_status_page
+            .set_description(Some(&gettext("_Choose the version to keep")));
+        imp.mark_as_resolved_button.set_sensitive(!is_binary);
+
+        let result_buffer = self.get_buffer(&conflict.path);
+        if !is_binary {
+            result_buffer.set_text(&String::from_utf8_lossy(
+                conflict.working_content.as_deref().unwrap_or_default(),
+            ));
+        }
+        result_buffer.connect_cursor_position_notify(clone!(
+            @weak self as view => move |_buffer| {
+                view.update_hunk_buttons();
+            }
+        ));
+        imp.result_source_view.set_buffer(Some(&result_buffer));
+
+        for (source_view, button, content) in [
+            (
+                &imp.ours_source_view,
+                &imp.ours_button,
+                &conflict.our_content,
+            ),
+            (
+                &imp.theirs_source_view,
+                &imp.theirs_button,
+                &conflict.their_content,
+            ),
+            (
+                &imp.base_source_view,
+                &imp.base_button,
+                &conflict.base_content,
+            ),
+        ] {
+            let buffer = self.get_buffer(&conflict.path);
+            if !is_binary {
+                buffer.set_text(&String::from_utf8_lossy(
+                    content.as_deref().unwrap_or_default(),
+                ));
+            }
+            source_view.get().set_buffer(Some(&buffer));
+            button.set_sensitive(content.is_some() && !is_binary);
+        }
+
+        imp.result_button.set_active(true);
+        imp.result_button.set_sensitive(!is_binary);
+        imp.version_stack
+            .set_visible_child_name(if is_binary { "binary" } else { "result" });
+        imp.content_stack.set_visible_child_name("conflict page");
+
+        let index = imp
+            .conflicts
+            .borrow()
+            .iter()
+            .position(|listed_conflict| listed_conflict.path == conflict.path);
+        if let Some(index) = index {
+            self.select_row(index);
+        }
+
+        self.set_color_theme();
+        self.update_hunk_buttons();
+    }
+
+    /// Retrieves the conflicted file currently shown.
+    fn get_shown_conflict(&self) -> Option<MergeConflict> {
+        let shown_path = self.imp().shown_path.borrow().clone();
+
+        return self
+            .imp()
+            .conflicts
+            .borrow()
+            .iter()
+            .find(|conflict| conflict.path == shown_path)
+            .cloned();
+    }
+
+    /// Replaces the result by a side of the conflict.
+    /// When the file does not exist on this side, the resolution is to delete it.
+    fn use_side_for_file(&self, side: ConflictSide) {
+        let conflict = match self.get_shown_conflict() {
+            Some(conflict) => conflict,
+            None => return,
+        };
+
+        let content = conflict.get_content_of_side(side);
+
+        self.imp().chosen_side.set(Some(side));
+        self.imp().is_deleting_file.set(content.is_none());
+        self.imp().deleted_file_label.set_visible(content.is_none());
+
+        if conflict.is_binary() {
+            let description = match side {
+                ConflictSide::Ours => gettext("_Our version will be kept"),
+                ConflictSide::Theirs => gettext("_Their version will be kept"),
+            };
+            self.imp()
+                .binary_status_page
+                .set_description(Some(&description));
+            self.imp().mark_as_resolved_button.set_sensitive(true);
+            return;
+        }
+
+        self.imp()
+            .result_source_view
+            .buffer()
+            .set_text(&String::from_utf8_lossy(&content.unwrap_or_default()));
+        self.imp().result_button.set_active(true);
+    }
+
+    /// Replaces the conflict hunk containing the cursor by one of its sides.
+    fn use_side_for_hunk(&self, side: ConflictSide) {
+        let buffer = self.imp().result_source_view.buffer();
+        let content = buffer.text(&buffer.start_iter(), &buffer.end_iter(), true);
+        let cursor_line = buffer.iter_at_offset(buffer.cursor_position()).line();
+
+        if let Some(new_content) = pick_side_of_conflict_hunk(&content, cursor_line as usize, side)
+        {
+            buffer.set_text(&new_content);
+
+            // The cursor is kept near the resolved hunk:
+            if let Some(iter) = buffer.iter_at_line(cursor_line) {
+                buffer.place_cursor(&iter);
+            }
+        }
+    }
+
+    /// Activates the hunk buttons only when the cursor is in a conflict hunk.
+    fn update_hunk_buttons(&self) {
+        let buffer = self.imp().result_source_view.buffer();
+        let content = buffer.text(&buffer.start_iter(), &buffer.end_iter(), true);
+        let cursor_line = buffer.iter_at_offset(buffer.cursor_position()).line();
+
+        let is_in_hunk =
+            pick_side_of_conflict_hunk(&content, cursor_line as usize, ConflictSide::Ours)
+                .is_some();
+
+        self.imp().use_ours_in_hunk_button.set_sensitive(is_in_hunk);
+        self.imp()
+            .use_theirs_in_hunk_button
+            .set_sensitive(is_in_hunk);
+    }
+
+    /// Selects the row of a conflicted file without activating it.
+    fn select_row(&self, index: usize) {
+        if let Some(row) = self.imp().conflicts_list.row_at_index(index as i32) {
+            self.imp().conflicts_list.select_row(Some(&row));
+        }
+    }
+
+    /// Builds the row of a conflicted file.
+    fn build_conflict_row(&self, file_path: &str) -> adw::ActionRow {
+        let path = Path::new(file_path);
+
+        let file_name = match path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => file_path.to_string(),
+        };
+        let parent = match path.parent() {
+            Some(parent) => parent.to_string_lossy().to_string(),
+            None => String::new(),
+        };
+
+        let row = adw::ActionRow::builder()
+            .title(&file_name)
+            .activatable(true)
+            .build();
+        row.set_title_lines(1);
+        row.set_subtitle_lines(1);
+        if !parent.is_empty() {
+            row.set_subtitle(&parent);
+        }
+
+        let icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+        icon.set_tooltip_text(Some(&gettext("_Conflicted file")));
+        icon.add_css_class("error");
+        row.add_prefix(&icon);
+
+        return row;
+    }
+
+    /// Clears the list of conflicted files.
+    fn clear_conflicts_list(&self) {
+        let mut file_row = self.imp().conflicts_list.row_at_index(0);
+        while file_row != None {
+            self.imp().conflicts_list.remove(&file_row.unwrap());
+            file_row = self.imp().conflicts_list.row_at_index(0);
+        }
+    }
+
+    /// Sets the text views color theme depending on the one of the system.
+    pub fn set_color_theme_depending_on_system_theme(&self) {
+        self.set_color_theme();
+
+        self.settings()
+            .connect_gtk_application_prefer_dark_theme_notify(clone!(
+                @weak self as view
+                => move |_| {
+                    view.set_color_theme();
+            }));
+    }
+
+    /// Sets the color theme used for the views.
+    pub fn set_color_theme(&self) {
+        let style_scheme_manager = StyleSchemeManager::new();
+
+        let scheme_id = match self.settings().is_gtk_application_prefer_dark_theme() {
+            true => "Adwaita-dark",
+            false => "Adwaita",
+        };
+
+        let style: Option<StyleScheme> = style_scheme_manager.scheme(scheme_id);
+
+        for source_view in [
+            &self.imp().result_source_view,
+            &self.imp().ours_source_view,
+            &self.imp().theirs_source_view,
+            &self.imp().base_source_view,
+        ] {
+            if let Ok(buffer) = source_view.buffer().downcast::<Buffer>() {
+                buffer.set_style_scheme(style.as_ref());
+            }
+        }
+    }
+
+    /// Retrieves a buffer with a language given from a file name.
+    fn get_buffer(&self, file_name: &str) -> Buffer {
+        let language_manager = LanguageManager::new();
+        let found_language = language_manager.guess_language(Some(file_name), None);
+
+        match found_language {
+            Some(language) => Buffer::with_language(&language),
+            None => Buffer::new(None),
+        }
+    }
+}
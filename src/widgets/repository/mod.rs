@@ -18,14 +18,25 @@
  */
 
 pub mod branch_management_view;
+pub mod commit_detail_view;
 pub mod commit_view;
 pub mod commits_sidebar;
+pub mod file_browser_view;
 pub mod file_view;
+pub mod merge_conflict_view;
+pub mod rebase_view;
+pub mod remote_management_view;
+pub mod stash_view;
+pub mod submodule_view;
+pub mod tag_management_view;
 
 mod imp;
 
 use glib::Object;
 use gtk::glib::{self, object::ObjectBuilder};
+use gtk::subclass::prelude::*;
+
+use crate::utils::commit_graph::CommitGraphRow;
 
 glib::wrapper! {
     pub struct CommitObject(ObjectSubclass<imp::CommitObject>);
@@ -54,4 +65,14 @@ impl CommitObject {
 
         return object_builder.build();
     }
+
+    /// Retrieves the place of the commit in the commit graph, if it has one.
+    pub fn graph_row(&self) -> Option<CommitGraphRow> {
+        return self.imp().graph_row.borrow().clone();
+    }
+
+    /// Used to set the place of the commit in the commit graph.
+    pub fn set_graph_row(&self, graph_row: CommitGraphRow) {
+        self.imp().graph_row.replace(Some(graph_row));
+    }
 }
@@ -0,0 +1,341 @@
+/* rebase_view.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use adw::subclass::prelude::*;
+use gettextrs::gettext;
+use gtk::gdk;
+use gtk::glib;
+use gtk::glib::clone;
+
+use gtk::prelude::*;
+
+use crate::utils::interactive_rebase::{RebaseAction, RebaseStep};
+
+/// The number of characters of the commit ids shown.
+const SHORT_COMMIT_ID_LENGTH: usize = 7;
+
+mod imp {
+
+    use std::cell::RefCell;
+
+    use super::*;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::glib::ObjectExt;
+    use gtk::subclass::widget::CompositeTemplateInitializingExt;
+    use gtk::template_callbacks;
+    use gtk::{glib, CompositeTemplate};
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(
+        resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-rebase-view.ui"
+    )]
+    pub struct BagitRebaseView {
+        #[template_child]
+        pub rebase_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub base_commit_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub steps_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub stopped_status_page: TemplateChild<adw::StatusPage>,
+
+        pub base_commit_id: RefCell<String>,
+
+        pub steps: RefCell<Vec<RebaseStep>>,
+    }
+
+    #[template_callbacks]
+    impl BagitRebaseView {
+        #[template_callback]
+        fn start_rebase(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("start-rebase", &[]);
+        }
+
+        #[template_callback]
+        fn cancel_rebase(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("cancel-rebase", &[]);
+        }
+
+        #[template_callback]
+        fn continue_rebase(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("continue-rebase", &[]);
+        }
+
+        #[template_callback]
+        fn abort_rebase(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("abort-rebase", &[]);
+        }
+    }
+
+    // The central trait for subclassing a GObject
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitRebaseView {
+        const NAME: &'static str = "BagitRebaseView";
+        type Type = super::BagitRebaseView;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+    impl ObjectImpl for BagitRebaseView {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("start-rebase").build(),
+                    Signal::builder("cancel-rebase").build(),
+                    Signal::builder("continue-rebase").build(),
+                    Signal::builder("abort-rebase").build(),
+                ]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitRebaseView {}
+    impl BoxImpl for BagitRebaseView {}
+}
+glib::wrapper! {
+    pub struct BagitRebaseView(ObjectSubclass<imp::BagitRebaseView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl BagitRebaseView {
+    /// Shows the editor of an interactive rebase on a base commit, with the commits to rebase.
+    pub fn init_rebase_editor(&self, base_commit_id: String, steps: Vec<RebaseStep>) {
+        self.imp().base_commit_label.set_text(&format!(
+            "{} {}",
+            gettext("_Rebase onto"),
+            self.shorten_commit_id(&base_commit_id)
+        ));
+        self.imp().base_commit_id.replace(base_commit_id);
+        self.imp().steps.replace(steps);
+
+        self.update_steps_list();
+        self.imp()
+            .rebase_stack
+            .set_visible_child_name("editor page");
+    }
+
+    /// Shows that the rebase stopped, to let the user edit the commit of a step when there is one.
+    pub fn show_stopped_rebase(&self, edited_step: Option<&RebaseStep>) {
+        let description = match edited_step {
+            Some(step) => format!(
+                "{} {}",
+                gettext("_Edit commit description"),
+                step.get_summary()
+            ),
+            None => gettext("_Rebase interrupted description"),
+        };
+
+        self.imp()
+            .stopped_status_page
+            .set_description(Some(&description));
+        self.imp()
+            .rebase_stack
+            .set_visible_child_name("stopped page");
+    }
+
+    /// Retrieves the base commit of the rebase.
+    pub fn get_base_commit_id(&self) -> String {
+        return self.imp().base_commit_id.borrow().clone();
+    }
+
+    /// Retrieves the steps of the rebase, in the chosen order.
+    pub fn get_steps(&self) -> Vec<RebaseStep> {
+        return self.imp().steps.borrow().clone();
+    }
+
+    /// Shortens a commit id.
+    fn shorten_commit_id(&self, commit_id: &str) -> String {
+        return commit_id.chars().take(SHORT_COMMIT_ID_LENGTH).collect();
+    }
+
+    /// Moves a step to the position of another one.
+    fn move_step(&self, from_index: usize, to_index: usize) {
+        if from_index == to_index {
+            return;
+        }
+
+        {
+            let mut steps = self.imp().steps.borrow_mut();
+            if from_index >= steps.len() || to_index >= steps.len() {
+                return;
+            }
+            let step = steps.remove(from_index);
+            steps.insert(to_index, step);
+        }
+
+        self.update_steps_list();
+    }
+
+    /// Rebuilds the rows of the steps.
+    fn update_steps_list(&self) {
+        let mut step_row = self.imp().steps_list.row_at_index(0);
+        while step_row != None {
+            self.imp().steps_list.remove(&step_row.unwrap());
+            step_row = self.imp().steps_list.row_at_index(0);
+        }
+
+        let steps = self.imp().steps.borrow().clone();
+        for (index, step) in steps.iter().enumerate() {
+            let row = self.build_step_row(index, step);
+            self.imp().steps_list.append(&row);
+        }
+    }
+
+    /// Build the row of a step, which is dragged to reorder the steps.
+    /// The message of the commit can be changed when rewording it.
+    fn build_step_row(&self, index: usize, step: &RebaseStep) -> gtk::ListBoxRow {
+        let row = gtk::ListBoxRow::new();
+        row.set_activatable(false);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        main_box.set_margin_top(8);
+        main_box.set_margin_bottom(8);
+        main_box.set_margin_start(8);
+        main_box.set_margin_end(8);
+
+        let step_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+
+        let handle = gtk::Image::from_icon_name("list-drag-handle-symbolic");
+        handle.set_tooltip_text(Some(&gettext("_Drag to reorder")));
+        handle.add_css_class("dim-label");
+
+        let commit_id_label = gtk::Label::new(Some(&self.shorten_commit_id(&step.commit_id)));
+        commit_id_label.add_css_class("monospace");
+        commit_id_label.add_css_class("dim-label");
+
+        let summary_label = gtk::Label::new(Some(&step.get_summary()));
+        summary_label.set_hexpand(true);
+        summary_label.set_xalign(0.0);
+        summary_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+
+        let action_names: Vec<String> = RebaseAction::all()
+            .iter()
+            .map(|action| action.get_name())
+            .collect();
+        let action_dropdown = gtk::DropDown::from_strings(
+            &action_names
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<&str>>(),
+        );
+        action_dropdown.set_valign(gtk::Align::Center);
+        action_dropdown.set_selected(
+            RebaseAction::all()
+                .iter()
+                .position(|action| *action == step.action)
+                .unwrap_or(0) as u32,
+        );
+
+        step_box.append(&handle);
+        step_box.append(&commit_id_label);
+        step_box.append(&summary_label);
+        step_box.append(&action_dropdown);
+
+        let message_view = gtk::TextView::new();
+        message_view.set_wrap_mode(gtk::WrapMode::WordChar);
+        message_view.set_height_request(80);
+        message_view.set_top_margin(6);
+        message_view.set_bottom_margin(6);
+        message_view.set_left_margin(6);
+        message_view.set_right_margin(6);
+        message_view.buffer().set_text(&step.message);
+        message_view
+            .buffer()
+            .connect_changed(clone!(@weak self as win => move |buffer| {
+                let message = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+                let mut steps = win.imp().steps.borrow_mut();
+                if let Some(step) = steps.get_mut(index) {
+                    step.message = message.to_string();
+                }
+            }));
+
+        let message_frame = gtk::Frame::new(None);
+        message_frame.set_child(Some(&message_view));
+
+        let message_revealer = gtk::Revealer::new();
+        message_revealer.set_child(Some(&message_frame));
+        message_revealer.set_reveal_child(step.action == RebaseAction::Reword);
+
+        if step.action == RebaseAction::Drop {
+            summary_label.add_css_class("dim-label");
+        }
+
+        action_dropdown.connect_selected_notify(clone!(
+            @weak self as win,
+            @weak message_revealer,
+            @weak summary_label
+            => move |dropdown| {
+                let action = RebaseAction::all()[dropdown.selected() as usize];
+                if let Some(step) = win.imp().steps.borrow_mut().get_mut(index) {
+                    step.action = action;
+                }
+
+                message_revealer.set_reveal_child(action == RebaseAction::Reword);
+                if action == RebaseAction::Drop {
+                    summary_label.add_css_class("dim-label");
+                } else {
+                    summary_label.remove_css_class("dim-label");
+                }
+            }
+        ));
+
+        main_box.append(&step_box);
+        main_box.append(&message_revealer);
+        row.set_child(Some(&main_box));
+
+        let drag_source = gtk::DragSource::new();
+        drag_source.set_actions(gdk::DragAction::MOVE);
+        drag_source.connect_prepare(move |_source, _x, _y| {
+            return Some(gdk::ContentProvider::for_value(&(index as u32).to_value()));
+        });
+        drag_source.connect_drag_begin(clone!(@weak row => move |source, _drag| {
+            let paintable = gtk::WidgetPaintable::new(Some(&row));
+            source.set_icon(Some(&paintable), 0, 0);
+        }));
+        row.add_controller(drag_source);
+
+        let drop_target = gtk::DropTarget::new(u32::static_type(), gdk::DragAction::MOVE);
+        drop_target.connect_drop(clone!(
+            @weak self as win => @default-return false,
+            move |_target, value, _x, _y| {
+                match value.get::<u32>() {
+                    Ok(from_index) => {
+                        win.move_step(from_index as usize, index);
+                        true
+                    }
+                    Err(_) => false,
+                }
+            }
+        ));
+        row.add_controller(drop_target);
+
+        return row;
+    }
+}
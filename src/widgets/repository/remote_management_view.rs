@@ -0,0 +1,276 @@
+/* remote_management_view.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::thread;
+
+use adw::prelude::ActionRowExt;
+use adw::subclass::prelude::*;
+use gettextrs::gettext;
+use git2::Repository;
+use gtk::glib;
+use gtk::glib::{clone, MainContext, Priority};
+
+use gtk::prelude::*;
+
+use crate::utils::remote_entry::RemoteEntry;
+use crate::utils::repository_utils::RepositoryUtils;
+
+mod imp {
+
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::glib::ObjectExt;
+    use gtk::subclass::widget::CompositeTemplateInitializingExt;
+    use gtk::template_callbacks;
+    use gtk::{glib, CompositeTemplate};
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(
+        resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-remote-management-view.ui"
+    )]
+    pub struct BagitRemoteManagementView {
+        #[template_child]
+        pub remote_name_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub remote_url_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub add_remote_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub fetch_all_remotes_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub remotes_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub all_remotes: TemplateChild<gtk::ListBox>,
+
+        pub remotes: RefCell<Vec<RemoteEntry>>,
+
+        pub is_doing_operations: Cell<bool>,
+    }
+
+    #[template_callbacks]
+    impl BagitRemoteManagementView {
+        #[template_callback]
+        fn remote_information_changed(&self, _row: &adw::EntryRow) {
+            self.add_remote_button.set_sensitive(
+                !self.remote_name_row.text().trim().is_empty()
+                    && !self.remote_url_row.text().trim().is_empty(),
+            );
+        }
+
+        #[template_callback]
+        fn add_remote(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>(
+                "add-remote",
+                &[
+                    &self.remote_name_row.text().trim(),
+                    &self.remote_url_row.text().trim(),
+                ],
+            );
+        }
+
+        #[template_callback]
+        fn fetch_all_remotes(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("fetch-all-remotes", &[]);
+        }
+    }
+
+    // The central trait for subclassing a GObject
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitRemoteManagementView {
+        const NAME: &'static str = "BagitRemoteManagementView";
+        type Type = super::BagitRemoteManagementView;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+    impl ObjectImpl for BagitRemoteManagementView {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("add-remote")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("edit-remote")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("delete-remote")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("fetch-remote")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("fetch-all-remotes").build(),
+                    Signal::builder("push-to-remote")
+                        .param_types([str::static_type()])
+                        .build(),
+                ]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitRemoteManagementView {}
+    impl BoxImpl for BagitRemoteManagementView {}
+}
+glib::wrapper! {
+    pub struct BagitRemoteManagementView(ObjectSubclass<imp::BagitRemoteManagementView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl BagitRemoteManagementView {
+    /// Fetch all remotes of the repository.
+    pub fn fetch_all_remotes(&self, repository_path: String) {
+        if self.imp().is_doing_operations.get() {
+            return;
+        }
+        self.imp().is_doing_operations.set(true);
+
+        let (sender, receiver) = MainContext::channel::<Vec<RemoteEntry>>(Priority::default());
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            let remotes = match Repository::open(repository_path) {
+                Ok(repo) => match RepositoryUtils::get_remotes(&repo) {
+                    Ok(remotes) => remotes,
+                    Err(error) => {
+                        tracing::warn!("Could not get remotes: {}", error);
+                        vec![]
+                    }
+                },
+                Err(_) => vec![],
+            };
+
+            sender.send(remotes).expect("Cannot send remotes");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+            move |remotes| {
+                if remotes != *win.imp().remotes.borrow() {
+                    win.clear_remotes_list();
+
+                    for remote in &remotes {
+                        let row = win.build_remote_row(remote);
+                        win.imp().all_remotes.append(&row);
+                    }
+
+                    win.imp().remotes.replace(remotes);
+                }
+
+                let has_remotes = !win.imp().remotes.borrow().is_empty();
+                win.imp().fetch_all_remotes_button.set_sensitive(has_remotes);
+                win.imp().remotes_stack.set_visible_child_name(if has_remotes {
+                    "remotes page"
+                } else {
+                    "no remotes page"
+                });
+
+                win.imp().is_doing_operations.set(false);
+                Continue(true)
+            }),
+        );
+    }
+
+    /// Clear the information of the remote to add.
+    pub fn clear_remote_information(&self) {
+        self.imp().remote_name_row.set_text("");
+        self.imp().remote_url_row.set_text("");
+    }
+
+    /// Build a button of a remote row.
+    fn build_remote_button(&self, icon_name: &str, tooltip: &str) -> gtk::Button {
+        let button = gtk::Button::from_icon_name(icon_name);
+        button.set_tooltip_text(Some(tooltip));
+        button.set_margin_top(8);
+        button.set_margin_bottom(8);
+
+        return button;
+    }
+
+    /// Build a remote row, with the buttons to fetch, push, edit and delete the remote.
+    fn build_remote_row(&self, remote: &RemoteEntry) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(&remote.name)
+            .subtitle(&remote.url)
+            .build();
+        row.set_title_lines(1);
+        row.set_subtitle_lines(1);
+
+        let fetch_button = self.build_remote_button("arrow3-down-symbolic", &gettext("_Fetch"));
+        let remote_name = remote.name.clone();
+        fetch_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("fetch-remote", &[&remote_name]);
+        }));
+
+        let push_button = self.build_remote_button("arrow3-up-symbolic", &gettext("_Push"));
+        let remote_name = remote.name.clone();
+        push_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("push-to-remote", &[&remote_name]);
+        }));
+
+        let edit_button =
+            self.build_remote_button("document-edit-symbolic", &gettext("_Edit remote"));
+        let remote_name = remote.name.clone();
+        let remote_url = remote.url.clone();
+        edit_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("edit-remote", &[&remote_name, &remote_url]);
+        }));
+
+        let delete_button =
+            self.build_remote_button("user-trash-symbolic", &gettext("_Delete remote"));
+        delete_button.add_css_class("destructive-action");
+        let remote_name = remote.name.clone();
+        delete_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("delete-remote", &[&remote_name]);
+        }));
+
+        let end_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+        end_box.append(&fetch_button);
+        end_box.append(&push_button);
+        end_box.append(&edit_button);
+        end_box.append(&delete_button);
+
+        row.add_suffix(&end_box);
+
+        return row;
+    }
+
+    /// Clear remotes list.
+    fn clear_remotes_list(&self) {
+        let mut remote_row = self.imp().all_remotes.row_at_index(0);
+        while remote_row != None {
+            self.imp().all_remotes.remove(&remote_row.unwrap());
+            remote_row = self.imp().all_remotes.row_at_index(0);
+        }
+    }
+}
@@ -0,0 +1,305 @@
+/* stash_view.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use adw::prelude::ActionRowExt;
+use adw::subclass::prelude::*;
+use gettextrs::gettext;
+use git2::Delta;
+use gtk::glib::{self, clone};
+use gtk::prelude::*;
+
+use crate::utils::file_diff::FileDiff;
+use crate::utils::stash_entry::StashEntry;
+use crate::widgets::repository::commit_detail_view::BagitCommitDetailView;
+use crate::widgets::repository::file_view::BagitFileView;
+
+mod imp {
+
+    use std::cell::RefCell;
+
+    use super::*;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::subclass::widget::CompositeTemplateInitializingExt;
+    use gtk::template_callbacks;
+    use gtk::CompositeTemplate;
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-stash-view.ui")]
+    pub struct BagitStashView {
+        #[template_child]
+        pub message_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub include_untracked_switch: TemplateChild<gtk::Switch>,
+        #[template_child]
+        pub only_selected_switch: TemplateChild<gtk::Switch>,
+        #[template_child]
+        pub stashes_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub changed_files_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub changed_files_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub file_view: TemplateChild<BagitFileView>,
+
+        pub stashes: RefCell<Vec<StashEntry>>,
+        pub selected_stash_id: RefCell<String>,
+        pub changed_files: RefCell<Vec<(String, Delta)>>,
+    }
+
+    #[template_callbacks]
+    impl BagitStashView {
+        #[template_callback]
+        fn save_stash(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>(
+                "save-stash",
+                &[
+                    &self.message_row.text().trim().to_string(),
+                    &self.include_untracked_switch.is_active(),
+                    &self.only_selected_switch.is_active(),
+                ],
+            );
+        }
+
+        #[template_callback]
+        fn stash_activated(&self, row: &gtk::ListBoxRow) {
+            self.obj().select_stash(row.index());
+        }
+
+        #[template_callback]
+        fn changed_file_activated(&self, row: &gtk::ListBoxRow) {
+            self.obj().select_changed_file(row.index());
+        }
+    }
+
+    // The central trait for subclassing a GObject
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitStashView {
+        const NAME: &'static str = "BagitStashView";
+        type Type = super::BagitStashView;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+    impl ObjectImpl for BagitStashView {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            // The differences of a stash cannot be staged:
+            self.file_view.set_staging_controls_visible(false);
+            self.file_view.set_file_modes_visible(false);
+
+            let placeholder = gtk::Label::new(Some(&gettext("_No stash")));
+            placeholder.set_margin_top(12);
+            placeholder.set_margin_bottom(12);
+            placeholder.add_css_class("dim-label");
+            self.stashes_list.set_placeholder(Some(&placeholder));
+        }
+
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("save-stash")
+                        .param_types([str::static_type(), bool::static_type(), bool::static_type()])
+                        .build(),
+                    Signal::builder("apply-stash")
+                        .param_types([u32::static_type()])
+                        .build(),
+                    Signal::builder("pop-stash")
+                        .param_types([u32::static_type()])
+                        .build(),
+                    Signal::builder("drop-stash")
+                        .param_types([u32::static_type(), str::static_type()])
+                        .build(),
+                    Signal::builder("stash-selected")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("stash-file-selected")
+                        .param_types([str::static_type(), str::static_type()])
+                        .build(),
+                ]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitStashView {}
+    impl BoxImpl for BagitStashView {}
+}
+glib::wrapper! {
+    pub struct BagitStashView(ObjectSubclass<imp::BagitStashView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl BagitStashView {
+    /// Shows the stash list.
+    /// The previously selected stash stays selected if it still exists, otherwise the most recent one is selected.
+    pub fn update_stashes(&self, stashes: Vec<StashEntry>) {
+        let selected_stash_id = self.imp().selected_stash_id.borrow().clone();
+
+        self.clear_list(&self.imp().stashes_list);
+        for stash in &stashes {
+            let row = self.build_stash_row(stash);
+            self.imp().stashes_list.append(&row);
+        }
+
+        let selected_index = match stashes
+            .iter()
+            .position(|stash| stash.stash_id == selected_stash_id)
+        {
+            Some(index) => index as i32,
+            None => 0,
+        };
+
+        self.imp().stashes.replace(stashes);
+
+        match self.imp().stashes_list.row_at_index(selected_index) {
+            Some(row) => {
+                self.imp().stashes_list.select_row(Some(&row));
+                self.select_stash(selected_index);
+            }
+            None => {
+                self.imp().selected_stash_id.replace(String::new());
+                self.show_stash_files("", vec![]);
+            }
+        }
+    }
+
+    /// Shows the files changed by a stash.
+    /// The first changed file is selected to show its differences.
+    pub fn show_stash_files(&self, stash_id: &str, changed_files: Vec<(String, Delta)>) {
+        self.clear_list(&self.imp().changed_files_list);
+        for changed_file in &changed_files {
+            let row =
+                BagitCommitDetailView::build_changed_file_row(&changed_file.0, changed_file.1);
+            self.imp().changed_files_list.append(&row);
+        }
+
+        self.imp()
+            .changed_files_group
+            .set_visible(!changed_files.is_empty());
+        self.imp().selected_stash_id.replace(stash_id.to_string());
+        self.imp().changed_files.replace(changed_files);
+
+        self.imp().file_view.show_diff("", FileDiff::default());
+
+        if let Some(first_row) = self.imp().changed_files_list.row_at_index(0) {
+            self.imp().changed_files_list.select_row(Some(&first_row));
+            self.select_changed_file(0);
+        }
+    }
+
+    /// Shows the differences of a file of the stash.
+    pub fn show_file_diff(&self, file_path: &str, file_diff: FileDiff) {
+        self.imp().file_view.show_diff(file_path, file_diff);
+    }
+
+    /// Empties the stash message once the changes are stashed.
+    pub fn clear_stash_message(&self) {
+        self.imp().message_row.set_text("");
+    }
+
+    /// Asks for the files changed by a stash of the list.
+    fn select_stash(&self, index: i32) {
+        if index < 0 {
+            return;
+        }
+
+        let stash = match self.imp().stashes.borrow().get(index as usize) {
+            Some(stash) => stash.clone(),
+            None => return,
+        };
+
+        self.emit_by_name::<()>("stash-selected", &[&stash.stash_id]);
+    }
+
+    /// Asks for the differences of a changed file of the selected stash.
+    fn select_changed_file(&self, index: i32) {
+        if index < 0 {
+            return;
+        }
+
+        let stash_id = self.imp().selected_stash_id.borrow().clone();
+
+        if let Some(changed_file) = self.imp().changed_files.borrow().get(index as usize) {
+            self.emit_by_name::<()>("stash-file-selected", &[&stash_id, &changed_file.0]);
+        }
+    }
+
+    /// Builds the row of a stash, with the buttons to apply, pop and drop it.
+    fn build_stash_row(&self, stash: &StashEntry) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(&stash.message)
+            .subtitle(&format!("stash@{{{}}}", stash.index))
+            .activatable(true)
+            .build();
+        row.set_title_lines(2);
+
+        let buttons_box = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        buttons_box.set_valign(gtk::Align::Center);
+        buttons_box.add_css_class("linked");
+
+        let index = stash.index as u32;
+
+        let apply_button = gtk::Button::from_icon_name("document-revert-symbolic");
+        apply_button.set_tooltip_text(Some(&gettext("_Apply stash")));
+        apply_button.connect_clicked(clone!(@weak self as view => move |_| {
+            view.emit_by_name::<()>("apply-stash", &[&index]);
+        }));
+        buttons_box.append(&apply_button);
+
+        let pop_button = gtk::Button::from_icon_name("edit-paste-symbolic");
+        pop_button.set_tooltip_text(Some(&gettext("_Pop stash")));
+        pop_button.connect_clicked(clone!(@weak self as view => move |_| {
+            view.emit_by_name::<()>("pop-stash", &[&index]);
+        }));
+        buttons_box.append(&pop_button);
+
+        let message = stash.message.clone();
+        let drop_button = gtk::Button::from_icon_name("user-trash-symbolic");
+        drop_button.set_tooltip_text(Some(&gettext("_Drop stash")));
+        drop_button.add_css_class("destructive-action");
+        drop_button.connect_clicked(clone!(@weak self as view => move |_| {
+            view.emit_by_name::<()>("drop-stash", &[&index, &message]);
+        }));
+        buttons_box.append(&drop_button);
+
+        row.add_suffix(&buttons_box);
+
+        return row;
+    }
+
+    /// Removes all the rows of a list.
+    fn clear_list(&self, list: &gtk::ListBox) {
+        let mut row = list.row_at_index(0);
+        while row != None {
+            list.remove(&row.unwrap());
+            row = list.row_at_index(0);
+        }
+    }
+}
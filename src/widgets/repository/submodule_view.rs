@@ -0,0 +1,304 @@
+/* submodule_view.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::path::Path;
+use std::thread;
+
+use adw::prelude::ActionRowExt;
+use adw::subclass::prelude::*;
+use gettextrs::gettext;
+use git2::Repository;
+use gtk::glib;
+use gtk::glib::{clone, MainContext, Priority};
+
+use gtk::prelude::*;
+
+use crate::utils::repository_utils::RepositoryUtils;
+use crate::utils::submodule_entry::SubmoduleEntry;
+
+/// The number of characters of the commit ids shown.
+const SHORT_COMMIT_ID_LENGTH: usize = 7;
+
+mod imp {
+
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::glib::ObjectExt;
+    use gtk::subclass::widget::CompositeTemplateInitializingExt;
+    use gtk::template_callbacks;
+    use gtk::{glib, CompositeTemplate};
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(
+        resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-submodule-view.ui"
+    )]
+    pub struct BagitSubmoduleView {
+        #[template_child]
+        pub update_all_submodules_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub sync_all_submodules_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub submodules_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub all_submodules: TemplateChild<gtk::ListBox>,
+
+        pub submodules: RefCell<Vec<SubmoduleEntry>>,
+
+        pub repository_path: RefCell<String>,
+
+        pub is_doing_operations: Cell<bool>,
+    }
+
+    #[template_callbacks]
+    impl BagitSubmoduleView {
+        #[template_callback]
+        fn update_all_submodules(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("update-submodule", &[&""]);
+        }
+
+        #[template_callback]
+        fn sync_all_submodules(&self, _button: &gtk::Button) {
+            self.obj().emit_by_name::<()>("sync-submodule", &[&""]);
+        }
+    }
+
+    // The central trait for subclassing a GObject
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitSubmoduleView {
+        const NAME: &'static str = "BagitSubmoduleView";
+        type Type = super::BagitSubmoduleView;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+    impl ObjectImpl for BagitSubmoduleView {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("init-submodule")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("update-submodule")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("sync-submodule")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("open-submodule")
+                        .param_types([str::static_type()])
+                        .build(),
+                ]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitSubmoduleView {}
+    impl BoxImpl for BagitSubmoduleView {}
+}
+glib::wrapper! {
+    pub struct BagitSubmoduleView(ObjectSubclass<imp::BagitSubmoduleView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl BagitSubmoduleView {
+    /// Fetch all submodules of the repository with their state.
+    pub fn fetch_all_submodules(&self, repository_path: String) {
+        if self.imp().is_doing_operations.get() {
+            return;
+        }
+        self.imp().is_doing_operations.set(true);
+
+        // The rows of another repository open submodules at other paths:
+        let is_other_repository = *self.imp().repository_path.borrow() != repository_path;
+        self.imp().repository_path.replace(repository_path.clone());
+
+        let (sender, receiver) = MainContext::channel::<Vec<SubmoduleEntry>>(Priority::default());
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            let submodules = match Repository::open(repository_path) {
+                Ok(repo) => match RepositoryUtils::get_submodules(&repo) {
+                    Ok(submodules) => submodules,
+                    Err(error) => {
+                        tracing::warn!("Could not get submodules: {}", error);
+                        vec![]
+                    }
+                },
+                Err(_) => vec![],
+            };
+
+            sender.send(submodules).expect("Cannot send submodules");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+            move |submodules| {
+                if is_other_repository || submodules != *win.imp().submodules.borrow() {
+                    win.clear_submodules_list();
+
+                    for submodule in &submodules {
+                        let row = win.build_submodule_row(submodule);
+                        win.imp().all_submodules.append(&row);
+                    }
+
+                    win.imp().submodules.replace(submodules);
+                }
+
+                let has_submodules = !win.imp().submodules.borrow().is_empty();
+                win.imp().update_all_submodules_button.set_sensitive(has_submodules);
+                win.imp().sync_all_submodules_button.set_sensitive(has_submodules);
+                win.imp().submodules_stack.set_visible_child_name(if has_submodules {
+                    "submodules page"
+                } else {
+                    "no submodules page"
+                });
+
+                win.imp().is_doing_operations.set(false);
+                Continue(true)
+            }),
+        );
+    }
+
+    /// Shortens a commit id, an empty id meaning that there is no commit.
+    fn shorten_commit_id(&self, commit_id: &str) -> String {
+        if commit_id.is_empty() {
+            return gettext("_None");
+        }
+
+        return commit_id.chars().take(SHORT_COMMIT_ID_LENGTH).collect();
+    }
+
+    /// Build a button of a submodule row.
+    fn build_submodule_button(&self, icon_name: &str, tooltip: &str) -> gtk::Button {
+        let button = gtk::Button::from_icon_name(icon_name);
+        button.set_tooltip_text(Some(tooltip));
+        button.set_margin_top(8);
+        button.set_margin_bottom(8);
+
+        return button;
+    }
+
+    /// Build a label giving the state of a submodule.
+    fn build_state_label(&self, text: &str, css_class_name: &str) -> gtk::Label {
+        let label = gtk::Label::new(Some(text));
+        label.set_valign(gtk::Align::Center);
+        label.add_css_class("caption");
+        label.add_css_class(css_class_name);
+
+        return label;
+    }
+
+    /// Build a submodule row, with its commits, its state
+    /// and the buttons to initialize, update, synchronize and open the submodule.
+    fn build_submodule_row(&self, submodule: &SubmoduleEntry) -> adw::ActionRow {
+        let commits = format!(
+            "{} {}  {} {}",
+            gettext("_Recorded commit"),
+            self.shorten_commit_id(&submodule.recorded_commit_id),
+            gettext("_Checked out commit"),
+            self.shorten_commit_id(&submodule.checked_out_commit_id)
+        );
+
+        let row = adw::ActionRow::builder()
+            .title(&submodule.path)
+            .subtitle(&format!("{}\n{}", submodule.url, commits))
+            .build();
+        row.set_title_lines(1);
+        row.set_subtitle_lines(2);
+
+        let end_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+
+        if !submodule.is_initialized {
+            end_box.append(&self.build_state_label(&gettext("_Not initialized"), "dim-label"));
+        } else if submodule.recorded_commit_id != submodule.checked_out_commit_id {
+            end_box.append(&self.build_state_label(&gettext("_Other commit"), "warning"));
+        }
+
+        if submodule.is_dirty {
+            end_box.append(&self.build_state_label(&gettext("_Modified"), "warning"));
+        }
+
+        if !submodule.is_initialized {
+            let init_button =
+                self.build_submodule_button("list-add-symbolic", &gettext("_Init submodule"));
+            let submodule_name = submodule.name.clone();
+            init_button.connect_clicked(clone!(@weak self as win => move |_| {
+                win.emit_by_name::<()>("init-submodule", &[&submodule_name]);
+            }));
+            end_box.append(&init_button);
+        }
+
+        let update_button =
+            self.build_submodule_button("arrow3-down-symbolic", &gettext("_Update submodule"));
+        let submodule_name = submodule.name.clone();
+        update_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("update-submodule", &[&submodule_name]);
+        }));
+        end_box.append(&update_button);
+
+        let sync_button = self
+            .build_submodule_button("emblem-synchronizing-symbolic", &gettext("_Sync submodule"));
+        let submodule_name = submodule.name.clone();
+        sync_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("sync-submodule", &[&submodule_name]);
+        }));
+        end_box.append(&sync_button);
+
+        let open_button =
+            self.build_submodule_button("folder-open-symbolic", &gettext("_Open submodule"));
+        open_button.set_sensitive(submodule.is_initialized);
+        let submodule_path = Path::new(&self.imp().repository_path.borrow().clone())
+            .join(&submodule.path)
+            .to_str()
+            .unwrap_or("")
+            .to_string();
+        open_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("open-submodule", &[&submodule_path]);
+        }));
+        end_box.append(&open_button);
+
+        row.add_suffix(&end_box);
+
+        return row;
+    }
+
+    /// Clear submodules list.
+    fn clear_submodules_list(&self) {
+        let mut submodule_row = self.imp().all_submodules.row_at_index(0);
+        while submodule_row != None {
+            self.imp().all_submodules.remove(&submodule_row.unwrap());
+            submodule_row = self.imp().all_submodules.row_at_index(0);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+/* tag_management_view.rs
+ *
+ * Copyright 2023 SkillDary
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, version 3 of the License, only.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only
+ */
+
+use std::thread;
+
+use adw::prelude::ActionRowExt;
+use adw::subclass::prelude::*;
+use gettextrs::gettext;
+use git2::Repository;
+use gtk::glib;
+use gtk::glib::{clone, MainContext, Priority};
+
+use gtk::prelude::*;
+
+use crate::utils::repository_utils::RepositoryUtils;
+use crate::utils::tag_entry::TagEntry;
+
+mod imp {
+
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    use gtk::glib::subclass::Signal;
+    use gtk::glib::ObjectExt;
+    use gtk::subclass::widget::CompositeTemplateInitializingExt;
+    use gtk::template_callbacks;
+    use gtk::{glib, CompositeTemplate};
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(
+        resource = "/com/skilldary/bagit/desktop/ui/widgets/repository/bagit-tag-management-view.ui"
+    )]
+    pub struct BagitTagManagementView {
+        #[template_child]
+        pub push_all_tags_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub tags_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub all_tags: TemplateChild<gtk::ListBox>,
+
+        pub tags: RefCell<Vec<TagEntry>>,
+
+        pub is_doing_operations: Cell<bool>,
+    }
+
+    #[template_callbacks]
+    impl BagitTagManagementView {
+        #[template_callback]
+        fn push_all_tags(&self, _button: &gtk::Button) {
+            // An empty tag name means that all tags are pushed:
+            self.obj().emit_by_name::<()>("push-tag", &[&""]);
+        }
+    }
+
+    // The central trait for subclassing a GObject
+    #[glib::object_subclass]
+    impl ObjectSubclass for BagitTagManagementView {
+        const NAME: &'static str = "BagitTagManagementView";
+        type Type = super::BagitTagManagementView;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+    impl ObjectImpl for BagitTagManagementView {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![
+                    Signal::builder("push-tag")
+                        .param_types([str::static_type()])
+                        .build(),
+                    Signal::builder("delete-tag")
+                        .param_types([str::static_type()])
+                        .build(),
+                ]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+    impl WidgetImpl for BagitTagManagementView {}
+    impl BoxImpl for BagitTagManagementView {}
+}
+glib::wrapper! {
+    pub struct BagitTagManagementView(ObjectSubclass<imp::BagitTagManagementView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Actionable,
+                    gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl BagitTagManagementView {
+    /// Fetch all tags.
+    pub fn fetch_all_tags(&self, repository_path: String) {
+        if self.imp().is_doing_operations.get() {
+            return;
+        }
+        self.imp().is_doing_operations.set(true);
+
+        let (sender, receiver) = MainContext::channel::<Vec<TagEntry>>(Priority::default());
+
+        thread::spawn(move || {
+            let sender = sender.clone();
+
+            let tags = match Repository::open(repository_path) {
+                Ok(repo) => match RepositoryUtils::get_all_tags(&repo) {
+                    Ok(tags) => tags,
+                    Err(error) => {
+                        tracing::warn!("Could not get tags: {}", error);
+                        vec![]
+                    }
+                },
+                Err(_) => vec![],
+            };
+
+            sender.send(tags).expect("Cannot send tags");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false),
+            move |tags| {
+                if tags != *win.imp().tags.borrow() {
+                    win.clear_tags_list();
+
+                    for tag in &tags {
+                        let row = win.build_tag_row(tag);
+                        win.imp().all_tags.append(&row);
+                    }
+
+                    win.imp().tags.replace(tags);
+                }
+
+                let has_tags = !win.imp().tags.borrow().is_empty();
+                win.imp().push_all_tags_button.set_sensitive(has_tags);
+                win.imp().tags_stack.set_visible_child_name(if has_tags {
+                    "tags page"
+                } else {
+                    "no tags page"
+                });
+
+                win.imp().is_doing_operations.set(false);
+                Continue(true)
+            }),
+        );
+    }
+
+    /// Build a tag type pill to add to a tag row.
+    fn build_tag_type_pill(&self, is_annotated: bool) -> gtk::Button {
+        let pill = gtk::Button::new();
+        pill.set_margin_bottom(8);
+        pill.set_margin_top(8);
+        if is_annotated {
+            pill.set_label(&gettext("_Annotated"));
+            pill.add_css_class("accent");
+        } else {
+            pill.set_label(&gettext("_Lightweight"));
+            pill.add_css_class("warning");
+        }
+        pill.set_can_target(false);
+        pill.add_css_class("pill");
+
+        return pill;
+    }
+
+    /// Build a tag row, with the buttons to push and delete the tag.
+    fn build_tag_row(&self, tag: &TagEntry) -> adw::ActionRow {
+        let row = adw::ActionRow::builder().title(&tag.name).build();
+        row.set_title_lines(1);
+        row.set_subtitle_lines(1);
+
+        let short_commit_id: String = tag.commit_id.chars().take(7).collect();
+        match tag.message.lines().next() {
+            Some(title) => row.set_subtitle(&format!("{} · {}", short_commit_id, title)),
+            None => row.set_subtitle(&short_commit_id),
+        }
+
+        let push_button = gtk::Button::from_icon_name("arrow3-up-symbolic");
+        push_button.set_tooltip_text(Some(&gettext("_Push tag")));
+        push_button.set_margin_top(8);
+        push_button.set_margin_bottom(8);
+        let tag_name = tag.name.clone();
+        push_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("push-tag", &[&tag_name]);
+        }));
+
+        let delete_button = gtk::Button::from_icon_name("user-trash-symbolic");
+        delete_button.set_tooltip_text(Some(&gettext("_Delete tag")));
+        delete_button.add_css_class("destructive-action");
+        delete_button.set_margin_top(8);
+        delete_button.set_margin_bottom(8);
+        let tag_name = tag.name.clone();
+        delete_button.connect_clicked(clone!(@weak self as win => move |_| {
+            win.emit_by_name::<()>("delete-tag", &[&tag_name]);
+        }));
+
+        let end_box = gtk::Box::new(gtk::Orientation::Horizontal, 10);
+        end_box.append(&self.build_tag_type_pill(tag.is_annotated));
+        end_box.append(&push_button);
+        end_box.append(&delete_button);
+
+        row.add_suffix(&end_box);
+
+        return row;
+    }
+
+    /// Clear tags list.
+    fn clear_tags_list(&self) {
+        let mut tag_row = self.imp().all_tags.row_at_index(0);
+        while tag_row != None {
+            self.imp().all_tags.remove(&tag_row.unwrap());
+            tag_row = self.imp().all_tags.row_at_index(0);
+        }
+    }
+}
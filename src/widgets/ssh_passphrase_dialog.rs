@@ -19,6 +19,7 @@
 
 use adw::subclass::prelude::*;
 
+use adw::prelude::ComboRowExt;
 use adw::prelude::EditableExt;
 use adw::prelude::StaticType;
 use gtk::subclass::widget::CompositeTemplateInitializingExt;
@@ -26,12 +27,18 @@ use gtk::{
     gio,
     glib::{self},
 };
-use gtk::{glib::subclass::Signal, prelude::ObjectExt, template_callbacks};
+use gtk::{
+    glib::subclass::Signal,
+    prelude::{ObjectExt, SettingsExt},
+    template_callbacks,
+};
 use once_cell::sync::Lazy;
 
+use crate::utils::passphrase_cache::PassphraseRetention;
+
 mod imp {
 
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
 
     use super::*;
 
@@ -40,9 +47,12 @@ mod imp {
     pub struct BagitSshPassphraseDialog {
         #[template_child]
         pub passphrase_row: TemplateChild<adw::PasswordEntryRow>,
+        #[template_child]
+        pub remember_row: TemplateChild<adw::ComboRow>,
 
         pub username: RefCell<String>,
         pub private_key_path: RefCell<String>,
+        pub remembering_minutes: Cell<u32>,
     }
 
     #[template_callbacks]
@@ -119,6 +129,29 @@ impl BagitSshPassphraseDialog {
         let win: BagitSshPassphraseDialog = Self::default();
         win.imp().username.replace(username);
         win.imp().private_key_path.replace(private_key_path);
+        win.init_remember_row();
         win
     }
+
+    /// Used to fill the choices of how long the passphrase is remembered.
+    fn init_remember_row(&self) {
+        let settings = gio::Settings::new("com.skilldary.bagit.desktop");
+        let minutes = settings.uint("passphrase-cache-minutes");
+        self.imp().remembering_minutes.set(minutes);
+
+        let choices = PassphraseRetention::choices(minutes);
+        let choices: Vec<&str> = choices.iter().map(|choice| choice.as_str()).collect();
+        self.imp()
+            .remember_row
+            .set_model(Some(&gtk::StringList::new(&choices)));
+        self.imp().remember_row.set_selected(1);
+    }
+
+    /// Used to get how long the user wants the passphrase to be remembered.
+    pub fn get_retention(&self) -> PassphraseRetention {
+        return PassphraseRetention::from_choice(
+            self.imp().remember_row.selected(),
+            self.imp().remembering_minutes.get(),
+        );
+    }
 }
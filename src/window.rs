@@ -24,8 +24,16 @@ use crate::{
     glib::clone,
     models::{bagit_git_profile::BagitGitProfile, bagit_repository::BagitRepository},
     utils::{
-        action_type::ActionType, db::AppDatabase, profile_mode::ProfileMode,
-        repository_utils::RepositoryUtils, selected_repository::SelectedRepository,
+        action_type::ActionType,
+        db::AppDatabase,
+        gpg_utils::GpgUtils,
+        passphrase_cache::{PassphraseCache, PassphraseRetention},
+        profile_mode::ProfileMode,
+        repository_template::RepositoryTemplate,
+        repository_utils::RepositoryUtils,
+        secret_store::SecretStoreUtils,
+        selected_repository::SelectedRepository,
+        transfer_progress::TransferProgress,
     },
     widgets::gpg_passphrase_dialog::BagitGpgPassphraseDialog,
     widgets::https_action_dialog::BagitHttpsActionDialog,
@@ -37,6 +45,7 @@ use adw::{
     subclass::prelude::*,
     traits::{ActionRowExt, MessageDialogExt, PreferencesRowExt},
 };
+use chrono::Datelike;
 use gettextrs::gettext;
 use git2::Repository;
 use gtk::{
@@ -51,11 +60,9 @@ use crate::clone_repository_page::BagitCloneRepositoryPage;
 use crate::repository_page::BagitRepositoryPage;
 use crate::widgets::action_bar::BagitActionBar;
 use crate::widgets::repositories::BagitRepositories;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 mod imp {
-    use std::collections::HashMap;
-
     use crate::create_repository_page::BagitCreateRepositoryPage;
 
     use super::*;
@@ -87,9 +94,8 @@ mod imp {
         pub app_database: RefCell<AppDatabase>,
 
         pub selected_repositories_ids_for_deletion: RefCell<Vec<Uuid>>,
-
-        pub ssh_passphrases: RefCell<HashMap<String, String>>,
-        pub gpg_passphrases: RefCell<HashMap<String, String>>,
+        /// Set while the user is asked to unlock the credential store.
+        pub is_unlocking_secret_store: Cell<bool>,
     }
 
     #[template_callbacks]
@@ -142,9 +148,6 @@ mod imp {
 
                     match win.stack.visible_child_name().unwrap().as_str() {
                         "repository page" => {
-                            let ssh_passphrases = win.ssh_passphrases.take();
-                            win.repository_page.imp().ssh_passphrases.replace(ssh_passphrases.clone()); //TODO: MARKER
-                            win.ssh_passphrases.replace(ssh_passphrases);
                             win.repository_page.update_repository_page();
                         },
                         "create repository page" => {
@@ -213,6 +216,18 @@ impl BagitDesktopWindow {
 
         win.imp().app_database.replace(app_database);
 
+        let settings = gtk::gio::Settings::new("com.skilldary.bagit.desktop");
+        PassphraseCache::set_agent_delegation_enabled(
+            settings.boolean("is-agent-delegation-enabled"),
+        );
+
+        let unlock_credentials_action = gio::ActionEntry::builder("unlock-credentials")
+            .activate(move |win: &Self, _, _| win.unlock_secret_store())
+            .build();
+        win.add_action_entries([unlock_credentials_action]);
+
+        win.open_secret_store();
+
         win.repositories_page_signals();
         win.repository_page_signals();
         win.action_bar_signals();
@@ -294,20 +309,92 @@ impl BagitDesktopWindow {
         );
     }
 
-    pub fn save_ssh_passphrase(&self, key_path: String, passphrase: String) {
-        let mut ssh_passphrases = self.imp().ssh_passphrases.take();
+    /// Used to run an action needing the passphrase of a GPG signing key.
+    /// The passphrase is asked to the user when it is not remembered,
+    /// unless it is left to the gpg-agent and the agent can sign without it.
+    pub fn run_with_gpg_passphrase<F: Fn(&str) + 'static>(&self, signing_key: &str, action: F) {
+        self.run_with_gpg_passphrase_or_cancel(signing_key, action, || {});
+    }
+
+    /// Used to run an action needing the passphrase of a GPG signing key,
+    /// or to run `on_cancel` when the user does not give the passphrase.
+    pub fn run_with_gpg_passphrase_or_cancel<F: Fn(&str) + 'static, C: Fn() + 'static>(
+        &self,
+        signing_key: &str,
+        action: F,
+        on_cancel: C,
+    ) {
+        if let Some(passphrase) = PassphraseCache::get_gpg_passphrase(signing_key) {
+            action(&passphrase);
+            return;
+        }
+
+        if PassphraseCache::is_agent_delegation_enabled() {
+            let (sender, receiver) = MainContext::channel::<bool>(Priority::default());
+
+            let cloned_signing_key = String::from(signing_key);
+            thread::spawn(move || {
+                sender
+                    .send(GpgUtils::can_agent_sign(&cloned_signing_key))
+                    .expect("Could not send through channel");
+            });
+
+            // The passphrase is asked when the gpg-agent cannot sign without it:
+            let cloned_signing_key = String::from(signing_key);
+            let mut callbacks = Some((action, on_cancel));
+            receiver.attach(
+                None,
+                clone!(@weak self as win => @default-return Continue(false), move |can_agent_sign| {
+                    if let Some((action, on_cancel)) = callbacks.take() {
+                        if can_agent_sign {
+                            action("");
+                        } else {
+                            win.ask_gpg_passphrase(&cloned_signing_key, action, on_cancel);
+                        }
+                    }
+
+                    Continue(false)
+                }),
+            );
 
-        ssh_passphrases.insert(key_path, passphrase);
+            return;
+        }
 
-        self.imp().ssh_passphrases.replace(ssh_passphrases);
+        self.ask_gpg_passphrase(signing_key, action, on_cancel);
     }
 
-    pub fn save_gpg_passphrase(&self, key: String, passphrase: String) {
-        let mut gpg_passphrases = self.imp().gpg_passphrases.take();
+    /// Used to ask the passphrase of a GPG signing key, then to run the action with it.
+    fn ask_gpg_passphrase<F: Fn(&str) + 'static, C: Fn() + 'static>(
+        &self,
+        signing_key: &str,
+        action: F,
+        on_cancel: C,
+    ) {
+        let cloned_signing_key = String::from(signing_key);
+        let passphrase_dialog: BagitGpgPassphraseDialog =
+            BagitGpgPassphraseDialog::new(&cloned_signing_key);
+        passphrase_dialog.set_transient_for(Some(self));
+        passphrase_dialog.set_modal(true);
+        passphrase_dialog.present();
+
+        passphrase_dialog.connect_closure(
+            "fetch-passphrase",
+            false,
+            closure_local!(
+                move |passphrase_dialog: BagitGpgPassphraseDialog, passphrase: &str| {
+                    PassphraseCache::save_gpg_passphrase(
+                        &cloned_signing_key,
+                        passphrase,
+                        passphrase_dialog.get_retention(),
+                    );
 
-        gpg_passphrases.insert(key, passphrase);
+                    passphrase_dialog.close();
+                    action(passphrase);
+                }
+            ),
+        );
 
-        self.imp().gpg_passphrases.replace(gpg_passphrases);
+        passphrase_dialog.connect_response(Some("cancel"), move |_, _| on_cancel());
     }
 
     /// Used to build a new repository row.
@@ -655,12 +742,13 @@ impl BagitDesktopWindow {
                 location: &str
                 | {
                     let repository_path = format!("{}/{}", location, repository_name);
+                    let repository_template = create_repository_page.get_repository_template();
 
                     let repository;
 
                     match fs::create_dir(&repository_path) {
                         Ok(_) => {
-                            match Repository::init(&repository_path) {
+                            match RepositoryUtils::create_repository(&repository_path, &repository_template.initial_branch) {
                                 Ok(repo) => repository = repo,
                                 Err(error) => {
                                     win.show_error_dialog(&error.to_string());
@@ -687,7 +775,7 @@ impl BagitDesktopWindow {
                         _ => None
                     };
 
-                    match selected_profile {
+                    match &selected_profile {
                         Some(profile) => {
                             // Once the repository is created, we update its config file:
                             match RepositoryUtils::override_git_config(&repository, &profile) {
@@ -698,6 +786,12 @@ impl BagitDesktopWindow {
                         None => {},
                     }
 
+                    let signing_key = selected_profile
+                        .map(|profile| profile.signing_key)
+                        .unwrap_or_default();
+
+                    win.add_initial_content(&repository, repository_name, &repository_template, &signing_key);
+
                     let mut new_repository = BagitRepository::new(Uuid::new_v4(), repository_name.to_string(), repository_path, None);
 
                     let profile_mode =  create_repository_page.imp().profile_mode.take();
@@ -765,6 +859,9 @@ impl BagitDesktopWindow {
 
                         let toast = adw::Toast::new(&gettext("_Could not add Git profile"));
                         win.imp().toast_overlay.add_toast(toast);
+                    } else {
+                        // The new repository uses the new profile, e.g. to author its initial commit:
+                        create_repository_page.imp().profile_mode.replace(ProfileMode::SelectedProfile(new_profile));
                     }
 
                     win.imp().app_database.replace(app_database);
@@ -775,6 +872,78 @@ impl BagitDesktopWindow {
         );
     }
 
+    /// Used to write the chosen initial files in a new repository, and to commit them when asked.
+    /// The initial commit is signed when a signing key is given.
+    fn add_initial_content(
+        &self,
+        repository: &Repository,
+        repository_name: &str,
+        repository_template: &RepositoryTemplate,
+        signing_key: &str,
+    ) {
+        let (author, author_email) = match repository.signature() {
+            Ok(signature) => (
+                signature.name().unwrap_or_default().to_string(),
+                signature.email().unwrap_or_default().to_string(),
+            ),
+            Err(_) => (String::new(), String::new()),
+        };
+
+        let files = match repository_template.build_files(
+            repository_name,
+            &author,
+            chrono::Local::now().year(),
+        ) {
+            Ok(files) => files,
+            Err(error) => {
+                self.show_error_dialog(&error.to_string());
+                tracing::warn!("Could not load the repository templates: {}", error);
+
+                return;
+            }
+        };
+
+        if let Err(error) = RepositoryUtils::add_initial_files(repository, &files) {
+            self.show_error_dialog(&error.to_string());
+            tracing::warn!("Could not add the initial files: {}", error);
+
+            return;
+        }
+
+        if !repository_template.has_initial_commit {
+            return;
+        }
+
+        let repository_path = repository.path().to_path_buf();
+        let cloned_signing_key = String::from(signing_key);
+        let win = self.clone();
+
+        let create_initial_commit = move |passphrase: &str| {
+            let result = Repository::open(&repository_path).and_then(|repository| {
+                RepositoryUtils::commit_files(
+                    &repository,
+                    &gettext("_Initial commit"),
+                    "",
+                    &author,
+                    &author_email,
+                    &cloned_signing_key,
+                    passphrase,
+                )
+            });
+
+            if let Err(error) = result {
+                win.show_error_dialog(&error.to_string());
+                tracing::warn!("Could not create the initial commit: {}", error);
+            }
+        };
+
+        if signing_key.is_empty() {
+            create_initial_commit("");
+        } else {
+            self.run_with_gpg_passphrase(signing_key, create_initial_commit);
+        }
+    }
+
     /**
      * Used for listenning to the clone button of the BagitActionBar widget.
      */
@@ -950,7 +1119,7 @@ impl BagitDesktopWindow {
 
                             (
                                 profile.username.to_string(),
-                                profile.password.to_string(),
+                                AppDatabase::retrieve_password_of_git_profile(profile),
                                 profile.private_key_path.to_string(),
                                 clone_repository_page.imp().passphrase.text().to_string(),
                             )
@@ -958,19 +1127,37 @@ impl BagitDesktopWindow {
                     };
 
                     if !ssh_key_path.is_empty() {
-                        win.save_ssh_passphrase(ssh_key_path, ssh_passphrase);
+                        PassphraseCache::save_ssh_passphrase(
+                            &ssh_key_path,
+                            &ssh_passphrase,
+                            PassphraseRetention::Session,
+                        );
                     }
 
+                    let is_cancelled = clone_repository_page.get_clone_cancellation();
+                    let (progress_sender, progress_receiver) = MainContext::channel::<TransferProgress>(Priority::default());
+
+                    let clone_options = clone_repository_page.get_clone_options();
+                    let is_bare = clone_options.is_bare;
+
                     thread::spawn(move || {
                         let sender = sender.clone();
 
-                        let callback = RepositoryUtils::find_correct_callback(
+                        let submodule_username = username.clone();
+                        let submodule_password = password.clone();
+                        let submodule_private_key_path = private_key_path.clone();
+                        let submodule_passphrase = passphrase.clone();
+
+                        let mut callback = RepositoryUtils::find_correct_callback(
                             url_copy.clone(),
                             username,
                             password,
                             passphrase,
                             private_key_path
                         );
+                        RepositoryUtils::add_transfer_callbacks(&mut callback, is_cancelled, move |progress| {
+                            let _ = progress_sender.send(progress);
+                        });
 
                         let new_path = RepositoryUtils::create_new_folder_path(&url_copy, &location_copy);
 
@@ -984,8 +1171,18 @@ impl BagitDesktopWindow {
 
                         let repository: Repository;
 
-                        match RepositoryUtils::clone_repository(&url_copy, &new_path, callback) {
-                            Ok(repo) => {repository = repo}
+                        match RepositoryUtils::clone_repository(&url_copy, &new_path, callback, &clone_options) {
+                            Ok(repo) => {
+                                RepositoryUtils::update_cloned_submodules(
+                                    &repo,
+                                    &clone_options,
+                                    submodule_username,
+                                    submodule_password,
+                                    submodule_private_key_path,
+                                    submodule_passphrase,
+                                );
+                                repository = repo
+                            }
                             Err(e) => {
                                 // We must make sure to delete the created folder !
                                 let removed_directory = fs::remove_dir_all(&new_path);
@@ -1029,12 +1226,27 @@ impl BagitDesktopWindow {
                         ).expect("Could not send result through channel")
                     });
 
+                    progress_receiver.attach(
+                        None,
+                        clone!(@weak win as win2 => @default-return Continue(false),
+                                    move |progress| {
+                                        win2.imp().clone_repository_page.update_progress(&progress);
+                                        Continue(true)
+                                    }
+                        ),
+                    );
+
                     receiver.attach(
                         None,
                         clone!(
                             @weak win as win2 => @default-return Continue(false),
                                     move |result| {
                                         match result {
+                                            Ok(_) if is_bare => {
+                                                win2.imp().clone_repository_page.to_main_page();
+                                                win2.show_bare_repository_cloned_toast();
+                                                Continue(true)
+                                            }
                                             Ok(elements) => {
                                                 let mut new_repository = BagitRepository::new(Uuid::new_v4(), elements.0, elements.1, None);
 
@@ -1046,6 +1258,12 @@ impl BagitDesktopWindow {
                                                 win2.imp().clone_repository_page.to_main_page();
                                                 Continue(true)
                                             }
+                                            Err(_) if win2.imp().clone_repository_page.is_clone_cancelled() => {
+                                                win2.imp().clone_repository_page.to_main_page();
+                                                let toast = adw::Toast::new(&gettext("_Clone cancelled"));
+                                                win2.imp().toast_overlay.add_toast(toast);
+                                                Continue(true)
+                                            }
                                             Err(error) => {
                                                 win2.imp().clone_repository_page.to_main_page();
                                                 win2.show_error_dialog(&error);
@@ -1059,6 +1277,53 @@ impl BagitDesktopWindow {
             ),
         );
 
+        self.imp().clone_repository_page.connect_closure(
+            "list-remote-branches",
+            false,
+            closure_local!(@watch self as win => move |
+                clone_repository_page: BagitCloneRepositoryPage,
+                url: &str
+                | {
+                    let (sender, receiver) = MainContext::channel::<Result<Vec<String>, String>>(Priority::default());
+
+                    let url_copy = url.to_owned();
+                    let (username, password, private_key_path, passphrase) =
+                        clone_repository_page.get_authentication_information();
+
+                    thread::spawn(move || {
+                        let callback = RepositoryUtils::find_correct_callback(
+                            url_copy.clone(),
+                            username,
+                            password,
+                            passphrase,
+                            private_key_path
+                        );
+
+                        let branch_names = RepositoryUtils::get_remote_branch_names(&url_copy, callback)
+                            .map_err(|error| error.to_string());
+
+                        sender.send(branch_names).expect("Could not send result through channel");
+                    });
+
+                    receiver.attach(
+                        None,
+                        clone!(@weak win as win2 => @default-return Continue(false),
+                                    move |result| {
+                                        match result {
+                                            Ok(branch_names) => win2.imp().clone_repository_page.set_remote_branch_names(branch_names),
+                                            Err(error) => {
+                                                win2.imp().clone_repository_page.set_remote_branch_names(vec![]);
+                                                win2.show_error_dialog(&error);
+                                            }
+                                        }
+                                        Continue(true)
+                                    }
+                        ),
+                    );
+                }
+            ),
+        );
+
         self.imp().clone_repository_page.connect_closure(
             "clone-repository-and-add-profile",
             false,
@@ -1125,7 +1390,11 @@ impl BagitDesktopWindow {
                     let app_database = win.imp().app_database.take();
 
                     if !private_key_path.is_empty() {
-                        win.save_ssh_passphrase(private_key_path.to_string(), passphrase.clone());
+                        PassphraseCache::save_ssh_passphrase(
+                            &private_key_path,
+                            &passphrase,
+                            PassphraseRetention::Session,
+                        );
                     }
 
                     if let Err(error) = app_database.add_git_profile(&new_profile) {
@@ -1137,24 +1406,47 @@ impl BagitDesktopWindow {
 
                     win.imp().app_database.replace(app_database);
 
+                    let is_cancelled = clone_repository_page.get_clone_cancellation();
+                    let (progress_sender, progress_receiver) = MainContext::channel::<TransferProgress>(Priority::default());
+
+                    let clone_options = clone_repository_page.get_clone_options();
+                    let is_bare = clone_options.is_bare;
+
                     thread::spawn(move || {
                         let error_sender = error_sender.clone();
                         let result_sender = result_sender.clone();
 
-                        let callback = RepositoryUtils::find_correct_callback(
+                        let submodule_username = username_copy.clone();
+                        let submodule_password = password_copy.clone();
+                        let submodule_private_key_path = private_key_path_copy.clone();
+                        let submodule_passphrase = passphrase.clone();
+
+                        let mut callback = RepositoryUtils::find_correct_callback(
                             url_copy.clone(),
                             username_copy,
                             password_copy,
                             passphrase,
                             private_key_path_copy
                         );
+                        RepositoryUtils::add_transfer_callbacks(&mut callback, is_cancelled, move |progress| {
+                            let _ = progress_sender.send(progress);
+                        });
                         let new_path = RepositoryUtils::create_new_folder_path(&url_copy, &location_copy);
 
                         let new_folder = fs::create_dir(&new_path);
                         match new_folder {
                             Ok(_) => {
-                                match RepositoryUtils::clone_repository(&url_copy, &new_path, callback) {
+                                match RepositoryUtils::clone_repository(&url_copy, &new_path, callback, &clone_options) {
                                     Ok(repository) => {
+                                        RepositoryUtils::update_cloned_submodules(
+                                            &repository,
+                                            &clone_options,
+                                            submodule_username,
+                                            submodule_password,
+                                            submodule_private_key_path,
+                                            submodule_passphrase,
+                                        );
+
                                         // Once the repository is cloned, we update it's config file:
                                         match RepositoryUtils::override_git_config(&repository, &new_profile) {
                                             Ok(_) => result_sender.send(
@@ -1182,12 +1474,27 @@ impl BagitDesktopWindow {
                         }
                     });
 
+                    progress_receiver.attach(
+                        None,
+                        clone!(@weak win as win2 => @default-return Continue(false),
+                                    move |progress| {
+                                        win2.imp().clone_repository_page.update_progress(&progress);
+                                        Continue(true)
+                                    }
+                        ),
+                    );
+
                     error_receiver.attach(
                         None,
                         clone!(@weak win as win2 => @default-return Continue(false),
                                     move |error| {
                                         win2.imp().clone_repository_page.to_main_page();
-                                        win2.show_error_dialog(&error);
+                                        if win2.imp().clone_repository_page.is_clone_cancelled() {
+                                            let toast = adw::Toast::new(&gettext("_Clone cancelled"));
+                                            win2.imp().toast_overlay.add_toast(toast);
+                                        } else {
+                                            win2.show_error_dialog(&error);
+                                        }
                                         Continue(true)
                                     }
                         ),
@@ -1198,6 +1505,12 @@ impl BagitDesktopWindow {
                         clone!(
                             @weak win as win2 => @default-return Continue(false),
                                     move |elements| {
+                                        if is_bare {
+                                            win2.imp().clone_repository_page.to_main_page();
+                                            win2.show_bare_repository_cloned_toast();
+                                            return Continue(true);
+                                        }
+
                                         let mut new_repository = BagitRepository::new(Uuid::new_v4(), elements.0, elements.1, None);
 
                                         let profile_mode = clone_repository_page.imp().profile_mode.take();
@@ -1256,6 +1569,160 @@ impl BagitDesktopWindow {
             ),
         );
 
+        self.imp().repository_page.connect_closure(
+            "create-tag-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage,
+                commit_id: &str
+                | {
+                win.show_create_tag_dialog(commit_id.to_string());
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "create-tag-with-signing-key",
+            false,
+            closure_local!(@watch self as win => move |
+                repository_page: BagitRepositoryPage,
+                commit_id: &str,
+                tag_name: &str,
+                message: &str,
+                author: &str,
+                author_email: &str,
+                signing_key: &str
+                | {
+                    let cloned_commit_id = String::from(commit_id);
+                    let cloned_tag_name = String::from(tag_name);
+                    let cloned_message = String::from(message);
+                    let cloned_author = String::from(author);
+                    let cloned_author_email = String::from(author_email);
+                    let cloned_signing_key = String::from(signing_key);
+
+                    win.run_with_gpg_passphrase(signing_key, move |passphrase| {
+                        repository_page.create_annotated_tag_and_update_ui(
+                            &cloned_commit_id,
+                            &cloned_tag_name,
+                            &cloned_message,
+                            &cloned_author,
+                            &cloned_author_email,
+                            &cloned_signing_key,
+                            passphrase,
+                        );
+                    });
+                }
+            ),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "delete-tag-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage,
+                tag_name: &str
+                | {
+                win.show_delete_tag_dialog(tag_name.to_string());
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "edit-remote-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage,
+                remote_name: &str,
+                remote_url: &str
+                | {
+                win.show_edit_remote_dialog(remote_name.to_string(), remote_url.to_string());
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "delete-remote-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage,
+                remote_name: &str
+                | {
+                win.show_delete_remote_dialog(remote_name.to_string());
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "publish-dialog",
+            false,
+            closure_local!(@watch self as win => move |_repository_page: BagitRepositoryPage| {
+                win.show_publish_dialog();
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "deepen-history-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage
+                | {
+                win.show_deepen_history_dialog();
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "open-submodule",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage,
+                submodule_path: &str
+                | {
+                win.open_submodule(submodule_path);
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "save-file-version-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                repository_page: BagitRepositoryPage,
+                revision: &str,
+                file_path: &str
+                | {
+                let revision = revision.to_string();
+                let file_path = file_path.to_string();
+                let file_name = file_path.rsplit('/').next().unwrap_or(&file_path).to_string();
+
+                let ctx: MainContext = glib::MainContext::default();
+                ctx.spawn_local(clone!(@weak win, @weak repository_page => async move {
+                    let dialog = gtk::FileDialog::builder()
+                        .accept_label(gettext("_Save"))
+                        .modal(true)
+                        .title(gettext("_Save this version as…"))
+                        .initial_name(file_name)
+                        .build();
+
+                    if let Ok(file) = dialog.save_future(Some(&win)).await {
+                        if let Some(destination_path) = file.path() {
+                            repository_page.save_file_version(
+                                &revision,
+                                &file_path,
+                                destination_path.to_str().unwrap(),
+                            );
+                        }
+                    }
+                }));
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "drop-stash-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage,
+                index: u32,
+                stash_message: &str
+                | {
+                win.show_drop_stash_dialog(index as usize, stash_message.to_string());
+            }),
+        );
+
         self.imp().repository_page.connect_closure(
             "discard-dialog",
             false,
@@ -1280,78 +1747,186 @@ impl BagitDesktopWindow {
                 description: &str,
                 need_to_save_profile: bool
                 | {
-                    let ctx: MainContext = glib::MainContext::default();
-                    let cloned_signing_key = String::from(signing_key);
                     let cloned_message = String::from(message);
                     let cloned_author = String::from(author);
                     let cloned_author_email = String::from(author_email);
+                    let cloned_signing_key = String::from(signing_key);
                     let cloned_description = String::from(description);
-                    let cloned_need_to_save_profile = need_to_save_profile.clone();
-
-                    let gpg_passphrases = win.imp().gpg_passphrases.take();
 
-                    if let Some(passphrase) = gpg_passphrases.get(&cloned_signing_key) {
+                    win.run_with_gpg_passphrase(signing_key, move |passphrase| {
                         repository_page.commit_files_and_update_ui(
                             &cloned_author,
                             &cloned_author_email,
                             &cloned_message,
                             &cloned_signing_key,
-                            &passphrase,
+                            passphrase,
                             &cloned_description,
-                            cloned_need_to_save_profile,
+                            need_to_save_profile,
                         );
-
-                        win.imp().gpg_passphrases.replace(gpg_passphrases);
-
-                        return;
-                    }
-
-                    win.imp().gpg_passphrases.replace(gpg_passphrases);
-
-                    ctx.spawn_local(clone!(@weak win as win2 => async move {
-                        let passphrase_dialog: BagitGpgPassphraseDialog = BagitGpgPassphraseDialog::new(&cloned_signing_key);
-                        passphrase_dialog.set_transient_for(Some(&win2));
-                        passphrase_dialog.set_modal(true);
-                        passphrase_dialog.present();
-
-                        passphrase_dialog.connect_closure("fetch-passphrase", false, closure_local!(
-                            move |
-                            passphrase_dialog: BagitGpgPassphraseDialog,
-                            passphrase: &str
-                            | {
-                                win2.save_gpg_passphrase(cloned_signing_key.clone(), passphrase.to_string().clone());
-
-                                passphrase_dialog.close();
-                                repository_page.commit_files_and_update_ui(
-                                    &cloned_author,
-                                    &cloned_author_email,
-                                    &cloned_message,
-                                    &cloned_signing_key,
-                                    &passphrase,
-                                    &cloned_description,
-                                    cloned_need_to_save_profile,
-                                );
-                            }
-                        ));
-                    }
-                ));
-            }),
+                    });
+                }
+            ),
         );
 
         self.imp().repository_page.connect_closure(
-            "missing-ssh-information",
+            "integrate-fetched-branch-with-signing-key",
             false,
             closure_local!(@watch self as win => move |
                 repository_page: BagitRepositoryPage,
-                username: &str,
-                private_key_path: &str,
-                action_type: ActionType,
-                remote_branch_name: &str
-                | {
+                author: &str,
+                author_email: &str,
+                signing_key: &str
+                | {
+                    let cloned_author = String::from(author);
+                    let cloned_author_email = String::from(author_email);
+                    let cloned_signing_key = String::from(signing_key);
+
+                    let cancelled_repository_page = repository_page.clone();
+
+                    win.run_with_gpg_passphrase_or_cancel(
+                        signing_key,
+                        move |passphrase| {
+                            repository_page.integrate_fetched_branch_and_update_ui(
+                                &cloned_author,
+                                &cloned_author_email,
+                                &cloned_signing_key,
+                                passphrase,
+                            );
+                        },
+                        move || cancelled_repository_page.cancel_fetched_branch_integration(),
+                    );
+                }
+            ),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "conclude-merge-with-signing-key",
+            false,
+            closure_local!(@watch self as win => move |
+                repository_page: BagitRepositoryPage,
+                author: &str,
+                author_email: &str,
+                signing_key: &str
+                | {
+                    let cloned_author = String::from(author);
+                    let cloned_author_email = String::from(author_email);
+                    let cloned_signing_key = String::from(signing_key);
+
+                    win.run_with_gpg_passphrase(signing_key, move |passphrase| {
+                        repository_page.conclude_merge_and_update_ui(
+                            &cloned_author,
+                            &cloned_author_email,
+                            &cloned_signing_key,
+                            passphrase,
+                        );
+                    });
+                }
+            ),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "apply-commit-with-signing-key",
+            false,
+            closure_local!(@watch self as win => move |
+                repository_page: BagitRepositoryPage,
+                commit_id: &str,
+                author: &str,
+                author_email: &str,
+                signing_key: &str,
+                is_reverting: bool
+                | {
+                    let cloned_commit_id = String::from(commit_id);
+                    let cloned_author = String::from(author);
+                    let cloned_author_email = String::from(author_email);
+                    let cloned_signing_key = String::from(signing_key);
+
+                    win.run_with_gpg_passphrase(signing_key, move |passphrase| {
+                        repository_page.apply_commit_and_update_ui(
+                            &cloned_commit_id,
+                            is_reverting,
+                            &cloned_author,
+                            &cloned_author_email,
+                            &cloned_signing_key,
+                            passphrase,
+                        );
+                    });
+                }
+            ),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "reset-to-commit-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage,
+                commit_id: &str
+                | {
+                win.show_reset_to_commit_dialog(commit_id.to_string());
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "create-branch-from-commit-dialog",
+            false,
+            closure_local!(@watch self as win => move |
+                _repository_page: BagitRepositoryPage,
+                commit_id: &str
+                | {
+                win.show_create_branch_from_commit_dialog(commit_id.to_string());
+            }),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "interactive-rebase-with-signing-key",
+            false,
+            closure_local!(@watch self as win => move |
+                repository_page: BagitRepositoryPage,
+                author: &str,
+                author_email: &str,
+                signing_key: &str,
+                is_continuing: bool
+                | {
+                    let cloned_author = String::from(author);
+                    let cloned_author_email = String::from(author_email);
+                    let cloned_signing_key = String::from(signing_key);
+
+                    win.run_with_gpg_passphrase(signing_key, move |passphrase| {
+                        if is_continuing {
+                            repository_page.continue_interactive_rebase_and_update_ui(
+                                &cloned_author,
+                                &cloned_author_email,
+                                &cloned_signing_key,
+                                passphrase,
+                            );
+                        } else {
+                            repository_page.start_interactive_rebase_and_update_ui(
+                                &cloned_author,
+                                &cloned_author_email,
+                                &cloned_signing_key,
+                                passphrase,
+                            );
+                        }
+                    });
+                }
+            ),
+        );
+
+        self.imp().repository_page.connect_closure(
+            "missing-ssh-information",
+            false,
+            closure_local!(@watch self as win => move |
+                repository_page: BagitRepositoryPage,
+                username: &str,
+                private_key_path: &str,
+                action_type: ActionType,
+                remote_branch_name: &str,
+                remote_name: &str
+                | {
                     let ctx: MainContext = glib::MainContext::default();
                     let cloned_username = String::from(username);
                     let cloned_private_key_path = String::from(private_key_path);
                     let cloned_remote_branch_name = String::from(remote_branch_name);
+                    let cloned_remote_name = String::from(remote_name);
 
                     ctx.spawn_local(clone!(@weak win as win2 => async move {
                         let dialog: BagitSshActionDialog = BagitSshActionDialog::new(&cloned_username, &cloned_private_key_path);
@@ -1395,7 +1970,8 @@ impl BagitDesktopWindow {
                                     String::from(private_key_path),
                                     String::from(passphrase),
                                     action_type,
-                                    cloned_remote_branch_name.clone()
+                                    cloned_remote_branch_name.clone(),
+                                    cloned_remote_name.clone()
                                 );
                             }
                         ));
@@ -1423,13 +1999,15 @@ impl BagitDesktopWindow {
                 username: &str,
                 private_key_path: &str,
                 action_type: ActionType,
-                remote_branch_name: &str
+                remote_branch_name: &str,
+                remote_name: &str
                 | {
                     let ctx: MainContext = glib::MainContext::default();
 
                     let cloned_username = String::from(username);
                     let cloned_private_key_path= String::from(private_key_path);
                     let cloned_remote_branch_name = String::from(remote_branch_name);
+                    let cloned_remote_name = String::from(remote_name);
 
                     ctx.spawn_local(clone!(@weak win as win2, => async move {
                         let dialog: BagitSshPassphraseDialog = BagitSshPassphraseDialog::new(cloned_username, cloned_private_key_path);
@@ -1446,7 +2024,11 @@ impl BagitDesktopWindow {
                             passphrase: &str,
                             | {
                                 if !private_key_path.is_empty() {
-                                    win2.save_ssh_passphrase(private_key_path.to_string(), passphrase.to_string());
+                                    PassphraseCache::save_ssh_passphrase(
+                                        private_key_path,
+                                        passphrase,
+                                        dialog.get_retention(),
+                                    );
                                 }
 
                                 dialog.close();
@@ -1456,7 +2038,8 @@ impl BagitDesktopWindow {
                                     String::from(private_key_path),
                                     String::from(passphrase),
                                     action_type,
-                                    cloned_remote_branch_name.clone()
+                                    cloned_remote_branch_name.clone(),
+                                    cloned_remote_name.clone()
                                 );
                             }
                         ));
@@ -1484,12 +2067,14 @@ impl BagitDesktopWindow {
                 username: &str,
                 password: &str,
                 action_type: ActionType,
-                remote_branch_name: &str
+                remote_branch_name: &str,
+                remote_name: &str
                 | {
                     let ctx: MainContext = glib::MainContext::default();
                     let cloned_username = String::from(username);
                     let cloned_password = String::from(password);
                     let cloned_remote_branch_name = String::from(remote_branch_name);
+                    let cloned_remote_name = String::from(remote_name);
 
                     ctx.spawn_local(clone!(
                         @weak win as win2 => async move {
@@ -1512,7 +2097,8 @@ impl BagitDesktopWindow {
                                     String::new(),
                                     String::new(),
                                     action_type,
-                                    cloned_remote_branch_name.clone()
+                                    cloned_remote_branch_name.clone(),
+                                    cloned_remote_name.clone()
                                 );
                             }
                         ));
@@ -1560,7 +2146,7 @@ impl BagitDesktopWindow {
                                     Ok(repo) => {
                                         if is_remote {
                                             // When deleting a remote branch, we need to authenticate:
-                                            win2.imp().repository_page.do_git_action_with_auth_check(ActionType::DeleteRemoteBranch, &cloned_branch_name);
+                                            win2.imp().repository_page.do_git_action_with_auth_check(ActionType::DeleteRemoteBranch, &cloned_branch_name, &"");
                                         } else {
                                             match RepositoryUtils::delete_local_branch(&repo, &cloned_branch_name) {
                                                 Ok(_) => {
@@ -1593,6 +2179,7 @@ impl BagitDesktopWindow {
             .build();
 
             branch_dialog.add_response("cancel", &gettext("_Cancel"));
+            branch_dialog.add_response("stash", &gettext("_Stash and switch"));
             branch_dialog.add_response("validate", &gettext("_Validate"));
 
 
@@ -1603,6 +2190,9 @@ impl BagitDesktopWindow {
                         "validate" => {
                             win2.imp().repository_page.checkout_branch_and_update_ui(branch_name.to_owned(), is_remote);
                         },
+                        "stash" => {
+                            win2.imp().repository_page.stash_and_checkout_branch_and_update_ui(branch_name.to_owned(), is_remote);
+                        },
                         _ => {}
                     }
                 }
@@ -1630,6 +2220,188 @@ impl BagitDesktopWindow {
         }));
     }
 
+    /// Opens the store keeping the passwords of the Git profiles.
+    /// When the Secret Service is not available, the passwords are kept in an encrypted file
+    /// and its master passphrase is asked.
+    pub fn open_secret_store(&self) {
+        let (sender, receiver) = MainContext::channel::<()>(Priority::default());
+        SecretStoreUtils::set_unlock_request_sender(sender);
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false), move |_| {
+                win.unlock_secret_store();
+                Continue(true)
+            }),
+        );
+
+        SecretStoreUtils::open_secret_service();
+        self.unlock_secret_store();
+    }
+
+    /// Asks the user to unlock the store keeping the passwords of the Git profiles.
+    /// The Secret Service prompts the user itself, so it is unlocked in another thread.
+    pub fn unlock_secret_store(&self) {
+        if self.imp().is_unlocking_secret_store.get() {
+            return;
+        }
+
+        if SecretStoreUtils::needs_master_passphrase() {
+            self.show_master_passphrase_dialog(None);
+            return;
+        }
+
+        self.imp().is_unlocking_secret_store.set(true);
+
+        let (sender, receiver) = MainContext::channel::<Result<(), String>>(Priority::default());
+
+        thread::spawn(move || {
+            sender
+                .send(SecretStoreUtils::unlock())
+                .expect("Could not send through channel");
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win => @default-return Continue(false), move |result| {
+                win.imp().is_unlocking_secret_store.set(false);
+
+                match result {
+                    Ok(_) => win.update_after_secret_store_unlocked(),
+                    Err(error) => {
+                        tracing::warn!("Could not unlock the credential store: {}", error);
+
+                        let toast = adw::Toast::new(&gettext("_Credential store locked"));
+                        win.imp().toast_overlay.add_toast(toast);
+                    }
+                }
+
+                Continue(true)
+            }),
+        );
+    }
+
+    /// Used to move the passwords to the store once unlocked and to load the saved passphrases.
+    fn update_after_secret_store_unlocked(&self) {
+        self.move_passwords_to_secret_store();
+        PassphraseCache::load();
+    }
+
+    /// Moves the passwords still written in the database to the opened secret store.
+    fn move_passwords_to_secret_store(&self) {
+        let app_database = self.imp().app_database.take();
+
+        match app_database.move_passwords_to_secret_store() {
+            Ok(0) => {}
+            Ok(number) => tracing::info!("{} passwords moved to the secret store.", number),
+            Err(error) => self.show_error_dialog(&error.to_string()),
+        };
+
+        self.imp().app_database.replace(app_database);
+    }
+
+    /// Used to show the dialog asking the master passphrase of the encrypted credential file.
+    /// The passphrase is chosen, with a confirmation, when the file does not exist yet.
+    pub fn show_master_passphrase_dialog(&self, error_message: Option<String>) {
+        let is_creating = !SecretStoreUtils::does_encrypted_file_exist();
+
+        self.imp().is_unlocking_secret_store.set(true);
+
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let body = match error_message {
+                Some(error_message) => error_message,
+                None if is_creating => gettext("_Choose master passphrase message"),
+                None => gettext("_Enter master passphrase message"),
+            };
+
+            let passphrase_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Credential store"))
+                .body(&body)
+                .build();
+
+            let passphrase_row = adw::PasswordEntryRow::builder()
+                .title(&gettext("_Master passphrase"))
+                .build();
+            let confirmation_row = adw::PasswordEntryRow::builder()
+                .title(&gettext("_Confirm master passphrase"))
+                .visible(is_creating)
+                .build();
+
+            let passphrase_list = gtk::ListBox::new();
+            passphrase_list.add_css_class("boxed-list");
+            passphrase_list.set_selection_mode(gtk::SelectionMode::None);
+            passphrase_list.append(&passphrase_row);
+            passphrase_list.append(&confirmation_row);
+            passphrase_dialog.set_extra_child(Some(&passphrase_list));
+
+            passphrase_dialog.add_response("cancel", &gettext("_Later"));
+            passphrase_dialog.add_response("unlock", &gettext("_Unlock"));
+            passphrase_dialog.set_response_appearance("unlock", adw::ResponseAppearance::Suggested);
+            passphrase_dialog.set_response_enabled("unlock", false);
+
+            let update_unlock_response = clone!(
+                @weak passphrase_dialog, @weak passphrase_row, @weak confirmation_row => move || {
+                    let passphrase = passphrase_row.text();
+                    passphrase_dialog.set_response_enabled(
+                        "unlock",
+                        !passphrase.is_empty()
+                            && (!is_creating || passphrase == confirmation_row.text()),
+                    );
+                }
+            );
+            let cloned_update_unlock_response = update_unlock_response.clone();
+            passphrase_row.connect_changed(move |_| update_unlock_response());
+            confirmation_row.connect_changed(move |_| cloned_update_unlock_response());
+
+            passphrase_dialog.connect_response(None, clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    if response != "unlock" {
+                        win2.imp().is_unlocking_secret_store.set(false);
+                        return;
+                    }
+
+                    // Deriving the key of the encrypted file takes a while, so it is done in another thread:
+                    let (sender, receiver) = MainContext::channel::<Result<(), String>>(Priority::default());
+
+                    let master_passphrase = passphrase_row.text().to_string();
+
+                    thread::spawn(move || {
+                        sender
+                            .send(SecretStoreUtils::open_encrypted_file(&master_passphrase))
+                            .expect("Could not send through channel");
+                    });
+
+                    receiver.attach(
+                        None,
+                        clone!(@weak win2 as win3 => @default-return Continue(false), move |result| {
+                            win3.imp().is_unlocking_secret_store.set(false);
+
+                            match result {
+                                Ok(_) => win3.update_after_secret_store_unlocked(),
+                                Err(error) => win3.show_master_passphrase_dialog(Some(error)),
+                            }
+
+                            Continue(true)
+                        }),
+                    );
+                }
+            ));
+
+            passphrase_dialog.present();
+        }));
+    }
+
+    /// Used to tell the user that a bare repository has been cloned.
+    /// It is not added to the repositories as it has no working directory to show.
+    fn show_bare_repository_cloned_toast(&self) {
+        let toast = adw::Toast::new(&gettext("_Bare repository cloned"));
+        self.imp().toast_overlay.add_toast(toast);
+    }
+
     /// Used to show the dialog for discarding an element.
     pub fn show_discard_dialog(&self, is_discarding_folder: bool, discarded_element: String) {
         let (discard_message, discard_title) = if is_discarding_folder {
@@ -1681,7 +2453,483 @@ impl BagitDesktopWindow {
         }));
     }
 
+    /// Used to show the dialog for dropping a stash.
+    pub fn show_drop_stash_dialog(&self, index: usize, stash_message: String) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let drop_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Drop stash dialog"))
+                .body(&format!("{}\n{}", gettext("_Drop stash message"), stash_message))
+                .build();
+
+            drop_dialog.add_response("cancel", &gettext("_Cancel"));
+            drop_dialog.add_response("validate", &gettext("_Validate"));
+            drop_dialog.set_response_appearance("validate", adw::ResponseAppearance::Destructive);
+
+            drop_dialog.connect_response(None,clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    match response {
+                        "validate" => {
+                            win2.imp().repository_page.drop_stash_and_update_ui(index);
+                        },
+                        _ => {}
+                    }
+                }
+            ));
+
+            drop_dialog.present();
+        }));
+    }
+
+    /// Used to show the dialog for resetting the checked out branch to a commit.
+    pub fn show_reset_to_commit_dialog(&self, commit_id: String) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let reset_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Reset branch dialog"))
+                .body(&format!(
+                    "{} {}",
+                    gettext("_Reset branch message"),
+                    commit_id.chars().take(7).collect::<String>()
+                ))
+                .build();
+
+            reset_dialog.add_response("cancel", &gettext("_Cancel"));
+            reset_dialog.add_response("soft", &gettext("_Soft reset"));
+            reset_dialog.add_response("mixed", &gettext("_Mixed reset"));
+            reset_dialog.add_response("hard", &gettext("_Hard reset"));
+            reset_dialog.set_close_response("cancel");
+            reset_dialog.set_response_appearance("hard", adw::ResponseAppearance::Destructive);
+
+            reset_dialog.connect_response(None,clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    let reset_type = match response {
+                        "soft" => git2::ResetType::Soft,
+                        "mixed" => git2::ResetType::Mixed,
+                        "hard" => git2::ResetType::Hard,
+                        _ => return,
+                    };
+
+                    win2.imp().repository_page.reset_to_commit_and_update_ui(&commit_id, reset_type);
+                }
+            ));
+
+            reset_dialog.present();
+        }));
+    }
+
+    /// Used to show the dialog for creating a branch on a commit.
+    pub fn show_create_branch_from_commit_dialog(&self, commit_id: String) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let branch_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Create branch from commit"))
+                .body(&gettext("_Create branch from commit message"))
+                .build();
+
+            let branch_name_row = adw::EntryRow::builder()
+                .title(&gettext("_Branch name"))
+                .build();
+
+            let branch_list = gtk::ListBox::new();
+            branch_list.add_css_class("boxed-list");
+            branch_list.set_selection_mode(gtk::SelectionMode::None);
+            branch_list.append(&branch_name_row);
+            branch_dialog.set_extra_child(Some(&branch_list));
+
+            branch_dialog.add_response("cancel", &gettext("_Cancel"));
+            branch_dialog.add_response("validate", &gettext("_Validate"));
+            branch_dialog.set_response_appearance("validate", adw::ResponseAppearance::Suggested);
+            branch_dialog.set_response_enabled("validate", false);
+
+            branch_name_row.connect_changed(clone!(@weak branch_dialog => move |row| {
+                branch_dialog.set_response_enabled("validate", !row.text().trim().is_empty());
+            }));
+
+            branch_dialog.connect_response(None,clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    match response {
+                        "validate" => {
+                            win2.imp().repository_page.create_branch_from_commit_and_update_ui(
+                                &commit_id,
+                                branch_name_row.text().trim(),
+                            );
+                        },
+                        _ => {}
+                    }
+                }
+            ));
+
+            branch_dialog.present();
+        }));
+    }
+
+    /// Used to show the dialog for creating a tag on a commit.
+    pub fn show_create_tag_dialog(&self, commit_id: String) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let tag_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Create tag dialog"))
+                .body(&gettext("_Create tag message"))
+                .build();
+
+            let tag_name_row = adw::EntryRow::builder()
+                .title(&gettext("_Tag name"))
+                .build();
+            let tag_message_row = adw::EntryRow::builder()
+                .title(&gettext("_Tag message"))
+                .build();
+            let sign_switch = gtk::Switch::builder()
+                .valign(gtk::Align::Center)
+                .build();
+            let sign_row = adw::ActionRow::builder()
+                .title(&gettext("_Sign tag"))
+                .activatable_widget(&sign_switch)
+                .build();
+            sign_row.add_suffix(&sign_switch);
+
+            // Only annotated tags can be signed:
+            sign_row.set_sensitive(false);
+            tag_message_row.connect_changed(clone!(@weak sign_row => move |row| {
+                sign_row.set_sensitive(!row.text().trim().is_empty());
+            }));
+
+            let tag_list = gtk::ListBox::new();
+            tag_list.add_css_class("boxed-list");
+            tag_list.set_selection_mode(gtk::SelectionMode::None);
+            tag_list.append(&tag_name_row);
+            tag_list.append(&tag_message_row);
+            tag_list.append(&sign_row);
+            tag_dialog.set_extra_child(Some(&tag_list));
+
+            tag_dialog.add_response("cancel", &gettext("_Cancel"));
+            tag_dialog.add_response("validate", &gettext("_Validate"));
+            tag_dialog.set_response_appearance("validate", adw::ResponseAppearance::Suggested);
+            tag_dialog.set_response_enabled("validate", false);
+
+            tag_name_row.connect_changed(clone!(@weak tag_dialog => move |row| {
+                tag_dialog.set_response_enabled("validate", !row.text().trim().is_empty());
+            }));
+
+            tag_dialog.connect_response(None,clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    match response {
+                        "validate" => {
+                            win2.imp().repository_page.create_tag(
+                                &commit_id,
+                                tag_name_row.text().trim(),
+                                tag_message_row.text().trim(),
+                                sign_switch.is_active() && sign_row.is_sensitive(),
+                            );
+                        },
+                        _ => {}
+                    }
+                }
+            ));
+
+            tag_dialog.present();
+        }));
+    }
+
+    /// Used to show the dialog for deleting a tag, locally or also on the remote.
+    pub fn show_delete_tag_dialog(&self, tag_name: String) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let delete_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Delete tag"))
+                .body(&format!("{} {}", gettext("_Delete tag confirmation"), tag_name))
+                .build();
+
+            delete_dialog.add_response("cancel", &gettext("_Cancel"));
+            delete_dialog.add_response("local", &gettext("_Delete locally"));
+            delete_dialog.add_response("remote", &gettext("_Delete locally and on remote"));
+            delete_dialog.set_close_response("cancel");
+            delete_dialog.set_response_appearance("local", adw::ResponseAppearance::Destructive);
+            delete_dialog.set_response_appearance("remote", adw::ResponseAppearance::Destructive);
+
+            delete_dialog.connect_response(None,clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    match response {
+                        "local" => {
+                            win2.imp().repository_page.delete_local_tag_and_update_ui(&tag_name);
+                        },
+                        "remote" => {
+                            // When deleting a remote tag, we need to authenticate:
+                            win2.imp().repository_page.do_git_action_with_auth_check(ActionType::DeleteRemoteTag, &tag_name, &"");
+                        },
+                        _ => {}
+                    }
+                }
+            ));
+
+            delete_dialog.present();
+        }));
+    }
+
+    /// Used to show the dialog for renaming a remote and changing its URL.
+    pub fn show_edit_remote_dialog(&self, remote_name: String, remote_url: String) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let remote_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Edit remote"))
+                .build();
+
+            let remote_name_row = adw::EntryRow::builder()
+                .title(&gettext("_Remote name"))
+                .text(&remote_name)
+                .build();
+            let remote_url_row = adw::EntryRow::builder()
+                .title(&gettext("_Remote URL"))
+                .text(&remote_url)
+                .build();
+
+            let remote_list = gtk::ListBox::new();
+            remote_list.add_css_class("boxed-list");
+            remote_list.set_selection_mode(gtk::SelectionMode::None);
+            remote_list.append(&remote_name_row);
+            remote_list.append(&remote_url_row);
+            remote_dialog.set_extra_child(Some(&remote_list));
+
+            remote_dialog.add_response("cancel", &gettext("_Cancel"));
+            remote_dialog.add_response("validate", &gettext("_Validate"));
+            remote_dialog.set_response_appearance("validate", adw::ResponseAppearance::Suggested);
+
+            let update_validate_response = clone!(
+                @weak remote_dialog, @weak remote_name_row, @weak remote_url_row => move || {
+                    remote_dialog.set_response_enabled(
+                        "validate",
+                        !remote_name_row.text().trim().is_empty()
+                            && !remote_url_row.text().trim().is_empty(),
+                    );
+                }
+            );
+            let cloned_update_validate_response = update_validate_response.clone();
+            remote_name_row.connect_changed(move |_| update_validate_response());
+            remote_url_row.connect_changed(move |_| cloned_update_validate_response());
+
+            remote_dialog.connect_response(None,clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    match response {
+                        "validate" => {
+                            win2.imp().repository_page.update_remote_and_update_ui(
+                                &remote_name,
+                                remote_name_row.text().trim(),
+                                remote_url_row.text().trim(),
+                            );
+                        },
+                        _ => {}
+                    }
+                }
+            ));
+
+            remote_dialog.present();
+        }));
+    }
+
+    /// Used to show the dialog for publishing the repository to a new remote.
+    pub fn show_publish_dialog(&self) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let publish_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Publish repository"))
+                .body(&gettext("_Publish repository description"))
+                .build();
+
+            let remote_name_row = adw::EntryRow::builder()
+                .title(&gettext("_Remote name"))
+                .text("origin")
+                .build();
+            let remote_url_row = adw::EntryRow::builder()
+                .title(&gettext("_Remote URL"))
+                .build();
+
+            let remote_list = gtk::ListBox::new();
+            remote_list.add_css_class("boxed-list");
+            remote_list.set_selection_mode(gtk::SelectionMode::None);
+            remote_list.append(&remote_name_row);
+            remote_list.append(&remote_url_row);
+            publish_dialog.set_extra_child(Some(&remote_list));
+
+            publish_dialog.add_response("cancel", &gettext("_Cancel"));
+            publish_dialog.add_response("publish", &gettext("_Publish"));
+            publish_dialog.set_close_response("cancel");
+            publish_dialog.set_response_appearance("publish", adw::ResponseAppearance::Suggested);
+            publish_dialog.set_response_enabled("publish", false);
+
+            let update_publish_response = clone!(
+                @weak publish_dialog, @weak remote_name_row, @weak remote_url_row => move || {
+                    publish_dialog.set_response_enabled(
+                        "publish",
+                        !remote_name_row.text().trim().is_empty()
+                            && !remote_url_row.text().trim().is_empty(),
+                    );
+                }
+            );
+            let cloned_update_publish_response = update_publish_response.clone();
+            remote_name_row.connect_changed(move |_| update_publish_response());
+            remote_url_row.connect_changed(move |_| cloned_update_publish_response());
+
+            publish_dialog.connect_response(None,clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    if response == "publish" {
+                        win2.imp().repository_page.do_git_action_with_auth_check(
+                            ActionType::Publish,
+                            remote_url_row.text().trim(),
+                            remote_name_row.text().trim(),
+                        );
+                    }
+                }
+            ));
+
+            publish_dialog.present();
+        }));
+    }
+
+    /// Used to show the dialog for deleting a remote.
+    pub fn show_delete_remote_dialog(&self, remote_name: String) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let delete_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Delete remote"))
+                .body(&format!("{} {}", gettext("_Delete remote confirmation"), remote_name))
+                .build();
+
+            delete_dialog.add_response("cancel", &gettext("_Cancel"));
+            delete_dialog.add_response("delete", &gettext("_Delete"));
+            delete_dialog.set_close_response("cancel");
+            delete_dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+
+            delete_dialog.connect_response(None,clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    if response == "delete" {
+                        win2.imp().repository_page.delete_remote_and_update_ui(&remote_name);
+                    }
+                }
+            ));
+
+            delete_dialog.present();
+        }));
+    }
+
+    /// Used to show the dialog choosing how much history is fetched in a shallow repository.
+    pub fn show_deepen_history_dialog(&self) {
+        let ctx: MainContext = glib::MainContext::default();
+        ctx.spawn_local(clone!(@weak self as win => async move {
+            let deepen_dialog = adw::MessageDialog::builder()
+                .modal(true)
+                .transient_for(&win)
+                .heading(&gettext("_Deepen history"))
+                .body(&gettext("_Deepen history message"))
+                .build();
+
+            let depth_spin_button = gtk::SpinButton::with_range(1.0, 1000000.0, 1.0);
+            depth_spin_button.set_value(50.0);
+            depth_spin_button.set_valign(gtk::Align::Center);
+
+            let depth_row = adw::ActionRow::builder()
+                .title(&gettext("_Commits to fetch"))
+                .build();
+            depth_row.add_suffix(&depth_spin_button);
+
+            let depth_list = gtk::ListBox::new();
+            depth_list.add_css_class("boxed-list");
+            depth_list.set_selection_mode(gtk::SelectionMode::None);
+            depth_list.append(&depth_row);
+            deepen_dialog.set_extra_child(Some(&depth_list));
+
+            deepen_dialog.add_response("cancel", &gettext("_Cancel"));
+            deepen_dialog.add_response("whole", &gettext("_Whole history"));
+            deepen_dialog.add_response("deepen", &gettext("_Deepen"));
+            deepen_dialog.set_close_response("cancel");
+            deepen_dialog.set_response_appearance("deepen", adw::ResponseAppearance::Suggested);
+
+            deepen_dialog.connect_response(None, clone!(
+                @weak win as win2,
+                => move |_, response| {
+                    match response {
+                        "deepen" => win2.imp().repository_page.deepen_history(
+                            depth_spin_button.value_as_int() as u32
+                        ),
+                        "whole" => win2.imp().repository_page.deepen_history(0),
+                        _ => {}
+                    }
+                }
+            ));
+
+            deepen_dialog.present();
+        }));
+    }
+
     /// Saves a created repository.
+    /// Used to open a submodule as a repository.
+    /// The submodule is added to the repositories with the profile of its superproject if needed.
+    pub fn open_submodule(&self, submodule_path: &str) {
+        let app_database = self.imp().app_database.take();
+
+        let submodule_repository = match app_database.get_repository_from_path(submodule_path) {
+            Ok(Some(repository)) => repository,
+            Ok(None) => {
+                let superproject = self.imp().repository_page.get_selected_repository();
+
+                let new_repository = BagitRepository::new(
+                    Uuid::new_v4(),
+                    RepositoryUtils::get_folder_name_from_os(submodule_path),
+                    submodule_path.to_string(),
+                    superproject.user_repository.git_profile_id,
+                );
+
+                if let Err(error) = app_database.add_repository(&new_repository) {
+                    tracing::warn!("Could not add repository: {}", error);
+                } else {
+                    self.add_list_row_to_all_repositories(&new_repository);
+                }
+
+                new_repository
+            }
+            Err(error) => {
+                tracing::warn!("Could not get repository from path: {}", error);
+                self.imp().app_database.replace(app_database);
+
+                return;
+            }
+        };
+
+        self.imp().app_database.replace(app_database);
+        self.update_recent_repositories();
+
+        match SelectedRepository::try_fetching_selected_repository(&submodule_repository) {
+            Ok(selected_repository) => self
+                .imp()
+                .repository_page
+                .init_repository_page(selected_repository),
+            Err(error) => self.show_error_dialog(&error.to_string()),
+        }
+    }
+
     pub fn save_repository(&self, new_repository: &mut BagitRepository, profile_mode: ProfileMode) {
         self.add_list_row_to_all_repositories(&new_repository);
         let profile_id: Option<Uuid> = match profile_mode.get_profile_mode() {